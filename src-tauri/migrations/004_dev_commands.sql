@@ -0,0 +1,7 @@
+-- Houston v0.13.0 Saved dev server start command per project
+
+CREATE TABLE dev_commands (
+    project_path TEXT PRIMARY KEY NOT NULL,
+    command      TEXT NOT NULL,
+    updated_at   TEXT NOT NULL DEFAULT (datetime('now'))
+);
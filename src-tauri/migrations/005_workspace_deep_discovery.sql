@@ -0,0 +1,3 @@
+-- Houston v0.13.0 Per-workspace opt-in for nested project discovery
+
+ALTER TABLE workspaces ADD COLUMN deep_discovery INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,851 @@
+//! Optional localhost JSON API mirroring the Tauri command surface.
+//!
+//! Editors, shell prompts and dashboards can call the same commands the
+//! webview uses without going through it:
+//!
+//! ```text
+//! curl -s -X POST http://127.0.0.1:47474/api/get_git_status \
+//!      -H "Authorization: Bearer $TOKEN" \
+//!      -d '{"project_path": "/Users/me/code/houston"}'
+//! ```
+//!
+//! Requests are dispatched to the `#[tauri::command]` functions themselves, so
+//! they share `AppState`'s caches and stats with the UI. The server only binds
+//! to 127.0.0.1 and every call except `/api/health` needs the token stored in
+//! the `api_token` setting. It is off unless `api_server_enabled` is "true".
+
+use crate::commands::{
+    ai_tools_cmds, branch_cmds, changelog_cmds, claude_cmds, dev_server_cmds, diagnostics_cmds,
+    docker_cmds, env_cmds, fetch_cmds, history_cmds, issue_cmds, language_cmds, manifest_cmds,
+    package_cmds, port_cmds, registry_cmds, settings_cmds, stats_cmds, system_cmds, workspace_cmds,
+    worktree_cmds,
+};
+use crate::db::Database;
+use crate::scanners::project_analysis;
+use crate::state::AppState;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+pub const DEFAULT_PORT: u16 = 47474;
+
+/// Largest request body accepted; command arguments are tiny.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Every command reachable over HTTP, in `generate_handler!` order. Log
+/// subscriptions are left out: they deliver through app events, not a reply.
+pub const COMMANDS: &[&str] = &[
+    "get_system_info",
+    "get_path_entries",
+    "refresh_system_info",
+    "refresh_path_entries",
+    "get_languages",
+    "refresh_languages",
+    "get_env_vars",
+    "refresh_env_vars",
+    "get_workspace_paths",
+    "add_workspace",
+    "remove_workspace",
+    "get_deep_discovery_workspaces",
+    "set_workspace_deep_discovery",
+    "scan_projects",
+    "get_git_status",
+    "get_all_git_statuses",
+    "get_monorepo_packages",
+    "get_packages",
+    "refresh_packages",
+    "get_claude_config",
+    "refresh_claude_config",
+    "open_in_terminal",
+    "open_in_editor",
+    "open_in_ai_tool",
+    "get_diagnostics",
+    "refresh_diagnostics",
+    "run_diagnostic_fix",
+    "get_ai_tools",
+    "refresh_ai_tools",
+    "update_ai_tool",
+    "get_tool_mcp_servers",
+    "get_app_stats",
+    "get_settings",
+    "get_setting",
+    "set_setting",
+    "get_scan_history",
+    "get_latest_scan",
+    "diff_scans",
+    "export_manifest",
+    "compare_manifest",
+    "get_issue_count",
+    "get_issues",
+    "dismiss_issue",
+    "update_issue_status",
+    "analyze_project",
+    "get_changelogs",
+    "get_changelog",
+    "sync_changelog",
+    "get_docker_status",
+    "refresh_docker_status",
+    "start_docker_container",
+    "stop_docker_container",
+    "restart_docker_container",
+    "run_compose_action",
+    "get_compose_stacks",
+    "get_docker_container_logs",
+    "get_dev_servers",
+    "refresh_dev_servers",
+    "stop_dev_server",
+    "start_dev_server",
+    "restart_dev_server",
+    "get_managed_servers",
+    "get_dev_server_output",
+    "set_dev_server_auto_restart",
+    "get_dev_commands",
+    "set_dev_command",
+    "get_port_map",
+    "find_port_owners",
+    "get_detection_registry",
+    "reload_detection_registry",
+    "get_branch_report",
+    "get_project_branches",
+    "delete_merged_branches",
+    "fetch_all_repos",
+    "list_worktrees",
+    "create_worktree",
+    "remove_worktree",
+    "prune_worktrees",
+];
+
+/// Commands that change something (processes, files, settings, git state).
+/// They only answer POST, so a link or a prefetching client can't set one off.
+const POST_ONLY: &[&str] = &[
+    "add_workspace",
+    "remove_workspace",
+    "set_workspace_deep_discovery",
+    "open_in_terminal",
+    "open_in_editor",
+    "open_in_ai_tool",
+    "run_diagnostic_fix",
+    "update_ai_tool",
+    "set_setting",
+    "export_manifest",
+    "dismiss_issue",
+    "update_issue_status",
+    "sync_changelog",
+    "start_docker_container",
+    "stop_docker_container",
+    "restart_docker_container",
+    "run_compose_action",
+    "stop_dev_server",
+    "start_dev_server",
+    "restart_dev_server",
+    "set_dev_server_auto_restart",
+    "set_dev_command",
+    "reload_detection_registry",
+    "delete_merged_branches",
+    "fetch_all_repos",
+    "create_worktree",
+    "remove_worktree",
+    "prune_worktrees",
+];
+
+/// Status reported to the settings UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiServerStatus {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    pub token: String,
+    pub error: Option<String>,
+}
+
+/// Handle to a running server, kept in `AppState::api_server`.
+pub struct ApiServerHandle {
+    pub port: u16,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl ApiServerHandle {
+    /// Stop accepting connections. In-flight requests finish on their own threads.
+    pub fn stop(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the blocking accept() so the loop sees the flag
+        let _ = TcpStream::connect_timeout(
+            &SocketAddr::from((Ipv4Addr::LOCALHOST, self.port)),
+            Duration::from_millis(200),
+        );
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Settings
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub fn is_enabled(db: &Database) -> bool {
+    db.get_setting("api_server_enabled")
+        .ok()
+        .flatten()
+        .map(|v| v == "true")
+        .unwrap_or(false)
+}
+
+pub fn configured_port(db: &Database) -> u16 {
+    db.get_setting("api_server_port")
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_PORT)
+}
+
+/// Return the stored API token, generating and saving one on first use.
+pub fn ensure_token(db: &Database) -> Result<String, String> {
+    if let Some(token) = db.get_setting("api_token")?.filter(|t| !t.is_empty()) {
+        return Ok(token);
+    }
+    let token = generate_token();
+    db.set_setting("api_token", &token)?;
+    Ok(token)
+}
+
+/// 128-bit hex token. `RandomState` is seeded from the OS RNG, which is
+/// plenty for a loopback-only credential and avoids pulling in `rand`.
+pub fn generate_token() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut token = String::with_capacity(32);
+    for i in 0..2u64 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(i);
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default(),
+        );
+        hasher.write_u32(std::process::id());
+        token.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    token
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Lifecycle
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Start the server if the `api_server_enabled` setting is on. Called from setup.
+pub fn start_if_enabled(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let enabled = is_enabled(&state.db.lock().unwrap());
+    if enabled {
+        if let Err(e) = start(app) {
+            log::warn!("API server failed to start: {}", e);
+        }
+    }
+}
+
+/// Bind 127.0.0.1 on the configured port and serve on a background thread.
+/// No-op if the server is already running.
+pub fn start(app: &AppHandle) -> Result<u16, String> {
+    let state = app.state::<AppState>();
+    let mut slot = state.api_server.lock().unwrap();
+    if let Some(handle) = slot.as_ref() {
+        return Ok(handle.port);
+    }
+
+    let (port, token) = {
+        let db = state.db.lock().unwrap();
+        (configured_port(&db), ensure_token(&db)?)
+    };
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|e| format!("Failed to bind 127.0.0.1:{}: {}", port, e))?;
+    let port = listener.local_addr().map(|a| a.port()).unwrap_or(port);
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let flag = shutdown.clone();
+    let app = app.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            if flag.load(Ordering::SeqCst) {
+                break;
+            }
+            let Ok(stream) = stream else { continue };
+            let app = app.clone();
+            let token = token.clone();
+            // One thread per connection: refresh_* calls can take seconds
+            std::thread::spawn(move || handle_connection(stream, &app, &token));
+        }
+        log::info!("API server on port {} stopped", port);
+    });
+
+    log::info!("API server listening on http://127.0.0.1:{}", port);
+    *slot = Some(ApiServerHandle { port, shutdown });
+    Ok(port)
+}
+
+pub fn stop(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let handle = state.api_server.lock().unwrap().take();
+    if let Some(handle) = handle {
+        handle.stop();
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// HTTP
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Read a single HTTP/1.1 request (request line, headers, Content-Length body).
+fn parse_request<R: BufRead>(reader: &mut R) -> Result<Request, String> {
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .map_err(|e| format!("Failed to read request: {}", e))?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or("Empty request")?.to_uppercase();
+    let target = parts.next().ok_or("Missing request target")?;
+    // Query strings carry no meaning here
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        let n = reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Failed to read headers: {}", e))?;
+        let trimmed = line.trim_end();
+        if n == 0 || trimmed.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = trimmed.split_once(':') {
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let length: usize = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, v)| v.parse().ok())
+        .unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        return Err("Request body too large".to_string());
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| format!("Failed to read body: {}", e))?;
+
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+/// Accept `Authorization: Bearer <token>` or `X-Houston-Token: <token>`.
+fn is_authorized(req: &Request, token: &str) -> bool {
+    let provided = req
+        .header("authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .or_else(|| req.header("x-houston-token"));
+    match provided {
+        Some(p) => constant_time_eq(p.trim().as_bytes(), token.as_bytes()),
+        None => false,
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Only answer requests addressed to the loopback interface by name, so a
+/// web page can't reach the API through DNS rebinding.
+fn is_local_host(req: &Request) -> bool {
+    match req.header("host") {
+        Some(host) => {
+            let name = host.rsplit_once(':').map(|(h, _)| h).unwrap_or(host);
+            matches!(name, "127.0.0.1" | "localhost")
+        }
+        None => true,
+    }
+}
+
+fn handle_connection(stream: TcpStream, app: &AppHandle, token: &str) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    let mut reader = BufReader::new(&stream);
+    let response = match parse_request(&mut reader) {
+        Ok(req) => route(&req, app, token),
+        Err(e) => Response::error(400, e),
+    };
+    write_response(&stream, &response);
+}
+
+fn route(req: &Request, app: &AppHandle, token: &str) -> Response {
+    if !is_local_host(req) {
+        return Response::error(403, "Forbidden host");
+    }
+
+    let Some(name) = req.path.strip_prefix("/api/") else {
+        return Response::error(404, "Not found");
+    };
+
+    if name == "health" {
+        return Response::ok(json!({ "ok": true, "version": env!("CARGO_PKG_VERSION") }));
+    }
+    if !is_authorized(req, token) {
+        return Response::error(401, "Missing or invalid API token");
+    }
+    if name == "commands" {
+        return Response::ok(json!(COMMANDS));
+    }
+    if req.method != "POST" && req.method != "GET" {
+        return Response::error(405, "Use POST (or GET for commands without arguments)");
+    }
+    if !COMMANDS.contains(&name) {
+        return Response::error(404, format!("Unknown command: {}", name));
+    }
+    if !method_allowed(&req.method, name) {
+        return Response::error(405, format!("{} changes state; use POST", name));
+    }
+
+    let args: Value = if req.body.iter().all(|b| b.is_ascii_whitespace()) {
+        json!({})
+    } else {
+        match serde_json::from_slice(&req.body) {
+            Ok(v @ Value::Object(_)) => v,
+            Ok(_) => return Response::error(400, "Arguments must be a JSON object"),
+            Err(e) => return Response::error(400, format!("Invalid JSON: {}", e)),
+        }
+    };
+
+    match dispatch(app, name, &args) {
+        Ok(value) => Response::ok(value),
+        Err(DispatchError::BadArgs(e)) => Response::error(400, e),
+        Err(DispatchError::Failed(e)) => Response::error(500, e),
+    }
+}
+
+fn method_allowed(method: &str, command: &str) -> bool {
+    method == "POST" || !POST_ONLY.contains(&command)
+}
+
+fn write_response(mut stream: &TcpStream, response: &Response) {
+    let body = response.body.to_string();
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason,
+        body.len()
+    );
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(body.as_bytes());
+    let _ = stream.flush();
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Dispatch
+// ─────────────────────────────────────────────────────────────────────────────
+
+enum DispatchError {
+    BadArgs(String),
+    Failed(String),
+}
+
+/// Look up an argument by its Rust parameter name, falling back to the
+/// camelCase spelling the frontend uses with `invoke()`.
+fn arg<T: DeserializeOwned>(args: &Value, name: &str) -> Result<T, DispatchError> {
+    let value = args
+        .get(name)
+        .or_else(|| args.get(snake_to_camel(name)))
+        .cloned()
+        .unwrap_or(Value::Null);
+    serde_json::from_value(value)
+        .map_err(|e| DispatchError::BadArgs(format!("Argument `{}`: {}", name, e)))
+}
+
+fn snake_to_camel(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn to_json<T: Serialize>(value: T) -> Result<Value, DispatchError> {
+    serde_json::to_value(value).map_err(|e| DispatchError::Failed(e.to_string()))
+}
+
+fn from_result<T: Serialize>(result: Result<T, String>) -> Result<Value, DispatchError> {
+    to_json(result.map_err(DispatchError::Failed)?)
+}
+
+fn dispatch(app: &AppHandle, name: &str, args: &Value) -> Result<Value, DispatchError> {
+    use crate::commands::action_cmds;
+
+    let state = || app.state::<AppState>();
+
+    match name {
+        // System
+        "get_system_info" => to_json(system_cmds::get_system_info(state())),
+        "get_path_entries" => to_json(system_cmds::get_path_entries(state())),
+        "refresh_system_info" => to_json(system_cmds::refresh_system_info(state())),
+        "refresh_path_entries" => to_json(system_cmds::refresh_path_entries(state())),
+        // Languages
+        "get_languages" => to_json(language_cmds::get_languages(state())),
+        "refresh_languages" => to_json(language_cmds::refresh_languages(state())),
+        // Environment
+        "get_env_vars" => to_json(env_cmds::get_env_vars(state())),
+        "refresh_env_vars" => to_json(env_cmds::refresh_env_vars(state())),
+        // Workspace
+        "get_workspace_paths" => to_json(workspace_cmds::get_workspace_paths(state())),
+        "add_workspace" => to_json(workspace_cmds::add_workspace(state(), arg(args, "path")?)),
+        "remove_workspace" => to_json(workspace_cmds::remove_workspace(
+            state(),
+            arg(args, "path")?,
+        )),
+        "get_deep_discovery_workspaces" => {
+            to_json(workspace_cmds::get_deep_discovery_workspaces(state()))
+        }
+        "set_workspace_deep_discovery" => from_result(workspace_cmds::set_workspace_deep_discovery(
+            state(),
+            arg(args, "path")?,
+            arg(args, "enabled")?,
+        )),
+        "scan_projects" => to_json(workspace_cmds::scan_projects(state())),
+        "get_git_status" => to_json(workspace_cmds::get_git_status(
+            state(),
+            arg(args, "project_path")?,
+        )),
+        "get_all_git_statuses" => to_json(workspace_cmds::get_all_git_statuses(state())),
+        "get_monorepo_packages" => to_json(workspace_cmds::get_monorepo_packages(arg(
+            args,
+            "root_path",
+        )?)),
+        // Packages
+        "get_packages" => to_json(package_cmds::get_packages(state())),
+        "refresh_packages" => to_json(package_cmds::refresh_packages(state())),
+        // Claude
+        "get_claude_config" => to_json(claude_cmds::get_claude_config(state())),
+        "refresh_claude_config" => to_json(claude_cmds::refresh_claude_config(state())),
+        // Actions
+        "open_in_terminal" => {
+            from_result(action_cmds::open_in_terminal(state(), arg(args, "path")?))
+        }
+        "open_in_editor" => from_result(action_cmds::open_in_editor(state(), arg(args, "path")?)),
+        "open_in_ai_tool" => from_result(action_cmds::open_in_ai_tool(state(), arg(args, "path")?)),
+        // Diagnostics
+        "get_diagnostics" => to_json(diagnostics_cmds::get_diagnostics(state())),
+        "refresh_diagnostics" => to_json(diagnostics_cmds::refresh_diagnostics(state())),
+        "run_diagnostic_fix" => to_json(diagnostics_cmds::run_diagnostic_fix(arg(args, "fix_id")?)),
+        // AI Tools
+        "get_ai_tools" => to_json(ai_tools_cmds::get_ai_tools(state())),
+        "refresh_ai_tools" => to_json(ai_tools_cmds::refresh_ai_tools(state())),
+        "update_ai_tool" => to_json(ai_tools_cmds::update_ai_tool(
+            arg(args, "tool_name")?,
+            state(),
+        )),
+        "get_tool_mcp_servers" => {
+            to_json(ai_tools_cmds::get_tool_mcp_servers(arg(args, "tool_name")?))
+        }
+        // Stats
+        "get_app_stats" => to_json(stats_cmds::get_app_stats(state())),
+        // Settings
+        "get_settings" => from_result(settings_cmds::get_settings(state())),
+        "get_setting" => from_result(settings_cmds::get_setting(state(), arg(args, "key")?)),
+        "set_setting" => from_result(settings_cmds::set_setting(
+            state(),
+            arg(args, "key")?,
+            arg(args, "value")?,
+        )),
+        // History
+        "get_scan_history" => from_result(history_cmds::get_scan_history(
+            state(),
+            arg(args, "scanner")?,
+            arg(args, "limit")?,
+        )),
+        "get_latest_scan" => from_result(history_cmds::get_latest_scan(
+            state(),
+            arg(args, "scanner")?,
+        )),
+        "diff_scans" => from_result(history_cmds::diff_scans(
+            state(),
+            arg(args, "scanner")?,
+            arg(args, "from_id")?,
+            arg(args, "to_id")?,
+        )),
+        // Manifest
+        "export_manifest" => from_result(manifest_cmds::export_manifest(
+            state(),
+            arg(args, "path")?,
+        )),
+        "compare_manifest" => from_result(manifest_cmds::compare_manifest(
+            state(),
+            arg(args, "path")?,
+        )),
+        // Issues
+        "get_issue_count" => from_result(issue_cmds::get_issue_count(state())),
+        "get_issues" => from_result(issue_cmds::get_issues(state(), arg(args, "status")?)),
+        "dismiss_issue" => from_result(issue_cmds::dismiss_issue(
+            state(),
+            arg(args, "diagnostic_id")?,
+        )),
+        "update_issue_status" => from_result(issue_cmds::update_issue_status(
+            state(),
+            arg(args, "diagnostic_id")?,
+            arg(args, "status")?,
+        )),
+        // Project Analysis (the command is async; we're already off the main thread)
+        "analyze_project" => {
+            let path: String = arg(args, "project_path")?;
+            from_result(project_analysis::analyze_project(&path))
+        }
+        // Changelogs
+        "get_changelogs" => from_result(changelog_cmds::get_changelogs(state())),
+        "get_changelog" => from_result(changelog_cmds::get_changelog(
+            state(),
+            arg(args, "version")?,
+        )),
+        "sync_changelog" => from_result(changelog_cmds::sync_changelog(
+            state(),
+            arg(args, "changelog")?,
+        )),
+        // Docker
+        "get_docker_status" => to_json(docker_cmds::get_docker_status(state())),
+        "refresh_docker_status" => to_json(docker_cmds::refresh_docker_status(state())),
+        "start_docker_container" => from_result(docker_cmds::start_docker_container(
+            arg(args, "container_id")?,
+            state(),
+        )),
+        "stop_docker_container" => from_result(docker_cmds::stop_docker_container(
+            arg(args, "container_id")?,
+            state(),
+        )),
+        "restart_docker_container" => from_result(docker_cmds::restart_docker_container(
+            arg(args, "container_id")?,
+            state(),
+        )),
+        "run_compose_action" => from_result(docker_cmds::run_compose_action(
+            state(),
+            arg(args, "project")?,
+            arg(args, "action")?,
+            arg(args, "service")?,
+        )),
+        "get_compose_stacks" => to_json(docker_cmds::get_compose_stacks(state())),
+        "get_docker_container_logs" => from_result(docker_cmds::get_docker_container_logs(
+            arg(args, "container_id")?,
+            arg(args, "tail")?,
+        )),
+        // Dev Servers
+        "get_dev_servers" => to_json(dev_server_cmds::get_dev_servers(state())),
+        "refresh_dev_servers" => to_json(dev_server_cmds::refresh_dev_servers(state())),
+        "stop_dev_server" => {
+            from_result(dev_server_cmds::stop_dev_server(arg(args, "pid")?, state()))
+        }
+        "start_dev_server" => from_result(dev_server_cmds::start_dev_server(
+            arg(args, "project_path")?,
+            arg(args, "command")?,
+            arg(args, "auto_restart")?,
+            state(),
+        )),
+        "restart_dev_server" => {
+            from_result(dev_server_cmds::restart_dev_server(arg(args, "id")?, state()))
+        }
+        "get_managed_servers" => to_json(dev_server_cmds::get_managed_servers(state())),
+        "get_dev_server_output" => {
+            from_result(dev_server_cmds::get_dev_server_output(arg(args, "id")?, state()))
+        }
+        "set_dev_server_auto_restart" => from_result(dev_server_cmds::set_dev_server_auto_restart(
+            arg(args, "id")?,
+            arg(args, "enabled")?,
+            state(),
+        )),
+        "get_dev_commands" => to_json(dev_server_cmds::get_dev_commands(
+            arg(args, "project_path")?,
+            state(),
+        )),
+        "set_dev_command" => from_result(dev_server_cmds::set_dev_command(
+            arg(args, "project_path")?,
+            arg(args, "command")?,
+            state(),
+        )),
+        // Ports
+        "get_port_map" => to_json(port_cmds::get_port_map(state())),
+        "find_port_owners" => to_json(port_cmds::find_port_owners(arg(args, "port")?, state())),
+        // Detection Registry
+        "get_detection_registry" => to_json(registry_cmds::get_detection_registry()),
+        "reload_detection_registry" => to_json(registry_cmds::reload_detection_registry(state())),
+        // Branches
+        "get_branch_report" => to_json(branch_cmds::get_branch_report(state())),
+        "get_project_branches" => to_json(branch_cmds::get_project_branches(arg(
+            args,
+            "project_path",
+        )?)),
+        "delete_merged_branches" => to_json(branch_cmds::delete_merged_branches(
+            state(),
+            arg(args, "project_path")?,
+            arg(args, "dry_run")?,
+            arg(args, "branches")?,
+        )),
+        // Fetch
+        "fetch_all_repos" => from_result(fetch_cmds::fetch_repos(
+            app,
+            &state(),
+            arg::<Option<bool>>(args, "fast_forward")?.unwrap_or(false),
+            arg(args, "concurrency")?,
+        )),
+        // Worktrees
+        "list_worktrees" => from_result(worktree_cmds::list_worktrees(arg(args, "project_path")?)),
+        "create_worktree" => from_result(worktree_cmds::create_worktree(
+            state(),
+            arg(args, "project_path")?,
+            arg(args, "branch")?,
+            arg::<Option<bool>>(args, "new_branch")?.unwrap_or(false),
+            arg(args, "base")?,
+        )),
+        "remove_worktree" => from_result(worktree_cmds::remove_worktree(
+            state(),
+            arg(args, "project_path")?,
+            arg(args, "worktree_path")?,
+            arg::<Option<bool>>(args, "force")?.unwrap_or(false),
+        )),
+        "prune_worktrees" => from_result(worktree_cmds::prune_worktrees(
+            state(),
+            arg(args, "project_path")?,
+            arg::<Option<bool>>(args, "dry_run")?.unwrap_or(false),
+        )),
+        _ => Err(DispatchError::BadArgs(format!("Unknown command: {}", name))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn request(raw: &str) -> Request {
+        parse_request(&mut Cursor::new(raw.as_bytes().to_vec())).unwrap()
+    }
+
+    #[test]
+    fn test_parse_request_with_body() {
+        let req = request(
+            "POST /api/get_git_status?x=1 HTTP/1.1\r\nHost: 127.0.0.1:47474\r\nContent-Length: 18\r\n\r\n{\"project_path\":1}",
+        );
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/api/get_git_status");
+        assert_eq!(req.header("content-length"), Some("18"));
+        assert_eq!(req.body, b"{\"project_path\":1}");
+    }
+
+    #[test]
+    fn test_auth_and_host_checks() {
+        let token = "abc123";
+        let bearer = request("GET /api/commands HTTP/1.1\r\nAuthorization: Bearer abc123\r\n\r\n");
+        let header = request("GET /api/commands HTTP/1.1\r\nX-Houston-Token: abc123\r\n\r\n");
+        let wrong = request("GET /api/commands HTTP/1.1\r\nAuthorization: Bearer abc124\r\n\r\n");
+        let missing = request("GET /api/commands HTTP/1.1\r\n\r\n");
+        assert!(is_authorized(&bearer, token));
+        assert!(is_authorized(&header, token));
+        assert!(!is_authorized(&wrong, token));
+        assert!(!is_authorized(&missing, token));
+
+        let rebound = request("GET /api/commands HTTP/1.1\r\nHost: evil.example:47474\r\n\r\n");
+        let local = request("GET /api/commands HTTP/1.1\r\nHost: localhost:47474\r\n\r\n");
+        assert!(!is_local_host(&rebound));
+        assert!(is_local_host(&local));
+    }
+
+    #[test]
+    fn test_arg_accepts_snake_and_camel_case() {
+        let snake = json!({ "project_path": "/a" });
+        let camel = json!({ "projectPath": "/b" });
+        assert_eq!(
+            arg::<String>(&snake, "project_path").ok(),
+            Some("/a".to_string())
+        );
+        assert_eq!(
+            arg::<String>(&camel, "project_path").ok(),
+            Some("/b".to_string())
+        );
+        // Optional arguments may be omitted, required ones may not
+        assert_eq!(arg::<Option<u32>>(&json!({}), "limit").ok(), Some(None));
+        assert!(arg::<String>(&json!({}), "path").is_err());
+    }
+
+    #[test]
+    fn test_mutating_commands_require_post() {
+        assert!(POST_ONLY.iter().all(|name| COMMANDS.contains(name)));
+        assert!(method_allowed("GET", "get_git_status"));
+        assert!(method_allowed("POST", "get_git_status"));
+        assert!(!method_allowed("GET", "remove_worktree"));
+        assert!(method_allowed("POST", "remove_worktree"));
+    }
+
+    #[test]
+    fn test_generate_token() {
+        let a = generate_token();
+        let b = generate_token();
+        assert_eq!(a.len(), 32);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(a, b);
+    }
+}
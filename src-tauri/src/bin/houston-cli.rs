@@ -0,0 +1,7 @@
+//! Headless entry point: `houston-cli <command> [--json]`.
+//! Shares scanners and `houston.db` with the desktop app via `houston_lib`.
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::exit(houston_lib::cli::run(&args));
+}
@@ -0,0 +1,818 @@
+//! Headless command-line front end for the scanners.
+//!
+//! Runs the same scanners the desktop app uses and reads/writes the same
+//! `houston.db`, so workspaces, scan history and issues stay in sync between
+//! the two. Output is a plain-text table by default or JSON with `--json`.
+
+use crate::commands::diagnostics_cmds;
+use crate::db::{self, Database};
+use crate::registry;
+use crate::scanners::manifest::{self, GapKind, MachineManifest};
+use crate::scanners::{
+    branches, diagnostics, environment, fetch, git, languages, packages, path, system,
+    version_check, workspace,
+};
+use serde::Serialize;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: houston-cli [OPTIONS] <COMMAND> [ARGS]
+
+Commands:
+  system                     OS, shell, CPU and memory
+  path                       PATH entries with existence and duplicate checks
+  langs                      Installed language runtimes
+  env                        Environment variables (sensitive values masked)
+  packages                   Globally installed packages
+  projects [DIR...]          Projects in DIR (default: saved workspaces)
+  git [DIR...]               Git status for projects in DIR (default: saved workspaces)
+  branches [DIR...]          Every local branch, flagging unpushed, merged and stale ones
+  fetch [DIR...]             Fetch every repo; exits 1 if any fetch failed
+  diagnostics                Run diagnostics and sync issues; exits 1 on errors
+  issues                     Tracked issues from the database
+  workspaces [add|remove DIR] List or edit saved workspaces
+  workspaces deep|shallow DIR Turn nested project discovery on or off for DIR
+  manifest export [FILE]     Write this machine's manifest (default: stdout)
+  manifest compare FILE      List what this machine is missing vs FILE; exits 1 on gaps
+
+Options:
+      --json                 Print JSON instead of a table
+      --db <PATH>            Use this database instead of the app's houston.db
+      --depth <N>            Project scan depth (2-10, default: app setting)
+      --status <STATUS>      Filter issues by status (open, resolved, dismissed)
+      --ff                   With fetch: fast-forward clean branches that are only behind
+      --jobs <N>             With fetch: repos fetched at once (default: 4)
+  -h, --help                 Print help
+  -V, --version              Print version
+";
+
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    json: bool,
+    db_path: Option<PathBuf>,
+    depth: Option<usize>,
+    status: Option<String>,
+    fast_forward: bool,
+    jobs: Option<usize>,
+    help: bool,
+    version: bool,
+    command: Option<String>,
+    args: Vec<String>,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut opts = Options::default();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "-h" | "--help" => opts.help = true,
+            "-V" | "--version" => opts.version = true,
+            "--db" => {
+                let value = iter.next().ok_or("--db requires a path")?;
+                opts.db_path = Some(PathBuf::from(value));
+            }
+            "--depth" => {
+                let value = iter.next().ok_or("--depth requires a number")?;
+                let depth: usize = value
+                    .parse()
+                    .map_err(|_| format!("Invalid --depth value: {}", value))?;
+                opts.depth = Some(depth.clamp(2, 10));
+            }
+            "--status" => {
+                let value = iter.next().ok_or("--status requires a value")?;
+                opts.status = Some(value.clone());
+            }
+            "--ff" => opts.fast_forward = true,
+            "--jobs" => {
+                let value = iter.next().ok_or("--jobs requires a number")?;
+                let jobs: usize = value
+                    .parse()
+                    .map_err(|_| format!("Invalid --jobs value: {}", value))?;
+                opts.jobs = Some(jobs);
+            }
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(format!("Unknown option: {}", flag));
+            }
+            _ => {
+                if opts.command.is_none() {
+                    opts.command = Some(arg.clone());
+                } else {
+                    opts.args.push(arg.clone());
+                }
+            }
+        }
+    }
+
+    Ok(opts)
+}
+
+/// Entry point for the `houston-cli` binary. Takes the arguments without
+/// the program name and returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let opts = match parse_args(args) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    if opts.version {
+        println!("houston-cli {}", env!("CARGO_PKG_VERSION"));
+        return 0;
+    }
+    let command = match (&opts.command, opts.help) {
+        (Some(c), false) => c.clone(),
+        _ => {
+            print!("{}", USAGE);
+            return if opts.help { 0 } else { 2 };
+        }
+    };
+
+    registry::config::reload();
+
+    let db = match open_db(&opts) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 1;
+        }
+    };
+
+    let result = match command.as_str() {
+        "system" => cmd_system(&db, &opts),
+        "path" => cmd_path(&db, &opts),
+        "langs" | "languages" => cmd_langs(&db, &opts),
+        "env" => cmd_env(&db, &opts),
+        "packages" => cmd_packages(&db, &opts),
+        "projects" => cmd_projects(&db, &opts),
+        "git" => cmd_git(&db, &opts),
+        "branches" => cmd_branches(&db, &opts),
+        "fetch" => cmd_fetch(&db, &opts),
+        "diagnostics" | "diag" => cmd_diagnostics(&db, &opts),
+        "issues" => cmd_issues(&db, &opts),
+        "workspaces" => cmd_workspaces(&db, &opts),
+        "manifest" => cmd_manifest(&opts),
+        other => Err(format!("Unknown command: {}\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+fn open_db(opts: &Options) -> Result<Database, String> {
+    let db_path = match &opts.db_path {
+        Some(p) => p.clone(),
+        None => db::default_path().ok_or("Could not determine the app data directory")?,
+    };
+    Database::open(&db_path)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Commands
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn cmd_system(db: &Database, opts: &Options) -> Result<i32, String> {
+    let info = system::scan();
+    let _ = db.record_scan("system", &info);
+    if opts.json {
+        return print_json(&info);
+    }
+    let mut rows = vec![
+        vec![
+            "OS".to_string(),
+            format!("{} {}", info.os_name, info.os_version),
+        ],
+        vec!["Kernel".to_string(), info.kernel_version.clone()],
+        vec!["Architecture".to_string(), info.architecture.clone()],
+        vec!["Hostname".to_string(), info.hostname.clone()],
+        vec![
+            "Shell".to_string(),
+            format!("{} {}", info.shell, info.shell_version),
+        ],
+        vec!["CPU".to_string(), info.cpu_brand.clone()],
+        vec!["Memory".to_string(), format!("{} GB", info.memory_gb)],
+        vec!["User".to_string(), info.username.clone()],
+        vec!["Home".to_string(), info.home_dir.clone()],
+    ];
+    if info.architecture_mismatch {
+        rows.push(vec![
+            "Binary arch".to_string(),
+            format!("{} (mismatch)", info.binary_architecture),
+        ]);
+    }
+    print_table(&["FIELD", "VALUE"], &rows);
+    Ok(0)
+}
+
+fn cmd_path(db: &Database, opts: &Options) -> Result<i32, String> {
+    let entries = path::scan();
+    let _ = db.record_scan("path", &entries);
+    if opts.json {
+        return print_json(&entries);
+    }
+    let rows: Vec<Vec<String>> = entries
+        .iter()
+        .map(|e| {
+            let status = if !e.exists {
+                "missing"
+            } else if e.is_duplicate {
+                "duplicate"
+            } else {
+                "ok"
+            };
+            vec![
+                e.index.to_string(),
+                e.path.clone(),
+                e.category.clone(),
+                status.to_string(),
+            ]
+        })
+        .collect();
+    print_table(&["#", "PATH", "CATEGORY", "STATUS"], &rows);
+    Ok(0)
+}
+
+fn cmd_langs(db: &Database, opts: &Options) -> Result<i32, String> {
+    let langs = languages::scan();
+    let _ = db.record_scan("languages", &langs);
+    if opts.json {
+        return print_json(&langs);
+    }
+    let rows: Vec<Vec<String>> = langs
+        .iter()
+        .filter(|l| l.installed)
+        .map(|l| {
+            vec![
+                l.name.clone(),
+                l.version.clone(),
+                l.manager.clone(),
+                l.binary_path.clone(),
+            ]
+        })
+        .collect();
+    print_table(&["LANGUAGE", "VERSION", "MANAGER", "BINARY"], &rows);
+    Ok(0)
+}
+
+fn cmd_env(db: &Database, opts: &Options) -> Result<i32, String> {
+    let vars = environment::scan();
+    let _ = db.record_scan("environment", &vars);
+    if opts.json {
+        return print_json(&vars);
+    }
+    let rows: Vec<Vec<String>> = vars
+        .iter()
+        .map(|v| vec![v.key.clone(), v.category.clone(), truncate(&v.value, 80)])
+        .collect();
+    print_table(&["KEY", "CATEGORY", "VALUE"], &rows);
+    Ok(0)
+}
+
+fn cmd_packages(db: &Database, opts: &Options) -> Result<i32, String> {
+    let list = packages::scan();
+    let _ = db.record_scan("packages", &list);
+    if opts.json {
+        return print_json(&list);
+    }
+    let sources = [
+        ("npm", &list.npm_global),
+        ("brew", &list.brew),
+        ("pip", &list.pip),
+        ("cargo", &list.cargo),
+        ("scoop", &list.scoop),
+        ("choco", &list.chocolatey),
+    ];
+    let rows: Vec<Vec<String>> = sources
+        .iter()
+        .flat_map(|(source, pkgs)| {
+            pkgs.iter()
+                .map(move |p| vec![source.to_string(), p.name.clone(), p.version.clone()])
+        })
+        .collect();
+    print_table(&["SOURCE", "NAME", "VERSION"], &rows);
+    Ok(0)
+}
+
+/// Projects under the directories given on the command line, or under the
+/// saved workspaces when none are given.
+fn scan_target_projects(
+    db: &Database,
+    opts: &Options,
+) -> Result<Vec<workspace::ProjectInfo>, String> {
+    let dirs: Vec<String> = if opts.args.is_empty() {
+        let saved = db.get_workspaces()?;
+        if saved.is_empty() {
+            return Err(
+                "No workspaces saved. Pass a directory or run `houston-cli workspaces add <DIR>`."
+                    .to_string(),
+            );
+        }
+        saved
+    } else {
+        opts.args.iter().map(|d| absolute(d)).collect()
+    };
+
+    let max_depth = opts.depth.unwrap_or_else(|| {
+        db.get_setting("scan_max_depth")
+            .ok()
+            .flatten()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(workspace::DEFAULT_MAX_SCAN_DEPTH)
+            .clamp(2, 10)
+    });
+
+    let deep = db.get_deep_discovery_workspaces()?;
+    let mut projects = workspace::scan_workspaces(&dirs, max_depth, &deep);
+    if projects.iter().any(|p| !p.version_files.is_empty()) {
+        version_check::annotate(&mut projects, &languages::scan());
+    }
+    Ok(projects)
+}
+
+fn cmd_projects(db: &Database, opts: &Options) -> Result<i32, String> {
+    let projects = scan_target_projects(db, opts)?;
+    if opts.json {
+        return print_json(&projects);
+    }
+    let rows: Vec<Vec<String>> = projects
+        .iter()
+        .map(|p| {
+            vec![
+                p.name.clone(),
+                p.project_type.clone(),
+                p.framework.clone(),
+                p.package_manager.clone(),
+                p.group.clone(),
+                p.path.clone(),
+            ]
+        })
+        .collect();
+    print_table(&["NAME", "TYPE", "FRAMEWORK", "PM", "GROUP", "PATH"], &rows);
+    Ok(0)
+}
+
+fn cmd_git(db: &Database, opts: &Options) -> Result<i32, String> {
+    let projects = scan_target_projects(db, opts)?;
+    let paths: Vec<String> = projects
+        .iter()
+        .filter(|p| p.has_git)
+        .map(|p| p.path.clone())
+        .collect();
+    let statuses = git::get_statuses(&paths);
+    if opts.json {
+        return print_json(&statuses);
+    }
+    let now = chrono::Utc::now().timestamp();
+    let rows: Vec<Vec<String>> = statuses
+        .iter()
+        .map(|s| {
+            let mut changes = if s.is_dirty {
+                format!(
+                    "+{} ~{} ?{}",
+                    s.staged_count, s.modified_count, s.untracked_count
+                )
+            } else {
+                "clean".to_string()
+            };
+            if s.conflicted_count > 0 {
+                changes.push_str(&format!(" !{}", s.conflicted_count));
+            }
+            let branch = match &s.detached_at {
+                Some(oid) => format!("(detached at {})", oid),
+                None => s.branch.clone(),
+            };
+            let state = match s.operation {
+                git::GitOperation::None => String::new(),
+                op => op.label().to_string(),
+            };
+            let fetched = match s.fetched_at {
+                Some(at) => git::relative_date(at, now),
+                None if s.remote_url.is_empty() => String::new(),
+                None => "never".to_string(),
+            };
+            vec![
+                s.project_path.clone(),
+                branch,
+                state,
+                changes,
+                format!("↑{} ↓{}", s.ahead, s.behind),
+                fetched,
+                s.last_commit_date.clone(),
+            ]
+        })
+        .collect();
+    print_table(
+        &[
+            "PROJECT",
+            "BRANCH",
+            "STATE",
+            "CHANGES",
+            "REMOTE",
+            "FETCHED",
+            "LAST COMMIT",
+        ],
+        &rows,
+    );
+    Ok(0)
+}
+
+fn cmd_branches(db: &Database, opts: &Options) -> Result<i32, String> {
+    let projects = scan_target_projects(db, opts)?;
+    let paths: Vec<String> = projects
+        .iter()
+        .filter(|p| p.has_git)
+        .map(|p| p.path.clone())
+        .collect();
+    let report = branches::report(&paths);
+    if opts.json {
+        return print_json(&report);
+    }
+    let mut rows = Vec::new();
+    for repo in &report.repos {
+        if let Some(e) = &repo.error {
+            rows.push(vec![
+                repo.project_path.clone(),
+                String::new(),
+                format!("error: {}", e),
+            ]);
+            continue;
+        }
+        for branch in &repo.branches {
+            let mut flags = Vec::new();
+            if branch.ahead > 0 {
+                flags.push(format!("{} unpushed", branch.ahead));
+            }
+            if branch.unique_commits > 0 {
+                flags.push(format!("{} local-only", branch.unique_commits));
+            }
+            if branch.upstream_gone {
+                flags.push("upstream gone".to_string());
+            }
+            if branch.merged {
+                flags.push("merged".to_string());
+            }
+            if branch.stale {
+                flags.push("stale".to_string());
+            }
+            rows.push(vec![
+                repo.project_path.clone(),
+                branch.name.clone(),
+                flags.join(", "),
+                branch.upstream.clone(),
+                format!("↑{} ↓{}", branch.ahead, branch.behind),
+                branch.last_commit_date.clone(),
+            ]);
+        }
+    }
+    print_table(
+        &[
+            "PROJECT",
+            "BRANCH",
+            "STATUS",
+            "UPSTREAM",
+            "REMOTE",
+            "LAST COMMIT",
+        ],
+        &rows,
+    );
+    write_stdout(&format!(
+        "\n{} with unpushed work, {} merged and safe to delete, {} stale\n",
+        report.unpushed_count, report.deletable_count, report.stale_count
+    ));
+    Ok(0)
+}
+
+fn cmd_fetch(db: &Database, opts: &Options) -> Result<i32, String> {
+    let projects = scan_target_projects(db, opts)?;
+    let paths: Vec<String> = projects
+        .iter()
+        .filter(|p| p.has_git)
+        .map(|p| p.path.clone())
+        .collect();
+    let options = fetch::FetchOptions {
+        fast_forward: opts.fast_forward,
+        concurrency: opts.jobs.unwrap_or(fetch::DEFAULT_FETCH_CONCURRENCY),
+    };
+    let summary = fetch::fetch_all(&paths, options, |progress| {
+        if !opts.json {
+            let result = &progress.result;
+            eprintln!(
+                "[{}/{}] {}: {}",
+                progress.completed,
+                progress.total,
+                result.project_path,
+                result.outcome.label()
+            );
+        }
+    });
+    let failed = !summary.failures.is_empty() || !summary.auth_errors.is_empty();
+    if opts.json {
+        print_json(&summary)?;
+        return Ok(if failed { 1 } else { 0 });
+    }
+
+    let rows: Vec<Vec<String>> = summary
+        .results
+        .iter()
+        .map(|r| {
+            vec![
+                r.project_path.clone(),
+                r.branch.clone(),
+                r.outcome.label().to_string(),
+                format!("↑{} ↓{}", r.ahead, r.behind),
+                r.message.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_table(&["PROJECT", "BRANCH", "RESULT", "REMOTE", "DETAILS"], &rows);
+    write_stdout(&format!(
+        "\n{} of {} fetched, {} fast-forwarded, {} skipped (dirty), {} auth errors, {} failed\n",
+        summary.fetched,
+        summary.total,
+        summary.fast_forwarded.len(),
+        summary.skipped_dirty.len(),
+        summary.auth_errors.len(),
+        summary.failures.len()
+    ));
+    Ok(if failed { 1 } else { 0 })
+}
+
+fn cmd_diagnostics(db: &Database, opts: &Options) -> Result<i32, String> {
+    let workspaces = db.get_workspaces().unwrap_or_default();
+    let projects = if workspaces.is_empty() {
+        Vec::new()
+    } else {
+        scan_target_projects(db, opts)?
+    };
+    let report = diagnostics::scan(&diagnostics::ProjectScope::new(workspaces, projects));
+    let _ = db.record_scan("diagnostics", &report);
+    diagnostics_cmds::sync_issues(db, &report);
+
+    let has_errors = report
+        .items
+        .iter()
+        .any(|i| matches!(i.severity, diagnostics::Severity::Error));
+
+    if opts.json {
+        print_json(&report)?;
+    } else {
+        let rows: Vec<Vec<String>> = report
+            .items
+            .iter()
+            .map(|i| {
+                vec![
+                    i.severity.as_str().to_string(),
+                    i.category.clone(),
+                    i.title.clone(),
+                    i.fix_label.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        print_table(&["SEVERITY", "CATEGORY", "TITLE", "FIX"], &rows);
+    }
+    Ok(if has_errors { 1 } else { 0 })
+}
+
+fn cmd_issues(db: &Database, opts: &Options) -> Result<i32, String> {
+    let issues = db.get_issues(opts.status.as_deref())?;
+    if opts.json {
+        return print_json(&issues);
+    }
+    let rows: Vec<Vec<String>> = issues
+        .iter()
+        .map(|i| {
+            vec![
+                i.status.clone(),
+                i.severity.clone(),
+                i.category.clone(),
+                i.title.clone(),
+                i.last_seen.clone(),
+            ]
+        })
+        .collect();
+    print_table(
+        &["STATUS", "SEVERITY", "CATEGORY", "TITLE", "LAST SEEN"],
+        &rows,
+    );
+    Ok(0)
+}
+
+fn cmd_workspaces(db: &Database, opts: &Options) -> Result<i32, String> {
+    match opts.args.first().map(String::as_str) {
+        None | Some("list") => {}
+        Some("add") => {
+            let dir = opts
+                .args
+                .get(1)
+                .ok_or("workspaces add requires a directory")?;
+            db.add_workspace(&absolute(dir))?;
+        }
+        Some("remove") => {
+            let dir = opts
+                .args
+                .get(1)
+                .ok_or("workspaces remove requires a directory")?;
+            db.remove_workspace(&absolute(dir))?;
+        }
+        Some(action @ ("deep" | "shallow")) => {
+            let dir = opts
+                .args
+                .get(1)
+                .ok_or_else(|| format!("workspaces {} requires a directory", action))?;
+            db.set_workspace_deep_discovery(&absolute(dir), action == "deep")?;
+        }
+        Some(other) => return Err(format!("Unknown workspaces action: {}", other)),
+    }
+
+    let paths = db.get_workspaces()?;
+    if opts.json {
+        return print_json(&paths);
+    }
+    let deep = db.get_deep_discovery_workspaces()?;
+    let rows: Vec<Vec<String>> = paths
+        .into_iter()
+        .map(|p| {
+            let mode = if deep.contains(&p) { "deep" } else { "" };
+            vec![p, mode.to_string()]
+        })
+        .collect();
+    print_table(&["WORKSPACE", "DISCOVERY"], &rows);
+    Ok(0)
+}
+
+fn cmd_manifest(opts: &Options) -> Result<i32, String> {
+    match opts.args.first().map(String::as_str) {
+        Some("export") => {
+            let json = MachineManifest::capture().to_json()?;
+            match opts.args.get(1) {
+                Some(file) => std::fs::write(file, json)
+                    .map_err(|e| format!("Failed to write {}: {}", file, e))?,
+                None => write_stdout(&format!("{}\n", json)),
+            }
+            Ok(0)
+        }
+        Some("compare") => {
+            let file = opts.args.get(1).ok_or("manifest compare requires a file")?;
+            let json = std::fs::read_to_string(file)
+                .map_err(|e| format!("Failed to read {}: {}", file, e))?;
+            let reference = MachineManifest::from_json(&json)?;
+            let result = manifest::compare(&reference, &MachineManifest::capture());
+            let code = if result.gaps.is_empty() { 0 } else { 1 };
+
+            if opts.json {
+                print_json(&result)?;
+                return Ok(code);
+            }
+            let rows: Vec<Vec<String>> = result
+                .gaps
+                .iter()
+                .map(|g| {
+                    let status = match g.kind {
+                        GapKind::Missing => "missing",
+                        GapKind::VersionMismatch => "different",
+                    };
+                    let name = match &g.group {
+                        Some(group) => format!("{}/{}", group, g.name),
+                        None => g.name.clone(),
+                    };
+                    vec![
+                        status.to_string(),
+                        g.category.clone(),
+                        name,
+                        g.expected.clone().unwrap_or_default(),
+                        g.actual.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect();
+            print_table(&["STATUS", "CATEGORY", "NAME", "EXPECTED", "ACTUAL"], &rows);
+            Ok(code)
+        }
+        Some(other) => Err(format!("Unknown manifest action: {}", other)),
+        None => Err("manifest requires an action: export or compare".to_string()),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Output helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn print_json<T: Serialize>(data: &T) -> Result<i32, String> {
+    let json = serde_json::to_string_pretty(data)
+        .map_err(|e| format!("Failed to serialize output: {}", e))?;
+    write_stdout(&format!("{}\n", json));
+    Ok(0)
+}
+
+fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    write_stdout(&format_table(headers, rows));
+}
+
+/// Like `print!`, but a closed pipe (`houston-cli path | head`) is not a panic.
+fn write_stdout(text: &str) {
+    use std::io::Write;
+    let mut out = std::io::stdout().lock();
+    let _ = out.write_all(text.as_bytes()).and_then(|_| out.flush());
+}
+
+/// Left-aligned columns separated by two spaces; the last column is not padded.
+fn format_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if let Some(w) = widths.get_mut(i) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| -> String {
+        let last = cells.len().saturating_sub(1);
+        let mut line = String::new();
+        for (i, cell) in cells.iter().enumerate() {
+            line.push_str(cell);
+            if i < last {
+                let pad = widths[i].saturating_sub(cell.chars().count()) + 2;
+                line.push_str(&" ".repeat(pad));
+            }
+        }
+        line.push('\n');
+        line
+    };
+
+    let mut out = format_row(headers.to_vec());
+    for row in rows {
+        out.push_str(&format_row(row.iter().map(String::as_str).collect()));
+    }
+    out
+}
+
+fn truncate(value: &str, max: usize) -> String {
+    if value.chars().count() <= max {
+        value.to_string()
+    } else {
+        let cut: String = value.chars().take(max.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    }
+}
+
+/// Workspaces are stored as absolute paths, so resolve relative CLI input
+/// against the current directory.
+fn absolute(dir: &str) -> String {
+    let p = PathBuf::from(dir);
+    let abs = if p.is_absolute() {
+        p
+    } else {
+        std::env::current_dir().map(|cwd| cwd.join(&p)).unwrap_or(p)
+    };
+    std::fs::canonicalize(&abs)
+        .unwrap_or(abs)
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_global_flags_anywhere() {
+        let opts = parse_args(&args(&["projects", "--json", "~/code", "--depth", "20"])).unwrap();
+        assert!(opts.json);
+        assert_eq!(opts.command.as_deref(), Some("projects"));
+        assert_eq!(opts.args, vec!["~/code".to_string()]);
+        assert_eq!(opts.depth, Some(10));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_and_incomplete_flags() {
+        assert!(parse_args(&args(&["system", "--verbose"])).is_err());
+        assert!(parse_args(&args(&["issues", "--status"])).is_err());
+        assert!(parse_args(&args(&["projects", "--depth", "deep"])).is_err());
+        assert!(parse_args(&args(&["fetch", "--jobs"])).is_err());
+    }
+
+    #[test]
+    fn test_format_table_aligns_columns() {
+        let rows = vec![
+            vec!["node".to_string(), "22.1.0".to_string()],
+            vec!["python".to_string(), "3.12".to_string()],
+        ];
+        let table = format_table(&["LANGUAGE", "VERSION"], &rows);
+        assert_eq!(
+            table,
+            "LANGUAGE  VERSION\nnode      22.1.0\npython    3.12\n"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::demo;
 use crate::scanners::ai_tools;
+use crate::scanners::process::{ProcessRunner, INSTALL_TIMEOUT};
 use crate::state::AppState;
 use tauri::State;
 
@@ -77,18 +78,20 @@ pub fn update_ai_tool(
         };
     };
 
-    let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    let result = match std::process::Command::new(&cmd).args(&args_refs).output() {
+    let result = match ProcessRunner::new(&cmd)
+        .args(&args)
+        .timeout(INSTALL_TIMEOUT)
+        .run()
+    {
         Ok(o) => {
-            let stdout = String::from_utf8_lossy(&o.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&o.stderr).to_string();
-            let combined = if stderr.is_empty() {
-                stdout
+            let success = o.success();
+            let combined = if o.stderr.is_empty() {
+                o.stdout
             } else {
-                format!("{}\n{}", stdout, stderr)
+                format!("{}\n{}", o.stdout, o.stderr)
             };
 
-            if o.status.success() {
+            if success {
                 FixResult {
                     success: true,
                     message: format!("Successfully updated {}", tool_name),
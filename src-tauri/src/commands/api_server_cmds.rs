@@ -0,0 +1,65 @@
+use crate::api_server::{self, ApiServerStatus};
+use crate::state::AppState;
+use tauri::{AppHandle, Manager};
+
+fn status(app: &AppHandle, error: Option<String>) -> Result<ApiServerStatus, String> {
+    let state = app.state::<AppState>();
+    let (enabled, configured_port, token) = {
+        let db = state.db.lock().unwrap();
+        (
+            api_server::is_enabled(&db),
+            api_server::configured_port(&db),
+            api_server::ensure_token(&db)?,
+        )
+    };
+    let running_port = state.api_server.lock().unwrap().as_ref().map(|h| h.port);
+    Ok(ApiServerStatus {
+        enabled,
+        running: running_port.is_some(),
+        port: running_port.unwrap_or(configured_port),
+        token,
+        error,
+    })
+}
+
+#[tauri::command]
+pub fn get_api_server_status(app: AppHandle) -> Result<ApiServerStatus, String> {
+    status(&app, None)
+}
+
+#[tauri::command]
+pub fn set_api_server_enabled(app: AppHandle, enabled: bool) -> Result<ApiServerStatus, String> {
+    {
+        let state = app.state::<AppState>();
+        let db = state.db.lock().unwrap();
+        db.set_setting("api_server_enabled", if enabled { "true" } else { "false" })?;
+    }
+
+    // Always stop first so a changed `api_server_port` takes effect on re-enable
+    api_server::stop(&app);
+    let error = if enabled {
+        api_server::start(&app).err()
+    } else {
+        None
+    };
+    status(&app, error)
+}
+
+#[tauri::command]
+pub fn regenerate_api_token(app: AppHandle) -> Result<ApiServerStatus, String> {
+    {
+        let state = app.state::<AppState>();
+        let db = state.db.lock().unwrap();
+        db.set_setting("api_token", &api_server::generate_token())?;
+    }
+
+    // The listener captured the old token; restart it if it's running
+    let running = app.state::<AppState>().api_server.lock().unwrap().is_some();
+    let error = if running {
+        api_server::stop(&app);
+        api_server::start(&app).err()
+    } else {
+        None
+    };
+    status(&app, error)
+}
@@ -0,0 +1,67 @@
+//! Branch inventory Tauri commands.
+
+use crate::commands::workspace_cmds;
+use crate::demo;
+use crate::scanners::branches::{self, BranchRef, BranchReport, DeleteMergedResult, RepoBranches};
+use crate::state::AppState;
+use tauri::State;
+
+/// Git repositories among the cached projects, scanning if there are none yet.
+pub(crate) fn repo_paths(state: &State<'_, AppState>) -> Vec<String> {
+    let cached = state.project_cache.lock().unwrap().get();
+    let projects = match cached {
+        Some(projects) => projects,
+        None => workspace_cmds::scan_projects(state.clone()),
+    };
+    projects
+        .into_iter()
+        .filter(|p| p.has_git)
+        .map(|p| p.path)
+        .collect()
+}
+
+/// Every local branch in every workspace repository.
+#[tauri::command]
+pub fn get_branch_report(state: State<'_, AppState>) -> BranchReport {
+    if demo::is_enabled() {
+        return BranchReport::default();
+    }
+    branches::report(&repo_paths(&state))
+}
+
+#[tauri::command]
+pub fn get_project_branches(project_path: String) -> Option<RepoBranches> {
+    if demo::is_enabled() {
+        return None;
+    }
+    Some(branches::inventory(&project_path))
+}
+
+/// Delete merged branches in one repository, or in all of them when
+/// `project_path` is omitted. `dry_run` only lists what would be deleted;
+/// passing that list back as `branches` deletes no more than it showed.
+#[tauri::command]
+pub fn delete_merged_branches(
+    state: State<'_, AppState>,
+    project_path: Option<String>,
+    dry_run: bool,
+    branches: Option<Vec<BranchRef>>,
+) -> DeleteMergedResult {
+    if demo::is_enabled() {
+        return DeleteMergedResult {
+            dry_run,
+            deleted: Vec::new(),
+            errors: Vec::new(),
+        };
+    }
+
+    let paths = match project_path {
+        Some(path) => vec![path],
+        None => repo_paths(&state),
+    };
+    let result = branches::delete_merged(&paths, dry_run, branches.as_deref());
+    if !dry_run && !result.deleted.is_empty() {
+        state.git_cache.lock().unwrap().invalidate();
+    }
+    result
+}
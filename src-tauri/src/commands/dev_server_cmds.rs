@@ -1,12 +1,17 @@
+use crate::demo;
+use crate::scanners::dev_commands::{self, DevCommandOptions};
 use crate::scanners::dev_servers::{self, DevServerReport};
 use crate::state::AppState;
+use crate::supervisor::{ManagedServer, OutputLine};
+use std::path::Path;
 use tauri::State;
 
 #[tauri::command]
 pub fn get_dev_servers(state: State<'_, AppState>) -> DevServerReport {
     let mut cache = state.dev_server_cache.lock().unwrap();
-    if let Some(cached) = cache.get() {
+    if let Some(mut cached) = cache.get() {
         state.dev_server_stats.record_hit();
+        state.supervisor.merge_into(&mut cached.servers);
         return cached;
     }
     let start = std::time::Instant::now();
@@ -25,7 +30,8 @@ pub fn get_dev_servers(state: State<'_, AppState>) -> DevServerReport {
         .dev_server_stats
         .record_miss(start.elapsed().as_millis() as u64);
     cache.set(report.clone());
-    report
+    drop(cache);
+    with_managed(&state, report)
 }
 
 #[tauri::command]
@@ -48,23 +54,123 @@ pub fn refresh_dev_servers(state: State<'_, AppState>) -> DevServerReport {
         .dev_server_stats
         .record_miss(start.elapsed().as_millis() as u64);
     cache.set(report.clone());
+    drop(cache);
+    with_managed(&state, report)
+}
+
+/// Scan results are cached without supervisor state, which changes far more
+/// often than the TTL.
+fn with_managed(state: &AppState, mut report: DevServerReport) -> DevServerReport {
+    state.supervisor.merge_into(&mut report.servers);
     report
 }
 
 #[tauri::command]
 pub fn stop_dev_server(pid: u32, state: State<'_, AppState>) -> Result<(), String> {
-    dev_servers::stop_dev_server(pid)?;
+    // Stopping a managed server through the supervisor keeps it from being
+    // reported as crashed (and auto-restarted)
+    match state.supervisor.owner_of(pid) {
+        Some(managed) => {
+            state.supervisor.stop(managed.id)?;
+        }
+        None => dev_servers::stop_dev_server(pid)?,
+    }
     state.dev_server_cache.lock().unwrap().invalidate();
     Ok(())
 }
 
+/// Start a dev server under the supervisor. Without a command, the saved
+/// choice for the project (or the best candidate) is used; an explicit
+/// command becomes the saved choice.
 #[tauri::command]
 pub fn start_dev_server(
+    project_path: String,
+    command: Option<String>,
+    auto_restart: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<ManagedServer, String> {
+    let command = match command {
+        Some(command) => {
+            state
+                .db
+                .lock()
+                .unwrap()
+                .set_dev_command(&project_path, &command)?;
+            command
+        }
+        None => dev_command_options(&project_path, &state)
+            .default_command()
+            .map(str::to_string)
+            .ok_or_else(|| format!("No start command found for {}", project_path))?,
+    };
+    let server = state
+        .supervisor
+        .start(&project_path, &command, auto_restart.unwrap_or(false))?;
+    state.dev_server_cache.lock().unwrap().invalidate();
+    Ok(server)
+}
+
+fn dev_command_options(project_path: &str, state: &AppState) -> DevCommandOptions {
+    let saved = state
+        .db
+        .lock()
+        .unwrap()
+        .get_dev_command(project_path)
+        .ok()
+        .flatten();
+    DevCommandOptions {
+        candidates: dev_commands::candidates(Path::new(project_path)),
+        saved,
+    }
+}
+
+#[tauri::command]
+pub fn get_dev_commands(project_path: String, state: State<'_, AppState>) -> DevCommandOptions {
+    dev_command_options(&project_path, &state)
+}
+
+/// Save (or with `None`, forget) the start command for a project.
+#[tauri::command]
+pub fn set_dev_command(
     project_path: String,
     command: Option<String>,
     state: State<'_, AppState>,
-) -> Result<dev_servers::DevServer, String> {
-    let server = dev_servers::start_dev_server(&project_path, command.as_deref())?;
+) -> Result<(), String> {
+    let db = state.db.lock().unwrap();
+    match command.filter(|c| !c.trim().is_empty()) {
+        Some(command) => db.set_dev_command(&project_path, &command),
+        None => db.delete_dev_command(&project_path),
+    }
+}
+
+#[tauri::command]
+pub fn restart_dev_server(id: u32, state: State<'_, AppState>) -> Result<ManagedServer, String> {
+    let server = state.supervisor.restart(id)?;
     state.dev_server_cache.lock().unwrap().invalidate();
     Ok(server)
 }
+
+#[tauri::command]
+pub fn get_managed_servers(state: State<'_, AppState>) -> Vec<ManagedServer> {
+    if demo::is_enabled() {
+        return Vec::new();
+    }
+    state.supervisor.list()
+}
+
+#[tauri::command]
+pub fn get_dev_server_output(
+    id: u32,
+    state: State<'_, AppState>,
+) -> Result<Vec<OutputLine>, String> {
+    state.supervisor.logs(id)
+}
+
+#[tauri::command]
+pub fn set_dev_server_auto_restart(
+    id: u32,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<ManagedServer, String> {
+    state.supervisor.set_auto_restart(id, enabled)
+}
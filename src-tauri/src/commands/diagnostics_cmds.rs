@@ -1,21 +1,23 @@
+use crate::commands::workspace_cmds;
+use crate::db::Database;
 use crate::demo;
 use crate::scanners::diagnostics;
 use crate::state::AppState;
 use tauri::State;
 
-fn sync_issues_to_db(state: &State<'_, AppState>, report: &diagnostics::DiagnosticReport) {
-    let db = state.db.lock().unwrap();
-
+/// Upsert the report's items as tracked issues and resolve the ones that
+/// disappeared. Shared with the headless CLI so both see the same issue list.
+pub(crate) fn sync_issues(db: &Database, report: &diagnostics::DiagnosticReport) {
     // Upsert each diagnostic item
     let mut current_ids: Vec<&str> = Vec::new();
     for item in &report.items {
-        let severity_str = match item.severity {
-            diagnostics::Severity::Error => "error",
-            diagnostics::Severity::Warning => "warning",
-            diagnostics::Severity::Info => "info",
-            diagnostics::Severity::Suggestion => "suggestion",
-        };
-        let _ = db.upsert_issue(&item.id, &item.category, severity_str, &item.title, &item.description);
+        let _ = db.upsert_issue(
+            &item.id,
+            &item.category,
+            item.severity.as_str(),
+            &item.title,
+            &item.description,
+        );
         current_ids.push(&item.id);
     }
 
@@ -23,6 +25,27 @@ fn sync_issues_to_db(state: &State<'_, AppState>, report: &diagnostics::Diagnost
     let _ = db.resolve_missing_issues(&current_ids);
 }
 
+/// Configured workspaces plus the projects in them, scanning if none are
+/// cached yet (the first run at startup).
+pub(crate) fn project_scope(state: &State<'_, AppState>) -> diagnostics::ProjectScope {
+    let workspaces = state.workspace_paths.lock().unwrap().clone();
+    let cached = state.project_cache.lock().unwrap().get();
+    let projects = match cached {
+        Some(projects) => projects,
+        None => workspace_cmds::scan_projects(state.clone()),
+    };
+    let mut scope = diagnostics::ProjectScope::new(workspaces, projects);
+    // The policy check reuses these rather than scanning again
+    scope.languages = state.language_cache.lock().unwrap().get();
+    scope.packages = state.package_cache.lock().unwrap().get();
+    scope
+}
+
+fn sync_issues_to_db(state: &State<'_, AppState>, report: &diagnostics::DiagnosticReport) {
+    let db = state.db.lock().unwrap();
+    sync_issues(&db, report);
+}
+
 #[tauri::command]
 pub fn get_diagnostics(state: State<'_, AppState>) -> diagnostics::DiagnosticReport {
     if demo::is_enabled() {
@@ -35,7 +58,7 @@ pub fn get_diagnostics(state: State<'_, AppState>) -> diagnostics::DiagnosticRep
         return cached;
     }
     let start = std::time::Instant::now();
-    let report = diagnostics::scan();
+    let report = diagnostics::scan(&project_scope(&state));
     state
         .diagnostics_stats
         .record_miss(start.elapsed().as_millis() as u64);
@@ -55,7 +78,7 @@ pub fn refresh_diagnostics(state: State<'_, AppState>) -> diagnostics::Diagnosti
     let mut cache = state.diagnostics_cache.lock().unwrap();
     cache.invalidate();
     let start = std::time::Instant::now();
-    let report = diagnostics::scan();
+    let report = diagnostics::scan(&project_scope(&state));
     state
         .diagnostics_stats
         .record_miss(start.elapsed().as_millis() as u64);
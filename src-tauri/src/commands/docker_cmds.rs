@@ -1,9 +1,15 @@
 //! Docker-related Tauri commands.
 
+use crate::commands::{dev_server_cmds, workspace_cmds};
 use crate::demo;
-use crate::scanners::docker::{self, DockerStatus};
+use crate::scanners::compose::{self, ComposeAction, ComposeStack};
+use crate::scanners::docker::{self, DockerStatus, LogFollowOptions, LogLine};
 use crate::state::AppState;
-use tauri::State;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Tells a log subscription apart from a later one for the same container.
+static NEXT_LOG_STREAM_ID: AtomicU64 = AtomicU64::new(1);
 
 /// Only write to SQLite when container state actually changes
 fn maybe_record_docker_scan(state: &State<'_, AppState>, status: &DockerStatus) {
@@ -21,6 +27,20 @@ fn maybe_record_docker_scan(state: &State<'_, AppState>, status: &DockerStatus)
     }
 }
 
+/// Scan, then tie Compose projects to the workspace projects already discovered
+fn scan_linked(state: &State<'_, AppState>) -> DockerStatus {
+    let mut status = docker::scan();
+    let workspaces = state.workspace_paths.lock().unwrap().clone();
+    let projects = state
+        .project_cache
+        .lock()
+        .unwrap()
+        .get()
+        .unwrap_or_default();
+    compose::link_projects(&mut status.compose_projects, &workspaces, &projects);
+    status
+}
+
 #[tauri::command]
 pub fn get_docker_status(state: State<'_, AppState>) -> DockerStatus {
     if demo::is_enabled() {
@@ -33,7 +53,7 @@ pub fn get_docker_status(state: State<'_, AppState>) -> DockerStatus {
         return cached;
     }
     let start = std::time::Instant::now();
-    let status = docker::scan();
+    let status = scan_linked(&state);
     state
         .docker_stats
         .record_miss(start.elapsed().as_millis() as u64);
@@ -52,7 +72,7 @@ pub fn refresh_docker_status(state: State<'_, AppState>) -> DockerStatus {
     let mut cache = state.docker_cache.lock().unwrap();
     cache.invalidate();
     let start = std::time::Instant::now();
-    let status = docker::scan();
+    let status = scan_linked(&state);
     state
         .docker_stats
         .record_miss(start.elapsed().as_millis() as u64);
@@ -98,8 +118,134 @@ pub fn restart_docker_container(
     Ok(())
 }
 
+/// Run `docker compose up/down/restart/pull` (or recreate one service) for a
+/// project from the last scan, or one only known from a workspace compose
+/// file. Returns the CLI output.
+#[tauri::command]
+pub fn run_compose_action(
+    state: State<'_, AppState>,
+    project: String,
+    action: ComposeAction,
+    service: Option<String>,
+) -> Result<String, String> {
+    if demo::is_enabled() {
+        return Ok(String::new());
+    }
+
+    let status = get_docker_status(state.clone());
+    let target = status
+        .compose_projects
+        .into_iter()
+        .find(|p| p.name == project)
+        .or_else(|| {
+            let projects = state.project_cache.lock().unwrap().get()?;
+            projects
+                .iter()
+                .filter(|p| p.compose.as_ref().is_some_and(|c| c.project_name == project))
+                .find_map(compose::project_from_file)
+        })
+        .ok_or_else(|| format!("Compose project not found: {}", project))?;
+    let output = compose::run_action(&target, action, service.as_deref())?;
+    state.docker_cache.lock().unwrap().invalidate();
+    Ok(output)
+}
+
+/// Compose files in workspace projects, with which services are up and any
+/// ports a stopped service would fail to bind.
+#[tauri::command]
+pub fn get_compose_stacks(state: State<'_, AppState>) -> Vec<ComposeStack> {
+    if demo::is_enabled() {
+        return compose::stacks(&demo::mock_projects(), &demo::mock_docker_status(), &[]);
+    }
+
+    let cached = state.project_cache.lock().unwrap().get();
+    let projects = match cached {
+        Some(projects) => projects,
+        None => workspace_cmds::scan_projects(state.clone()),
+    };
+    if projects.iter().all(|p| p.compose.is_none()) {
+        return Vec::new();
+    }
+    let docker = get_docker_status(state.clone());
+    let dev_servers = dev_server_cmds::get_dev_servers(state.clone());
+    compose::stacks(&projects, &docker, &dev_servers.servers)
+}
+
 #[tauri::command]
 pub fn get_docker_container_logs(container_id: String, tail: Option<usize>) -> Result<Vec<String>, String> {
     let tail = tail.unwrap_or(100);
     docker::get_logs(&container_id, tail)
 }
+
+/// Follow a container's logs. New lines arrive as `docker://logs/<id>`
+/// events carrying a `LogLine[]`; `docker://logs-ended/<id>` fires once when
+/// the stream closes on its own, with the error message if there was one.
+/// Subscribing again replaces the previous stream for that container.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn subscribe_container_logs(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    container_id: String,
+    stdout: Option<bool>,
+    stderr: Option<bool>,
+    timestamps: Option<bool>,
+    since: Option<String>,
+    tail: Option<usize>,
+) -> Result<(), String> {
+    if demo::is_enabled() {
+        return Ok(());
+    }
+
+    let since = match since {
+        Some(s) => docker::parse_since(&s, chrono::Utc::now().timestamp())?,
+        None => 0,
+    };
+    let opts = LogFollowOptions {
+        stdout: stdout.unwrap_or(true),
+        stderr: stderr.unwrap_or(true),
+        timestamps: timestamps.unwrap_or(false),
+        since,
+        tail: tail.or(Some(100)),
+    };
+
+    let id = NEXT_LOG_STREAM_ID.fetch_add(1, Ordering::SeqCst);
+    let lines_event = format!("docker://logs/{}", container_id);
+    let ended_event = format!("docker://logs-ended/{}", container_id);
+    let lines_app = app.clone();
+    let ended_container = container_id.clone();
+    // Held until the stream is registered, so one that ends right away still
+    // finds its entry to remove
+    let mut streams = state.log_streams.lock().unwrap();
+    let stream = docker::follow_logs(
+        &container_id,
+        opts,
+        move |lines: Vec<LogLine>| {
+            let _ = lines_app.emit(&lines_event, lines);
+        },
+        move |error: Option<String>| {
+            let state = app.state::<AppState>();
+            let mut streams = state.log_streams.lock().unwrap();
+            // Unless a newer subscription has replaced this one
+            if streams
+                .get(&ended_container)
+                .is_some_and(|(current, _)| *current == id)
+            {
+                // Aborting only takes effect at the task's next await, and it
+                // finishes right after this
+                streams.remove(&ended_container);
+            }
+            drop(streams);
+            let _ = app.emit(&ended_event, error);
+        },
+    )?;
+    streams.insert(container_id, (id, stream));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unsubscribe_container_logs(container_id: String, state: State<'_, AppState>) {
+    if let Some((_, stream)) = state.log_streams.lock().unwrap().remove(&container_id) {
+        stream.cancel();
+    }
+}
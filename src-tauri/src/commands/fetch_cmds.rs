@@ -0,0 +1,70 @@
+//! Bulk fetch Tauri commands.
+
+use crate::commands::branch_cmds;
+use crate::demo;
+use crate::scanners::fetch::{self, FetchOptions, FetchSummary, DEFAULT_FETCH_CONCURRENCY};
+use crate::state::AppState;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+/// Carries a `FetchProgress` each time a repository finishes.
+const PROGRESS_EVENT: &str = "git://fetch-progress";
+
+/// Two overlapping runs would fetch every repository twice.
+static FETCH_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Clears `FETCH_RUNNING` when the run ends, even if it panics.
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        FETCH_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Fetch every workspace repository, optionally fast-forwarding clean
+/// branches that are only behind. Blocks until all of them are done.
+pub(crate) fn fetch_repos(
+    app: &AppHandle,
+    state: &State<'_, AppState>,
+    fast_forward: bool,
+    concurrency: Option<usize>,
+) -> Result<FetchSummary, String> {
+    if demo::is_enabled() {
+        return Ok(FetchSummary::default());
+    }
+    if FETCH_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A fetch is already running".to_string());
+    }
+    let running = RunningGuard;
+
+    let paths = branch_cmds::repo_paths(state);
+    let options = FetchOptions {
+        fast_forward,
+        concurrency: concurrency.unwrap_or(DEFAULT_FETCH_CONCURRENCY),
+    };
+    let summary = fetch::fetch_all(&paths, options, |progress| {
+        let _ = app.emit(PROGRESS_EVENT, progress);
+    });
+    drop(running);
+
+    // Ahead/behind counts and FETCH_HEAD ages have all moved
+    state.git_cache.lock().unwrap().invalidate();
+    Ok(summary)
+}
+
+/// Progress streams as `git://fetch-progress` events; the returned summary
+/// lists failures, auth errors and repos skipped for local changes.
+#[tauri::command]
+pub async fn fetch_all_repos(
+    app: AppHandle,
+    fast_forward: bool,
+    concurrency: Option<usize>,
+) -> Result<FetchSummary, String> {
+    tokio::task::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        fetch_repos(&app, &state, fast_forward, concurrency)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
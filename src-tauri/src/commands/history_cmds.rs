@@ -1,4 +1,5 @@
 use crate::db::ScanHistoryRow;
+use crate::scanners::scan_diff::{self, ScanDiff, Snapshot};
 use crate::state::AppState;
 use tauri::State;
 
@@ -20,3 +21,42 @@ pub fn get_latest_scan(
     let db = state.db.lock().unwrap();
     db.get_latest_scan(&scanner)
 }
+
+/// Compare two recorded snapshots of the same scanner.
+#[tauri::command]
+pub fn diff_scans(
+    state: State<'_, AppState>,
+    scanner: String,
+    from_id: i64,
+    to_id: i64,
+) -> Result<ScanDiff, String> {
+    let db = state.db.lock().unwrap();
+    let load = |id: i64| -> Result<ScanHistoryRow, String> {
+        let row = db
+            .get_scan(id)?
+            .ok_or_else(|| format!("Scan {} not found", id))?;
+        if row.scanner != scanner {
+            return Err(format!(
+                "Scan {} is a {} scan, not {}",
+                id, row.scanner, scanner
+            ));
+        }
+        Ok(row)
+    };
+    let from = load(from_id)?;
+    let to = load(to_id)?;
+
+    scan_diff::diff(
+        &scanner,
+        Snapshot {
+            id: from.id,
+            scanned_at: &from.scanned_at,
+            data_json: &from.data_json,
+        },
+        Snapshot {
+            id: to.id,
+            scanned_at: &to.scanned_at,
+            data_json: &to.data_json,
+        },
+    )
+}
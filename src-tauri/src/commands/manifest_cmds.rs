@@ -0,0 +1,40 @@
+use crate::commands::{ai_tools_cmds, language_cmds, package_cmds, system_cmds};
+use crate::scanners::manifest::{self, MachineManifest, ManifestComparison};
+use crate::state::AppState;
+use tauri::State;
+
+/// Build a manifest for this machine from the (cached) scanner results.
+fn local_manifest(state: &State<'_, AppState>) -> MachineManifest {
+    MachineManifest::new(
+        system_cmds::get_system_info(state.clone()),
+        language_cmds::get_languages(state.clone()),
+        system_cmds::get_path_entries(state.clone()),
+        package_cmds::get_packages(state.clone()),
+        ai_tools_cmds::get_ai_tools(state.clone()).tools,
+        manifest::scan_mcp_servers(),
+    )
+}
+
+/// Write this machine's manifest to `path` and return it.
+#[tauri::command]
+pub fn export_manifest(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<MachineManifest, String> {
+    let manifest = local_manifest(&state);
+    std::fs::write(&path, manifest.to_json()?)
+        .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    Ok(manifest)
+}
+
+/// Compare this machine against the manifest stored at `path`.
+#[tauri::command]
+pub fn compare_manifest(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<ManifestComparison, String> {
+    let json =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let reference = MachineManifest::from_json(&json)?;
+    Ok(manifest::compare(&reference, &local_manifest(&state)))
+}
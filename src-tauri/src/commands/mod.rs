@@ -1,17 +1,24 @@
 pub mod action_cmds;
 pub mod ai_tools_cmds;
+pub mod api_server_cmds;
+pub mod branch_cmds;
 pub mod changelog_cmds;
 pub mod claude_cmds;
 pub mod dev_server_cmds;
 pub mod diagnostics_cmds;
 pub mod docker_cmds;
 pub mod env_cmds;
+pub mod fetch_cmds;
 pub mod history_cmds;
 pub mod issue_cmds;
 pub mod language_cmds;
+pub mod manifest_cmds;
 pub mod package_cmds;
+pub mod port_cmds;
 pub mod project_cmds;
+pub mod registry_cmds;
 pub mod settings_cmds;
 pub mod stats_cmds;
 pub mod system_cmds;
 pub mod workspace_cmds;
+pub mod worktree_cmds;
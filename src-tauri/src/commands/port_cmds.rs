@@ -0,0 +1,41 @@
+//! Port map Tauri commands.
+
+use crate::commands::{dev_server_cmds, docker_cmds, workspace_cmds};
+use crate::demo;
+use crate::scanners::dev_servers::DevServerReport;
+use crate::scanners::ports::{self, PortMap, PortOwner};
+use crate::state::AppState;
+use tauri::State;
+
+/// Built from the cached dev server, Docker and project scans, so it's
+/// cheap enough to poll alongside them.
+#[tauri::command]
+pub fn get_port_map(state: State<'_, AppState>) -> PortMap {
+    if demo::is_enabled() {
+        let dev = DevServerReport {
+            servers: Vec::new(),
+            scanned_at: String::new(),
+            errors: Vec::new(),
+        };
+        return ports::build(&demo::mock_projects(), &dev, &demo::mock_docker_status());
+    }
+
+    let cached = state.project_cache.lock().unwrap().get();
+    let projects = match cached {
+        Some(projects) => projects,
+        None => workspace_cmds::scan_projects(state.clone()),
+    };
+    let docker = docker_cmds::get_docker_status(state.clone());
+    let dev = dev_server_cmds::get_dev_servers(state.clone());
+    ports::build(&projects, &dev, &docker)
+}
+
+/// Everything on a port, including processes the dev server scan ignores.
+#[tauri::command]
+pub fn find_port_owners(port: u16, state: State<'_, AppState>) -> Vec<PortOwner> {
+    let map = get_port_map(state);
+    if demo::is_enabled() {
+        return map.owners_of(port);
+    }
+    ports::owners_of(&map, port)
+}
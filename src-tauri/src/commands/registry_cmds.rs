@@ -0,0 +1,21 @@
+//! Detection registry Tauri commands.
+
+use crate::registry::config::{self, RegistryStatus};
+use crate::state::AppState;
+use tauri::State;
+
+/// Which registry files were loaded and what they added or got wrong.
+#[tauri::command]
+pub fn get_detection_registry() -> RegistryStatus {
+    config::status()
+}
+
+/// Re-read the registry files. Project detection and diagnostics depend on
+/// them, so both caches are dropped.
+#[tauri::command]
+pub fn reload_detection_registry(state: State<'_, AppState>) -> RegistryStatus {
+    let status = config::reload();
+    state.project_cache.lock().unwrap().invalidate();
+    state.diagnostics_cache.lock().unwrap().invalidate();
+    status
+}
@@ -1,3 +1,4 @@
+use crate::scanners::process::{ProcessRunner, QUICK_TIMEOUT};
 use crate::state::{AppState, AppStatsSnapshot, ScannerStatsSnapshot};
 use std::sync::atomic::Ordering;
 use tauri::State;
@@ -21,14 +22,12 @@ fn snapshot_scanner(
 
 fn get_memory_bytes() -> u64 {
     let pid = std::process::id();
-    std::process::Command::new("ps")
+    ProcessRunner::new("ps")
         .args(["-o", "rss=", "-p", &pid.to_string()])
-        .output()
+        .timeout(QUICK_TIMEOUT)
+        .run()
         .ok()
-        .and_then(|o| {
-            let s = String::from_utf8_lossy(&o.stdout).trim().to_string();
-            s.parse::<u64>().ok()
-        })
+        .and_then(|o| o.stdout.trim().parse::<u64>().ok())
         .map(|kb| kb * 1024) // rss is in KB, convert to bytes
         .unwrap_or(0)
 }
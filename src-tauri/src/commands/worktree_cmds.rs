@@ -0,0 +1,89 @@
+//! Worktree management Tauri commands.
+
+use crate::demo;
+use crate::scanners::worktrees::{self, WorktreeInfo, DEFAULT_WORKTREE_DIR};
+use crate::state::AppState;
+use tauri::State;
+
+/// Worktrees come and go from the project list, so the next scan starts fresh.
+fn invalidate(state: &State<'_, AppState>) {
+    state.project_cache.lock().unwrap().invalidate();
+    state.git_cache.lock().unwrap().invalidate();
+}
+
+/// Every worktree of the repository `project_path` belongs to, with dirty,
+/// locked and prunable state.
+#[tauri::command]
+pub fn list_worktrees(project_path: String) -> Result<Vec<WorktreeInfo>, String> {
+    if demo::is_enabled() {
+        return Ok(Vec::new());
+    }
+    worktrees::list(&project_path)
+}
+
+/// Check out `branch` in a new worktree. `new_branch` creates it from `base`
+/// (default: HEAD). The directory comes from the `worktree_directory` setting.
+#[tauri::command]
+pub fn create_worktree(
+    state: State<'_, AppState>,
+    project_path: String,
+    branch: String,
+    new_branch: bool,
+    base: Option<String>,
+) -> Result<WorktreeInfo, String> {
+    if demo::is_enabled() {
+        return Err("Worktrees can't be created in demo mode".to_string());
+    }
+
+    let template = {
+        let db = state.db.lock().unwrap();
+        db.get_setting("worktree_directory")
+            .ok()
+            .flatten()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_WORKTREE_DIR.to_string())
+    };
+    let worktree = worktrees::create(
+        &project_path,
+        &branch,
+        new_branch,
+        base.as_deref(),
+        &template,
+    )?;
+    invalidate(&state);
+    Ok(worktree)
+}
+
+/// Delete a linked worktree and its directory. `force` discards uncommitted
+/// changes; locked worktrees are refused either way.
+#[tauri::command]
+pub fn remove_worktree(
+    state: State<'_, AppState>,
+    project_path: String,
+    worktree_path: String,
+    force: bool,
+) -> Result<(), String> {
+    if demo::is_enabled() {
+        return Ok(());
+    }
+    worktrees::remove(&project_path, &worktree_path, force)?;
+    invalidate(&state);
+    Ok(())
+}
+
+/// Forget worktrees whose directories are gone. `dry_run` only lists them.
+#[tauri::command]
+pub fn prune_worktrees(
+    state: State<'_, AppState>,
+    project_path: String,
+    dry_run: bool,
+) -> Result<Vec<String>, String> {
+    if demo::is_enabled() {
+        return Ok(Vec::new());
+    }
+    let pruned = worktrees::prune(&project_path, dry_run)?;
+    if !dry_run && !pruned.is_empty() {
+        invalidate(&state);
+    }
+    Ok(pruned)
+}
@@ -2,7 +2,7 @@ use rusqlite::{Connection, params};
 use rusqlite_migration::{Migrations, M};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Row types for database results
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +48,15 @@ pub struct ChangelogRow {
     pub sections: Option<String>,  // JSON array (optional)
 }
 
+/// Bundle identifier from tauri.conf.json; Tauri derives the app data dir from it.
+pub const APP_IDENTIFIER: &str = "com.particularlabs.houston";
+
+/// Location of `houston.db` as resolved by Tauri's `app_data_dir()`, for
+/// callers (like the CLI) that run without a Tauri app handle.
+pub fn default_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join(APP_IDENTIFIER).join("houston.db"))
+}
+
 /// Database wrapper with all persistence operations
 pub struct Database {
     conn: Connection,
@@ -84,6 +93,8 @@ impl Database {
             M::up(include_str!("../migrations/001_initial.sql")),
             M::up(include_str!("../migrations/002_changelogs.sql")),
             M::up(include_str!("../migrations/003_changelog_summary.sql")),
+            M::up(include_str!("../migrations/004_dev_commands.sql")),
+            M::up(include_str!("../migrations/005_workspace_deep_discovery.sql")),
         ]);
 
         migrations.to_latest(&mut conn)
@@ -177,6 +188,29 @@ impl Database {
         Ok(())
     }
 
+    /// Workspaces that opted into nested project discovery
+    pub fn get_deep_discovery_workspaces(&self) -> Result<Vec<String>, String> {
+        let mut stmt = self.conn
+            .prepare("SELECT path FROM workspaces WHERE deep_discovery = 1 ORDER BY added_at")
+            .map_err(|e| e.to_string())?;
+
+        let rows = stmt.query_map([], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn set_workspace_deep_discovery(&self, path: &str, enabled: bool) -> Result<(), String> {
+        self.conn
+            .execute(
+                "UPDATE workspaces SET deep_discovery = ?2 WHERE path = ?1",
+                params![path, enabled as i32],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Scan History
     // ─────────────────────────────────────────────────────────────────────────────
@@ -245,6 +279,27 @@ impl Database {
         }
     }
 
+    pub fn get_scan(&self, id: i64) -> Result<Option<ScanHistoryRow>, String> {
+        let result = self.conn.query_row(
+            "SELECT id, scanner, data_json, scanned_at FROM scan_history WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok(ScanHistoryRow {
+                    id: row.get(0)?,
+                    scanner: row.get(1)?,
+                    data_json: row.get(2)?,
+                    scanned_at: row.get(3)?,
+                })
+            },
+        );
+
+        match result {
+            Ok(row) => Ok(Some(row)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
     fn prune_old_scans(&self, scanner: &str) -> Result<(), String> {
         // Get the limit from settings (default 50)
         let limit: i64 = self.get_setting("scan_history_limit")?
@@ -448,6 +503,44 @@ impl Database {
         Ok(())
     }
 
+    // ─────────────────────────────────────────────────────────────────────────────
+    // Dev Commands
+    // ─────────────────────────────────────────────────────────────────────────────
+
+    pub fn get_dev_command(&self, project_path: &str) -> Result<Option<String>, String> {
+        let result = self.conn.query_row(
+            "SELECT command FROM dev_commands WHERE project_path = ?1",
+            params![project_path],
+            |row| row.get(0),
+        );
+
+        match result {
+            Ok(command) => Ok(Some(command)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn set_dev_command(&self, project_path: &str, command: &str) -> Result<(), String> {
+        self.conn
+            .execute(
+                "INSERT INTO dev_commands (project_path, command) VALUES (?1, ?2)
+                 ON CONFLICT(project_path) DO UPDATE SET
+                    command = excluded.command,
+                    updated_at = datetime('now')",
+                params![project_path, command],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub fn delete_dev_command(&self, project_path: &str) -> Result<(), String> {
+        self.conn
+            .execute("DELETE FROM dev_commands WHERE project_path = ?1", params![project_path])
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
     // ─────────────────────────────────────────────────────────────────────────────
     // Database Info
     // ─────────────────────────────────────────────────────────────────────────────
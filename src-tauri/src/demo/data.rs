@@ -3,16 +3,34 @@
 
 use crate::scanners::{
     ai_tools::{AiToolInfo, AiToolsReport, InstallMethod, ToolType},
+    compose::{ComposeFile, ComposePort, ComposeService},
     diagnostics::{DiagnosticItem, DiagnosticReport, Severity},
-    docker::{ComposeProject, ContainerInfo, DockerStatus, PortBinding},
+    docker::{ComposeProject, ContainerInfo, DockerStatus, PortBinding, VolumeMount},
     environment::EnvVarInfo,
-    git::GitStatus,
+    git::{GitOperation, GitStatus},
     languages::LanguageInfo,
     packages::{PackageInfo, PackageList},
     path::PathEntry,
     system::SystemInfo,
+    version_check::{VersionCheck, VersionStatus},
     workspace::{ProjectInfo, VersionFile},
 };
+use std::collections::BTreeMap;
+
+fn demo_compose_service(name: &str, image: &str, port: u16) -> ComposeService {
+    ComposeService {
+        name: name.to_string(),
+        image: Some(image.to_string()),
+        build: false,
+        container_name: None,
+        ports: vec![ComposePort {
+            host_ip: None,
+            published: port,
+            target: port,
+            protocol: "tcp".to_string(),
+        }],
+    }
+}
 
 /// Mock system information for a generic macOS setup.
 pub fn mock_system_info() -> SystemInfo {
@@ -119,6 +137,7 @@ pub fn mock_languages() -> Vec<LanguageInfo> {
             manager: "nvm".to_string(),
             installed: true,
             icon: "node".to_string(),
+            error: None,
         },
         LanguageInfo {
             name: "Python".to_string(),
@@ -127,6 +146,7 @@ pub fn mock_languages() -> Vec<LanguageInfo> {
             manager: "pyenv".to_string(),
             installed: true,
             icon: "python".to_string(),
+            error: None,
         },
         LanguageInfo {
             name: "Rust".to_string(),
@@ -135,6 +155,7 @@ pub fn mock_languages() -> Vec<LanguageInfo> {
             manager: "rustup".to_string(),
             installed: true,
             icon: "rust".to_string(),
+            error: None,
         },
         LanguageInfo {
             name: "Go".to_string(),
@@ -143,6 +164,7 @@ pub fn mock_languages() -> Vec<LanguageInfo> {
             manager: "homebrew".to_string(),
             installed: true,
             icon: "go".to_string(),
+            error: None,
         },
         LanguageInfo {
             name: "Ruby".to_string(),
@@ -151,6 +173,7 @@ pub fn mock_languages() -> Vec<LanguageInfo> {
             manager: "homebrew".to_string(),
             installed: true,
             icon: "ruby".to_string(),
+            error: None,
         },
         LanguageInfo {
             name: "Bun".to_string(),
@@ -159,6 +182,7 @@ pub fn mock_languages() -> Vec<LanguageInfo> {
             manager: "system".to_string(),
             installed: true,
             icon: "bun".to_string(),
+            error: None,
         },
         LanguageInfo {
             name: "Java".to_string(),
@@ -167,6 +191,7 @@ pub fn mock_languages() -> Vec<LanguageInfo> {
             manager: String::new(),
             installed: false,
             icon: "java".to_string(),
+            error: None,
         },
         LanguageInfo {
             name: "PHP".to_string(),
@@ -175,6 +200,7 @@ pub fn mock_languages() -> Vec<LanguageInfo> {
             manager: String::new(),
             installed: false,
             icon: "php".to_string(),
+            error: None,
         },
         LanguageInfo {
             name: "Deno".to_string(),
@@ -183,6 +209,7 @@ pub fn mock_languages() -> Vec<LanguageInfo> {
             manager: String::new(),
             installed: false,
             icon: "deno".to_string(),
+            error: None,
         },
     ]
 }
@@ -276,6 +303,18 @@ pub fn mock_projects() -> Vec<ProjectInfo> {
                 expected_version: "20".to_string(),
                 language: "node".to_string(),
             }],
+            version_checks: vec![VersionCheck {
+                file: ".nvmrc".to_string(),
+                language: "node".to_string(),
+                expected: "20".to_string(),
+                active: Some("22.0.0".to_string()),
+                resolved: Some("20.18.0".to_string()),
+                manager: Some("nvm".to_string()),
+                status: VersionStatus::Mismatch,
+                fix: Some("nvm use".to_string()),
+            }],
+            compose: None,
+            stacks: Vec::new(),
         },
         ProjectInfo {
             name: "acme-api".to_string(),
@@ -297,6 +336,27 @@ pub fn mock_projects() -> Vec<ProjectInfo> {
                 expected_version: "18".to_string(),
                 language: "node".to_string(),
             }],
+            version_checks: vec![VersionCheck {
+                file: ".nvmrc".to_string(),
+                language: "node".to_string(),
+                expected: "18".to_string(),
+                active: Some("22.0.0".to_string()),
+                resolved: None,
+                manager: Some("nvm".to_string()),
+                status: VersionStatus::NotInstalled,
+                fix: Some("nvm install 18".to_string()),
+            }],
+            compose: Some(ComposeFile {
+                path: "/Users/developer/Projects/acme-api/docker-compose.yml".to_string(),
+                project_name: "acme-stack".to_string(),
+                services: vec![
+                    demo_compose_service("acme-postgres", "postgres:16", 5432),
+                    demo_compose_service("redis", "redis:7-alpine", 6379),
+                    demo_compose_service("api", "acme-api:dev", 3000),
+                    demo_compose_service("docs", "nginx:alpine", 3001),
+                ],
+            }),
+            stacks: Vec::new(),
         },
         ProjectInfo {
             name: "acme-mobile".to_string(),
@@ -314,6 +374,9 @@ pub fn mock_projects() -> Vec<ProjectInfo> {
             health_score: None,
             has_build_artifacts: false,
             version_files: vec![],
+            version_checks: vec![],
+            compose: None,
+            stacks: Vec::new(),
         },
         ProjectInfo {
             name: "dev-tools".to_string(),
@@ -331,6 +394,9 @@ pub fn mock_projects() -> Vec<ProjectInfo> {
             health_score: None,
             has_build_artifacts: true,
             version_files: vec![],
+            version_checks: vec![],
+            compose: None,
+            stacks: Vec::new(),
         },
         ProjectInfo {
             name: "design-system".to_string(),
@@ -348,6 +414,9 @@ pub fn mock_projects() -> Vec<ProjectInfo> {
             health_score: None,
             has_build_artifacts: true,
             version_files: vec![],
+            version_checks: vec![],
+            compose: None,
+            stacks: Vec::new(),
         },
         ProjectInfo {
             name: "data-pipeline".to_string(),
@@ -369,6 +438,18 @@ pub fn mock_projects() -> Vec<ProjectInfo> {
                 expected_version: "3.12".to_string(),
                 language: "python".to_string(),
             }],
+            version_checks: vec![VersionCheck {
+                file: ".python-version".to_string(),
+                language: "python".to_string(),
+                expected: "3.12".to_string(),
+                active: Some("3.12.1".to_string()),
+                resolved: Some("3.12.1".to_string()),
+                manager: Some("pyenv".to_string()),
+                status: VersionStatus::Ok,
+                fix: None,
+            }],
+            compose: None,
+            stacks: Vec::new(),
         },
     ]
 }
@@ -389,6 +470,13 @@ pub fn mock_git_statuses() -> Vec<GitStatus> {
             last_commit_date: "2 hours ago".to_string(),
             last_commit_epoch: Some(1739196000),
             remote_url: "https://github.com/acme/acme-web.git".to_string(),
+            error: None,
+            operation: GitOperation::None,
+            operation_since: None,
+            conflicted_count: 0,
+            detached_at: None,
+            upstream: "origin/main".to_string(),
+            fetched_at: Some(1739199600),
         },
         GitStatus {
             project_path: "/Users/developer/Projects/acme-api".to_string(),
@@ -403,6 +491,13 @@ pub fn mock_git_statuses() -> Vec<GitStatus> {
             last_commit_date: "30 minutes ago".to_string(),
             last_commit_epoch: Some(1739201400),
             remote_url: "https://github.com/acme/acme-api.git".to_string(),
+            error: None,
+            operation: GitOperation::None,
+            operation_since: None,
+            conflicted_count: 0,
+            detached_at: None,
+            upstream: "origin/feature/auth-v2".to_string(),
+            fetched_at: Some(1739199600),
         },
         GitStatus {
             project_path: "/Users/developer/Projects/acme-mobile".to_string(),
@@ -417,6 +512,13 @@ pub fn mock_git_statuses() -> Vec<GitStatus> {
             last_commit_date: "1 day ago".to_string(),
             last_commit_epoch: Some(1739116800),
             remote_url: "https://github.com/acme/acme-mobile.git".to_string(),
+            error: None,
+            operation: GitOperation::None,
+            operation_since: None,
+            conflicted_count: 0,
+            detached_at: None,
+            upstream: "origin/develop".to_string(),
+            fetched_at: Some(1738940400),
         },
         GitStatus {
             project_path: "/Users/developer/Projects/dev-tools".to_string(),
@@ -431,6 +533,13 @@ pub fn mock_git_statuses() -> Vec<GitStatus> {
             last_commit_date: "3 days ago".to_string(),
             last_commit_epoch: Some(1738944000),
             remote_url: "https://github.com/acme/dev-tools.git".to_string(),
+            error: None,
+            operation: GitOperation::None,
+            operation_since: None,
+            conflicted_count: 0,
+            detached_at: None,
+            upstream: "origin/main".to_string(),
+            fetched_at: Some(1739199600),
         },
         GitStatus {
             project_path: "/Users/developer/Projects/design-system".to_string(),
@@ -445,6 +554,13 @@ pub fn mock_git_statuses() -> Vec<GitStatus> {
             last_commit_date: "5 hours ago".to_string(),
             last_commit_epoch: Some(1739185200),
             remote_url: "https://github.com/acme/design-system.git".to_string(),
+            error: None,
+            operation: GitOperation::None,
+            operation_since: None,
+            conflicted_count: 0,
+            detached_at: None,
+            upstream: "origin/main".to_string(),
+            fetched_at: None,
         },
         GitStatus {
             project_path: "/Users/developer/Projects/data-pipeline".to_string(),
@@ -459,6 +575,13 @@ pub fn mock_git_statuses() -> Vec<GitStatus> {
             last_commit_date: "1 hour ago".to_string(),
             last_commit_epoch: Some(1739199600),
             remote_url: "https://github.com/acme/data-pipeline.git".to_string(),
+            error: None,
+            operation: GitOperation::None,
+            operation_since: None,
+            conflicted_count: 0,
+            detached_at: None,
+            upstream: "origin/main".to_string(),
+            fetched_at: Some(1738594800),
         },
     ]
 }
@@ -544,6 +667,7 @@ pub fn mock_packages() -> PackageList {
         ],
         scoop: vec![],
         chocolatey: vec![],
+        errors: vec![],
     }
 }
 
@@ -696,6 +820,7 @@ pub fn mock_diagnostics() -> DiagnosticReport {
             },
         ],
         scanned_at: chrono::Utc::now().to_rfc3339(),
+        errors: vec![],
     }
 }
 
@@ -716,6 +841,7 @@ pub fn mock_docker_status() -> DockerStatus {
                     host_port: Some(5432),
                     container_port: 5432,
                     protocol: "tcp".to_string(),
+                    host_ip: Some("0.0.0.0".to_string()),
                 }],
                 cpu_percent: 0.5,
                 memory_bytes: 48 * 1024 * 1024,
@@ -725,6 +851,27 @@ pub fn mock_docker_status() -> DockerStatus {
                 icon: "database".to_string(),
                 compose_project: Some("acme-stack".to_string()),
                 compose_service: Some("acme-postgres".to_string()),
+                health: Some("healthy".to_string()),
+                restart_count: 0,
+                exit_code: None,
+                labels: BTreeMap::from([
+                    (
+                        "com.docker.compose.project".to_string(),
+                        "acme-stack".to_string(),
+                    ),
+                    (
+                        "com.docker.compose.service".to_string(),
+                        "acme-postgres".to_string(),
+                    ),
+                ]),
+                networks: vec!["acme-stack_default".to_string()],
+                volumes: vec![VolumeMount {
+                    kind: "volume".to_string(),
+                    name: Some("acme-stack_pgdata".to_string()),
+                    source: "/var/lib/docker/volumes/acme-stack_pgdata/_data".to_string(),
+                    destination: "/var/lib/postgresql/data".to_string(),
+                    read_only: false,
+                }],
             },
             ContainerInfo {
                 id: "def456ghi789".to_string(),
@@ -737,6 +884,7 @@ pub fn mock_docker_status() -> DockerStatus {
                     host_port: Some(6379),
                     container_port: 6379,
                     protocol: "tcp".to_string(),
+                    host_ip: Some("0.0.0.0".to_string()),
                 }],
                 cpu_percent: 0.1,
                 memory_bytes: 12 * 1024 * 1024,
@@ -746,6 +894,21 @@ pub fn mock_docker_status() -> DockerStatus {
                 icon: "zap".to_string(),
                 compose_project: Some("acme-stack".to_string()),
                 compose_service: Some("redis".to_string()),
+                health: Some("healthy".to_string()),
+                restart_count: 0,
+                exit_code: None,
+                labels: BTreeMap::from([
+                    (
+                        "com.docker.compose.project".to_string(),
+                        "acme-stack".to_string(),
+                    ),
+                    (
+                        "com.docker.compose.service".to_string(),
+                        "redis".to_string(),
+                    ),
+                ]),
+                networks: vec!["acme-stack_default".to_string()],
+                volumes: vec![],
             },
             ContainerInfo {
                 id: "ghi789jkl012".to_string(),
@@ -758,6 +921,7 @@ pub fn mock_docker_status() -> DockerStatus {
                     host_port: Some(3000),
                     container_port: 3000,
                     protocol: "tcp".to_string(),
+                    host_ip: Some("0.0.0.0".to_string()),
                 }],
                 cpu_percent: 2.3,
                 memory_bytes: 156 * 1024 * 1024,
@@ -767,6 +931,21 @@ pub fn mock_docker_status() -> DockerStatus {
                 icon: "box".to_string(),
                 compose_project: Some("acme-stack".to_string()),
                 compose_service: Some("api".to_string()),
+                health: None,
+                restart_count: 0,
+                exit_code: None,
+                labels: BTreeMap::from([
+                    (
+                        "com.docker.compose.project".to_string(),
+                        "acme-stack".to_string(),
+                    ),
+                    (
+                        "com.docker.compose.service".to_string(),
+                        "api".to_string(),
+                    ),
+                ]),
+                networks: vec!["acme-stack_default".to_string()],
+                volumes: vec![],
             },
             ContainerInfo {
                 id: "jkl012mno345".to_string(),
@@ -779,6 +958,7 @@ pub fn mock_docker_status() -> DockerStatus {
                     host_port: Some(3001),
                     container_port: 3000,
                     protocol: "tcp".to_string(),
+                    host_ip: Some("0.0.0.0".to_string()),
                 }],
                 cpu_percent: 0.8,
                 memory_bytes: 96 * 1024 * 1024,
@@ -788,6 +968,18 @@ pub fn mock_docker_status() -> DockerStatus {
                 icon: "activity".to_string(),
                 compose_project: None,
                 compose_service: None,
+                health: None,
+                restart_count: 0,
+                exit_code: None,
+                labels: BTreeMap::new(),
+                networks: vec!["bridge".to_string()],
+                volumes: vec![VolumeMount {
+                    kind: "volume".to_string(),
+                    name: Some("grafana-storage".to_string()),
+                    source: "/var/lib/docker/volumes/grafana-storage/_data".to_string(),
+                    destination: "/var/lib/grafana".to_string(),
+                    read_only: false,
+                }],
             },
             ContainerInfo {
                 id: "mno345pqr678".to_string(),
@@ -805,6 +997,12 @@ pub fn mock_docker_status() -> DockerStatus {
                 icon: "globe".to_string(),
                 compose_project: None,
                 compose_service: None,
+                health: None,
+                restart_count: 0,
+                exit_code: Some(0),
+                labels: BTreeMap::new(),
+                networks: vec!["bridge".to_string()],
+                volumes: vec![],
             },
         ],
         compose_projects: vec![ComposeProject {
@@ -816,9 +1014,21 @@ pub fn mock_docker_status() -> DockerStatus {
                 "def456ghi789".to_string(),
                 "ghi789jkl012".to_string(),
             ],
+            working_dir: Some("/Users/developer/Projects/acme-api".to_string()),
+            config_files: vec!["/Users/developer/Projects/acme-api/docker-compose.yml".to_string()],
+            services: vec![
+                "acme-postgres".to_string(),
+                "api".to_string(),
+                "redis".to_string(),
+            ],
+            project_path: Some("/Users/developer/Projects/acme-api".to_string()),
+            project_name: Some("acme-api".to_string()),
         }],
         total_running: 4,
         total_stopped: 1,
         scanned_at: chrono::Utc::now().to_rfc3339(),
+        errors: vec![],
+        endpoint: Some("unix:///var/run/docker.sock".to_string()),
+        engine: Some("docker".to_string()),
     }
 }
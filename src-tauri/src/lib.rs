@@ -1,17 +1,25 @@
+mod api_server;
+pub mod cli;
 mod commands;
 mod db;
 pub mod demo;
 mod registry;
 mod scanners;
 mod state;
+mod supervisor;
+mod watcher;
 
 use commands::{
-    action_cmds, ai_tools_cmds, changelog_cmds, claude_cmds, dev_server_cmds, diagnostics_cmds,
-    docker_cmds, env_cmds, history_cmds, issue_cmds, language_cmds, package_cmds, project_cmds,
-    settings_cmds, stats_cmds, system_cmds, workspace_cmds,
+    action_cmds, ai_tools_cmds, api_server_cmds, branch_cmds, changelog_cmds, claude_cmds, dev_server_cmds,
+    diagnostics_cmds, docker_cmds, env_cmds, fetch_cmds, history_cmds, issue_cmds, language_cmds, manifest_cmds,
+    package_cmds, port_cmds, project_cmds, registry_cmds, settings_cmds, stats_cmds, system_cmds, workspace_cmds,
+    worktree_cmds,
 };
 use state::AppState;
 
+/// Used by `main` to load the login shell environment with a timeout.
+pub use scanners::process::ProcessRunner;
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -36,6 +44,8 @@ pub fn run() {
             workspace_cmds::get_workspace_paths,
             workspace_cmds::add_workspace,
             workspace_cmds::remove_workspace,
+            workspace_cmds::get_deep_discovery_workspaces,
+            workspace_cmds::set_workspace_deep_discovery,
             workspace_cmds::scan_projects,
             workspace_cmds::get_git_status,
             workspace_cmds::get_all_git_statuses,
@@ -68,6 +78,10 @@ pub fn run() {
             // History
             history_cmds::get_scan_history,
             history_cmds::get_latest_scan,
+            history_cmds::diff_scans,
+            // Manifest
+            manifest_cmds::export_manifest,
+            manifest_cmds::compare_manifest,
             // Issues
             issue_cmds::get_issue_count,
             issue_cmds::get_issues,
@@ -85,16 +99,50 @@ pub fn run() {
             docker_cmds::start_docker_container,
             docker_cmds::stop_docker_container,
             docker_cmds::restart_docker_container,
+            docker_cmds::run_compose_action,
+            docker_cmds::get_compose_stacks,
             docker_cmds::get_docker_container_logs,
+            docker_cmds::subscribe_container_logs,
+            docker_cmds::unsubscribe_container_logs,
             // Dev Servers
             dev_server_cmds::get_dev_servers,
             dev_server_cmds::refresh_dev_servers,
             dev_server_cmds::stop_dev_server,
             dev_server_cmds::start_dev_server,
+            dev_server_cmds::restart_dev_server,
+            dev_server_cmds::get_managed_servers,
+            dev_server_cmds::get_dev_server_output,
+            dev_server_cmds::set_dev_server_auto_restart,
+            dev_server_cmds::get_dev_commands,
+            dev_server_cmds::set_dev_command,
+            // Ports
+            port_cmds::get_port_map,
+            port_cmds::find_port_owners,
+            // Detection Registry
+            registry_cmds::get_detection_registry,
+            registry_cmds::reload_detection_registry,
+            // Branches
+            branch_cmds::get_branch_report,
+            branch_cmds::get_project_branches,
+            branch_cmds::delete_merged_branches,
+            // Fetch
+            fetch_cmds::fetch_all_repos,
+            // Worktrees
+            worktree_cmds::list_worktrees,
+            worktree_cmds::create_worktree,
+            worktree_cmds::remove_worktree,
+            worktree_cmds::prune_worktrees,
+            // API Server
+            api_server_cmds::get_api_server_status,
+            api_server_cmds::set_api_server_enabled,
+            api_server_cmds::regenerate_api_token,
         ])
         .setup(|app| {
             use tauri::Manager;
 
+            // Merge the user's detection registry before anything scans projects
+            registry::config::reload();
+
             // Initialize database
             let db_path = app.path().app_data_dir()?.join("houston.db");
             let _ = db::Database::backup(&db_path); // Best effort backup
@@ -103,6 +151,17 @@ pub fn run() {
 
             // Create AppState with database and register it
             app.manage(AppState::new(database));
+            app.state::<AppState>().supervisor.attach(app.handle().clone());
+
+            // Start the localhost API server if the user turned it on
+            api_server::start_if_enabled(app.handle());
+
+            // Push scanner updates on filesystem changes instead of waiting for TTLs
+            if !demo::is_enabled() && watcher::is_enabled(app.handle()) {
+                if let Err(e) = watcher::start(app.handle()) {
+                    log::warn!("{}", e);
+                }
+            }
             // Apply macOS vibrancy
             #[cfg(target_os = "macos")]
             {
@@ -161,7 +220,8 @@ pub fn run() {
                 let h = handle.clone();
                 std::thread::spawn(move || {
                     let state = h.state::<AppState>();
-                    let diag = scanners::diagnostics::scan();
+                    let scope = diagnostics_cmds::project_scope(&state);
+                    let diag = scanners::diagnostics::scan(&scope);
                     state.diagnostics_cache.lock().unwrap().set(diag);
                 });
             }
@@ -16,18 +16,18 @@ fn load_shell_env() {
     // Detect the user's login shell
     let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string());
 
-    // Ask the shell to run as an interactive login shell and print its env
-    let output = std::process::Command::new(&shell)
+    // Ask the shell to run as an interactive login shell and print its env.
+    // A profile that prompts for input would otherwise block startup.
+    let output = houston_lib::ProcessRunner::new(&shell)
         .args(["-ilc", "env"])
-        .output();
+        .run_ok();
 
     let output = match output {
-        Ok(o) if o.status.success() => o,
-        _ => return, // Fallback: keep whatever fix_path_env gave us
+        Ok(o) => o,
+        Err(_) => return, // Fallback: keep whatever fix_path_env gave us
     };
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    for line in stdout.lines() {
+    for line in output.stdout.lines() {
         // env output is KEY=VALUE (value may contain '=')
         if let Some((key, value)) = line.split_once('=') {
             // Skip empty keys and a few that shouldn't be overridden
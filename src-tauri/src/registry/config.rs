@@ -0,0 +1,580 @@
+//! User-defined registry entries loaded from a config file.
+//!
+//! Extends the built-in tables in `data.rs` without recompiling. Definitions
+//! are read from `~/.config/houston/registry.toml` and `registry.json` (same
+//! schema), validated, and merged with the builtins: extra frameworks join a
+//! language's rule list and compete on `priority`, and new languages are
+//! checked after the built-in ones.
+//!
+//! ```toml
+//! [[frameworks]]
+//! language = "JavaScript/TypeScript"
+//! name = "Acme Shell"
+//! patterns = ["\"@acme/shell\""]
+//! category = "desktop_app"
+//! priority = 101
+//!
+//! [[storage_dirs]]
+//! language = "Rust"
+//! name = ".acme-cache"
+//! category = "cache"
+//!
+//! [[languages]]
+//! name = "Gleam"
+//! manifest = "gleam.toml"
+//! lockfiles = [{ file = "manifest.toml", manager = "unknown" }]
+//! storage_dirs = [{ name = "build", category = "build" }]
+//! frameworks = [{ name = "Wisp", patterns = ["wisp"], category = "backend", priority = 50 }]
+//! ```
+//!
+//! The active registry is swapped as a whole on [`reload`]. Entries hold
+//! `&'static` data like the builtins, so user definitions are leaked once
+//! per load; files are small and reloads are rare.
+
+use super::data::REGISTRY;
+use super::types::{
+    AppCategory, DetectionSource, FrameworkRule, Language, LanguageEntry, LockfileEntry,
+    PackageManager, StorageDir,
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Categories a storage directory can be grouped under
+pub const STORAGE_CATEGORIES: &[&str] = &["dependencies", "build", "cache", "vcs"];
+
+/// One registry file: additions to built-in languages plus new languages
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryFile {
+    #[serde(default)]
+    pub frameworks: Vec<UserFramework>,
+    #[serde(default)]
+    pub storage_dirs: Vec<UserStorageDir>,
+    #[serde(default)]
+    pub languages: Vec<UserLanguage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserFramework {
+    /// Display name of the language to extend; omitted inside `[[languages]]`
+    pub language: Option<String>,
+    pub name: String,
+    /// Manifest substrings, or relative paths for `file_exists`/`directory_exists`
+    pub patterns: Vec<String>,
+    #[serde(default = "default_source")]
+    pub source: DetectionSource,
+    #[serde(default = "default_category")]
+    pub category: AppCategory,
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserStorageDir {
+    /// Display name of the language to extend; omitted inside `[[languages]]`
+    pub language: Option<String>,
+    pub name: String,
+    pub category: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserLockfile {
+    pub file: String,
+    pub manager: PackageManager,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserLanguage {
+    /// Display name; must not clash with a built-in language
+    pub name: String,
+    pub manifest: String,
+    #[serde(default)]
+    pub alt_manifests: Vec<String>,
+    #[serde(default)]
+    pub frameworks: Vec<UserFramework>,
+    #[serde(default)]
+    pub storage_dirs: Vec<UserStorageDir>,
+    #[serde(default)]
+    pub lockfiles: Vec<UserLockfile>,
+    #[serde(default = "default_manager")]
+    pub default_manager: PackageManager,
+    #[serde(default = "default_category")]
+    pub default_category: AppCategory,
+    /// Framework shown when no rule matches; defaults to `name`
+    pub default_framework: Option<String>,
+}
+
+fn default_source() -> DetectionSource {
+    DetectionSource::ManifestContent
+}
+
+fn default_category() -> AppCategory {
+    AppCategory::Library
+}
+
+fn default_priority() -> u8 {
+    50
+}
+
+fn default_manager() -> PackageManager {
+    PackageManager::Unknown
+}
+
+/// A problem with a registry file or one of its entries
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RegistryError {
+    /// File the error came from
+    pub source: String,
+    pub message: String,
+}
+
+/// What the last load picked up
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RegistryStatus {
+    /// Files searched, whether or not they exist
+    pub paths: Vec<String>,
+    /// Files that were found and parsed
+    pub loaded: Vec<String>,
+    pub languages: usize,
+    pub frameworks: usize,
+    pub storage_dirs: usize,
+    pub errors: Vec<RegistryError>,
+}
+
+struct Active {
+    entries: &'static [LanguageEntry],
+    status: RegistryStatus,
+}
+
+static ACTIVE: RwLock<Option<Active>> = RwLock::new(None);
+
+/// The registry detection runs against: the builtins until [`reload`] has
+/// been called, then the builtins merged with the user's definitions.
+pub fn entries() -> &'static [LanguageEntry] {
+    ACTIVE
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.entries)
+        .unwrap_or(REGISTRY)
+}
+
+/// Result of the last [`reload`].
+pub fn status() -> RegistryStatus {
+    ACTIVE
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.status.clone())
+        .unwrap_or_default()
+}
+
+/// `~/.config/houston/registry.toml` and `registry.json`
+pub fn user_registry_paths() -> Vec<PathBuf> {
+    dirs::home_dir()
+        .map(|h| {
+            let dir = h.join(".config").join("houston");
+            vec![dir.join("registry.toml"), dir.join("registry.json")]
+        })
+        .unwrap_or_default()
+}
+
+/// Re-read the user's registry files and make the result active.
+pub fn reload() -> RegistryStatus {
+    let (entries, status) = load(&user_registry_paths());
+    for error in &status.errors {
+        log::warn!("{}: {}", error.source, error.message);
+    }
+    *ACTIVE.write().unwrap() = Some(Active {
+        entries,
+        status: status.clone(),
+    });
+    status
+}
+
+/// Parse, validate and merge `paths` with the builtins. Missing files are
+/// skipped; a file that fails to parse is skipped whole, an invalid entry
+/// only drops that entry.
+pub fn load(paths: &[PathBuf]) -> (&'static [LanguageEntry], RegistryStatus) {
+    let mut status = RegistryStatus {
+        paths: paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect(),
+        ..Default::default()
+    };
+    let mut merged: Vec<LanguageEntry> = REGISTRY.to_vec();
+
+    for path in paths.iter().filter(|p| p.is_file()) {
+        let source = path.to_string_lossy().to_string();
+        match parse_file(path) {
+            Ok(file) => {
+                merge(&mut merged, file, &source, &mut status);
+                status.loaded.push(source);
+            }
+            Err(message) => status.errors.push(RegistryError { source, message }),
+        }
+    }
+
+    if status.loaded.is_empty() {
+        return (REGISTRY, status);
+    }
+    (merged.leak(), status)
+}
+
+fn parse_file(path: &Path) -> Result<RegistryFile, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let is_json = path.extension().is_some_and(|e| e == "json");
+    if is_json {
+        serde_json::from_str(&content).map_err(|e| e.to_string())
+    } else {
+        toml::from_str(&content).map_err(|e| e.to_string())
+    }
+}
+
+/// Fold one file into `merged`. New languages go first so top-level
+/// `[[frameworks]]` can target them too.
+fn merge(
+    merged: &mut Vec<LanguageEntry>,
+    file: RegistryFile,
+    source: &str,
+    status: &mut RegistryStatus,
+) {
+    let mut error = |message: String| {
+        status.errors.push(RegistryError {
+            source: source.to_string(),
+            message,
+        })
+    };
+    let mut added = (0, 0, 0);
+
+    for language in file.languages {
+        match build_language(language, merged) {
+            Ok(entry) => {
+                added.0 += 1;
+                added.1 += entry.frameworks.len();
+                added.2 += entry.storage_dirs.len();
+                merged.push(entry);
+            }
+            Err(message) => error(message),
+        }
+    }
+
+    for framework in file.frameworks {
+        let result = target(merged, &framework.language, "framework", &framework.name)
+            .and_then(|i| Ok((i, build_framework(framework)?)));
+        match result {
+            Ok((i, rule)) => {
+                let mut rules = merged[i].frameworks.to_vec();
+                rules.push(rule);
+                merged[i].frameworks = rules.leak();
+                added.1 += 1;
+            }
+            Err(message) => error(message),
+        }
+    }
+
+    for dir in file.storage_dirs {
+        let result = target(merged, &dir.language, "storage dir", &dir.name)
+            .and_then(|i| Ok((i, build_storage_dir(dir)?)));
+        match result {
+            Ok((i, dir)) => {
+                let mut dirs = merged[i].storage_dirs.to_vec();
+                dirs.push(dir);
+                merged[i].storage_dirs = dirs.leak();
+                added.2 += 1;
+            }
+            Err(message) => error(message),
+        }
+    }
+
+    status.languages += added.0;
+    status.frameworks += added.1;
+    status.storage_dirs += added.2;
+}
+
+/// Index of the language a top-level framework or storage dir extends
+fn target(
+    merged: &[LanguageEntry],
+    language: &Option<String>,
+    kind: &str,
+    name: &str,
+) -> Result<usize, String> {
+    let Some(language) = language else {
+        return Err(format!("{} \"{}\": missing `language`", kind, name));
+    };
+    merged
+        .iter()
+        .position(|e| e.display_name == language)
+        .ok_or_else(|| {
+            let known: Vec<&str> = merged.iter().map(|e| e.display_name).collect();
+            format!(
+                "{} \"{}\": unknown language \"{}\" (expected one of: {})",
+                kind,
+                name,
+                language,
+                known.join(", ")
+            )
+        })
+}
+
+fn build_language(
+    language: UserLanguage,
+    existing: &[LanguageEntry],
+) -> Result<LanguageEntry, String> {
+    let name = language.name.trim();
+    if name.is_empty() {
+        return Err("language: `name` is empty".to_string());
+    }
+    if existing
+        .iter()
+        .any(|e| e.display_name.eq_ignore_ascii_case(name))
+    {
+        return Err(format!(
+            "language \"{}\" already exists; add `[[frameworks]]` or `[[storage_dirs]]` with `language = \"{}\"` to extend it",
+            name, name
+        ));
+    }
+    let manifests: Vec<&str> = std::iter::once(language.manifest.as_str())
+        .chain(language.alt_manifests.iter().map(String::as_str))
+        .collect();
+    if manifests.iter().any(|m| m.trim().is_empty()) {
+        return Err(format!(
+            "language \"{}\": manifest names can't be empty",
+            name
+        ));
+    }
+    if manifests
+        .iter()
+        .any(|m| m.contains('/') || m.contains('\\'))
+    {
+        return Err(format!(
+            "language \"{}\": manifests are file names in the project root, not paths",
+            name
+        ));
+    }
+
+    let in_language = |item: &Option<String>, kind: &str, item_name: &str| match item {
+        Some(other) if other != name => Err(format!(
+            "language \"{}\": {} \"{}\" names a different language (\"{}\")",
+            name, kind, item_name, other
+        )),
+        _ => Ok(()),
+    };
+    let mut frameworks = Vec::new();
+    for framework in language.frameworks {
+        in_language(&framework.language, "framework", &framework.name)?;
+        frameworks.push(build_framework(framework)?);
+    }
+    let mut storage_dirs = Vec::new();
+    for dir in language.storage_dirs {
+        in_language(&dir.language, "storage dir", &dir.name)?;
+        storage_dirs.push(build_storage_dir(dir)?);
+    }
+    let mut lockfiles = Vec::new();
+    for lockfile in language.lockfiles {
+        if lockfile.file.trim().is_empty() {
+            return Err(format!("language \"{}\": lockfile name is empty", name));
+        }
+        lockfiles.push(LockfileEntry {
+            file: leak(lockfile.file),
+            manager: lockfile.manager,
+        });
+    }
+
+    Ok(LanguageEntry {
+        language: Language::Unknown,
+        display_name: leak(name.to_string()),
+        manifest: leak(language.manifest),
+        alt_manifests: language
+            .alt_manifests
+            .into_iter()
+            .map(leak)
+            .collect::<Vec<_>>()
+            .leak(),
+        frameworks: frameworks.leak(),
+        storage_dirs: storage_dirs.leak(),
+        lockfiles: lockfiles.leak(),
+        default_manager: language.default_manager,
+        default_category: language.default_category,
+        default_framework: leak(
+            language
+                .default_framework
+                .unwrap_or_else(|| name.to_string()),
+        ),
+    })
+}
+
+fn build_framework(framework: UserFramework) -> Result<FrameworkRule, String> {
+    let name = framework.name.trim();
+    if name.is_empty() {
+        return Err("framework: `name` is empty".to_string());
+    }
+    if framework.patterns.is_empty() || framework.patterns.iter().any(|p| p.is_empty()) {
+        return Err(format!(
+            "framework \"{}\": `patterns` needs at least one non-empty pattern",
+            name
+        ));
+    }
+    Ok(FrameworkRule {
+        name: leak(name.to_string()),
+        patterns: framework
+            .patterns
+            .into_iter()
+            .map(leak)
+            .collect::<Vec<_>>()
+            .leak(),
+        source: framework.source,
+        category: framework.category,
+        priority: framework.priority,
+    })
+}
+
+fn build_storage_dir(dir: UserStorageDir) -> Result<StorageDir, String> {
+    if dir.name.trim().is_empty() {
+        return Err("storage dir: `name` is empty".to_string());
+    }
+    let Some(category) = STORAGE_CATEGORIES.iter().find(|c| **c == dir.category) else {
+        return Err(format!(
+            "storage dir \"{}\": unknown category \"{}\" (expected one of: {})",
+            dir.name,
+            dir.category,
+            STORAGE_CATEGORIES.join(", ")
+        ));
+    };
+    Ok(StorageDir {
+        name: leak(dir.name),
+        category,
+    })
+}
+
+fn leak(s: String) -> &'static str {
+    s.leak()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn entry<'a>(entries: &'a [LanguageEntry], name: &str) -> &'a LanguageEntry {
+        entries.iter().find(|e| e.display_name == name).unwrap()
+    }
+
+    #[test]
+    fn test_merges_user_definitions() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("registry.toml");
+        fs::write(
+            &toml_path,
+            r#"
+[[frameworks]]
+language = "JavaScript/TypeScript"
+name = "Acme Shell"
+patterns = ['"@acme/shell"']
+category = "desktop_app"
+priority = 101
+
+[[storage_dirs]]
+language = "Gleam"
+name = ".gleam-cache"
+category = "cache"
+
+[[languages]]
+name = "Gleam"
+manifest = "gleam.toml"
+storage_dirs = [{ name = "build", category = "build" }]
+frameworks = [{ name = "Wisp", patterns = ["wisp"], category = "backend" }]
+"#,
+        )
+        .unwrap();
+        let json_path = dir.path().join("registry.json");
+        fs::write(
+            &json_path,
+            r#"{ "storage_dirs": [{ "language": "Rust", "name": ".acme", "category": "cache" }] }"#,
+        )
+        .unwrap();
+
+        let (entries, status) = load(&[toml_path, json_path, dir.path().join("missing.toml")]);
+        assert!(status.errors.is_empty(), "{:?}", status.errors);
+        assert_eq!(status.loaded.len(), 2);
+        assert_eq!(
+            (status.languages, status.frameworks, status.storage_dirs),
+            (1, 2, 3)
+        );
+
+        let js = entry(entries, "JavaScript/TypeScript");
+        assert!(js.frameworks.iter().any(|f| f.name == "Acme Shell"));
+        assert!(js.frameworks.len() > 1);
+
+        let gleam = entry(entries, "Gleam");
+        assert_eq!(gleam.default_framework, "Gleam");
+        assert_eq!(gleam.default_category, AppCategory::Library);
+        let dirs: Vec<&str> = gleam.storage_dirs.iter().map(|d| d.name).collect();
+        assert_eq!(dirs, vec!["build", ".gleam-cache"]);
+
+        assert!(entry(entries, "Rust")
+            .storage_dirs
+            .iter()
+            .any(|d| d.name == ".acme"));
+    }
+
+    #[test]
+    fn test_reports_invalid_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("registry.toml");
+        fs::write(
+            &good,
+            r#"
+[[frameworks]]
+language = "Cobol"
+name = "Mainframe"
+patterns = ["cics"]
+
+[[frameworks]]
+language = "Rust"
+name = "Empty"
+patterns = []
+
+[[storage_dirs]]
+language = "Rust"
+name = "out"
+category = "junk"
+
+[[languages]]
+name = "rust"
+manifest = "Cargo.toml"
+
+[[frameworks]]
+language = "Go"
+name = "Acme Kit"
+patterns = ["acme.dev/kit"]
+"#,
+        )
+        .unwrap();
+        let bad = dir.path().join("registry.json");
+        fs::write(&bad, r#"{ "framework": [] }"#).unwrap();
+
+        let (entries, status) = load(&[good, bad.clone()]);
+        let messages: Vec<&str> = status.errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.len(), 5, "{:?}", messages);
+        assert!(messages[0].contains("already exists"));
+        assert!(messages[1].contains("unknown language \"Cobol\""));
+        assert!(messages[2].contains("`patterns`"));
+        assert!(messages[3].contains("unknown category \"junk\""));
+        assert!(messages[4].contains("unknown field"));
+        assert_eq!(status.errors[4].source, bad.to_string_lossy());
+
+        // Valid entries from the same file still apply
+        assert_eq!(status.frameworks, 1);
+        assert!(entry(entries, "Go")
+            .frameworks
+            .iter()
+            .any(|f| f.name == "Acme Kit"));
+    }
+}
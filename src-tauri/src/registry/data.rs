@@ -5,7 +5,7 @@
 
 use super::types::{
     AppCategory, DetectionSource, FrameworkRule, Language, LanguageEntry, LockfileEntry,
-    PackageManager, StorageDir,
+    PackageManager, SourceLanguage, StorageDir,
 };
 
 // ============================================================================
@@ -889,6 +889,478 @@ const ELIXIR_LOCKFILES: &[LockfileEntry] = &[
     LockfileEntry { file: "mix.lock", manager: PackageManager::Mix },
 ];
 
+// ============================================================================
+// Zig Frameworks
+// ============================================================================
+
+const ZIG_FRAMEWORKS: &[FrameworkRule] = &[
+    FrameworkRule {
+        name: "Mach",
+        patterns: &["\"mach\""],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::DesktopApp,
+        priority: 80,
+    },
+    FrameworkRule {
+        name: "Capy",
+        patterns: &["\"capy\""],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::DesktopApp,
+        priority: 79,
+    },
+    FrameworkRule {
+        name: "Jetzig",
+        patterns: &["jetzig"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 75,
+    },
+    FrameworkRule {
+        name: "Zap",
+        patterns: &["\"zap\""],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 70,
+    },
+    FrameworkRule {
+        name: "http.zig",
+        patterns: &["\"httpz\""],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 69,
+    },
+];
+
+const ZIG_STORAGE_DIRS: &[StorageDir] = &[
+    StorageDir { name: "zig-out", category: "build" },
+    StorageDir { name: ".zig-cache", category: "cache" },
+    StorageDir { name: "zig-cache", category: "cache" },
+];
+
+const ZIG_LOCKFILES: &[LockfileEntry] = &[
+    LockfileEntry { file: "build.zig.zon", manager: PackageManager::Zig },
+];
+
+// ============================================================================
+// Haskell Frameworks
+// ============================================================================
+
+const HASKELL_FRAMEWORKS: &[FrameworkRule] = &[
+    FrameworkRule {
+        name: "IHP",
+        patterns: &["ihp"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 80,
+    },
+    FrameworkRule {
+        name: "Yesod",
+        patterns: &["yesod"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 79,
+    },
+    FrameworkRule {
+        name: "Servant",
+        patterns: &["servant-server", "servant"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 70,
+    },
+    FrameworkRule {
+        name: "Scotty",
+        patterns: &["scotty"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 69,
+    },
+    FrameworkRule {
+        name: "Brick",
+        patterns: &["brick"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Cli,
+        priority: 60,
+    },
+    FrameworkRule {
+        name: "optparse-applicative",
+        patterns: &["optparse-applicative"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Cli,
+        priority: 50,
+    },
+];
+
+const HASKELL_STORAGE_DIRS: &[StorageDir] = &[
+    StorageDir { name: ".stack-work", category: "build" },
+    StorageDir { name: "dist-newstyle", category: "build" },
+];
+
+const HASKELL_LOCKFILES: &[LockfileEntry] = &[
+    LockfileEntry { file: "stack.yaml.lock", manager: PackageManager::Stack },
+    LockfileEntry { file: "stack.yaml", manager: PackageManager::Stack },
+    LockfileEntry { file: "cabal.project.freeze", manager: PackageManager::Cabal },
+];
+
+// ============================================================================
+// OCaml Frameworks
+// ============================================================================
+
+const OCAML_FRAMEWORKS: &[FrameworkRule] = &[
+    FrameworkRule {
+        name: "Bonsai",
+        patterns: &["bonsai"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 80,
+    },
+    FrameworkRule {
+        name: "js_of_ocaml",
+        patterns: &["js_of_ocaml"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 75,
+    },
+    FrameworkRule {
+        name: "Dream",
+        patterns: &["dream"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 70,
+    },
+    FrameworkRule {
+        name: "Opium",
+        patterns: &["opium"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 69,
+    },
+    FrameworkRule {
+        name: "Cmdliner",
+        patterns: &["cmdliner"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Cli,
+        priority: 50,
+    },
+];
+
+const OCAML_STORAGE_DIRS: &[StorageDir] = &[
+    StorageDir { name: "_build", category: "build" },
+    StorageDir { name: "_opam", category: "dependencies" },
+];
+
+const OCAML_LOCKFILES: &[LockfileEntry] = &[
+    LockfileEntry { file: "dune.lock", manager: PackageManager::Dune },
+];
+
+// ============================================================================
+// Scala Frameworks
+// ============================================================================
+
+const SCALA_FRAMEWORKS: &[FrameworkRule] = &[
+    FrameworkRule {
+        name: "Play",
+        patterns: &["playscala", "com.typesafe.play", "org.playframework"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 80,
+    },
+    FrameworkRule {
+        name: "Scala.js",
+        patterns: &["scalajsplugin", "scalajs-dom"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 79,
+    },
+    FrameworkRule {
+        name: "http4s",
+        patterns: &["http4s"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 70,
+    },
+    FrameworkRule {
+        name: "ZIO HTTP",
+        patterns: &["zio-http"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 69,
+    },
+    FrameworkRule {
+        name: "Akka HTTP",
+        patterns: &["akka-http"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 68,
+    },
+    FrameworkRule {
+        name: "Pekko HTTP",
+        patterns: &["pekko-http"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 67,
+    },
+    FrameworkRule {
+        name: "Cask",
+        patterns: &["cask"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 66,
+    },
+    FrameworkRule {
+        name: "Spark",
+        patterns: &["spark-core", "spark-sql"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Library,
+        priority: 60,
+    },
+];
+
+const SCALA_STORAGE_DIRS: &[StorageDir] = &[
+    StorageDir { name: "target", category: "build" },
+    StorageDir { name: "out", category: "build" },
+    StorageDir { name: ".bsp", category: "cache" },
+    StorageDir { name: ".bloop", category: "cache" },
+    StorageDir { name: ".metals", category: "cache" },
+];
+
+const SCALA_LOCKFILES: &[LockfileEntry] = &[
+    LockfileEntry { file: "build.mill", manager: PackageManager::Mill },
+    LockfileEntry { file: "build.sc", manager: PackageManager::Mill },
+];
+
+// ============================================================================
+// Clojure Frameworks
+// ============================================================================
+
+const CLOJURE_FRAMEWORKS: &[FrameworkRule] = &[
+    FrameworkRule {
+        name: "re-frame",
+        patterns: &["re-frame"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 80,
+    },
+    FrameworkRule {
+        name: "Reagent",
+        patterns: &["reagent"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 79,
+    },
+    FrameworkRule {
+        name: "ClojureScript",
+        patterns: &["shadow-cljs", "clojurescript"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 75,
+    },
+    FrameworkRule {
+        name: "Biff",
+        patterns: &["biff"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::WebApp,
+        priority: 74,
+    },
+    FrameworkRule {
+        name: "Pedestal",
+        patterns: &["pedestal"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 70,
+    },
+    FrameworkRule {
+        name: "Compojure",
+        patterns: &["compojure"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 69,
+    },
+    FrameworkRule {
+        name: "Ring",
+        patterns: &["ring/ring-core", "ring/ring-jetty-adapter"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Backend,
+        priority: 68,
+    },
+];
+
+const CLOJURE_STORAGE_DIRS: &[StorageDir] = &[
+    StorageDir { name: "target", category: "build" },
+    StorageDir { name: ".cpcache", category: "cache" },
+    StorageDir { name: ".shadow-cljs", category: "cache" },
+];
+
+const CLOJURE_LOCKFILES: &[LockfileEntry] = &[
+    LockfileEntry { file: "project.clj", manager: PackageManager::Leiningen },
+];
+
+// ============================================================================
+// Nix Frameworks
+// ============================================================================
+
+const NIX_FRAMEWORKS: &[FrameworkRule] = &[
+    FrameworkRule {
+        name: "NixOS",
+        patterns: &["nixosconfigurations"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Infrastructure,
+        priority: 80,
+    },
+    FrameworkRule {
+        name: "nix-darwin",
+        patterns: &["darwinconfigurations"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Infrastructure,
+        priority: 79,
+    },
+    FrameworkRule {
+        name: "Home Manager",
+        patterns: &["homeconfigurations", "home-manager"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Infrastructure,
+        priority: 78,
+    },
+    FrameworkRule {
+        name: "devenv",
+        patterns: &["devenv"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Library,
+        priority: 60,
+    },
+];
+
+const NIX_STORAGE_DIRS: &[StorageDir] = &[
+    StorageDir { name: "result", category: "build" },
+    StorageDir { name: ".direnv", category: "cache" },
+];
+
+const NIX_LOCKFILES: &[LockfileEntry] = &[
+    LockfileEntry { file: "flake.lock", manager: PackageManager::Nix },
+];
+
+// ============================================================================
+// Terraform Frameworks
+// ============================================================================
+
+const TERRAFORM_FRAMEWORKS: &[FrameworkRule] = &[
+    FrameworkRule {
+        name: "Terragrunt",
+        patterns: &["terragrunt.hcl"],
+        source: DetectionSource::FileExists,
+        category: AppCategory::Infrastructure,
+        priority: 90,
+    },
+    FrameworkRule {
+        name: "AWS",
+        patterns: &["hashicorp/aws", "provider \"aws\""],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Infrastructure,
+        priority: 70,
+    },
+    FrameworkRule {
+        name: "Google Cloud",
+        patterns: &["hashicorp/google", "provider \"google\""],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Infrastructure,
+        priority: 69,
+    },
+    FrameworkRule {
+        name: "Azure",
+        patterns: &["hashicorp/azurerm", "provider \"azurerm\""],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Infrastructure,
+        priority: 68,
+    },
+    FrameworkRule {
+        name: "Kubernetes",
+        patterns: &["hashicorp/kubernetes", "provider \"kubernetes\""],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Infrastructure,
+        priority: 67,
+    },
+    FrameworkRule {
+        name: "Cloudflare",
+        patterns: &["cloudflare/cloudflare", "provider \"cloudflare\""],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::Infrastructure,
+        priority: 66,
+    },
+];
+
+const TERRAFORM_STORAGE_DIRS: &[StorageDir] = &[
+    StorageDir { name: ".terraform", category: "dependencies" },
+    StorageDir { name: ".terragrunt-cache", category: "cache" },
+];
+
+const TERRAFORM_LOCKFILES: &[LockfileEntry] = &[
+    LockfileEntry { file: ".terraform.lock.hcl", manager: PackageManager::Terraform },
+];
+
+// ============================================================================
+// C/C++ Frameworks
+// ============================================================================
+
+const C_FRAMEWORKS: &[FrameworkRule] = &[
+    FrameworkRule {
+        name: "Qt",
+        patterns: &["qt6", "qt5", "find_package(qt"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::DesktopApp,
+        priority: 80,
+    },
+    FrameworkRule {
+        name: "GTK",
+        patterns: &["gtk4", "gtk+-3.0", "gtk3"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::DesktopApp,
+        priority: 79,
+    },
+    FrameworkRule {
+        name: "SDL",
+        patterns: &["sdl2", "sdl3"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::DesktopApp,
+        priority: 78,
+    },
+    FrameworkRule {
+        name: "raylib",
+        patterns: &["raylib"],
+        source: DetectionSource::ManifestContent,
+        category: AppCategory::DesktopApp,
+        priority: 77,
+    },
+    FrameworkRule {
+        name: "Meson",
+        patterns: &["meson.build"],
+        source: DetectionSource::FileExists,
+        category: AppCategory::Library,
+        priority: 10,
+    },
+    FrameworkRule {
+        name: "CMake",
+        patterns: &["CMakeLists.txt"],
+        source: DetectionSource::FileExists,
+        category: AppCategory::Library,
+        priority: 9,
+    },
+];
+
+const C_STORAGE_DIRS: &[StorageDir] = &[
+    StorageDir { name: "build", category: "build" },
+    StorageDir { name: "builddir", category: "build" },
+    StorageDir { name: "cmake-build-debug", category: "build" },
+    StorageDir { name: "cmake-build-release", category: "build" },
+    StorageDir { name: "vcpkg_installed", category: "dependencies" },
+    StorageDir { name: ".cache", category: "cache" },
+];
+
+const C_LOCKFILES: &[LockfileEntry] = &[
+    LockfileEntry { file: "conan.lock", manager: PackageManager::Conan },
+    LockfileEntry { file: "conanfile.txt", manager: PackageManager::Conan },
+    LockfileEntry { file: "conanfile.py", manager: PackageManager::Conan },
+    LockfileEntry { file: "vcpkg.json", manager: PackageManager::Vcpkg },
+];
+
 // ============================================================================
 // Common Storage Directories
 // ============================================================================
@@ -1048,6 +1520,151 @@ pub static REGISTRY: &[LanguageEntry] = &[
         default_category: AppCategory::Backend,
         default_framework: "Elixir",
     },
+    // Scala
+    LanguageEntry {
+        language: Language::Scala,
+        display_name: "Scala",
+        manifest: "build.sbt",
+        alt_manifests: &["build.mill", "build.sc", "project.scala"],
+        frameworks: SCALA_FRAMEWORKS,
+        storage_dirs: SCALA_STORAGE_DIRS,
+        lockfiles: SCALA_LOCKFILES,
+        default_manager: PackageManager::Sbt,
+        default_category: AppCategory::Library,
+        default_framework: "Scala",
+    },
+    // Haskell
+    LanguageEntry {
+        language: Language::Haskell,
+        display_name: "Haskell",
+        manifest: "stack.yaml",
+        alt_manifests: &["cabal.project", "package.yaml", "*.cabal"],
+        frameworks: HASKELL_FRAMEWORKS,
+        storage_dirs: HASKELL_STORAGE_DIRS,
+        lockfiles: HASKELL_LOCKFILES,
+        default_manager: PackageManager::Cabal,
+        default_category: AppCategory::Library,
+        default_framework: "Haskell",
+    },
+    // Zig
+    LanguageEntry {
+        language: Language::Zig,
+        display_name: "Zig",
+        manifest: "build.zig",
+        alt_manifests: &["build.zig.zon"],
+        frameworks: ZIG_FRAMEWORKS,
+        storage_dirs: ZIG_STORAGE_DIRS,
+        lockfiles: ZIG_LOCKFILES,
+        default_manager: PackageManager::Zig,
+        default_category: AppCategory::Library,
+        default_framework: "Zig",
+    },
+    // OCaml
+    LanguageEntry {
+        language: Language::OCaml,
+        display_name: "OCaml",
+        manifest: "dune-project",
+        alt_manifests: &["*.opam"],
+        frameworks: OCAML_FRAMEWORKS,
+        storage_dirs: OCAML_STORAGE_DIRS,
+        lockfiles: OCAML_LOCKFILES,
+        default_manager: PackageManager::Opam,
+        default_category: AppCategory::Library,
+        default_framework: "OCaml",
+    },
+    // Clojure
+    LanguageEntry {
+        language: Language::Clojure,
+        display_name: "Clojure",
+        manifest: "deps.edn",
+        alt_manifests: &["project.clj", "shadow-cljs.edn", "bb.edn"],
+        frameworks: CLOJURE_FRAMEWORKS,
+        storage_dirs: CLOJURE_STORAGE_DIRS,
+        lockfiles: CLOJURE_LOCKFILES,
+        default_manager: PackageManager::ClojureCli,
+        default_category: AppCategory::Library,
+        default_framework: "Clojure",
+    },
+    // C/C++ (CMake and Meson builds)
+    LanguageEntry {
+        language: Language::C,
+        display_name: "C/C++",
+        manifest: "CMakeLists.txt",
+        alt_manifests: &["meson.build"],
+        frameworks: C_FRAMEWORKS,
+        storage_dirs: C_STORAGE_DIRS,
+        lockfiles: C_LOCKFILES,
+        default_manager: PackageManager::Unknown,
+        default_category: AppCategory::Library,
+        default_framework: "C/C++",
+    },
+    // Terraform
+    LanguageEntry {
+        language: Language::Terraform,
+        display_name: "Terraform",
+        manifest: "main.tf",
+        alt_manifests: &["terragrunt.hcl", "*.tf"],
+        frameworks: TERRAFORM_FRAMEWORKS,
+        storage_dirs: TERRAFORM_STORAGE_DIRS,
+        lockfiles: TERRAFORM_LOCKFILES,
+        default_manager: PackageManager::Terraform,
+        default_category: AppCategory::Infrastructure,
+        default_framework: "Terraform",
+    },
+    // Nix, last: flake.nix and shell.nix often sit next to another manifest
+    LanguageEntry {
+        language: Language::Nix,
+        display_name: "Nix",
+        manifest: "flake.nix",
+        alt_manifests: &["default.nix", "shell.nix"],
+        frameworks: NIX_FRAMEWORKS,
+        storage_dirs: NIX_STORAGE_DIRS,
+        lockfiles: NIX_LOCKFILES,
+        default_manager: PackageManager::Nix,
+        default_category: AppCategory::Infrastructure,
+        default_framework: "Nix",
+    },
+];
+
+// ============================================================================
+// Source Languages
+// ============================================================================
+
+/// File extensions used for per-language line counts. Finer-grained than the
+/// registry entries (TypeScript and CSS are counted apart from JavaScript).
+pub const SOURCE_LANGUAGES: &[SourceLanguage] = &[
+    SourceLanguage { name: "TypeScript", extensions: &["ts", "tsx", "mts", "cts"] },
+    SourceLanguage { name: "JavaScript", extensions: &["js", "jsx", "mjs", "cjs"] },
+    SourceLanguage { name: "Vue", extensions: &["vue"] },
+    SourceLanguage { name: "Svelte", extensions: &["svelte"] },
+    SourceLanguage { name: "Python", extensions: &["py", "pyi"] },
+    SourceLanguage { name: "Rust", extensions: &["rs"] },
+    SourceLanguage { name: "Go", extensions: &["go"] },
+    SourceLanguage { name: "Java", extensions: &["java"] },
+    SourceLanguage { name: "Kotlin", extensions: &["kt", "kts"] },
+    SourceLanguage { name: "Swift", extensions: &["swift"] },
+    SourceLanguage { name: "Ruby", extensions: &["rb", "rake"] },
+    SourceLanguage { name: "PHP", extensions: &["php"] },
+    SourceLanguage { name: "C#", extensions: &["cs"] },
+    SourceLanguage { name: "F#", extensions: &["fs", "fsx"] },
+    SourceLanguage { name: "C", extensions: &["c", "h"] },
+    SourceLanguage { name: "C++", extensions: &["cc", "cpp", "cxx", "hh", "hpp", "hxx"] },
+    SourceLanguage { name: "Objective-C", extensions: &["m", "mm"] },
+    SourceLanguage { name: "Dart", extensions: &["dart"] },
+    SourceLanguage { name: "Elixir", extensions: &["ex", "exs"] },
+    SourceLanguage { name: "Erlang", extensions: &["erl", "hrl"] },
+    SourceLanguage { name: "Zig", extensions: &["zig"] },
+    SourceLanguage { name: "Haskell", extensions: &["hs", "lhs"] },
+    SourceLanguage { name: "OCaml", extensions: &["ml", "mli"] },
+    SourceLanguage { name: "Scala", extensions: &["scala", "sc"] },
+    SourceLanguage { name: "Clojure", extensions: &["clj", "cljs", "cljc"] },
+    SourceLanguage { name: "Nix", extensions: &["nix"] },
+    SourceLanguage { name: "Terraform", extensions: &["tf"] },
+    SourceLanguage { name: "Lua", extensions: &["lua"] },
+    SourceLanguage { name: "Shell", extensions: &["sh", "bash", "zsh", "fish"] },
+    SourceLanguage { name: "SQL", extensions: &["sql"] },
+    SourceLanguage { name: "HTML", extensions: &["html", "htm"] },
+    SourceLanguage { name: "CSS", extensions: &["css", "scss", "sass", "less"] },
 ];
 
 /// Directories that should never be descended into during project scanning
@@ -1071,4 +1688,17 @@ pub const SKIP_DIRS: &[&str] = &[
     ".gradle",
     "bin",
     "obj",
+    "zig-out",
+    ".zig-cache",
+    "zig-cache",
+    ".stack-work",
+    "dist-newstyle",
+    "_opam",
+    ".cpcache",
+    ".shadow-cljs",
+    ".bloop",
+    ".metals",
+    ".terraform",
+    ".terragrunt-cache",
+    ".direnv",
 ];
@@ -7,12 +7,19 @@
 use std::fs;
 use std::path::Path;
 
-use super::data::{REGISTRY, SKIP_DIRS, COMMON_STORAGE_DIRS};
+use super::config::entries;
+use super::data::{SKIP_DIRS, COMMON_STORAGE_DIRS};
 use super::types::{
-    AppCategory, DetectionResult, DetectionSource, FrameworkRule, Language,
+    AppCategory, DetectedStack, DetectionResult, DetectionSource, FrameworkRule, Language,
     LanguageEntry, PackageManager, StorageDir,
 };
 
+/// How far below the project root `detect_stacks` looks for more manifests
+const STACK_SEARCH_DEPTH: usize = 2;
+
+/// Subdirectories whose manifests belong to samples, not the project
+const STACK_SKIP_DIRS: &[&str] = &["examples", "fixtures", "testdata", "test-fixtures"];
+
 // ============================================================================
 // Main Detection Functions
 // ============================================================================
@@ -22,7 +29,76 @@ use super::types::{
 pub fn detect(path: &Path) -> Option<DetectionResult> {
     // First, find the language entry based on manifest files
     let entry = detect_language_entry(path)?;
+    Some(detect_for_entry(path, entry))
+}
+
+/// Every stack in a project, ranked: the root's own manifests first (the
+/// first one is what `detect` returns), then subdirectories up to
+/// `STACK_SEARCH_DEPTH` levels down, shallowest first. A language/framework
+/// pair is only reported once, at its shallowest location.
+pub fn detect_stacks(path: &Path) -> Vec<DetectedStack> {
+    let mut stacks: Vec<DetectedStack> = Vec::new();
+    let mut level = vec![(path.to_path_buf(), String::new())];
+
+    for depth in 0..=STACK_SEARCH_DEPTH {
+        let mut next = Vec::new();
+        for (dir, subdir) in level {
+            let Ok(read) = fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut names = Vec::new();
+            let mut children = Vec::new();
+            for child in read.flatten() {
+                let name = child.file_name().to_string_lossy().to_string();
+                if child.file_type().is_ok_and(|t| t.is_dir()) {
+                    let skip = name.starts_with('.')
+                        || SKIP_DIRS.contains(&name.as_str())
+                        || STACK_SKIP_DIRS.contains(&name.as_str());
+                    if !skip && depth < STACK_SEARCH_DEPTH {
+                        let child_subdir = if subdir.is_empty() {
+                            name.clone()
+                        } else {
+                            format!("{}/{}", subdir, name)
+                        };
+                        children.push((child.path(), child_subdir));
+                    }
+                }
+                names.push(name);
+            }
+
+            for entry in entries_matching(&names) {
+                let detection = detect_for_entry(&dir, entry);
+                let seen = stacks.iter().any(|s| {
+                    s.detection.language_display == detection.language_display
+                        && s.detection.framework == detection.framework
+                });
+                if !seen {
+                    stacks.push(DetectedStack {
+                        subdir: subdir.clone(),
+                        detection,
+                    });
+                }
+            }
+            children.sort();
+            next.extend(children);
+        }
+        level = next;
+    }
 
+    stacks
+}
+
+/// Registry entries whose manifests are among `names`, ranked like
+/// `detect_language_entry`
+fn entries_matching(names: &[String]) -> Vec<&'static LanguageEntry> {
+    ranked_matches(|manifest| match manifest.strip_prefix('*') {
+        Some(suffix) => names.iter().any(|n| n.ends_with(suffix)),
+        None => names.iter().any(|n| n == manifest),
+    })
+}
+
+/// Full detection against one registry entry
+fn detect_for_entry(path: &Path, entry: &'static LanguageEntry) -> DetectionResult {
     // Read manifest content for framework detection
     let manifest_content = read_manifest_content(path, entry);
 
@@ -32,7 +108,7 @@ pub fn detect(path: &Path) -> Option<DetectionResult> {
     // Detect package manager
     let package_manager = detect_package_manager_for_entry(path, entry);
 
-    Some(DetectionResult {
+    DetectionResult {
         language: entry.language,
         language_display: entry.display_name.to_string(),
         framework: framework.to_string(),
@@ -40,12 +116,12 @@ pub fn detect(path: &Path) -> Option<DetectionResult> {
         category_display: category.as_str().to_string(),
         package_manager,
         package_manager_display: package_manager.as_str().to_string(),
-    })
+    }
 }
 
 /// Check if a path is a project directory (contains any manifest file)
 pub fn is_project_dir(path: &Path) -> bool {
-    REGISTRY.iter().any(|entry| {
+    entries().iter().any(|entry| {
         manifest_exists(path, entry.manifest)
             || entry.alt_manifests.iter().any(|m| manifest_exists(path, m))
     })
@@ -54,7 +130,7 @@ pub fn is_project_dir(path: &Path) -> bool {
 /// Get storage directories for a given language
 #[allow(dead_code)]
 pub fn get_storage_dirs(language: Language) -> Vec<&'static StorageDir> {
-    let mut dirs: Vec<&StorageDir> = REGISTRY
+    let mut dirs: Vec<&StorageDir> = entries()
         .iter()
         .find(|e| e.language == language)
         .map(|e| e.storage_dirs.iter().collect())
@@ -67,7 +143,7 @@ pub fn get_storage_dirs(language: Language) -> Vec<&'static StorageDir> {
 
 /// Get storage directories by project type string (for backward compatibility)
 pub fn get_storage_dirs_by_type(project_type: &str) -> Vec<&'static StorageDir> {
-    let mut dirs: Vec<&StorageDir> = REGISTRY
+    let mut dirs: Vec<&StorageDir> = entries()
         .iter()
         .find(|e| e.display_name == project_type)
         .map(|e| e.storage_dirs.iter().collect())
@@ -88,7 +164,7 @@ pub fn skip_dirs() -> &'static [&'static str] {
 #[allow(dead_code)]
 pub fn detect_package_manager(path: &Path) -> PackageManager {
     // Check all lockfiles from all languages
-    for entry in REGISTRY.iter() {
+    for entry in entries().iter() {
         for lockfile in entry.lockfiles.iter() {
             if path.join(lockfile.file).exists() {
                 return lockfile.manager;
@@ -104,23 +180,37 @@ pub fn detect_package_manager(path: &Path) -> PackageManager {
 
 /// Find the language entry that matches the project at the given path
 fn detect_language_entry(path: &Path) -> Option<&'static LanguageEntry> {
-    // Check primary manifests first (in registry order = priority order)
-    for entry in REGISTRY.iter() {
-        if manifest_exists(path, entry.manifest) {
-            return Some(entry);
-        }
-    }
+    ranked_matches(|manifest| manifest_exists(path, manifest))
+        .into_iter()
+        .next()
+}
 
-    // Check alternative manifests
-    for entry in REGISTRY.iter() {
-        for alt_manifest in entry.alt_manifests.iter() {
-            if manifest_exists(path, alt_manifest) {
-                return Some(entry);
+/// Registry entries with a manifest `has` reports present, best first:
+/// application languages before the build/infra tooling that often sits next
+/// to them, and within each, primary manifests before alternative ones
+/// (registry order = priority order).
+fn ranked_matches(has: impl Fn(&str) -> bool) -> Vec<&'static LanguageEntry> {
+    let (primary, infrastructure): (Vec<_>, Vec<_>) = entries()
+        .iter()
+        .partition(|entry| !is_build_infrastructure(entry.language));
+
+    let mut matched: Vec<&'static LanguageEntry> = Vec::new();
+    for group in [primary, infrastructure] {
+        matched.extend(group.iter().filter(|e| has(e.manifest)));
+        for entry in group {
+            let by_alt = entry.alt_manifests.iter().any(|m| has(m));
+            if by_alt && !matched.iter().any(|m| std::ptr::eq(*m, entry)) {
+                matched.push(entry);
             }
         }
     }
+    matched
+}
 
-    None
+/// CMake, Terraform and Nix files are commonly added alongside a project
+/// written in another language, so they only decide when nothing else does.
+fn is_build_infrastructure(language: Language) -> bool {
+    matches!(language, Language::C | Language::Terraform | Language::Nix)
 }
 
 /// Check if a manifest file exists (supports glob patterns for C#)
@@ -192,6 +282,20 @@ fn detect_framework(
                 return ("Android", AppCategory::MobileApp);
             }
         }
+        Language::Haskell => {
+            // Dependencies live in package.yaml or the .cabal files, not stack.yaml
+            let combined = get_combined_content(path, manifest_content, &["package.yaml"], ".cabal");
+            return detect_framework_from_rules(path, entry, &combined);
+        }
+        Language::OCaml => {
+            let combined = get_combined_content(path, manifest_content, &[], ".opam");
+            return detect_framework_from_rules(path, entry, &combined);
+        }
+        Language::Terraform => {
+            // Providers can be declared in any .tf file of the module
+            let combined = get_combined_content(path, "", &[], ".tf");
+            return detect_framework_from_rules(path, entry, &combined);
+        }
         _ => {}
     }
 
@@ -201,7 +305,7 @@ fn detect_framework(
 
 /// Detect framework by matching rules against content
 fn detect_framework_from_rules(
-    _path: &Path,
+    path: &Path,
     entry: &LanguageEntry,
     content: &str,
 ) -> (&'static str, AppCategory) {
@@ -210,7 +314,7 @@ fn detect_framework_from_rules(
     frameworks.sort_by(|a, b| b.priority.cmp(&a.priority));
 
     for rule in frameworks {
-        if matches_rule(content, rule) {
+        if matches_rule(path, content, rule) {
             return (rule.name, rule.category);
         }
     }
@@ -218,8 +322,8 @@ fn detect_framework_from_rules(
     (entry.default_framework, entry.default_category)
 }
 
-/// Check if content matches a framework rule
-fn matches_rule(content: &str, rule: &FrameworkRule) -> bool {
+/// Check if a project matches a framework rule
+fn matches_rule(path: &Path, content: &str, rule: &FrameworkRule) -> bool {
     match rule.source {
         DetectionSource::ManifestContent => {
             // For rules with multiple patterns, check if any match
@@ -227,9 +331,12 @@ fn matches_rule(content: &str, rule: &FrameworkRule) -> bool {
                 content.contains(&pattern.to_lowercase())
             })
         }
-        DetectionSource::FileExists | DetectionSource::DirectoryExists => {
-            // These are handled separately in language-specific detection
-            false
+        // Patterns are paths relative to the project root
+        DetectionSource::FileExists => {
+            rule.patterns.iter().any(|pattern| path.join(pattern).is_file())
+        }
+        DetectionSource::DirectoryExists => {
+            rule.patterns.iter().any(|pattern| path.join(pattern).is_dir())
         }
     }
 }
@@ -266,7 +373,7 @@ fn detect_js_framework(
         if rule.name == "React + Vite" {
             continue; // Already checked
         }
-        if matches_rule(&content_lower, rule) {
+        if matches_rule(path, &content_lower, rule) {
             return (rule.name, rule.category);
         }
     }
@@ -286,6 +393,30 @@ fn get_combined_python_content(path: &Path, pyproject_content: &str) -> String {
     combined
 }
 
+/// Manifest content plus the named files and every root file ending in
+/// `suffix`, lowercased for rule matching
+fn get_combined_content(path: &Path, manifest_content: &str, files: &[&str], suffix: &str) -> String {
+    let mut combined = manifest_content.to_lowercase();
+    let mut extra: Vec<std::path::PathBuf> = files.iter().map(|f| path.join(f)).collect();
+    if let Ok(entries) = fs::read_dir(path) {
+        let mut matching: Vec<_> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.to_string_lossy().ends_with(suffix))
+            .collect();
+        matching.sort();
+        extra.extend(matching.into_iter().take(20));
+    }
+
+    for file in extra {
+        if let Ok(content) = fs::read_to_string(&file) {
+            combined.push('\n');
+            combined.push_str(&content.to_lowercase());
+        }
+    }
+    combined
+}
+
 /// Detect Flutter app category based on platform directories
 fn detect_flutter_category(path: &Path, entry: &LanguageEntry) -> (&'static str, AppCategory) {
     let has_android = path.join("android").exists();
@@ -409,7 +540,7 @@ fn detect_package_manager_for_entry(path: &Path, entry: &LanguageEntry) -> Packa
     }
 
     // Check all other lockfiles
-    for other_entry in REGISTRY.iter() {
+    for other_entry in entries().iter() {
         if other_entry.language != entry.language {
             for lockfile in other_entry.lockfiles.iter() {
                 if path.join(lockfile.file).exists() {
@@ -451,3 +582,68 @@ pub fn detect_language_name(path: &Path) -> String {
         .map(|e| e.display_name.to_string())
         .unwrap_or_else(|| "Unknown".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect_files(files: &[(&str, &str)]) -> DetectionResult {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, content) in files {
+            fs::write(dir.path().join(name), content).unwrap();
+        }
+        detect(dir.path()).unwrap()
+    }
+
+    #[test]
+    fn test_detects_added_ecosystems() {
+        let zig = detect_files(&[("build.zig", "const zap = b.dependency(\"zap\", .{});")]);
+        assert_eq!((zig.language_display.as_str(), zig.framework.as_str()), ("Zig", "Zap"));
+        assert_eq!(zig.package_manager, PackageManager::Zig);
+
+        let haskell = detect_files(&[
+            ("stack.yaml", "resolver: lts-22.0\n"),
+            ("shop.cabal", "build-depends: base, servant-server\n"),
+        ]);
+        assert_eq!(haskell.framework, "Servant");
+        assert_eq!(haskell.package_manager, PackageManager::Stack);
+
+        let terraform = detect_files(&[
+            ("variables.tf", "variable \"region\" {}"),
+            ("providers.tf", "provider \"aws\" {}"),
+            (".terraform.lock.hcl", ""),
+        ]);
+        assert_eq!(terraform.language_display, "Terraform");
+        assert_eq!(terraform.framework, "AWS");
+        assert_eq!(terraform.category, AppCategory::Infrastructure);
+
+        let meson = detect_files(&[("meson.build", "project('tool', 'c')")]);
+        assert_eq!((meson.language_display.as_str(), meson.framework.as_str()), ("C/C++", "Meson"));
+
+        // A flake next to a real manifest doesn't take over the project
+        let rust = detect_files(&[("Cargo.toml", "[package]"), ("flake.nix", "{}")]);
+        assert_eq!(rust.language_display, "Rust");
+
+        // Build/infra manifests lose to a real project's alternative manifests too
+        let python = detect_files(&[("requirements.txt", "flask\n"), ("CMakeLists.txt", "")]);
+        assert_eq!(python.language_display, "Python");
+        let gradle = detect_files(&[("build.gradle", "plugins {}"), ("flake.nix", "{}")]);
+        assert_eq!(gradle.language_display, "Java");
+    }
+
+    #[test]
+    fn test_first_stack_matches_detect() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("requirements.txt"), "flask\n").unwrap();
+        fs::write(dir.path().join("CMakeLists.txt"), "").unwrap();
+
+        let detected = detect(dir.path()).unwrap();
+        let stacks = detect_stacks(dir.path());
+        let displays: Vec<&str> = stacks
+            .iter()
+            .map(|s| s.detection.language_display.as_str())
+            .collect();
+        assert_eq!(displays, vec!["Python", "C/C++"]);
+        assert_eq!(stacks[0].detection.framework, detected.framework);
+    }
+}
@@ -0,0 +1,152 @@
+//! Dev server start commands by framework.
+//!
+//! Follows the same pattern as the container registry: a flat table keyed by
+//! the framework names in `data.rs` (and each language's `default_framework`
+//! as a fallback). JavaScript projects start from their package.json scripts,
+//! so only the package manager's script syntax is defined for them here.
+
+use super::types::PackageManager;
+
+/// How a framework's dev server is usually started
+#[derive(Debug, Clone, Copy)]
+pub struct DevCommandRule {
+    /// Framework name as detected (`FrameworkRule::name` or `default_framework`)
+    pub framework: &'static str,
+    /// Command line; `{run}` is replaced with the package manager's run
+    /// prefix (e.g. `uv run `, `bundle exec `)
+    pub command: &'static str,
+    /// Only offered when this file exists in the project
+    pub requires: Option<&'static str>,
+}
+
+const fn rule(framework: &'static str, command: &'static str) -> DevCommandRule {
+    DevCommandRule {
+        framework,
+        command,
+        requires: None,
+    }
+}
+
+const fn rule_if(
+    framework: &'static str,
+    command: &'static str,
+    requires: &'static str,
+) -> DevCommandRule {
+    DevCommandRule {
+        framework,
+        command,
+        requires: Some(requires),
+    }
+}
+
+/// Static registry of start commands, most specific first within a framework.
+pub const DEV_COMMAND_RULES: &[DevCommandRule] = &[
+    // Rust
+    rule("Leptos", "cargo leptos watch"),
+    rule("Dioxus", "dx serve"),
+    rule("Yew", "trunk serve"),
+    rule("Sycamore", "trunk serve"),
+    rule("Tauri", "cargo tauri dev"),
+    rule("Rust", "cargo run"),
+    // Python
+    rule_if("Django", "{run}python manage.py runserver", "manage.py"),
+    rule_if("FastAPI", "{run}uvicorn main:app --reload", "main.py"),
+    rule_if(
+        "FastAPI",
+        "{run}uvicorn app.main:app --reload",
+        "app/main.py",
+    ),
+    rule("Flask", "{run}flask run --debug"),
+    rule_if("Starlette", "{run}uvicorn main:app --reload", "main.py"),
+    rule("Litestar", "{run}litestar run --reload"),
+    // Go
+    rule("Wails", "wails dev"),
+    rule("Buffalo", "buffalo dev"),
+    rule("Go", "go run ."),
+    // Java/Kotlin
+    rule_if("Spring Boot", "./mvnw spring-boot:run", "mvnw"),
+    rule_if("Spring Boot", "./gradlew bootRun", "gradlew"),
+    rule_if("Quarkus", "./mvnw quarkus:dev", "mvnw"),
+    rule_if("Quarkus", "./gradlew quarkusDev", "gradlew"),
+    rule_if("Micronaut", "./mvnw mn:run", "mvnw"),
+    rule_if("Micronaut", "./gradlew run", "gradlew"),
+    rule_if("Ktor", "./gradlew run", "gradlew"),
+    // C#
+    rule("ASP.NET Core", "dotnet watch run"),
+    rule("Blazor", "dotnet watch run"),
+    rule(".NET", "dotnet run"),
+    // PHP
+    rule_if("Laravel", "php artisan serve", "artisan"),
+    rule("Symfony", "symfony serve"),
+    rule_if("PHP", "php -S localhost:8000 -t public", "public/index.php"),
+    // Ruby
+    rule_if("Rails", "bin/dev", "bin/dev"),
+    rule_if("Rails", "bin/rails server", "bin/rails"),
+    rule("Rails", "{run}rails s"),
+    rule("Hanami", "{run}hanami server"),
+    rule_if("Sinatra", "{run}ruby app.rb", "app.rb"),
+    // Swift
+    rule("Vapor", "swift run"),
+    rule("Hummingbird", "swift run"),
+    // Dart
+    rule("Flutter", "flutter run"),
+    rule("Dart", "dart run"),
+    // Elixir
+    rule("Phoenix LiveView", "mix phx.server"),
+    rule("Phoenix", "mix phx.server"),
+    rule("Elixir", "iex -S mix"),
+    // Scala
+    rule_if("Play", "sbt run", "build.sbt"),
+    rule_if("Scala", "sbt run", "build.sbt"),
+    // Haskell
+    rule_if("IHP", "./start", "start"),
+    rule_if("Haskell", "stack run", "stack.yaml"),
+    rule("Haskell", "cabal run"),
+    // Clojure
+    rule_if("Clojure", "lein run", "project.clj"),
+    rule_if("ClojureScript", "npx shadow-cljs watch app", "shadow-cljs.edn"),
+    // Zig
+    rule("Zig", "zig build run"),
+];
+
+/// Rules for a framework, in registry order.
+pub fn rules_for(framework: &str) -> impl Iterator<Item = &'static DevCommandRule> + '_ {
+    DEV_COMMAND_RULES
+        .iter()
+        .filter(move |r| r.framework == framework)
+}
+
+/// What `{run}` expands to: the package manager's "run in the project's
+/// environment" prefix, or nothing for tools installed globally.
+pub fn run_prefix(manager: PackageManager) -> &'static str {
+    match manager {
+        PackageManager::Uv => "uv run ",
+        PackageManager::Poetry => "poetry run ",
+        PackageManager::Pipenv => "pipenv run ",
+        PackageManager::Bundler => "bundle exec ",
+        _ => "",
+    }
+}
+
+/// Script names the package manager itself claims, so `pnpm <script>`
+/// wouldn't run the script.
+const BUILTIN_SUBCOMMANDS: &[&str] = &[
+    "add", "build", "create", "dlx", "exec", "init", "install", "link", "outdated", "pack",
+    "publish", "remove", "test", "update", "upgrade", "x",
+];
+
+/// The command that runs a package.json script with the project's package
+/// manager: `npm run dev`, `pnpm dev`, `yarn dev`, `bun dev`.
+pub fn script_command(manager: PackageManager, script: &str) -> String {
+    let program = match manager {
+        PackageManager::Pnpm => "pnpm",
+        PackageManager::Yarn => "yarn",
+        PackageManager::Bun => "bun",
+        _ => return format!("npm run {}", script),
+    };
+    if BUILTIN_SUBCOMMANDS.contains(&script) {
+        format!("{} run {}", program, script)
+    } else {
+        format!("{} {}", program, script)
+    }
+}
@@ -33,27 +33,33 @@
 //! - `types.rs` - Core type definitions (Language, AppCategory, etc.)
 //! - `data.rs` - Static registry data with all frameworks
 //! - `detector.rs` - Detection engine functions
+//! - `dev_commands.rs` - Dev server start commands per framework
+//! - `config.rs` - User-defined entries merged with the builtins at runtime
 //!
 //! All detection rules are declarative and defined in `data.rs`, making it easy
 //! to add new languages and frameworks without modifying detection logic.
+//! Users can add their own from a TOML or JSON file (see `config.rs`) without
+//! rebuilding.
 
+pub mod config;
 pub mod containers;
 pub mod data;
+pub mod dev_commands;
 pub mod detector;
 pub mod types;
 
 // Re-export main detection functions used by the application
-pub use detector::{detect, get_storage_dirs_by_type, is_project_dir};
+pub use detector::{detect, detect_stacks, get_storage_dirs_by_type, is_project_dir};
 
 // Re-export registry data used by the application
-pub use data::SKIP_DIRS;
+pub use data::{SKIP_DIRS, SOURCE_LANGUAGES};
 
 // Re-export types that are part of the public API (for future crate extraction)
 // These are intentionally exported even if not currently used internally
 #[allow(unused_imports)]
 pub use types::{
-    AppCategory, DetectionResult, DetectionSource, FrameworkRule, Language,
-    LanguageEntry, LockfileEntry, PackageManager, StorageDir,
+    AppCategory, DetectedStack, DetectionResult, DetectionSource, FrameworkRule, Language,
+    LanguageEntry, LockfileEntry, PackageManager, SourceLanguage, StorageDir,
 };
 
 // Re-export additional functions that are part of the public API
@@ -16,6 +16,7 @@ pub enum AppCategory {
     Backend,
     Library,
     Cli,
+    Infrastructure,
     Unknown,
 }
 
@@ -28,6 +29,7 @@ impl AppCategory {
             AppCategory::Backend => "Backend",
             AppCategory::Library => "Library",
             AppCategory::Cli => "CLI",
+            AppCategory::Infrastructure => "Infrastructure",
             AppCategory::Unknown => "Unknown",
         }
     }
@@ -57,6 +59,14 @@ pub enum Language {
     Cpp,
     Dart,
     Elixir,
+    Zig,
+    Haskell,
+    OCaml,
+    Scala,
+    Clojure,
+    Nix,
+    Terraform,
+    C,
     Unknown,
 }
 
@@ -77,6 +87,14 @@ impl Language {
             Language::Cpp => "C++",
             Language::Dart => "Dart",
             Language::Elixir => "Elixir",
+            Language::Zig => "Zig",
+            Language::Haskell => "Haskell",
+            Language::OCaml => "OCaml",
+            Language::Scala => "Scala",
+            Language::Clojure => "Clojure",
+            Language::Nix => "Nix",
+            Language::Terraform => "Terraform",
+            Language::C => "C",
             Language::Unknown => "Unknown",
         }
     }
@@ -87,6 +105,7 @@ impl Language {
         match self {
             Language::JavaScript | Language::TypeScript => "JavaScript/TypeScript",
             Language::Java | Language::Kotlin => "Java/Kotlin",
+            Language::C | Language::Cpp => "C/C++",
             _ => self.as_str(),
         }
     }
@@ -121,6 +140,19 @@ pub enum PackageManager {
     Nuget,
     Dotnet,
     Mix,
+    Zig,
+    Stack,
+    Cabal,
+    Opam,
+    Dune,
+    Sbt,
+    Mill,
+    Leiningen,
+    ClojureCli,
+    Nix,
+    Terraform,
+    Conan,
+    Vcpkg,
     Unknown,
 }
 
@@ -146,6 +178,19 @@ impl PackageManager {
             PackageManager::Nuget => "nuget",
             PackageManager::Dotnet => "dotnet",
             PackageManager::Mix => "mix",
+            PackageManager::Zig => "zig",
+            PackageManager::Stack => "stack",
+            PackageManager::Cabal => "cabal",
+            PackageManager::Opam => "opam",
+            PackageManager::Dune => "dune",
+            PackageManager::Sbt => "sbt",
+            PackageManager::Mill => "mill",
+            PackageManager::Leiningen => "leiningen",
+            PackageManager::ClojureCli => "clojure cli",
+            PackageManager::Nix => "nix",
+            PackageManager::Terraform => "terraform",
+            PackageManager::Conan => "conan",
+            PackageManager::Vcpkg => "vcpkg",
             PackageManager::Unknown => "unknown",
         }
     }
@@ -158,7 +203,8 @@ impl Default for PackageManager {
 }
 
 /// Where to look for detection patterns
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 #[allow(dead_code)]
 pub enum DetectionSource {
     /// Check if a file exists (e.g., "package.json")
@@ -259,3 +305,22 @@ impl Default for DetectionResult {
         }
     }
 }
+
+/// One stack found in a project: the detection for a manifest in the root
+/// or a subdirectory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedStack {
+    /// Directory relative to the project root, empty for the root itself
+    pub subdir: String,
+    #[serde(flatten)]
+    pub detection: DetectionResult,
+}
+
+/// Source file extensions counted towards a language's line count
+#[derive(Debug, Clone, Copy)]
+pub struct SourceLanguage {
+    /// Language name for display (e.g., "TypeScript", "CSS")
+    pub name: &'static str,
+    /// Extensions without the leading dot
+    pub extensions: &'static [&'static str],
+}
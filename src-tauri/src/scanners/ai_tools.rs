@@ -1,7 +1,7 @@
+use super::process::{self, ProcessRunner, QUICK_TIMEOUT};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
-use std::process::Command;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -530,15 +530,14 @@ fn get_app_version(app_path: &str) -> Option<String> {
     #[cfg(target_os = "macos")]
     {
         let plist_path = format!("{}/Contents/Info", app_path);
-        let output = Command::new("defaults")
+        let output = ProcessRunner::new("defaults")
             .args(["read", &plist_path, "CFBundleShortVersionString"])
-            .output()
+            .timeout(QUICK_TIMEOUT)
+            .run_ok()
             .ok()?;
-        if output.status.success() {
-            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !version.is_empty() {
-                return Some(version);
-            }
+        let version = output.stdout.trim();
+        if !version.is_empty() {
+            return Some(version.to_string());
         }
     }
 
@@ -687,22 +686,19 @@ fn fetch_brew_outdated() -> HashMap<String, BrewOutdated> {
 /// gh extension list → tab-separated text, no --json flag
 fn fetch_gh_extensions() -> HashMap<String, String> {
     let mut map = HashMap::new();
-    let output = Command::new("gh").args(["extension", "list"]).output();
+    let output = ProcessRunner::new("gh").args(["extension", "list"]).run_ok();
     if let Ok(out) = output {
-        if out.status.success() {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            for line in stdout.lines() {
-                let parts: Vec<&str> = line.split('\t').collect();
-                if parts.len() >= 2 {
-                    // format: "gh-copilot\tgithub/gh-copilot\tv1.2.3"
-                    let name = parts[0].trim().to_string();
-                    let version = if parts.len() >= 3 {
-                        parts[2].trim().trim_start_matches('v').to_string()
-                    } else {
-                        String::new()
-                    };
-                    map.insert(name, version);
-                }
+        for line in out.stdout.lines() {
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() >= 2 {
+                // format: "gh-copilot\tgithub/gh-copilot\tv1.2.3"
+                let name = parts[0].trim().to_string();
+                let version = if parts.len() >= 3 {
+                    parts[2].trim().trim_start_matches('v').to_string()
+                } else {
+                    String::new()
+                };
+                map.insert(name, version);
             }
         }
     }
@@ -713,35 +709,29 @@ fn which_binary(binary: &str) -> Option<String> {
     if binary.is_empty() {
         return None;
     }
-    #[cfg(unix)]
-    let cmd = "which";
-    #[cfg(windows)]
-    let cmd = "where.exe";
-
-    let output = Command::new(cmd).arg(binary).output().ok()?;
-    if output.status.success() {
-        let out = String::from_utf8_lossy(&output.stdout);
-        let path = out.lines().next()?.trim().to_string();
-        if !path.is_empty() {
-            return Some(path);
-        }
-    }
-    None
+    process::which(binary).filter(|path| !path.is_empty())
 }
 
 fn get_version(binary: &str) -> Option<String> {
     if binary.is_empty() {
         return None;
     }
-    let output = Command::new(binary).arg("--version").output().ok()?;
+    let output = ProcessRunner::new(binary)
+        .arg("--version")
+        .timeout(QUICK_TIMEOUT)
+        .run()
+        .ok()?;
     // Check both stdout and stderr -- some tools output to stderr
-    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-    let raw = if !stdout.is_empty() { stdout } else { stderr };
+    let stdout = output.stdout.trim();
+    let raw = if !stdout.is_empty() {
+        stdout
+    } else {
+        output.stderr.trim()
+    };
     if raw.is_empty() {
         return None;
     }
-    Some(extract_version(&raw))
+    Some(extract_version(raw))
 }
 
 /// Extract a semver-like version from a string (e.g. "claude v1.2.3" → "1.2.3")
@@ -836,6 +826,25 @@ fn get_mcp_config_spec(tool_name: &str) -> Option<McpConfigSpec> {
     })
 }
 
+/// Tools whose MCP server config Houston knows how to read.
+pub const MCP_TOOLS: &[&str] = &[
+    "Claude Code",
+    "Claude Desktop",
+    "Cursor",
+    "Windsurf",
+    "Zed",
+    "VS Code",
+];
+
+/// Every MCP config file location, whether or not it exists yet.
+pub fn mcp_config_paths() -> Vec<std::path::PathBuf> {
+    MCP_TOOLS
+        .iter()
+        .filter_map(|tool| get_mcp_config_spec(tool))
+        .map(|spec| std::path::PathBuf::from(spec.config_path))
+        .collect()
+}
+
 pub fn scan_mcp_servers(tool_name: &str) -> Vec<crate::scanners::claude::McpServer> {
     let spec = match get_mcp_config_spec(tool_name) {
         Some(s) => s,
@@ -0,0 +1,536 @@
+//! Every local branch across workspace repositories: what hasn't been pushed,
+//! what is merged and safe to delete, and what has gone stale.
+
+use super::git::{git_pool, relative_date};
+use git2::{BranchType, Oid, Repository};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Branches without a commit for this long are marked stale.
+const STALE_AFTER_SECS: i64 = 90 * 24 * 60 * 60;
+
+/// `unique_commits` stops counting here; a repo with no remote at all would
+/// otherwise walk its whole history for every branch.
+const UNIQUE_COMMIT_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchInfo {
+    pub name: String,
+    /// Checked out in the working tree the report was made from
+    pub is_head: bool,
+    /// Checked out in this or another worktree of the repository
+    pub checked_out: bool,
+    pub is_default: bool,
+    /// Tracking branch such as `origin/feature`; empty when none is configured
+    pub upstream: String,
+    /// An upstream is configured but its ref is gone (deleted on the remote)
+    pub upstream_gone: bool,
+    pub ahead: usize,
+    pub behind: usize,
+    /// Commits on no remote-tracking branch, counted only when there is no
+    /// upstream to compare against (up to `UNIQUE_COMMIT_LIMIT`)
+    pub unique_commits: usize,
+    /// Fully contained in the default branch
+    pub merged: bool,
+    pub stale: bool,
+    pub last_commit_message: String,
+    pub last_commit_date: String,
+    pub last_commit_epoch: Option<i64>,
+}
+
+impl BranchInfo {
+    /// Commits that only exist on this machine.
+    pub fn has_unpushed_work(&self) -> bool {
+        self.ahead > 0 || self.unique_commits > 0
+    }
+
+    /// Merged branches other than the default or one that's checked out.
+    pub fn is_deletable(&self) -> bool {
+        self.merged && !self.is_default && !self.checked_out
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoBranches {
+    pub project_path: String,
+    /// Branch `merged` is measured against, e.g. `main`
+    pub default_branch: String,
+    pub has_remote: bool,
+    pub branches: Vec<BranchInfo>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BranchReport {
+    pub repos: Vec<RepoBranches>,
+    /// Branches with commits that exist only on this machine
+    pub unpushed_count: usize,
+    /// Merged branches that `delete_merged` would remove
+    pub deletable_count: usize,
+    pub stale_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedBranch {
+    pub project_path: String,
+    pub name: String,
+    /// Abbreviated tip, so the branch can be recreated with `git branch <name> <commit>`
+    pub commit: String,
+}
+
+/// A branch picked out by repository and name, e.g. from a dry run's list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchRef {
+    pub project_path: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteMergedResult {
+    pub dry_run: bool,
+    /// Deleted, or that would be deleted on a dry run
+    pub deleted: Vec<DeletedBranch>,
+    pub errors: Vec<String>,
+}
+
+/// Branch inventory for one repository.
+pub fn inventory(project_path: &str) -> RepoBranches {
+    read_inventory(project_path).unwrap_or_else(|e| RepoBranches {
+        project_path: project_path.to_string(),
+        default_branch: String::new(),
+        has_remote: false,
+        branches: Vec::new(),
+        error: Some(e.message().to_string()),
+    })
+}
+
+/// Inventory of every repository, reading worktrees of the same repository
+/// only once. Paths that can't be opened are kept, so they show up with an
+/// error instead of quietly missing from the report.
+pub fn report(project_paths: &[String]) -> BranchReport {
+    let mut seen = HashSet::new();
+    let unique: Vec<&String> = project_paths
+        .iter()
+        .filter(|path| seen.insert(common_dir(path).unwrap_or_else(|| PathBuf::from(path))))
+        .collect();
+
+    let repos: Vec<RepoBranches> =
+        git_pool().install(|| unique.par_iter().map(|path| inventory(path)).collect());
+
+    let branches = || repos.iter().flat_map(|r| r.branches.iter());
+    BranchReport {
+        unpushed_count: branches().filter(|b| b.has_unpushed_work()).count(),
+        deletable_count: branches().filter(|b| b.is_deletable()).count(),
+        stale_count: branches().filter(|b| b.stale).count(),
+        repos,
+    }
+}
+
+/// Delete every merged branch in the given repositories. With `dry_run`,
+/// only reports what would go. `only` limits it to those branches (the ones a
+/// dry run showed), and only while they are still merged.
+pub fn delete_merged(
+    project_paths: &[String],
+    dry_run: bool,
+    only: Option<&[BranchRef]>,
+) -> DeleteMergedResult {
+    let mut result = DeleteMergedResult {
+        dry_run,
+        deleted: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for repo_branches in report(project_paths).repos {
+        let path = &repo_branches.project_path;
+        if let Some(e) = repo_branches.error {
+            result.errors.push(format!("{}: {}", path, e));
+            continue;
+        }
+        let repo = match Repository::open(path) {
+            Ok(r) => r,
+            Err(e) => {
+                result.errors.push(format!("{}: {}", path, e.message()));
+                continue;
+            }
+        };
+
+        let selected = |name: &str| {
+            only.is_none_or(|only| {
+                only.iter()
+                    .any(|b| b.project_path == *path && b.name == name)
+            })
+        };
+        // Picked earlier but gone or no longer merged since
+        for wanted in only.unwrap_or_default() {
+            let still_deletable = repo_branches
+                .branches
+                .iter()
+                .any(|b| b.name == wanted.name && b.is_deletable());
+            if wanted.project_path == *path && !still_deletable {
+                result
+                    .errors
+                    .push(format!("{}: {}: no longer merged, kept", path, wanted.name));
+            }
+        }
+
+        for info in repo_branches
+            .branches
+            .iter()
+            .filter(|b| b.is_deletable() && selected(&b.name))
+        {
+            let deleted = || -> Result<DeletedBranch, git2::Error> {
+                let mut branch = repo.find_branch(&info.name, BranchType::Local)?;
+                let commit = branch
+                    .get()
+                    .target()
+                    .map(|oid| oid.to_string()[..7].to_string())
+                    .unwrap_or_default();
+                if !dry_run {
+                    branch.delete()?;
+                }
+                Ok(DeletedBranch {
+                    project_path: path.clone(),
+                    name: info.name.clone(),
+                    commit,
+                })
+            };
+            match deleted() {
+                Ok(branch) => result.deleted.push(branch),
+                Err(e) => result
+                    .errors
+                    .push(format!("{}: {}: {}", path, info.name, e.message())),
+            }
+        }
+    }
+    result
+}
+
+/// Shared `.git` directory, identical for every worktree of a repository.
+fn common_dir(project_path: &str) -> Option<PathBuf> {
+    let repo = Repository::open(project_path).ok()?;
+    let dir = repo.commondir().to_path_buf();
+    Some(dir.canonicalize().unwrap_or(dir))
+}
+
+fn read_inventory(project_path: &str) -> Result<RepoBranches, git2::Error> {
+    let repo = Repository::open(project_path)?;
+    let now = chrono::Utc::now().timestamp();
+
+    let remote_tips: Vec<Oid> = repo
+        .branches(Some(BranchType::Remote))?
+        .flatten()
+        .filter_map(|(branch, _)| branch.get().target())
+        .collect();
+    let has_remote = !repo.remotes()?.is_empty();
+
+    let (default_branch, default_tips) = default_branch(&repo);
+    let checked_out = checked_out_branches(&repo);
+    let head_name = repo
+        .head()
+        .ok()
+        .filter(|h| h.is_branch())
+        .and_then(|h| h.shorthand().map(str::to_string));
+
+    let mut branches = Vec::new();
+    for (branch, _) in repo.branches(Some(BranchType::Local))?.flatten() {
+        let Some(name) = branch.name()?.map(str::to_string) else {
+            continue;
+        };
+        let Some(tip) = branch.get().target() else {
+            continue;
+        };
+
+        let mut info = BranchInfo {
+            is_head: head_name.as_deref() == Some(name.as_str()),
+            checked_out: checked_out.contains(&name),
+            is_default: name == default_branch,
+            upstream: String::new(),
+            upstream_gone: false,
+            ahead: 0,
+            behind: 0,
+            unique_commits: 0,
+            merged: false,
+            stale: false,
+            last_commit_message: String::new(),
+            last_commit_date: String::new(),
+            last_commit_epoch: None,
+            name,
+        };
+
+        match branch.upstream() {
+            Ok(upstream) => {
+                info.upstream = upstream.name()?.unwrap_or_default().to_string();
+                if let Some(up_tip) = upstream.get().target() {
+                    (info.ahead, info.behind) = repo.graph_ahead_behind(tip, up_tip)?;
+                }
+            }
+            Err(e) => {
+                // Configured but missing means the remote branch was deleted
+                info.upstream_gone = e.code() == git2::ErrorCode::NotFound
+                    && repo
+                        .config()?
+                        .get_string(&format!("branch.{}.merge", info.name))
+                        .is_ok();
+                info.unique_commits = count_unique(&repo, tip, &remote_tips)?;
+            }
+        }
+
+        info.merged = !info.is_default
+            && default_tips
+                .iter()
+                .any(|&d| d == tip || repo.graph_descendant_of(d, tip).unwrap_or(false));
+
+        if let Ok(commit) = repo.find_commit(tip) {
+            let epoch = commit.time().seconds();
+            info.last_commit_message = commit.summary().unwrap_or_default().to_string();
+            info.last_commit_date = relative_date(epoch, now);
+            info.last_commit_epoch = Some(epoch);
+            info.stale = now - epoch > STALE_AFTER_SECS;
+        }
+        branches.push(info);
+    }
+
+    // Default branch first, then most recently committed
+    branches.sort_by(|a, b| {
+        b.is_default
+            .cmp(&a.is_default)
+            .then_with(|| b.last_commit_epoch.cmp(&a.last_commit_epoch))
+    });
+
+    Ok(RepoBranches {
+        project_path: project_path.to_string(),
+        default_branch,
+        has_remote,
+        branches,
+        error: None,
+    })
+}
+
+/// The branch others merge into: whatever `origin/HEAD` points at, else
+/// `main` or `master`, else the checked-out branch. Returns its name and the
+/// tips (local and `origin/`) that count as "merged into".
+fn default_branch(repo: &Repository) -> (String, Vec<Oid>) {
+    let from_origin = repo
+        .find_reference("refs/remotes/origin/HEAD")
+        .ok()
+        .and_then(|r| r.symbolic_target().map(str::to_string))
+        .and_then(|t| t.strip_prefix("refs/remotes/origin/").map(str::to_string));
+    let name = from_origin
+        .or_else(|| {
+            ["main", "master"]
+                .into_iter()
+                .find(|n| repo.find_branch(n, BranchType::Local).is_ok())
+                .map(str::to_string)
+        })
+        .or_else(|| {
+            repo.head()
+                .ok()
+                .and_then(|h| h.shorthand().map(str::to_string))
+        })
+        .unwrap_or_default();
+
+    let tips = [
+        format!("refs/heads/{}", name),
+        format!("refs/remotes/origin/{}", name),
+    ]
+    .iter()
+    .filter_map(|r| repo.refname_to_id(r).ok())
+    .collect();
+    (name, tips)
+}
+
+/// Branches checked out in the main working tree or any linked worktree.
+fn checked_out_branches(repo: &Repository) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let Ok(main) = Repository::open(repo.commondir()) else {
+        return names;
+    };
+    let mut heads = vec![main
+        .head()
+        .ok()
+        .and_then(|h| h.shorthand().map(str::to_string))];
+    if let Ok(worktrees) = main.worktrees() {
+        for name in worktrees.iter().flatten() {
+            let head = main
+                .find_worktree(name)
+                .and_then(|wt| Repository::open_from_worktree(&wt))
+                .ok()
+                .and_then(|r| {
+                    r.head()
+                        .ok()
+                        .and_then(|h| h.shorthand().map(str::to_string))
+                });
+            heads.push(head);
+        }
+    }
+    names.extend(heads.into_iter().flatten());
+    names
+}
+
+/// Commits reachable from `tip` but from no remote-tracking branch.
+fn count_unique(repo: &Repository, tip: Oid, remote_tips: &[Oid]) -> Result<usize, git2::Error> {
+    let mut walk = repo.revwalk()?;
+    walk.push(tip)?;
+    for &remote in remote_tips {
+        walk.hide(remote)?;
+    }
+    Ok(walk.take(UNIQUE_COMMIT_LIMIT).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, file: &str, message: &str) -> Oid {
+        fs::write(repo.workdir().unwrap().join(file), message).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Houston", "houston@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    /// `origin` is a second local repo; `main` is pushed, `shipped` is merged,
+    /// `wip` has two local-only commits and `tracked` is one ahead of origin.
+    fn fixture(tmp: &Path) -> (Repository, String) {
+        let origin_dir = tmp.join("origin");
+        let origin = Repository::init(&origin_dir).unwrap();
+        commit(&origin, "README.md", "initial commit");
+        let mut main_name = origin.head().unwrap().shorthand().unwrap().to_string();
+        if main_name != "main" {
+            origin
+                .find_branch(&main_name, BranchType::Local)
+                .unwrap()
+                .rename("main", true)
+                .unwrap();
+            main_name = "main".to_string();
+        }
+        let base = origin.head().unwrap().peel_to_commit().unwrap();
+        origin.branch("tracked", &base, false).unwrap();
+
+        let clone_dir = tmp.join("clone");
+        let url = format!("file://{}", origin_dir.display());
+        let repo = Repository::clone(&url, &clone_dir).unwrap();
+        {
+            let origin_tracked = repo
+                .find_reference("refs/remotes/origin/tracked")
+                .unwrap()
+                .peel_to_commit()
+                .unwrap();
+            repo.branch("tracked", &origin_tracked, false)
+                .unwrap()
+                .set_upstream(Some("origin/tracked"))
+                .unwrap();
+
+            let main_tip = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.branch("shipped", &main_tip, false).unwrap();
+            repo.branch("wip", &main_tip, false).unwrap();
+        }
+
+        for (branch, commits) in [("wip", 2), ("tracked", 1)] {
+            repo.set_head(&format!("refs/heads/{}", branch)).unwrap();
+            for i in 0..commits {
+                commit(&repo, &format!("{}-{}.txt", branch, i), branch);
+            }
+        }
+        repo.set_head(&format!("refs/heads/{}", main_name)).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let path = clone_dir.to_string_lossy().to_string();
+        (repo, path)
+    }
+
+    #[test]
+    fn test_inventory_flags_unpushed_and_merged() {
+        let tmp = TempDir::new().unwrap();
+        let (_repo, path) = fixture(tmp.path());
+        let inv = inventory(&path);
+        assert_eq!(inv.error, None);
+        assert_eq!(inv.default_branch, "main");
+        assert!(inv.has_remote);
+        assert_eq!(inv.branches[0].name, "main");
+
+        let get = |name: &str| inv.branches.iter().find(|b| b.name == name).unwrap();
+        assert!(get("main").is_head && get("main").is_default);
+        assert!(!get("main").has_unpushed_work());
+        assert_eq!(get("wip").unique_commits, 2);
+        assert!(get("wip").has_unpushed_work() && !get("wip").merged);
+        assert_eq!(get("tracked").upstream, "origin/tracked");
+        assert_eq!((get("tracked").ahead, get("tracked").behind), (1, 0));
+        assert!(get("shipped").merged && get("shipped").is_deletable());
+        assert!(!get("main").is_deletable());
+    }
+
+    #[test]
+    fn test_unreadable_repo_is_reported() {
+        let tmp = TempDir::new().unwrap();
+        let (_repo, path) = fixture(tmp.path());
+        let broken = tmp.path().join("not-a-repo").to_string_lossy().to_string();
+        let report = report(&[path, broken.clone(), broken.clone()]);
+        assert_eq!(report.repos.len(), 2);
+        let failed = &report.repos[1];
+        assert_eq!(failed.project_path, broken);
+        assert!(failed.error.is_some());
+    }
+
+    #[test]
+    fn test_delete_merged_dry_run_then_delete() {
+        let tmp = TempDir::new().unwrap();
+        let (repo, path) = fixture(tmp.path());
+        let paths = vec![path.clone(), path];
+
+        let preview = delete_merged(&paths, true, None);
+        assert!(preview.dry_run);
+        let names: Vec<&str> = preview.deleted.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["shipped"]);
+        assert!(repo.find_branch("shipped", BranchType::Local).is_ok());
+
+        let done = delete_merged(&paths, false, None);
+        assert!(done.errors.is_empty(), "{:?}", done.errors);
+        assert_eq!(done.deleted.len(), 1);
+        assert!(repo.find_branch("shipped", BranchType::Local).is_err());
+        assert!(repo.find_branch("wip", BranchType::Local).is_ok());
+    }
+
+    #[test]
+    fn test_delete_merged_only_previewed_branches() {
+        let tmp = TempDir::new().unwrap();
+        let (repo, path) = fixture(tmp.path());
+        let paths = vec![path.clone()];
+        let previewed: Vec<BranchRef> = delete_merged(&paths, true, None)
+            .deleted
+            .into_iter()
+            .map(|b| BranchRef {
+                project_path: b.project_path,
+                name: b.name,
+            })
+            .collect();
+
+        // Merged after the preview: not part of what was confirmed
+        let tip = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("late", &tip, false).unwrap();
+        let mut picked = previewed.clone();
+        picked.push(BranchRef {
+            project_path: path.clone(),
+            name: "wip".to_string(),
+        });
+
+        let done = delete_merged(&paths, false, Some(&picked));
+        let names: Vec<&str> = done.deleted.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["shipped"]);
+        assert!(repo.find_branch("late", BranchType::Local).is_ok());
+        assert!(repo.find_branch("wip", BranchType::Local).is_ok());
+        assert_eq!(done.errors.len(), 1);
+        assert!(done.errors[0].contains("wip"));
+    }
+}
@@ -0,0 +1,1067 @@
+//! Docker Compose projects.
+//!
+//! The Engine API has no notion of Compose, so projects are rebuilt from the
+//! labels Compose puts on every container it creates, and project-level
+//! actions go through the `docker compose` CLI pointed at the same files.
+//!
+//! Compose files inside workspace projects are parsed too, so a stack that
+//! isn't running yet still shows its services and the ports it would take.
+
+use super::dev_servers::DevServer;
+use super::docker::{ComposeProject, ContainerInfo, DockerStatus};
+use super::process::{ProcessRunner, INSTALL_TIMEOUT, QUICK_TIMEOUT};
+use super::workspace::ProjectInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+const WORKING_DIR_LABEL: &str = "com.docker.compose.project.working_dir";
+const CONFIG_FILES_LABEL: &str = "com.docker.compose.project.config_files";
+
+/// Project-level operations, mirroring the `docker compose` subcommands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComposeAction {
+    Up,
+    Down,
+    Restart,
+    Pull,
+    /// Recreate one service's containers without touching its dependencies
+    Recreate,
+}
+
+/// Group containers into their Compose projects, sorted by name.
+pub fn group_projects(containers: &[ContainerInfo]) -> Vec<ComposeProject> {
+    let mut projects: BTreeMap<String, ComposeProject> = BTreeMap::new();
+
+    for container in containers {
+        let Some(name) = container.labels.get(PROJECT_LABEL) else {
+            continue;
+        };
+        let project = projects
+            .entry(name.clone())
+            .or_insert_with(|| ComposeProject {
+                name: name.clone(),
+                container_count: 0,
+                running_count: 0,
+                containers: vec![],
+                working_dir: None,
+                config_files: vec![],
+                services: vec![],
+                project_path: None,
+                project_name: None,
+            });
+
+        project.container_count += 1;
+        if container.status == "running" {
+            project.running_count += 1;
+        }
+        project.containers.push(container.id.clone());
+
+        if let Some(service) = container.labels.get(SERVICE_LABEL) {
+            if !project.services.contains(service) {
+                project.services.push(service.clone());
+            }
+        }
+        if project.working_dir.is_none() {
+            project.working_dir = container.labels.get(WORKING_DIR_LABEL).cloned();
+        }
+        if project.config_files.is_empty() {
+            if let Some(files) = container.labels.get(CONFIG_FILES_LABEL) {
+                project.config_files = files
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|f| !f.is_empty())
+                    .map(String::from)
+                    .collect();
+            }
+        }
+    }
+
+    projects
+        .into_values()
+        .map(|mut p| {
+            p.services.sort();
+            p
+        })
+        .collect()
+}
+
+/// Point each Compose project at the workspace project it was started from:
+/// the deepest discovered project containing its working dir, as long as that
+/// dir is inside one of the configured workspaces.
+pub fn link_projects(
+    compose: &mut [ComposeProject],
+    workspaces: &[String],
+    projects: &[ProjectInfo],
+) {
+    for project in compose.iter_mut() {
+        let Some(dir) = project.working_dir.as_deref().map(Path::new) else {
+            continue;
+        };
+        if !workspaces.iter().any(|w| dir.starts_with(w)) {
+            continue;
+        }
+        let owner = projects
+            .iter()
+            .filter(|p| dir.starts_with(&p.path))
+            .max_by_key(|p| Path::new(&p.path).components().count());
+        if let Some(owner) = owner {
+            project.project_path = Some(owner.path.clone());
+            project.project_name = Some(owner.name.clone());
+        }
+    }
+}
+
+/// A project that has never been started has no containers to read labels
+/// from, so act on its compose file directly. No `--file` flags: Compose
+/// finds the file (and its override) in the working dir on its own.
+pub fn project_from_file(project: &ProjectInfo) -> Option<ComposeProject> {
+    let file = project.compose.as_ref()?;
+    Some(ComposeProject {
+        name: file.project_name.clone(),
+        container_count: 0,
+        running_count: 0,
+        containers: vec![],
+        working_dir: Some(project.path.clone()),
+        config_files: vec![],
+        services: file.services.iter().map(|s| s.name.clone()).collect(),
+        project_path: Some(project.path.clone()),
+        project_name: Some(project.name.clone()),
+    })
+}
+
+/// The Compose CLI to use: the v2 plugin, standalone `docker-compose`, or
+/// Podman's equivalents. Probed once.
+fn compose_cli() -> Option<&'static (String, Vec<String>)> {
+    static CLI: OnceLock<Option<(String, Vec<String>)>> = OnceLock::new();
+    CLI.get_or_init(|| {
+        let candidates: [(&str, &[&str]); 4] = [
+            ("docker", &["compose"]),
+            ("docker-compose", &[]),
+            ("podman", &["compose"]),
+            ("podman-compose", &[]),
+        ];
+        candidates.iter().find_map(|(program, prefix)| {
+            ProcessRunner::new(program)
+                .args(prefix.iter())
+                .arg("version")
+                .timeout(QUICK_TIMEOUT)
+                .run_ok()
+                .ok()
+                .map(|_| {
+                    (
+                        program.to_string(),
+                        prefix.iter().map(|s| s.to_string()).collect(),
+                    )
+                })
+        })
+    })
+    .as_ref()
+}
+
+/// The compose command as typed in a shell (`docker compose`,
+/// `podman-compose`, ...), for commands that are suggested rather than run.
+pub fn cli_command() -> String {
+    compose_cli()
+        .map(|(program, prefix)| {
+            std::iter::once(program.as_str())
+                .chain(prefix.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_else(|| "docker compose".to_string())
+}
+
+/// Arguments after `docker compose` for an action on a project.
+fn compose_args(
+    project: &ComposeProject,
+    action: ComposeAction,
+    service: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let mut args = vec!["--project-name".to_string(), project.name.clone()];
+    if let Some(dir) = &project.working_dir {
+        args.push("--project-directory".to_string());
+        args.push(dir.clone());
+    }
+    for file in &project.config_files {
+        args.push("--file".to_string());
+        args.push(file.clone());
+    }
+
+    match action {
+        ComposeAction::Up => args.extend(["up", "--detach"].map(String::from)),
+        ComposeAction::Down => args.push("down".to_string()),
+        ComposeAction::Restart => args.push("restart".to_string()),
+        ComposeAction::Pull => args.push("pull".to_string()),
+        ComposeAction::Recreate => {
+            let service = service.ok_or("Recreate needs a service name")?;
+            args.extend(["up", "--detach", "--force-recreate", "--no-deps"].map(String::from));
+            args.push(service.to_string());
+            return Ok(args);
+        }
+    }
+    args.extend(service.map(String::from));
+    Ok(args)
+}
+
+/// Run a Compose action against a project found by the Docker scan, using
+/// the working dir and files recorded in its labels. Returns the CLI output
+/// (Compose reports progress on stderr).
+pub fn run_action(
+    project: &ComposeProject,
+    action: ComposeAction,
+    service: Option<&str>,
+) -> Result<String, String> {
+    let dir = project.working_dir.as_deref().ok_or_else(|| {
+        format!(
+            "Compose project {} has no working directory label",
+            project.name
+        )
+    })?;
+    if !Path::new(dir).is_dir() {
+        return Err(format!(
+            "Compose working directory no longer exists: {}",
+            dir
+        ));
+    }
+    if let Some(missing) = project
+        .config_files
+        .iter()
+        .find(|f| !Path::new(f).is_file())
+    {
+        return Err(format!("Compose file not found: {}", missing));
+    }
+    if let Some(service) = service {
+        if !project.services.iter().any(|s| s == service) {
+            return Err(format!("Unknown service {} in {}", service, project.name));
+        }
+    }
+
+    let (program, prefix) = compose_cli().ok_or(
+        "Docker Compose is not installed (tried docker compose, docker-compose, podman compose)",
+    )?;
+    let output = ProcessRunner::new(program)
+        .args(prefix)
+        .args(compose_args(project, action, service)?)
+        .current_dir(dir)
+        .timeout(INSTALL_TIMEOUT)
+        .run_ok()
+        .map_err(|e| e.to_string())?;
+    Ok(format!("{}{}", output.stdout, output.stderr)
+        .trim()
+        .to_string())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Compose files in workspace projects
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// File names `docker compose` looks for, in its order of preference
+const COMPOSE_FILE_NAMES: &[&str] = &[
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yml",
+    "docker-compose.yaml",
+];
+
+/// A port a service publishes on the host
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComposePort {
+    pub host_ip: Option<String>,
+    pub published: u16,
+    pub target: u16,
+    pub protocol: String,
+}
+
+/// A service declared in a compose file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposeService {
+    pub name: String,
+    pub image: Option<String>,
+    /// Built from a local Dockerfile rather than pulled
+    pub build: bool,
+    pub container_name: Option<String>,
+    pub ports: Vec<ComposePort>,
+}
+
+/// A project's compose file (plus its override file, when there is one)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposeFile {
+    pub path: String,
+    /// The Compose project name: top-level `name:`, `COMPOSE_PROJECT_NAME`
+    /// from `.env`, or the normalized directory name
+    pub project_name: String,
+    pub services: Vec<ComposeService>,
+}
+
+/// Parse the compose file in a project directory, if it has one.
+pub fn parse_project(dir: &Path) -> Option<ComposeFile> {
+    let path = COMPOSE_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|p| p.is_file())?;
+    let content = fs::read_to_string(&path).ok()?;
+
+    let dotenv = read_dotenv(&dir.join(".env"));
+    let vars = |name: &str| {
+        std::env::var(name)
+            .ok()
+            .or_else(|| dotenv.get(name).cloned())
+    };
+    let mut parsed = parse_compose(&content, &vars);
+
+    // compose.override.yaml is merged on top automatically
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path.extension().unwrap_or_default().to_string_lossy();
+    let override_path = dir.join(format!("{}.override.{}", stem, ext));
+    if let Ok(content) = fs::read_to_string(override_path) {
+        let overrides = parse_compose(&content, &vars);
+        for service in overrides.services {
+            match parsed.services.iter_mut().find(|s| s.name == service.name) {
+                Some(base) => {
+                    base.image = service.image.or(base.image.take());
+                    base.build |= service.build;
+                    base.container_name = service.container_name.or(base.container_name.take());
+                    base.ports.extend(service.ports);
+                }
+                None => parsed.services.push(service),
+            }
+        }
+        parsed.name = parsed.name.or(overrides.name);
+    }
+
+    let project_name = parsed
+        .name
+        .or_else(|| dotenv.get("COMPOSE_PROJECT_NAME").cloned())
+        .unwrap_or_else(|| {
+            normalize_project_name(&dir.file_name().unwrap_or_default().to_string_lossy())
+        });
+
+    Some(ComposeFile {
+        path: path.to_string_lossy().to_string(),
+        project_name,
+        services: parsed.services,
+    })
+}
+
+struct ParsedCompose {
+    name: Option<String>,
+    services: Vec<ComposeService>,
+}
+
+/// Pull project name, services, images and ports out of a compose file.
+///
+/// Like `detect_pnpm_workspace`, this reads the YAML line by line rather than
+/// pulling in a YAML parser: it follows indentation for the handful of keys
+/// we need and ignores the rest. Anchors and `extends` are not expanded.
+fn parse_compose(content: &str, vars: &dyn Fn(&str) -> Option<String>) -> ParsedCompose {
+    let lines: Vec<(usize, String)> = content
+        .lines()
+        .filter_map(|line| {
+            let text = strip_comment(line).trim_end();
+            let trimmed = text.trim_start();
+            if trimmed.is_empty() || trimmed == "---" {
+                return None;
+            }
+            Some((text.len() - trimmed.len(), trimmed.to_string()))
+        })
+        .collect();
+
+    let mut parsed = ParsedCompose {
+        name: None,
+        services: Vec::new(),
+    };
+    let mut i = 0;
+    while i < lines.len() {
+        let (indent, text) = &lines[i];
+        i += 1;
+        if *indent != 0 {
+            continue;
+        }
+        match split_key(text) {
+            Some(("name", value)) if !value.is_empty() => {
+                parsed.name = Some(interpolate(&unquote(value), vars));
+            }
+            Some(("services", _)) => {
+                let start = i;
+                while i < lines.len() && lines[i].0 > 0 {
+                    i += 1;
+                }
+                parsed.services = parse_services(&lines[start..i], vars);
+            }
+            _ => {}
+        }
+    }
+    parsed
+}
+
+fn parse_services(
+    lines: &[(usize, String)],
+    vars: &dyn Fn(&str) -> Option<String>,
+) -> Vec<ComposeService> {
+    let Some(service_indent) = lines.first().map(|(indent, _)| *indent) else {
+        return Vec::new();
+    };
+
+    let mut services = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let (indent, text) = &lines[i];
+        i += 1;
+        if *indent != service_indent {
+            continue;
+        }
+        let Some((name, _)) = split_key(text) else {
+            continue;
+        };
+        let start = i;
+        while i < lines.len() && lines[i].0 > service_indent {
+            i += 1;
+        }
+        services.push(parse_service(name, &lines[start..i], vars));
+    }
+    services
+}
+
+fn parse_service(
+    name: &str,
+    lines: &[(usize, String)],
+    vars: &dyn Fn(&str) -> Option<String>,
+) -> ComposeService {
+    let mut service = ComposeService {
+        name: unquote(name),
+        image: None,
+        build: false,
+        container_name: None,
+        ports: Vec::new(),
+    };
+    let Some(key_indent) = lines.first().map(|(indent, _)| *indent) else {
+        return service;
+    };
+
+    let mut i = 0;
+    while i < lines.len() {
+        let (indent, text) = &lines[i];
+        i += 1;
+        if *indent != key_indent {
+            continue;
+        }
+        let Some((key, value)) = split_key(text) else {
+            continue;
+        };
+        let start = i;
+        while i < lines.len() && lines[i].0 > key_indent {
+            i += 1;
+        }
+        let nested = &lines[start..i];
+
+        match key {
+            "image" => service.image = Some(interpolate(&unquote(value), vars)),
+            "container_name" => service.container_name = Some(interpolate(&unquote(value), vars)),
+            "build" => service.build = true,
+            "ports" => service.ports = parse_ports(value, nested, vars),
+            _ => {}
+        }
+    }
+    service
+}
+
+/// `ports:` as a flow list (`["3000:3000"]`), short-syntax items
+/// (`- "127.0.0.1:5432:5432"`) or long-syntax maps (`- target: 80`).
+fn parse_ports(
+    inline: &str,
+    lines: &[(usize, String)],
+    vars: &dyn Fn(&str) -> Option<String>,
+) -> Vec<ComposePort> {
+    if let Some(flow) = inline.strip_prefix('[') {
+        return flow
+            .trim_end_matches(']')
+            .split(',')
+            .flat_map(|item| parse_short_port(&interpolate(&unquote(item.trim()), vars)))
+            .collect();
+    }
+
+    let mut ports = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let Some(item) = lines[i].1.strip_prefix('-').map(str::trim) else {
+            i += 1;
+            continue;
+        };
+        let item_indent = lines[i].0;
+        i += 1;
+
+        match split_key(item) {
+            // Long syntax: the first key sits on the dash line, the rest below it
+            Some((key, value)) => {
+                let mut fields = BTreeMap::from([(key.to_string(), value.to_string())]);
+                while i < lines.len() && lines[i].0 > item_indent {
+                    if let Some((k, v)) = split_key(&lines[i].1) {
+                        fields.insert(k.to_string(), v.to_string());
+                    }
+                    i += 1;
+                }
+                let field = |k: &str| fields.get(k).map(|v| interpolate(&unquote(v), vars));
+                let (Some(target), Some(published)) = (
+                    field("target").and_then(|t| t.parse().ok()),
+                    field("published"),
+                ) else {
+                    continue;
+                };
+                let protocol = field("protocol").unwrap_or_else(|| "tcp".to_string());
+                let host_ip = field("host_ip").filter(|ip| !ip.is_empty());
+                for published in port_range(&published) {
+                    ports.push(ComposePort {
+                        host_ip: host_ip.clone(),
+                        published,
+                        target,
+                        protocol: protocol.clone(),
+                    });
+                }
+            }
+            None => ports.extend(parse_short_port(&interpolate(&unquote(item), vars))),
+        }
+    }
+    ports
+}
+
+/// `[HOST_IP:]HOST:CONTAINER[/PROTOCOL]`, where either side may be a range.
+/// A bare container port or an empty host port publishes on a random port,
+/// which can't collide, so those yield nothing.
+fn parse_short_port(spec: &str) -> Vec<ComposePort> {
+    let (spec, protocol) = spec.split_once('/').unwrap_or((spec, "tcp"));
+
+    // Bracketed IPv6 host addresses contain colons of their own
+    let (host_ip, rest) = match spec.strip_prefix('[') {
+        Some(v6) => match v6.split_once("]:") {
+            Some((ip, rest)) => (Some(ip.to_string()), rest),
+            None => return Vec::new(),
+        },
+        None => (None, spec),
+    };
+    let parts: Vec<&str> = rest.split(':').collect();
+    let (host_ip, published, target) = match parts.as_slice() {
+        [published, target] => (host_ip, *published, *target),
+        [ip, published, target] if host_ip.is_none() => (Some(ip.to_string()), *published, *target),
+        _ => return Vec::new(),
+    };
+
+    let targets = port_range(target);
+    port_range(published)
+        .into_iter()
+        .enumerate()
+        .map(|(n, port)| ComposePort {
+            host_ip: host_ip.clone().filter(|ip| !ip.is_empty()),
+            published: port,
+            target: targets.get(n).or(targets.first()).copied().unwrap_or(port),
+            protocol: protocol.to_string(),
+        })
+        .collect()
+}
+
+/// "8080" or "8080-8085"; empty or unparsable gives nothing.
+fn port_range(spec: &str) -> Vec<u16> {
+    let spec = spec.trim();
+    match spec.split_once('-') {
+        Some((start, end)) => match (start.parse::<u16>(), end.parse::<u16>()) {
+            (Ok(start), Ok(end)) if start <= end => (start..=end).take(1000).collect(),
+            _ => Vec::new(),
+        },
+        None => spec.parse().map(|p| vec![p]).unwrap_or_default(),
+    }
+}
+
+/// Expand `${VAR}`, `${VAR:-default}`, `${VAR-default}` and `$VAR`.
+fn interpolate(value: &str, vars: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        if let Some(escaped) = after.strip_prefix('$') {
+            out.push('$');
+            rest = escaped;
+        } else if let Some(braced) = after.strip_prefix('{') {
+            let Some(end) = braced.find('}') else {
+                out.push_str(&rest[pos..]);
+                return out;
+            };
+            let expr = &braced[..end];
+            let value = if let Some((name, default)) = expr.split_once(":-") {
+                vars(name)
+                    .filter(|v| !v.is_empty())
+                    .unwrap_or(default.to_string())
+            } else if let Some((name, default)) = expr.split_once('-') {
+                vars(name).unwrap_or(default.to_string())
+            } else {
+                let name = expr.split([':', '?']).next().unwrap_or(expr);
+                vars(name).unwrap_or_default()
+            };
+            out.push_str(&value);
+            rest = &braced[end + 1..];
+        } else {
+            let len = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            out.push_str(&vars(&after[..len]).unwrap_or_default());
+            rest = &after[len..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+pub(super) fn read_dotenv(path: &Path) -> HashMap<String, String> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.starts_with('#') {
+                return None;
+            }
+            let (key, value) = line.trim_start_matches("export ").split_once('=')?;
+            Some((key.trim().to_string(), unquote(value.trim())))
+        })
+        .collect()
+}
+
+/// Compose lowercases the directory name and drops anything but
+/// `[a-z0-9_-]`.
+fn normalize_project_name(dir_name: &str) -> String {
+    dir_name
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+        .collect::<String>()
+        .trim_start_matches(['_', '-'])
+        .to_string()
+}
+
+/// `key: value` or `key:`; `None` for list items and plain scalars.
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with('-') {
+        return None;
+    }
+    let (key, value) = if let Some(key) = text.strip_suffix(':') {
+        (key, "")
+    } else {
+        text.split_once(": ")?
+    };
+    let key = key.trim().trim_matches(['"', '\'']);
+    if key.is_empty() || key.contains(' ') {
+        return None;
+    }
+    Some((key, value.trim()))
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    let quoted = value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')));
+    if quoted {
+        value[1..value.len() - 1].to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Drop a trailing `# comment`, leaving `#` inside quotes alone.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut prev = ' ';
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && prev.is_whitespace() => return &line[..i],
+            None => {}
+        }
+        prev = c;
+    }
+    line
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Stacks: compose files vs. what's actually running
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A declared service and whether a container for it is up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StackService {
+    pub name: String,
+    pub image: Option<String>,
+    pub ports: Vec<ComposePort>,
+    pub running: bool,
+    pub container_id: Option<String>,
+}
+
+/// Something already holding a port a stopped service wants to publish
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortCollision {
+    pub port: u16,
+    pub service: String,
+    /// "container", "dev_server" or "stack"
+    pub kind: String,
+    /// Who has it, e.g. "blog-web-1", "vite (pid 4312)" or "blog/web"
+    pub owner: String,
+}
+
+/// A workspace project's compose file cross-referenced with Docker and the
+/// dev servers that are running right now
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComposeStack {
+    pub project_path: String,
+    pub project_name: String,
+    pub file: String,
+    pub compose_project: String,
+    pub services: Vec<StackService>,
+    pub running_count: usize,
+    pub collisions: Vec<PortCollision>,
+}
+
+/// Build a stack for every project with a compose file.
+pub fn stacks(
+    projects: &[ProjectInfo],
+    docker: &DockerStatus,
+    dev_servers: &[DevServer],
+) -> Vec<ComposeStack> {
+    let mut stacks: Vec<ComposeStack> = projects
+        .iter()
+        .filter_map(|project| {
+            let file = project.compose.as_ref()?;
+            let services: Vec<StackService> = file
+                .services
+                .iter()
+                .map(|service| {
+                    let container = docker.containers.iter().find(|c| {
+                        c.status == "running"
+                            && c.compose_project.as_deref() == Some(&file.project_name)
+                            && c.compose_service.as_deref() == Some(&service.name)
+                    });
+                    StackService {
+                        name: service.name.clone(),
+                        image: service.image.clone(),
+                        ports: service.ports.clone(),
+                        running: container.is_some(),
+                        container_id: container.map(|c| c.id.clone()),
+                    }
+                })
+                .collect();
+            Some(ComposeStack {
+                project_path: project.path.clone(),
+                project_name: project.name.clone(),
+                file: file.path.clone(),
+                compose_project: file.project_name.clone(),
+                running_count: services.iter().filter(|s| s.running).count(),
+                services,
+                collisions: Vec::new(),
+            })
+        })
+        .collect();
+
+    let collisions: Vec<Vec<PortCollision>> = stacks
+        .iter()
+        .map(|stack| find_collisions(stack, &stacks, docker, dev_servers))
+        .collect();
+    for (stack, collisions) in stacks.iter_mut().zip(collisions) {
+        stack.collisions = collisions;
+    }
+    stacks
+}
+
+/// Ports that `docker compose up` would fail to bind for services that
+/// aren't running yet.
+fn find_collisions(
+    stack: &ComposeStack,
+    all: &[ComposeStack],
+    docker: &DockerStatus,
+    dev_servers: &[DevServer],
+) -> Vec<PortCollision> {
+    let mut collisions = Vec::new();
+
+    for service in stack.services.iter().filter(|s| !s.running) {
+        for port in &service.ports {
+            let mut collide = |kind: &str, owner: String| {
+                collisions.push(PortCollision {
+                    port: port.published,
+                    service: service.name.clone(),
+                    kind: kind.to_string(),
+                    owner,
+                });
+            };
+
+            for container in &docker.containers {
+                if container.status != "running"
+                    || container.compose_project.as_deref() == Some(&stack.compose_project)
+                {
+                    continue;
+                }
+                let taken = container.ports.iter().any(|p| {
+                    p.host_port == Some(port.published)
+                        && p.protocol == port.protocol
+                        && ips_overlap(p.host_ip.as_deref(), port.host_ip.as_deref())
+                });
+                if taken {
+                    collide("container", container.name.clone());
+                }
+            }
+
+            if port.protocol == "tcp" {
+                for server in dev_servers.iter().filter(|s| s.port == port.published) {
+                    let name = server.framework.as_deref().unwrap_or(&server.process_name);
+                    collide("dev_server", format!("{} (pid {})", name, server.pid));
+                }
+            }
+
+            for other in all
+                .iter()
+                .filter(|o| o.compose_project != stack.compose_project)
+            {
+                for other_service in &other.services {
+                    // A running service already shows up as a container above
+                    let clash = !other_service.running
+                        && other_service.ports.iter().any(|p| {
+                            p.published == port.published
+                                && p.protocol == port.protocol
+                                && ips_overlap(p.host_ip.as_deref(), port.host_ip.as_deref())
+                        });
+                    if clash {
+                        collide(
+                            "stack",
+                            format!("{}/{}", other.compose_project, other_service.name),
+                        );
+                    }
+                }
+            }
+        }
+    }
+    collisions
+}
+
+/// Binding to all interfaces conflicts with any address on the same port.
+fn ips_overlap(a: Option<&str>, b: Option<&str>) -> bool {
+    let any = |ip: Option<&str>| matches!(ip, None | Some("" | "0.0.0.0" | "::"));
+    any(a) || any(b) || a == b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanners::docker::PortBinding;
+
+    fn container(id: &str, project: &str, service: &str, status: &str) -> ContainerInfo {
+        let labels = BTreeMap::from([
+            (PROJECT_LABEL.to_string(), project.to_string()),
+            (SERVICE_LABEL.to_string(), service.to_string()),
+            (
+                WORKING_DIR_LABEL.to_string(),
+                "/home/dev/code/shop".to_string(),
+            ),
+            (
+                CONFIG_FILES_LABEL.to_string(),
+                "/home/dev/code/shop/compose.yaml,/home/dev/code/shop/compose.override.yaml"
+                    .to_string(),
+            ),
+        ]);
+        ContainerInfo {
+            id: id.to_string(),
+            name: format!("{}-{}-1", project, service),
+            image: "postgres:16".to_string(),
+            status: status.to_string(),
+            state_detail: String::new(),
+            created: String::new(),
+            ports: vec![],
+            cpu_percent: 0.0,
+            memory_bytes: 0,
+            memory_limit: 0,
+            service_name: String::new(),
+            category: "unknown".to_string(),
+            icon: "Box".to_string(),
+            compose_project: Some(project.to_string()),
+            compose_service: Some(service.to_string()),
+            health: None,
+            restart_count: 0,
+            exit_code: None,
+            labels,
+            networks: vec![],
+            volumes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_group_projects_from_labels() {
+        let containers = vec![
+            container("a", "shop", "web", "running"),
+            container("b", "shop", "db", "exited"),
+            container("c", "blog", "web", "running"),
+        ];
+        let projects = group_projects(&containers);
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].name, "blog");
+
+        let shop = &projects[1];
+        assert_eq!(shop.container_count, 2);
+        assert_eq!(shop.running_count, 1);
+        assert_eq!(shop.services, vec!["db", "web"]);
+        assert_eq!(shop.working_dir.as_deref(), Some("/home/dev/code/shop"));
+        assert_eq!(shop.config_files.len(), 2);
+
+        let args = compose_args(shop, ComposeAction::Recreate, Some("web")).unwrap();
+        assert_eq!(
+            args,
+            [
+                "--project-name",
+                "shop",
+                "--project-directory",
+                "/home/dev/code/shop",
+                "--file",
+                "/home/dev/code/shop/compose.yaml",
+                "--file",
+                "/home/dev/code/shop/compose.override.yaml",
+                "up",
+                "--detach",
+                "--force-recreate",
+                "--no-deps",
+                "web",
+            ]
+        );
+        assert!(compose_args(shop, ComposeAction::Recreate, None).is_err());
+    }
+
+    #[test]
+    fn test_link_projects_picks_deepest_in_workspace() {
+        let mut compose = group_projects(&[container("a", "shop", "web", "running")]);
+        let project = |name: &str, path: &str| ProjectInfo {
+            name: name.to_string(),
+            path: path.to_string(),
+            ..Default::default()
+        };
+        let projects = vec![
+            project("code", "/home/dev/code"),
+            project("shop", "/home/dev/code/shop"),
+            project("shop-admin", "/home/dev/code/shop-admin"),
+        ];
+
+        link_projects(&mut compose, &["/elsewhere".to_string()], &projects);
+        assert_eq!(compose[0].project_path, None);
+
+        link_projects(&mut compose, &["/home/dev/code".to_string()], &projects);
+        assert_eq!(
+            compose[0].project_path.as_deref(),
+            Some("/home/dev/code/shop")
+        );
+        assert_eq!(compose[0].project_name.as_deref(), Some("shop"));
+    }
+
+    #[test]
+    fn test_parse_compose_services_and_ports() {
+        let content = r#"
+name: shop
+services:
+  db:
+    image: "postgres:${PG_VERSION:-16}"  # pinned
+    ports:
+      - "127.0.0.1:${DB_PORT}:5432"
+  web:
+    build: .
+    container_name: shop-web
+    ports: ["3000:3000", "9229"]
+  proxy:
+    image: caddy
+    ports:
+      - target: 443
+        published: "8443"
+        protocol: udp
+      - 8080-8081:80-81
+x-defaults: &defaults
+  restart: always
+"#;
+        let vars = |name: &str| (name == "DB_PORT").then(|| "5433".to_string());
+        let parsed = parse_compose(content, &vars);
+        assert_eq!(parsed.name.as_deref(), Some("shop"));
+        assert_eq!(parsed.services.len(), 3);
+
+        let db = &parsed.services[0];
+        assert_eq!(db.image.as_deref(), Some("postgres:16"));
+        assert_eq!(
+            db.ports,
+            vec![ComposePort {
+                host_ip: Some("127.0.0.1".to_string()),
+                published: 5433,
+                target: 5432,
+                protocol: "tcp".to_string(),
+            }]
+        );
+
+        let web = &parsed.services[1];
+        assert!(web.build);
+        assert_eq!(web.container_name.as_deref(), Some("shop-web"));
+        assert_eq!(web.ports.len(), 1, "container-only ports aren't published");
+
+        let proxy = &parsed.services[2];
+        let published: Vec<_> = proxy
+            .ports
+            .iter()
+            .map(|p| (p.published, p.target))
+            .collect();
+        assert_eq!(published, vec![(8443, 443), (8080, 80), (8081, 81)]);
+        assert_eq!(proxy.ports[0].protocol, "udp");
+    }
+
+    #[test]
+    fn test_stack_port_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_dir = dir.path().join("My Shop");
+        fs::create_dir(&project_dir).unwrap();
+        fs::write(
+            project_dir.join("compose.yaml"),
+            "services:\n  web:\n    image: nginx\n    ports:\n      - 8080:80\n  db:\n    image: postgres\n    ports:\n      - 5432:5432\n",
+        )
+        .unwrap();
+        let file = parse_project(&project_dir).unwrap();
+        assert_eq!(file.project_name, "myshop");
+
+        let project = ProjectInfo {
+            name: "shop".to_string(),
+            path: project_dir.to_string_lossy().to_string(),
+            compose: Some(file),
+            ..Default::default()
+        };
+        let mut other = container("x", "blog", "web", "running");
+        other.name = "blog-web-1".to_string();
+        other.ports = vec![PortBinding {
+            host_port: Some(8080),
+            container_port: 80,
+            protocol: "tcp".to_string(),
+            host_ip: Some("0.0.0.0".to_string()),
+        }];
+        let docker = DockerStatus {
+            available: true,
+            version: None,
+            containers: vec![other, container("y", "myshop", "db", "running")],
+            compose_projects: vec![],
+            total_running: 2,
+            total_stopped: 0,
+            scanned_at: String::new(),
+            errors: vec![],
+            endpoint: None,
+            engine: None,
+        };
+
+        let stacks = stacks(&[project], &docker, &[]);
+        assert_eq!(stacks.len(), 1);
+        assert_eq!(stacks[0].running_count, 1);
+        assert_eq!(stacks[0].collisions.len(), 1);
+        let collision = &stacks[0].collisions[0];
+        assert_eq!((collision.port, collision.service.as_str()), (8080, "web"));
+        assert_eq!(collision.owner, "blog-web-1");
+    }
+}
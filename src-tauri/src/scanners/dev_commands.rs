@@ -0,0 +1,249 @@
+//! Candidate commands for starting a project's dev server.
+//!
+//! Gathered from package.json scripts (run with the detected package
+//! manager), the framework's entry in the dev command registry, Procfile
+//! entries, and the project's compose file. The first candidate is the
+//! default unless the user saved a choice for the project.
+
+use super::compose;
+use crate::registry::{self, config, dev_commands, PackageManager};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Scripts that usually start a dev server, in order of preference
+pub(super) const DEV_SCRIPTS: &[&str] = &["dev", "start", "serve", "develop", "watch", "preview"];
+
+/// Procfiles, most dev-specific first
+const PROCFILES: &[&str] = &["Procfile.dev", "Procfile"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DevCommand {
+    /// Shell command line, run from the project directory
+    pub command: String,
+    /// Where it came from: "package.json", "Procfile", "compose", or the
+    /// framework name
+    pub source: String,
+    /// Script, process or service name, when there is one
+    pub name: Option<String>,
+}
+
+/// The candidates for a project plus the user's saved choice
+#[derive(Debug, Clone, Serialize)]
+pub struct DevCommandOptions {
+    pub candidates: Vec<DevCommand>,
+    pub saved: Option<String>,
+}
+
+impl DevCommandOptions {
+    /// The command to run when none is given
+    pub fn default_command(&self) -> Option<&str> {
+        self.saved
+            .as_deref()
+            .or_else(|| self.candidates.first().map(|c| c.command.as_str()))
+    }
+}
+
+/// Every candidate for the project at `path`, best first, without duplicates.
+pub fn candidates(path: &Path) -> Vec<DevCommand> {
+    let detection = registry::detect(path);
+    let manager = detection
+        .as_ref()
+        .map(|d| d.package_manager)
+        .unwrap_or(PackageManager::Unknown);
+
+    let mut found = package_scripts(path, manager);
+
+    if let Some(detection) = &detection {
+        let language_default = config::entries()
+            .iter()
+            .find(|e| e.display_name == detection.language_display)
+            .map(|e| e.default_framework);
+        let frameworks = std::iter::once(detection.framework.as_str()).chain(language_default);
+        for framework in frameworks {
+            for rule in dev_commands::rules_for(framework) {
+                if rule.requires.is_some_and(|f| !path.join(f).exists()) {
+                    continue;
+                }
+                found.push(DevCommand {
+                    command: rule
+                        .command
+                        .replace("{run}", dev_commands::run_prefix(manager)),
+                    source: framework.to_string(),
+                    name: None,
+                });
+            }
+        }
+    }
+
+    for procfile in PROCFILES {
+        if let Ok(content) = fs::read_to_string(path.join(procfile)) {
+            found.extend(parse_procfile(&content));
+        }
+    }
+
+    if let Some(file) = compose::parse_project(path) {
+        let cli = compose::cli_command();
+        // Services built from the project itself are the app; the rest
+        // (databases, caches) come along as dependencies
+        for service in file.services.iter().filter(|s| s.build) {
+            found.push(DevCommand {
+                command: format!("{} up {}", cli, service.name),
+                source: "compose".to_string(),
+                name: Some(service.name.clone()),
+            });
+        }
+        found.push(DevCommand {
+            command: format!("{} up", cli),
+            source: "compose".to_string(),
+            name: None,
+        });
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    found.retain(|c| seen.insert(c.command.clone()));
+    found
+}
+
+/// Dev-ish scripts from package.json: the usual names first, then any
+/// `dev:*`/`start:*` variants, then the Tauri CLI if it's wired up.
+fn package_scripts(path: &Path, manager: PackageManager) -> Vec<DevCommand> {
+    let Ok(content) = fs::read_to_string(path.join("package.json")) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Vec::new();
+    };
+    let Some(scripts) = json.get("scripts").and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+
+    let script = |name: &str| DevCommand {
+        command: dev_commands::script_command(manager, name),
+        source: "package.json".to_string(),
+        name: Some(name.to_string()),
+    };
+
+    let mut found: Vec<DevCommand> = DEV_SCRIPTS
+        .iter()
+        .filter(|name| scripts.contains_key(**name))
+        .map(|name| script(name))
+        .collect();
+    found.extend(
+        scripts
+            .keys()
+            .filter(|name| name.starts_with("dev:") || name.starts_with("start:"))
+            .map(|name| script(name)),
+    );
+    if scripts.contains_key("tauri") {
+        found.push(DevCommand {
+            command: format!("{} dev", dev_commands::script_command(manager, "tauri")),
+            source: "package.json".to_string(),
+            name: Some("tauri".to_string()),
+        });
+    }
+    found
+}
+
+/// `name: command` lines; comments and blank lines are skipped.
+fn parse_procfile(content: &str) -> Vec<DevCommand> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (name, command) = line.split_once(':')?;
+            let (name, command) = (name.trim(), command.trim());
+            let valid_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            (valid_name && !command.is_empty()).then(|| DevCommand {
+                command: command.to_string(),
+                source: "Procfile".to_string(),
+                name: Some(name.to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_scripts_use_package_manager() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{
+                "scripts": {
+                    "build": "vite build",
+                    "start": "node server.js",
+                    "dev": "vite",
+                    "dev:api": "tsx watch api.ts",
+                    "test": "vitest"
+                },
+                "devDependencies": { "vite": "^6.0.0" }
+            }"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        let commands: Vec<String> = candidates(dir.path())
+            .into_iter()
+            .map(|c| c.command)
+            .collect();
+        assert_eq!(commands, vec!["pnpm dev", "pnpm start", "pnpm dev:api"]);
+
+        assert_eq!(
+            dev_commands::script_command(PackageManager::Bun, "dev"),
+            "bun dev"
+        );
+        assert_eq!(
+            dev_commands::script_command(PackageManager::Yarn, "test"),
+            "yarn run test"
+        );
+        assert_eq!(
+            dev_commands::script_command(PackageManager::Npm, "start"),
+            "npm run start"
+        );
+    }
+
+    #[test]
+    fn test_framework_rules_and_procfile() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"shop\"\ndependencies = [\"django>=5\"]\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("uv.lock"), "").unwrap();
+        fs::write(dir.path().join("manage.py"), "").unwrap();
+        fs::write(
+            dir.path().join("Procfile"),
+            "# processes\nweb: gunicorn shop.wsgi\nworker: celery -A shop worker\n",
+        )
+        .unwrap();
+
+        let found = candidates(dir.path());
+        assert_eq!(
+            found[0],
+            DevCommand {
+                command: "uv run python manage.py runserver".to_string(),
+                source: "Django".to_string(),
+                name: None,
+            }
+        );
+        let procfile: Vec<_> = found.iter().filter(|c| c.source == "Procfile").collect();
+        assert_eq!(procfile.len(), 2);
+        assert_eq!(procfile[0].name.as_deref(), Some("web"));
+        assert_eq!(procfile[0].command, "gunicorn shop.wsgi");
+
+        let options = DevCommandOptions {
+            candidates: found,
+            saved: Some("make dev".to_string()),
+        };
+        assert_eq!(options.default_command(), Some("make dev"));
+    }
+}
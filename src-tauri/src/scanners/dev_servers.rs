@@ -1,6 +1,6 @@
+use super::process::{ProcessError, ProcessRunner, ScanError, QUICK_TIMEOUT};
 use serde::Serialize;
 use std::collections::HashMap;
-use std::process::Command;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct DevServer {
@@ -10,43 +10,61 @@ pub struct DevServer {
     pub framework: Option<String>,
     pub project_path: Option<String>,
     pub uptime_secs: Option<u64>,
+    /// Working directory of the process, when it could be read
+    pub cwd: Option<String>,
+    /// Supervisor id when Houston started this server itself
+    pub managed_id: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct DevServerReport {
     pub servers: Vec<DevServer>,
     pub scanned_at: String,
+    pub errors: Vec<ScanError>,
 }
 
 pub fn scan(known_project_paths: &[String]) -> DevServerReport {
-    let servers = discover_servers(known_project_paths);
+    let (servers, errors) = match discover_servers(known_project_paths) {
+        Ok(servers) => (servers, Vec::new()),
+        Err(e) => {
+            let errors = ScanError::reportable("listening ports", &e);
+            (Vec::new(), errors.into_iter().collect())
+        }
+    };
     DevServerReport {
         servers,
         scanned_at: chrono::Local::now().to_rfc3339(),
+        errors,
     }
 }
 
-fn discover_servers(known_project_paths: &[String]) -> Vec<DevServer> {
-    // Use lsof on macOS/Linux to find listening TCP processes
+fn discover_servers(known_project_paths: &[String]) -> Result<Vec<DevServer>, ProcessError> {
+    // Linux: read /proc directly. lsof is only the fallback for when
+    // /proc/net isn't readable (some sandboxes).
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(listeners) = super::procfs::listeners() {
+            return Ok(from_listeners(listeners, known_project_paths));
+        }
+    }
+
+    // Use lsof on macOS/Linux to find listening TCP processes.
+    // Both exit non-zero when nothing is listening, so only spawn failures
+    // and timeouts are errors.
     let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
+        ProcessRunner::new("cmd")
             .args(["/C", "netstat -ano | findstr LISTENING"])
-            .output()
+            .run()?
     } else {
-        Command::new("lsof")
+        ProcessRunner::new("lsof")
             .args(["-iTCP", "-sTCP:LISTEN", "-nP", "-Fn", "-Fp", "-Fi"])
-            .output()
-    };
-
-    let output = match output {
-        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).to_string(),
-        _ => return Vec::new(),
+            .run()?
     };
 
     if cfg!(target_os = "windows") {
-        parse_netstat(&output, known_project_paths)
+        Ok(parse_netstat(&output.stdout, known_project_paths))
     } else {
-        parse_lsof(&output, known_project_paths)
+        Ok(parse_lsof(&output.stdout, known_project_paths))
     }
 }
 
@@ -64,8 +82,7 @@ fn parse_lsof(output: &str, known_project_paths: &[String]) -> Vec<DevServer> {
             // Name/address line, e.g., n*:3000 or n127.0.0.1:8080
             if let Some(pid) = current_pid {
                 if let Some(port) = extract_port_from_lsof_name(&line[1..]) {
-                    // Skip system ports and common non-dev ports
-                    if port < 1024 || port == 5353 || port == 7000 || port == 49152 {
+                    if is_ignored_port(port) {
                         continue;
                     }
                     // Only add if not already seen for this PID+port combo
@@ -87,6 +104,8 @@ fn parse_lsof(output: &str, known_project_paths: &[String]) -> Vec<DevServer> {
                             framework,
                             project_path,
                             uptime_secs: get_process_uptime(pid),
+                            cwd,
+                            managed_id: None,
                         });
                     }
                 }
@@ -97,6 +116,45 @@ fn parse_lsof(output: &str, known_project_paths: &[String]) -> Vec<DevServer> {
     servers.into_values().collect()
 }
 
+#[cfg(target_os = "linux")]
+fn from_listeners(listeners: Vec<super::procfs::Listener>, known_project_paths: &[String]) -> Vec<DevServer> {
+    listeners
+        .into_iter()
+        .filter_map(|listener| {
+            let port = listener.ports.iter().copied().find(|p| !is_ignored_port(*p))?;
+            // comm is cut at 15 characters, so also try the executable's name
+            let exe = listener
+                .cmdline
+                .first()
+                .and_then(|arg| std::path::Path::new(arg).file_name())
+                .map(|name| name.to_string_lossy().to_string());
+            if !is_dev_server_process(&listener.name) && !exe.as_deref().is_some_and(is_dev_server_process) {
+                return None;
+            }
+            let project_path = listener
+                .cwd
+                .as_deref()
+                .and_then(|dir| match_to_project(dir, known_project_paths));
+            let framework = detect_framework_from_process(&listener.name, project_path.as_deref());
+            Some(DevServer {
+                pid: listener.pid,
+                port,
+                process_name: listener.name,
+                framework,
+                project_path,
+                uptime_secs: listener.uptime_secs,
+                cwd: listener.cwd,
+                managed_id: None,
+            })
+        })
+        .collect()
+}
+
+/// System ports and common non-dev ports (mDNS, AirPlay, …)
+fn is_ignored_port(port: u16) -> bool {
+    port < 1024 || port == 5353 || port == 7000 || port == 49152
+}
+
 fn parse_netstat(output: &str, _known_project_paths: &[String]) -> Vec<DevServer> {
     let mut servers = Vec::new();
     for line in output.lines() {
@@ -120,6 +178,8 @@ fn parse_netstat(output: &str, _known_project_paths: &[String]) -> Vec<DevServer
                     framework: None,
                     project_path: None,
                     uptime_secs: None,
+                    cwd: None,
+                    managed_id: None,
                 });
             }
         }
@@ -133,14 +193,13 @@ fn extract_port_from_lsof_name(name: &str) -> Option<u16> {
 }
 
 fn get_process_name(pid: u32) -> String {
-    let output = Command::new("ps")
+    let output = ProcessRunner::new("ps")
         .args(["-p", &pid.to_string(), "-o", "comm="])
-        .output();
+        .timeout(QUICK_TIMEOUT)
+        .run_ok();
     match output {
-        Ok(o) if o.status.success() => {
-            String::from_utf8_lossy(&o.stdout).trim().to_string()
-        }
-        _ => "unknown".to_string(),
+        Ok(o) => o.stdout.trim().to_string(),
+        Err(_) => "unknown".to_string(),
     }
 }
 
@@ -163,13 +222,13 @@ fn is_dev_server_process(name: &str) -> bool {
 fn get_process_cwd(pid: u32) -> Option<String> {
     // macOS: Use lsof -p PID to get cwd
     if cfg!(target_os = "macos") {
-        let output = Command::new("lsof")
+        let output = ProcessRunner::new("lsof")
             .args(["-p", &pid.to_string(), "-Fn", "-a", "-d", "cwd"])
-            .output()
+            .timeout(QUICK_TIMEOUT)
+            .run()
             .ok()?;
-        if output.status.success() {
-            let text = String::from_utf8_lossy(&output.stdout);
-            for line in text.lines() {
+        if output.success() {
+            for line in output.stdout.lines() {
                 if line.starts_with('n') && line.len() > 1 {
                     return Some(line[1..].to_string());
                 }
@@ -254,15 +313,12 @@ fn detect_framework_from_process(process_name: &str, project_path: Option<&str>)
 
 fn get_process_uptime(pid: u32) -> Option<u64> {
     // Use ps to get elapsed time
-    let output = Command::new("ps")
+    let output = ProcessRunner::new("ps")
         .args(["-p", &pid.to_string(), "-o", "etime="])
-        .output()
+        .timeout(QUICK_TIMEOUT)
+        .run_ok()
         .ok()?;
-    if !output.status.success() {
-        return None;
-    }
-    let etime = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    parse_etime(&etime)
+    parse_etime(output.stdout.trim())
 }
 
 fn parse_etime(etime: &str) -> Option<u64> {
@@ -291,63 +347,39 @@ pub fn stop_dev_server(pid: u32) -> Result<(), String> {
     // Send SIGTERM first
     #[cfg(unix)]
     {
-        let status = Command::new("kill")
+        let output = ProcessRunner::new("kill")
             .args(["-TERM", &pid.to_string()])
-            .status()
+            .timeout(QUICK_TIMEOUT)
+            .run()
             .map_err(|e| e.to_string())?;
-        if !status.success() {
+        if !output.success() {
             return Err(format!("Failed to send SIGTERM to PID {}", pid));
         }
         // Wait 5 seconds, then SIGKILL if still running
         std::thread::sleep(std::time::Duration::from_secs(5));
-        let check = Command::new("kill")
+        let check = ProcessRunner::new("kill")
             .args(["-0", &pid.to_string()])
-            .status();
-        if let Ok(s) = check {
-            if s.success() {
-                let _ = Command::new("kill")
+            .timeout(QUICK_TIMEOUT)
+            .run();
+        if let Ok(o) = check {
+            if o.success() {
+                let _ = ProcessRunner::new("kill")
                     .args(["-KILL", &pid.to_string()])
-                    .status();
+                    .timeout(QUICK_TIMEOUT)
+                    .run();
             }
         }
     }
     #[cfg(windows)]
     {
-        let status = Command::new("taskkill")
+        let output = ProcessRunner::new("taskkill")
             .args(["/PID", &pid.to_string(), "/F"])
-            .status()
+            .timeout(QUICK_TIMEOUT)
+            .run()
             .map_err(|e| e.to_string())?;
-        if !status.success() {
+        if !output.success() {
             return Err(format!("Failed to kill PID {}", pid));
         }
     }
     Ok(())
 }
-
-pub fn start_dev_server(project_path: &str, command: Option<&str>) -> Result<DevServer, String> {
-    let cmd = command.unwrap_or("npm run dev");
-    let parts: Vec<&str> = cmd.split_whitespace().collect();
-    if parts.is_empty() {
-        return Err("Empty command".to_string());
-    }
-
-    let child = Command::new(parts[0])
-        .args(&parts[1..])
-        .current_dir(project_path)
-        .stdin(std::process::Stdio::null())
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .spawn()
-        .map_err(|e| format!("Failed to start: {}", e))?;
-
-    let pid = child.id();
-
-    Ok(DevServer {
-        pid,
-        port: 0, // Will be detected on next scan
-        process_name: parts[0].to_string(),
-        framework: None,
-        project_path: Some(project_path.to_string()),
-        uptime_secs: Some(0),
-    })
-}
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::Path;
-use std::process::Command;
+use super::languages::LanguageInfo;
+use super::packages::PackageList;
+use super::process::{ProcessError, ProcessRunner, ScanError, INSTALL_TIMEOUT, NETWORK_TIMEOUT};
+use super::version_check;
+use super::workspace::ProjectInfo;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -12,6 +16,17 @@ pub enum Severity {
     Suggestion,
 }
 
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+            Severity::Suggestion => "suggestion",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiagnosticItem {
     pub id: String,
@@ -28,6 +43,9 @@ pub struct DiagnosticItem {
 pub struct DiagnosticReport {
     pub items: Vec<DiagnosticItem>,
     pub scanned_at: String,
+    /// Checks that couldn't run (e.g. `brew outdated` timed out)
+    #[serde(default)]
+    pub errors: Vec<ScanError>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,41 +56,28 @@ pub struct FixResult {
 }
 
 fn run_cmd(cmd: &str, args: &[&str]) -> Option<String> {
-    Command::new(cmd)
+    ProcessRunner::new(cmd)
         .args(args)
-        .output()
+        .run()
         .ok()
-        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .map(|o| o.stdout.trim().to_string())
 }
 
-fn run_cmd_full(cmd: &str, args: &[&str]) -> Option<(String, String, bool)> {
-    Command::new(cmd).args(args).output().ok().map(|o| {
-        (
-            String::from_utf8_lossy(&o.stdout).trim().to_string(),
-            String::from_utf8_lossy(&o.stderr).trim().to_string(),
-            o.status.success(),
-        )
-    })
+fn run_cmd_full(
+    cmd: &str,
+    args: &[&str],
+    timeout: std::time::Duration,
+) -> Result<(String, String, bool), ProcessError> {
+    let o = ProcessRunner::new(cmd).args(args).timeout(timeout).run()?;
+    Ok((
+        o.stdout.trim().to_string(),
+        o.stderr.trim().to_string(),
+        o.success(),
+    ))
 }
 
 fn which_cmd(binary: &str) -> Option<String> {
-    #[cfg(unix)]
-    let cmd = "which";
-    #[cfg(windows)]
-    let cmd = "where.exe";
-
-    Command::new(cmd)
-        .arg(binary)
-        .output()
-        .ok()
-        .and_then(|o| {
-            if o.status.success() {
-                let out = String::from_utf8_lossy(&o.stdout);
-                Some(out.lines().next()?.trim().to_string())
-            } else {
-                None
-            }
-        })
+    super::process::which(binary)
 }
 
 #[cfg(unix)]
@@ -85,16 +90,27 @@ fn which_all(binary: &str) -> Option<String> {
     run_cmd("where.exe", &[binary])
 }
 
-fn check_outdated_brew() -> Vec<DiagnosticItem> {
+/// Turn a failed command into a report error, or "nothing to report" if the
+/// tool simply isn't installed.
+fn check_failed(source: &str, err: ProcessError) -> Result<Vec<DiagnosticItem>, ScanError> {
+    match ScanError::reportable(source, &err) {
+        Some(e) => Err(e),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn check_outdated_brew() -> Result<Vec<DiagnosticItem>, ScanError> {
     if cfg!(target_os = "windows") {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
-    let json = super::outdated_cache::brew_outdated_json();
-    if json.is_null() {
-        return Vec::new();
+    match super::outdated_cache::brew_outdated() {
+        Ok(json) => Ok(brew_outdated_items(&json)),
+        Err(e) => check_failed("brew outdated", e),
     }
+}
 
+fn brew_outdated_items(json: &serde_json::Value) -> Vec<DiagnosticItem> {
     if let Some(formulae) = json.get("formulae").and_then(|f| f.as_array()) {
         return formulae
             .iter()
@@ -127,12 +143,14 @@ fn check_outdated_brew() -> Vec<DiagnosticItem> {
     Vec::new()
 }
 
-fn check_outdated_npm() -> Vec<DiagnosticItem> {
-    let json = super::outdated_cache::npm_outdated_json();
-    if json.is_null() {
-        return Vec::new();
+fn check_outdated_npm() -> Result<Vec<DiagnosticItem>, ScanError> {
+    match super::outdated_cache::npm_outdated() {
+        Ok(json) => Ok(npm_outdated_items(&json)),
+        Err(e) => check_failed("npm outdated", e),
     }
+}
 
+fn npm_outdated_items(json: &serde_json::Value) -> Vec<DiagnosticItem> {
     if let Some(obj) = json.as_object() {
         return obj
             .iter()
@@ -160,12 +178,14 @@ fn check_outdated_npm() -> Vec<DiagnosticItem> {
     Vec::new()
 }
 
-fn check_outdated_pip() -> Vec<DiagnosticItem> {
-    let json = super::outdated_cache::pip_outdated_json();
-    if json.is_null() {
-        return Vec::new();
+fn check_outdated_pip() -> Result<Vec<DiagnosticItem>, ScanError> {
+    match super::outdated_cache::pip_outdated() {
+        Ok(json) => Ok(pip_outdated_items(&json)),
+        Err(e) => check_failed("pip outdated", e),
     }
+}
 
+fn pip_outdated_items(json: &serde_json::Value) -> Vec<DiagnosticItem> {
     if let Some(packages) = json.as_array() {
         return packages
             .iter()
@@ -190,21 +210,23 @@ fn check_outdated_pip() -> Vec<DiagnosticItem> {
     Vec::new()
 }
 
-fn check_brew_doctor() -> Vec<DiagnosticItem> {
+fn check_brew_doctor() -> Result<Vec<DiagnosticItem>, ScanError> {
     if cfg!(target_os = "windows") {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
-    let (stdout, stderr, success) = match run_cmd_full("brew", &["doctor"]) {
-        Some(r) => r,
-        None => return Vec::new(),
-    };
+    match run_cmd_full("brew", &["doctor"], NETWORK_TIMEOUT) {
+        Ok((stdout, stderr, success)) => Ok(brew_doctor_items(&stdout, &stderr, success)),
+        Err(e) => check_failed("brew doctor", e),
+    }
+}
 
+fn brew_doctor_items(stdout: &str, stderr: &str, success: bool) -> Vec<DiagnosticItem> {
     if success {
         return Vec::new();
     }
 
-    let output_text = if !stderr.is_empty() { &stderr } else { &stdout };
+    let output_text = if !stderr.is_empty() { stderr } else { stdout };
 
     let mut items = Vec::new();
     let mut current_warning = String::new();
@@ -444,11 +466,11 @@ fn check_shell_config() -> Vec<DiagnosticItem> {
     #[cfg(windows)]
     {
         // Check for PowerShell profile
-        if let Ok(output) = Command::new("powershell")
+        if let Ok(output) = ProcessRunner::new("powershell")
             .args(["-NoProfile", "-Command", "echo $PROFILE"])
-            .output()
+            .run()
         {
-            let profile_path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let profile_path = output.stdout.trim().to_string();
             if !profile_path.is_empty() && !Path::new(&profile_path).exists() {
                 items.push(DiagnosticItem {
                     id: "shell_no_ps_profile".to_string(),
@@ -507,7 +529,9 @@ fn check_windows_tools() -> Vec<DiagnosticItem> {
 
     // Check winget health
     if which_cmd("winget").is_some() {
-        if let Some((_, stderr, success)) = run_cmd_full("winget", &["--info"]) {
+        if let Ok((_, stderr, success)) =
+            run_cmd_full("winget", &["--info"], super::process::DEFAULT_TIMEOUT)
+        {
             if !success {
                 items.push(DiagnosticItem {
                     id: "winget_unhealthy".to_string(),
@@ -618,7 +642,64 @@ fn check_linux_packages() -> Vec<DiagnosticItem> {
     items
 }
 
-pub fn scan() -> DiagnosticReport {
+/// Definitions in the user's detection registry files that were skipped,
+/// one item per file.
+fn check_detection_registry() -> Vec<DiagnosticItem> {
+    let status = crate::registry::config::status();
+    let mut by_file: Vec<(String, Vec<String>)> = Vec::new();
+    for error in status.errors {
+        match by_file.iter_mut().find(|(source, _)| *source == error.source) {
+            Some((_, messages)) => messages.push(error.message),
+            None => by_file.push((error.source, vec![error.message])),
+        }
+    }
+
+    by_file
+        .into_iter()
+        .map(|(source, messages)| DiagnosticItem {
+            id: format!("registry_invalid_{}", source),
+            category: "registry".to_string(),
+            severity: Severity::Warning,
+            title: "Detection registry has errors".to_string(),
+            description: format!(
+                "{} problem{} in {}; the affected definitions are ignored",
+                messages.len(),
+                if messages.len() == 1 { "" } else { "s" },
+                source
+            ),
+            details: Some(messages.join("\n")),
+            fix_id: None,
+            fix_label: None,
+        })
+        .collect()
+}
+
+/// Workspace-derived inputs for the project-aware check groups.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectScope {
+    /// Directories searched for a team `houston.toml` (see [`super::policy`])
+    pub roots: Vec<String>,
+    /// Discovered projects, already run through `version_check::annotate`
+    pub projects: Vec<ProjectInfo>,
+    /// Cached scanner results, if any; checks that need them scan otherwise
+    pub languages: Option<Vec<LanguageInfo>>,
+    pub packages: Option<PackageList>,
+}
+
+impl ProjectScope {
+    pub fn new(workspaces: Vec<String>, projects: Vec<ProjectInfo>) -> Self {
+        let mut roots = workspaces;
+        roots.extend(projects.iter().map(|p| p.path.clone()));
+        Self {
+            roots,
+            projects,
+            ..Self::default()
+        }
+    }
+}
+
+/// Run every check group.
+pub fn scan(scope: &ProjectScope) -> DiagnosticReport {
     // Spawn threads for the slow process-spawning checks
     let brew_outdated = std::thread::spawn(check_outdated_brew);
     let npm_outdated = std::thread::spawn(check_outdated_npm);
@@ -626,6 +707,12 @@ pub fn scan() -> DiagnosticReport {
     let brew_doctor = std::thread::spawn(check_brew_doctor);
     let dup_binaries = std::thread::spawn(check_duplicate_binaries);
     let env_tools = std::thread::spawn(check_environment_tools);
+    let policy_scope = scope.clone();
+    let policy = std::thread::spawn(move || super::policy::check(&policy_scope));
+    let (roots, projects) = (scope.roots.clone(), scope.projects.clone());
+    let ports = std::thread::spawn(move || {
+        super::ports::diagnostics(&super::ports::scan(&roots, &projects))
+    });
 
     // Platform-specific checks
     #[cfg(target_os = "windows")]
@@ -638,14 +725,23 @@ pub fn scan() -> DiagnosticReport {
     let mut items = Vec::new();
     items.extend(check_path_issues());
     items.extend(check_shell_config());
-
-    // Collect threaded results
-    items.extend(brew_outdated.join().unwrap_or_default());
-    items.extend(npm_outdated.join().unwrap_or_default());
-    items.extend(pip_outdated.join().unwrap_or_default());
-    items.extend(brew_doctor.join().unwrap_or_default());
+    items.extend(check_detection_registry());
+    items.extend(version_check::diagnostics(&scope.projects));
+    items.extend(super::git::diagnostics(&scope.projects));
+
+    // Collect threaded results; checks that couldn't run are reported as errors
+    let mut errors = Vec::new();
+    for handle in [brew_outdated, npm_outdated, pip_outdated, brew_doctor] {
+        match handle.join() {
+            Ok(Ok(found)) => items.extend(found),
+            Ok(Err(e)) => errors.push(e),
+            Err(_) => {}
+        }
+    }
     items.extend(dup_binaries.join().unwrap_or_default());
     items.extend(env_tools.join().unwrap_or_default());
+    items.extend(policy.join().unwrap_or_default());
+    items.extend(ports.join().unwrap_or_default());
 
     #[cfg(any(target_os = "windows", target_os = "linux"))]
     items.extend(platform_checks.join().unwrap_or_default());
@@ -653,6 +749,7 @@ pub fn scan() -> DiagnosticReport {
     DiagnosticReport {
         items,
         scanned_at: chrono::Local::now().to_rfc3339(),
+        errors,
     }
 }
 
@@ -673,6 +770,8 @@ pub fn execute_fix(fix_id: &str) -> FixResult {
         "brew_cleanup" => ("brew", vec!["cleanup"]),
         "npm_update" => ("npm", vec!["update", "-g", arg]),
         "pip_upgrade" => ("pip3", vec!["install", "--upgrade", arg]),
+        "kill_process" if cfg!(windows) => ("taskkill", vec!["/PID", arg, "/F"]),
+        "kill_process" => ("kill", vec![arg]),
         _ => {
             return FixResult {
                 success: false,
@@ -682,10 +781,15 @@ pub fn execute_fix(fix_id: &str) -> FixResult {
         }
     };
 
-    match Command::new(cmd).args(&args).output() {
+    // Upgrades can take a while, but shouldn't be able to hang forever
+    let fix = ProcessRunner::new(cmd)
+        .args(&args)
+        .timeout(INSTALL_TIMEOUT)
+        .run();
+    match fix {
         Ok(o) => {
-            let stdout = String::from_utf8_lossy(&o.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&o.stderr).to_string();
+            let stdout = o.stdout;
+            let stderr = o.stderr;
             let combined = if stderr.is_empty() {
                 stdout
             } else {
@@ -708,8 +812,11 @@ pub fn execute_fix(fix_id: &str) -> FixResult {
         }
         Err(e) => FixResult {
             success: false,
-            message: format!("Failed to execute {}: {}", cmd, e),
-            output: None,
+            message: e.to_string(),
+            output: match e {
+                ProcessError::TimedOut { stderr, .. } if !stderr.is_empty() => Some(stderr),
+                _ => None,
+            },
         },
     }
 }
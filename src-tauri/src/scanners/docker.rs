@@ -1,12 +1,29 @@
 //! Docker container scanner.
 //!
-//! Scans for running Docker containers and collects their status,
-//! port mappings, resource usage, and Compose project information.
+//! Talks to the Docker Engine API (via `DOCKER_HOST` or the usual local
+//! sockets, Podman's included) and collects container status, port
+//! mappings, health, resource usage, and Compose project information.
 
+use super::compose;
+use super::process::ScanError;
 use crate::registry::containers::{detect_container, ContainerCategory};
+use bollard::container::{
+    InspectContainerOptions, ListContainersOptions, LogOutput, LogsOptions, MemoryStats,
+    MemoryStatsStats, StartContainerOptions, Stats, StatsOptions,
+};
+use bollard::errors::Error as DockerError;
+use bollard::models::{ContainerInspectResponse, ContainerSummary, Port};
+use bollard::{Docker, API_DEFAULT_VERSION};
+use futures_util::{future, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::process::Command;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// A stats sample waits on the daemon's ~1s CPU sampling window, and
+/// start/stop wait on the container's own shutdown grace period.
+const DOCKER_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Port binding information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +31,19 @@ pub struct PortBinding {
     pub host_port: Option<u16>,
     pub container_port: u16,
     pub protocol: String,
+    /// Host interface the port is published on ("0.0.0.0", "127.0.0.1", …)
+    #[serde(default)]
+    pub host_ip: Option<String>,
+}
+
+/// A volume, bind mount or tmpfs attached to a container
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeMount {
+    pub kind: String, // "volume", "bind", "tmpfs", …
+    pub name: Option<String>,
+    pub source: String,
+    pub destination: String,
+    pub read_only: bool,
 }
 
 /// Individual container information
@@ -22,8 +52,8 @@ pub struct ContainerInfo {
     pub id: String,
     pub name: String,
     pub image: String,
-    pub status: String,       // "running", "paused", "exited", "created"
-    pub state_detail: String, // "Up 2 hours", "Exited (0) 5 mins ago"
+    pub status: String, // "running", "paused", "restarting", "exited", "created", "dead"
+    pub state_detail: String, // "Up 2 hours (healthy)", "Exited (0) 5 mins ago"
     pub created: String,
     pub ports: Vec<PortBinding>,
     pub cpu_percent: f64,
@@ -34,6 +64,19 @@ pub struct ContainerInfo {
     pub icon: String,         // Lucide icon name
     pub compose_project: Option<String>,
     pub compose_service: Option<String>,
+    /// "healthy", "unhealthy" or "starting"; `None` without a HEALTHCHECK
+    #[serde(default)]
+    pub health: Option<String>,
+    #[serde(default)]
+    pub restart_count: u64,
+    #[serde(default)]
+    pub exit_code: Option<i64>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default)]
+    pub networks: Vec<String>,
+    #[serde(default)]
+    pub volumes: Vec<VolumeMount>,
 }
 
 /// Compose project grouping
@@ -43,6 +86,19 @@ pub struct ComposeProject {
     pub container_count: usize,
     pub running_count: usize,
     pub containers: Vec<String>, // Container IDs
+    /// From the `com.docker.compose.project.working_dir` label
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// From the `com.docker.compose.project.config_files` label
+    #[serde(default)]
+    pub config_files: Vec<String>,
+    #[serde(default)]
+    pub services: Vec<String>,
+    /// Workspace project the working dir belongs to (see `compose::link_projects`)
+    #[serde(default)]
+    pub project_path: Option<String>,
+    #[serde(default)]
+    pub project_name: Option<String>,
 }
 
 /// Overall Docker status
@@ -55,391 +111,722 @@ pub struct DockerStatus {
     pub total_running: usize,
     pub total_stopped: usize,
     pub scanned_at: String,
+    /// Docker calls that failed or timed out (e.g. an unresponsive daemon)
+    #[serde(default)]
+    pub errors: Vec<ScanError>,
+    /// Where the Engine API was reached, e.g. "unix:///var/run/docker.sock"
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// "docker" or "podman"
+    #[serde(default)]
+    pub engine: Option<String>,
 }
 
-/// Docker stats response (parsed from docker stats --no-stream)
-#[derive(Debug, Clone)]
-struct ContainerStats {
-    cpu_percent: f64,
-    mem_usage: u64,
-    mem_limit: u64,
-}
-
-/// Check if Docker is available and get version
-fn get_docker_version() -> Option<String> {
-    let output = Command::new("docker")
-        .args(["version", "--format", "{{.Server.Version}}"])
-        .output()
-        .ok()?;
-
-    if output.status.success() {
-        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if !version.is_empty() {
-            return Some(version);
-        }
-    }
-    None
-}
-
-/// Get container stats (CPU/memory) for all containers
-fn get_container_stats() -> HashMap<String, ContainerStats> {
-    let mut stats = HashMap::new();
-
-    let output = Command::new("docker")
-        .args([
-            "stats",
-            "--no-stream",
-            "--format",
-            "{{.ID}}\t{{.CPUPerc}}\t{{.MemUsage}}",
-        ])
-        .output()
-        .ok();
-
-    if let Some(out) = output {
-        if out.status.success() {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            for line in stdout.lines() {
-                let parts: Vec<&str> = line.split('\t').collect();
-                if parts.len() >= 3 {
-                    let id = parts[0].to_string();
-
-                    // Parse CPU percentage (e.g., "0.50%")
-                    let cpu = parts[1]
-                        .trim_end_matches('%')
-                        .parse::<f64>()
-                        .unwrap_or(0.0);
-
-                    // Parse memory usage (e.g., "45.6MiB / 7.7GiB")
-                    let (mem_usage, mem_limit) = parse_memory_usage(parts[2]);
-
-                    stats.insert(
-                        id,
-                        ContainerStats {
-                            cpu_percent: cpu,
-                            mem_usage,
-                            mem_limit,
-                        },
-                    );
-                }
-            }
-        }
+/// Runtime for Engine API calls; the scanners and commands themselves are sync.
+pub(crate) fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("houston-docker")
+            .enable_all()
+            .build()
+            .expect("failed to start the Docker runtime")
+    })
+}
+
+/// Run an Engine API call to completion from sync code. Callers that are
+/// already on a runtime (async commands) get a scratch thread, since
+/// runtimes can't be nested.
+pub(crate) fn block_on<F>(fut: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    if tokio::runtime::Handle::try_current().is_ok() {
+        std::thread::scope(|s| {
+            s.spawn(|| runtime().block_on(fut))
+                .join()
+                .expect("Docker call panicked")
+        })
+    } else {
+        runtime().block_on(fut)
     }
+}
 
-    stats
+/// Sockets tried when `DOCKER_HOST` isn't set: the standard daemon, then
+/// Docker Desktop, Colima, OrbStack, rootless Docker and Podman.
+#[cfg(unix)]
+fn socket_candidates() -> Vec<std::path::PathBuf> {
+    use std::path::PathBuf;
+
+    let home = dirs::home_dir().unwrap_or_default();
+    let mut candidates = vec![
+        PathBuf::from("/var/run/docker.sock"),
+        home.join(".docker/run/docker.sock"),
+        home.join(".colima/default/docker.sock"),
+        home.join(".orbstack/run/docker.sock"),
+    ];
+    if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from) {
+        candidates.push(runtime_dir.join("docker.sock"));
+        candidates.push(runtime_dir.join("podman/podman.sock"));
+    }
+    candidates.push(PathBuf::from("/run/podman/podman.sock"));
+    candidates.push(home.join(".local/share/containers/podman/machine/podman.sock"));
+    candidates
 }
 
-/// Parse memory usage string like "45.6MiB / 7.7GiB"
-fn parse_memory_usage(s: &str) -> (u64, u64) {
-    let parts: Vec<&str> = s.split(" / ").collect();
-    if parts.len() != 2 {
-        return (0, 0);
+/// Connect to the Engine API, returning the client and the endpoint used.
+/// `DOCKER_HOST` always wins, as it does for the docker CLI.
+pub(crate) fn connect() -> Result<(Docker, String), String> {
+    if let Ok(host) = std::env::var("DOCKER_HOST") {
+        let docker = Docker::connect_with_defaults()
+            .map_err(|e| format!("Can't connect to {}: {}", host, e))?;
+        return Ok((docker.with_timeout(DOCKER_TIMEOUT), host));
     }
 
-    let usage = parse_memory_value(parts[0]);
-    let limit = parse_memory_value(parts[1]);
-    (usage, limit)
-}
-
-/// Parse memory value like "45.6MiB" or "7.7GiB"
-fn parse_memory_value(s: &str) -> u64 {
-    let s = s.trim();
-    if s.ends_with("GiB") {
-        let val: f64 = s.trim_end_matches("GiB").parse().unwrap_or(0.0);
-        (val * 1024.0 * 1024.0 * 1024.0) as u64
-    } else if s.ends_with("MiB") {
-        let val: f64 = s.trim_end_matches("MiB").parse().unwrap_or(0.0);
-        (val * 1024.0 * 1024.0) as u64
-    } else if s.ends_with("KiB") {
-        let val: f64 = s.trim_end_matches("KiB").parse().unwrap_or(0.0);
-        (val * 1024.0) as u64
-    } else if s.ends_with("B") {
-        let val: f64 = s.trim_end_matches("B").parse().unwrap_or(0.0);
-        val as u64
-    } else {
-        0
+    #[cfg(unix)]
+    {
+        let socket = socket_candidates()
+            .into_iter()
+            .find(|p| p.exists())
+            .ok_or_else(|| {
+                "Docker is not running (no Docker or Podman socket found)".to_string()
+            })?;
+        let endpoint = format!("unix://{}", socket.display());
+        let docker =
+            Docker::connect_with_unix(&endpoint, DOCKER_TIMEOUT.as_secs(), API_DEFAULT_VERSION)
+                .map_err(|e| format!("Can't connect to {}: {}", endpoint, e))?;
+        Ok((docker, endpoint))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let docker = Docker::connect_with_local_defaults().map_err(|e| e.to_string())?;
+        Ok((
+            docker.with_timeout(DOCKER_TIMEOUT),
+            "npipe:////./pipe/docker_engine".to_string(),
+        ))
     }
 }
 
-/// Parse port mappings from docker ps output like "0.0.0.0:5432->5432/tcp, 33060/tcp"
-fn parse_ports(ports_str: &str) -> Vec<PortBinding> {
-    let mut bindings = Vec::new();
+/// Human-readable message for an Engine API error ("No such container: …").
+pub(crate) fn api_message(err: DockerError) -> String {
+    match err {
+        DockerError::DockerResponseServerError { message, .. } => message,
+        DockerError::RequestTimeoutError => "Docker did not respond in time".to_string(),
+        other => other.to_string(),
+    }
+}
 
-    for part in ports_str.split(", ") {
-        let part = part.trim();
-        if part.is_empty() {
-            continue;
-        }
+fn api_error(source: &str, err: DockerError) -> ScanError {
+    let kind = match &err {
+        DockerError::RequestTimeoutError => "timed_out",
+        DockerError::DockerResponseServerError { .. } => "api",
+        _ => "connection",
+    };
+    ScanError {
+        source: source.to_string(),
+        kind: kind.to_string(),
+        message: api_message(err),
+    }
+}
 
-        if let Some(arrow_pos) = part.find("->") {
-            // Host-mapped port: "0.0.0.0:5432->5432/tcp"
-            let host_part = &part[..arrow_pos];
-            let container_part = &part[arrow_pos + 2..];
-
-            // Parse host port (after last colon)
-            let host_port = host_part
-                .rfind(':')
-                .and_then(|pos| host_part[pos + 1..].parse::<u16>().ok());
-
-            // Parse container port and protocol
-            let (container_port, protocol) = parse_port_protocol(container_part);
-
-            if let Some(port) = container_port {
-                bindings.push(PortBinding {
-                    host_port,
-                    container_port: port,
-                    protocol,
-                });
-            }
-        } else {
-            // Exposed but not mapped: "33060/tcp"
-            let (container_port, protocol) = parse_port_protocol(part);
-            if let Some(port) = container_port {
-                bindings.push(PortBinding {
-                    host_port: None,
-                    container_port: port,
-                    protocol,
-                });
-            }
+/// Published and exposed ports. The daemon lists IPv4 and IPv6 bindings
+/// separately; we keep one per host/container port pair.
+fn port_bindings(ports: &[Port]) -> Vec<PortBinding> {
+    let mut bindings: Vec<PortBinding> = Vec::new();
+    for port in ports {
+        let protocol = port
+            .typ
+            .map(|t| t.to_string())
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| "tcp".to_string());
+        let duplicate = bindings.iter().any(|b| {
+            b.host_port == port.public_port
+                && b.container_port == port.private_port
+                && b.protocol == protocol
+        });
+        if !duplicate {
+            bindings.push(PortBinding {
+                host_port: port.public_port,
+                container_port: port.private_port,
+                protocol,
+                host_ip: port.ip.clone().filter(|ip| !ip.is_empty()),
+            });
         }
     }
-
+    bindings.sort_by_key(|b| (b.container_port, b.host_port));
     bindings
 }
 
-/// Parse "5432/tcp" into (port, protocol)
-fn parse_port_protocol(s: &str) -> (Option<u16>, String) {
-    let parts: Vec<&str> = s.split('/').collect();
-    let port = parts.first().and_then(|p| p.parse::<u16>().ok());
-    let protocol = parts.get(1).unwrap_or(&"tcp").to_string();
-    (port, protocol)
+/// CPU usage the way `docker stats` computes it: the container's share of
+/// the host's CPU time since the previous sample, scaled by online CPUs.
+fn cpu_percent(stats: &Stats) -> f64 {
+    let cpu = &stats.cpu_stats;
+    let precpu = &stats.precpu_stats;
+    let cpu_delta = cpu.cpu_usage.total_usage as f64 - precpu.cpu_usage.total_usage as f64;
+    let system_delta =
+        cpu.system_cpu_usage.unwrap_or(0) as f64 - precpu.system_cpu_usage.unwrap_or(0) as f64;
+    let online_cpus = cpu
+        .online_cpus
+        .filter(|n| *n > 0)
+        .or_else(|| cpu.cpu_usage.percpu_usage.as_ref().map(|p| p.len() as u64))
+        .unwrap_or(1) as f64;
+
+    if cpu_delta > 0.0 && system_delta > 0.0 {
+        cpu_delta / system_delta * online_cpus * 100.0
+    } else {
+        0.0
+    }
 }
 
-/// Scan for Docker containers
-pub fn scan() -> DockerStatus {
-    let scanned_at = chrono::Utc::now().to_rfc3339();
-
-    // Check if Docker is available
-    let version = get_docker_version();
-    if version.is_none() {
-        return DockerStatus {
-            available: false,
-            version: None,
-            containers: vec![],
-            compose_projects: vec![],
-            total_running: 0,
-            total_stopped: 0,
-            scanned_at,
-        };
-    }
+/// Memory in use, excluding reclaimable page cache (as `docker stats` does).
+fn memory_usage(memory: &MemoryStats) -> u64 {
+    let usage = memory.usage.unwrap_or(0);
+    let inactive_file = match memory.stats {
+        Some(MemoryStatsStats::V1(v1)) => v1.total_inactive_file,
+        Some(MemoryStatsStats::V2(v2)) => v2.inactive_file,
+        None => 0,
+    };
+    usage.saturating_sub(inactive_file)
+}
 
-    // List all containers first (cheap: ~50ms)
-    let output = Command::new("docker")
-        .args([
-            "ps",
-            "-a",
-            "--format",
-            "{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.Status}}\t{{.CreatedAt}}\t{{.Ports}}\t{{.Label \"com.docker.compose.project\"}}\t{{.Label \"com.docker.compose.service\"}}",
-        ])
-        .output();
-
-    let mut containers = Vec::new();
-    let mut compose_map: HashMap<String, Vec<String>> = HashMap::new();
-    let mut running_count = 0;
-    let mut stopped_count = 0;
-
-    if let Ok(out) = output {
-        if out.status.success() {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            for line in stdout.lines() {
-                let parts: Vec<&str> = line.split('\t').collect();
-                if parts.len() < 6 {
-                    continue;
-                }
+fn category_name(category: ContainerCategory) -> &'static str {
+    match category {
+        ContainerCategory::Database => "database",
+        ContainerCategory::Cache => "cache",
+        ContainerCategory::Queue => "queue",
+        ContainerCategory::Proxy => "proxy",
+        ContainerCategory::WebApp => "webapp",
+        ContainerCategory::Monitoring => "monitoring",
+        ContainerCategory::Service => "service",
+        ContainerCategory::Unknown => "unknown",
+    }
+}
 
-                let id = parts[0].to_string();
-                let name = parts[1].to_string();
-                let image = parts[2].to_string();
-                let status_detail = parts[3].to_string();
-                let created = parts[4].to_string();
-                let ports_str = parts[5];
-                let compose_project = parts.get(6).map(|s| s.to_string()).filter(|s| !s.is_empty());
-                let compose_service = parts.get(7).map(|s| s.to_string()).filter(|s| !s.is_empty());
-
-                // Determine container status
-                let status = if status_detail.starts_with("Up") {
-                    running_count += 1;
-                    "running"
-                } else if status_detail.starts_with("Paused") {
-                    "paused"
-                } else if status_detail.starts_with("Exited") {
-                    stopped_count += 1;
-                    "exited"
-                } else {
-                    stopped_count += 1;
-                    "created"
-                }
-                .to_string();
-
-                // Detect container type from image
-                let detection = detect_container(&image);
-                let category_str = match detection.category {
-                    ContainerCategory::Database => "database",
-                    ContainerCategory::Cache => "cache",
-                    ContainerCategory::Queue => "queue",
-                    ContainerCategory::Proxy => "proxy",
-                    ContainerCategory::WebApp => "webapp",
-                    ContainerCategory::Monitoring => "monitoring",
-                    ContainerCategory::Service => "service",
-                    ContainerCategory::Unknown => "unknown",
-                }
-                .to_string();
+fn container_info(
+    summary: ContainerSummary,
+    inspect: Option<ContainerInspectResponse>,
+    stats: Option<Stats>,
+) -> ContainerInfo {
+    let id = summary.id.unwrap_or_default();
+    let name = summary
+        .names
+        .and_then(|names| names.into_iter().next())
+        .map(|n| n.trim_start_matches('/').to_string())
+        .unwrap_or_else(|| id.chars().take(12).collect());
+    let image = summary.image.unwrap_or_default();
+    let labels: BTreeMap<String, String> = summary.labels.unwrap_or_default().into_iter().collect();
+    let created = summary
+        .created
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default();
+
+    let mut networks: Vec<String> = summary
+        .network_settings
+        .and_then(|n| n.networks)
+        .map(|n| n.into_keys().collect())
+        .unwrap_or_default();
+    networks.sort();
+
+    let volumes = summary
+        .mounts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| VolumeMount {
+            kind: m.typ.map(|t| t.to_string()).unwrap_or_default(),
+            name: m.name.filter(|n| !n.is_empty()),
+            source: m.source.unwrap_or_default(),
+            destination: m.destination.unwrap_or_default(),
+            read_only: !m.rw.unwrap_or(true),
+        })
+        .collect();
 
-                // Parse ports
-                let ports = parse_ports(ports_str);
+    let state = inspect.as_ref().and_then(|i| i.state.as_ref());
+    let health = state
+        .and_then(|s| s.health.as_ref())
+        .and_then(|h| h.status)
+        .map(|s| s.to_string())
+        .filter(|s| !s.is_empty() && s != "none");
+    let exit_code = state
+        .filter(|s| s.running != Some(true))
+        .and_then(|s| s.exit_code);
+    let restart_count = inspect
+        .as_ref()
+        .and_then(|i| i.restart_count)
+        .unwrap_or(0)
+        .max(0) as u64;
+
+    let detection = detect_container(&image);
+
+    ContainerInfo {
+        ports: port_bindings(&summary.ports.unwrap_or_default()),
+        status: summary.state.unwrap_or_else(|| "created".to_string()),
+        state_detail: summary.status.unwrap_or_default(),
+        cpu_percent: stats.as_ref().map(cpu_percent).unwrap_or(0.0),
+        memory_bytes: stats
+            .as_ref()
+            .map(|s| memory_usage(&s.memory_stats))
+            .unwrap_or(0),
+        memory_limit: stats
+            .as_ref()
+            .and_then(|s| s.memory_stats.limit)
+            .unwrap_or(0),
+        service_name: detection.name,
+        category: category_name(detection.category).to_string(),
+        icon: detection.icon.to_string(),
+        compose_project: labels.get("com.docker.compose.project").cloned(),
+        compose_service: labels.get("com.docker.compose.service").cloned(),
+        id,
+        name,
+        image,
+        created,
+        health,
+        restart_count,
+        exit_code,
+        labels,
+        networks,
+        volumes,
+    }
+}
 
-                // Track compose projects
-                if let Some(ref project) = compose_project {
-                    compose_map
-                        .entry(project.clone())
-                        .or_default()
-                        .push(id.clone());
-                }
+/// Scan for Docker containers
+pub fn scan() -> DockerStatus {
+    block_on(scan_engine())
+}
 
-                containers.push(ContainerInfo {
-                    id,
-                    name,
-                    image,
-                    status,
-                    state_detail: status_detail,
-                    created,
-                    ports,
-                    cpu_percent: 0.0,
-                    memory_bytes: 0,
-                    memory_limit: 0,
-                    service_name: detection.name,
-                    category: category_str,
-                    icon: detection.icon.to_string(),
-                    compose_project,
-                    compose_service,
-                });
+async fn scan_engine() -> DockerStatus {
+    let mut status = DockerStatus {
+        available: false,
+        version: None,
+        containers: vec![],
+        compose_projects: vec![],
+        total_running: 0,
+        total_stopped: 0,
+        scanned_at: chrono::Utc::now().to_rfc3339(),
+        errors: vec![],
+        endpoint: None,
+        engine: None,
+    };
+
+    // No socket or a stopped daemon just means Docker isn't available
+    let Ok((docker, endpoint)) = connect() else {
+        return status;
+    };
+    status.endpoint = Some(endpoint);
+    let version = match docker.version().await {
+        Ok(v) => v,
+        Err(e) => {
+            if matches!(e, DockerError::RequestTimeoutError) {
+                status.errors.push(api_error("docker version", e));
             }
+            return status;
         }
-    }
-
-    // Only fetch expensive stats if there are running containers
-    if running_count > 0 {
-        let stats = get_container_stats();
-        for container in &mut containers {
-            if let Some(s) = stats.get(&container.id) {
-                container.cpu_percent = s.cpu_percent;
-                container.memory_bytes = s.mem_usage;
-                container.memory_limit = s.mem_limit;
-            }
+    };
+    let is_podman = version
+        .components
+        .iter()
+        .flatten()
+        .any(|c| c.name.to_lowercase().contains("podman"));
+    status.available = true;
+    status.version = version.version;
+    status.engine = Some(if is_podman { "podman" } else { "docker" }.to_string());
+
+    let summaries = match docker
+        .list_containers(Some(ListContainersOptions::<String> {
+            all: true,
+            ..Default::default()
+        }))
+        .await
+    {
+        Ok(list) => list,
+        Err(e) => {
+            status.errors.push(api_error("docker ps", e));
+            return status;
         }
-    }
+    };
+
+    // Inspect everything and sample stats for running containers, concurrently
+    let details = future::join_all(summaries.iter().map(|summary| {
+        let docker = &docker;
+        async move {
+            let id = summary.id.as_deref().unwrap_or_default();
+            let inspect = docker
+                .inspect_container(id, None::<InspectContainerOptions>)
+                .await;
+            let stats = if summary.state.as_deref() == Some("running") {
+                docker
+                    .stats(
+                        id,
+                        Some(StatsOptions {
+                            stream: false,
+                            one_shot: false,
+                        }),
+                    )
+                    .next()
+                    .await
+            } else {
+                None
+            };
+            (inspect, stats)
+        }
+    }))
+    .await;
 
-    // Build compose projects list
-    let compose_projects: Vec<ComposeProject> = compose_map
-        .into_iter()
-        .map(|(name, container_ids)| {
-            let running = container_ids
-                .iter()
-                .filter(|id| {
-                    containers
-                        .iter()
-                        .find(|c| &c.id == *id)
-                        .map(|c| c.status == "running")
-                        .unwrap_or(false)
-                })
-                .count();
-
-            ComposeProject {
-                name,
-                container_count: container_ids.len(),
-                running_count: running,
-                containers: container_ids,
-            }
-        })
-        .collect();
+    let mut inspect_error = None;
+    let mut stats_error = None;
 
-    DockerStatus {
-        available: true,
-        version,
-        containers,
-        compose_projects,
-        total_running: running_count,
-        total_stopped: stopped_count,
-        scanned_at,
+    for (summary, (inspect, stats)) in summaries.into_iter().zip(details) {
+        let inspect = inspect.map_err(|e| inspect_error.get_or_insert(e)).ok();
+        let stats = stats.and_then(|s| s.map_err(|e| stats_error.get_or_insert(e)).ok());
+        let container = container_info(summary, inspect, stats);
+
+        match container.status.as_str() {
+            "running" => status.total_running += 1,
+            "paused" => {}
+            _ => status.total_stopped += 1,
+        }
+        status.containers.push(container);
     }
+    status
+        .errors
+        .extend(inspect_error.map(|e| api_error("docker inspect", e)));
+    status
+        .errors
+        .extend(stats_error.map(|e| api_error("docker stats", e)));
+
+    status.compose_projects = compose::group_projects(&status.containers);
+    status
 }
 
 /// Start a container by ID
 pub fn start_container(container_id: &str) -> Result<(), String> {
-    let output = Command::new("docker")
-        .args(["start", container_id])
-        .output()
-        .map_err(|e| format!("Failed to execute docker start: {}", e))?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
-    }
+    block_on(async {
+        let (docker, _) = connect()?;
+        docker
+            .start_container(container_id, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(api_message)
+    })
 }
 
 /// Stop a container by ID
 pub fn stop_container(container_id: &str) -> Result<(), String> {
-    let output = Command::new("docker")
-        .args(["stop", container_id])
-        .output()
-        .map_err(|e| format!("Failed to execute docker stop: {}", e))?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
-    }
+    block_on(async {
+        let (docker, _) = connect()?;
+        docker
+            .stop_container(container_id, None)
+            .await
+            .map_err(api_message)
+    })
 }
 
 /// Restart a container by ID
 pub fn restart_container(container_id: &str) -> Result<(), String> {
-    let output = Command::new("docker")
-        .args(["restart", container_id])
-        .output()
-        .map_err(|e| format!("Failed to execute docker restart: {}", e))?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(String::from_utf8_lossy(&output.stderr).to_string())
-    }
+    block_on(async {
+        let (docker, _) = connect()?;
+        docker
+            .restart_container(container_id, None)
+            .await
+            .map_err(api_message)
+    })
 }
 
-/// Get container logs
+/// Get container logs (stdout and stderr, interleaved as written)
 pub fn get_logs(container_id: &str, tail: usize) -> Result<Vec<String>, String> {
-    let output = Command::new("docker")
-        .args(["logs", "--tail", &tail.to_string(), container_id])
-        .output()
-        .map_err(|e| format!("Failed to execute docker logs: {}", e))?;
+    block_on(async {
+        let (docker, _) = connect()?;
+        let mut stream = docker.logs(
+            container_id,
+            Some(LogsOptions::<String> {
+                stdout: true,
+                stderr: true,
+                tail: tail.to_string(),
+                ..Default::default()
+            }),
+        );
+
+        let mut logs = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(api_message)?;
+            logs.extend(
+                chunk
+                    .to_string()
+                    .lines()
+                    .filter(|l| !l.is_empty())
+                    .map(|l| l.to_string()),
+            );
+        }
+        Ok(logs)
+    })
+}
 
-    // Docker logs can output to both stdout and stderr
-    let mut logs = Vec::new();
+/// One line of container output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLine {
+    /// "stdout", "stderr", or "console" for containers started with a TTY
+    pub stream: String,
+    /// RFC 3339 timestamp from the daemon, when requested
+    pub timestamp: Option<String>,
+    pub message: String,
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+/// What a log subscription asks the daemon for (`docker logs -f` flags)
+#[derive(Debug, Clone)]
+pub struct LogFollowOptions {
+    pub stdout: bool,
+    pub stderr: bool,
+    pub timestamps: bool,
+    /// Unix seconds; 0 for everything
+    pub since: i64,
+    /// Lines of history to send before following; `None` for all of it
+    pub tail: Option<usize>,
+}
 
-    for line in stdout.lines() {
-        if !line.is_empty() {
-            logs.push(line.to_string());
+impl Default for LogFollowOptions {
+    fn default() -> Self {
+        Self {
+            stdout: true,
+            stderr: true,
+            timestamps: false,
+            since: 0,
+            tail: Some(100),
         }
     }
+}
+
+/// A running log follow. Cancelling or dropping it stops the stream.
+pub struct LogStream {
+    task: tokio::task::JoinHandle<()>,
+}
 
-    // stderr often contains actual log output for containers
-    for line in stderr.lines() {
-        if !line.is_empty() {
-            logs.push(line.to_string());
+impl LogStream {
+    pub fn cancel(&self) {
+        self.task.abort();
+    }
+}
+
+impl Drop for LogStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Parse a `--since` value: unix seconds, an RFC 3339 timestamp, or a
+/// duration ago like "10m", "2h" or "1d".
+pub fn parse_since(since: &str, now: i64) -> Result<i64, String> {
+    let since = since.trim();
+    if since.is_empty() {
+        return Ok(0);
+    }
+    if let Ok(secs) = since.parse::<i64>() {
+        return Ok(secs);
+    }
+    if let Ok(t) = chrono::DateTime::parse_from_rfc3339(since) {
+        return Ok(t.timestamp());
+    }
+    let invalid = || format!("Invalid since value: {}", since);
+    let (count, unit) = [('s', 1), ('m', 60), ('h', 3600), ('d', 86400)]
+        .into_iter()
+        .find_map(|(suffix, secs)| since.strip_suffix(suffix).map(|n| (n, secs)))
+        .ok_or_else(invalid)?;
+    // Unsigned: "-5m" would be a time in the future and match nothing
+    count
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| i64::try_from(n).ok()?.checked_mul(unit))
+        .map(|secs| now - secs)
+        .ok_or_else(invalid)
+}
+
+fn log_lines(output: &LogOutput, timestamps: bool) -> Vec<LogLine> {
+    let stream = match output {
+        LogOutput::StdOut { .. } => "stdout",
+        LogOutput::StdErr { .. } => "stderr",
+        LogOutput::StdIn { .. } => "stdin",
+        LogOutput::Console { .. } => "console",
+    };
+    output
+        .to_string()
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (timestamp, message) = match line.split_once(' ') {
+                Some((ts, rest)) if timestamps => (Some(ts.to_string()), rest),
+                _ => (None, line),
+            };
+            LogLine {
+                stream: stream.to_string(),
+                timestamp,
+                message: message.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Follow a container's logs like `docker logs -f`. `on_lines` gets each
+/// batch as the daemon sends it; `on_end` runs when the stream finishes on
+/// its own (container stopped or removed), with the error if there was one.
+pub fn follow_logs<F, E>(
+    container_id: &str,
+    opts: LogFollowOptions,
+    mut on_lines: F,
+    on_end: E,
+) -> Result<LogStream, String>
+where
+    F: FnMut(Vec<LogLine>) + Send + 'static,
+    E: FnOnce(Option<String>) + Send + 'static,
+{
+    let (docker, _) = connect()?;
+    let container_id = container_id.to_string();
+
+    let task = runtime().spawn(async move {
+        let mut stream = docker.logs(
+            &container_id,
+            Some(LogsOptions::<String> {
+                follow: true,
+                stdout: opts.stdout,
+                stderr: opts.stderr,
+                timestamps: opts.timestamps,
+                since: opts.since,
+                tail: opts
+                    .tail
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|| "all".to_string()),
+                ..Default::default()
+            }),
+        );
+
+        let mut error = None;
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(output) => {
+                    let lines = log_lines(&output, opts.timestamps);
+                    if !lines.is_empty() {
+                        on_lines(lines);
+                    }
+                }
+                Err(e) => {
+                    error = Some(api_message(e));
+                    break;
+                }
+            }
         }
+        on_end(error);
+    });
+
+    Ok(LogStream { task })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::PortTypeEnum;
+
+    fn port(ip: &str, private: u16, public: Option<u16>) -> Port {
+        Port {
+            ip: Some(ip.to_string()).filter(|s| !s.is_empty()),
+            private_port: private,
+            public_port: public,
+            typ: Some(PortTypeEnum::TCP),
+        }
+    }
+
+    #[test]
+    fn test_port_bindings_dedupes_ipv6() {
+        let bindings = port_bindings(&[
+            port("0.0.0.0", 5432, Some(15432)),
+            port("::", 5432, Some(15432)),
+            port("", 33060, None),
+            port("127.0.0.1", 3306, Some(3306)),
+        ]);
+        let summary: Vec<(u16, Option<u16>, Option<&str>)> = bindings
+            .iter()
+            .map(|b| (b.container_port, b.host_port, b.host_ip.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (3306, Some(3306), Some("127.0.0.1")),
+                (5432, Some(15432), Some("0.0.0.0")),
+                (33060, None, None),
+            ]
+        );
+        assert!(bindings.iter().all(|b| b.protocol == "tcp"));
     }
 
-    Ok(logs)
+    #[test]
+    fn test_cpu_and_memory_from_stats() {
+        let stats: Stats = serde_json::from_value(serde_json::json!({
+            "read": "2024-06-01T10:00:01Z",
+            "preread": "2024-06-01T10:00:00Z",
+            "num_procs": 0,
+            "pids_stats": {},
+            "memory_stats": {
+                "usage": 300_000_000u64,
+                "limit": 1_000_000_000u64,
+            },
+            "blkio_stats": {},
+            "cpu_stats": {
+                "cpu_usage": { "total_usage": 1_500_000_000u64, "usage_in_usermode": 0, "usage_in_kernelmode": 0 },
+                "system_cpu_usage": 20_000_000_000u64,
+                "online_cpus": 4,
+                "throttling_data": { "periods": 0, "throttled_periods": 0, "throttled_time": 0 },
+            },
+            "precpu_stats": {
+                "cpu_usage": { "total_usage": 1_000_000_000u64, "usage_in_usermode": 0, "usage_in_kernelmode": 0 },
+                "system_cpu_usage": 10_000_000_000u64,
+                "online_cpus": 4,
+                "throttling_data": { "periods": 0, "throttled_periods": 0, "throttled_time": 0 },
+            },
+            "storage_stats": {},
+        }))
+        .unwrap();
+
+        assert!((cpu_percent(&stats) - 20.0).abs() < 1e-9);
+        assert_eq!(memory_usage(&stats.memory_stats), 300_000_000);
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = 1_700_000_000;
+        assert_eq!(parse_since("", now), Ok(0));
+        assert_eq!(parse_since("1699990000", now), Ok(1_699_990_000));
+        assert_eq!(parse_since("10m", now), Ok(now - 600));
+        assert_eq!(parse_since("2h", now), Ok(now - 7200));
+        assert_eq!(parse_since("2023-11-14T22:13:20Z", now), Ok(1_700_000_000));
+        assert!(parse_since("yesterday", now).is_err());
+        assert!(parse_since("5µ", now).is_err());
+        assert!(parse_since("µ", now).is_err());
+        assert!(parse_since("-5m", now).is_err());
+        assert!(parse_since("99999999999999999999d", now).is_err());
+    }
+
+    #[test]
+    fn test_log_lines_split_timestamps() {
+        let output = LogOutput::StdErr {
+            message: "2024-06-01T10:00:00.123Z first\r\n2024-06-01T10:00:01.456Z second line\n"
+                .into(),
+        };
+        let lines = log_lines(&output, true);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].stream, "stderr");
+        assert_eq!(
+            lines[0].timestamp.as_deref(),
+            Some("2024-06-01T10:00:00.123Z")
+        );
+        assert_eq!(lines[0].message, "first");
+        assert_eq!(lines[1].message, "second line");
+
+        let plain = log_lines(&output, false);
+        assert_eq!(plain[1].timestamp, None);
+        assert!(plain[1].message.starts_with("2024-06-01"));
+    }
 }
@@ -0,0 +1,478 @@
+//! Bulk `git fetch` across workspace repositories, so `behind` counts reflect
+//! the remote instead of whenever someone last fetched by hand.
+//!
+//! Fetching goes through the `git` CLI rather than libgit2: credential
+//! helpers, SSH agents, `insteadOf` rewrites and `file://` remotes then behave
+//! exactly as they do in a terminal. Worktrees of one repository share their
+//! refs, so each repository is fetched once and every worktree is then
+//! checked (and optionally fast-forwarded) on its own.
+
+use super::git::{self, GitOperation};
+use super::process::{ProcessError, ProcessRunner, NETWORK_TIMEOUT};
+use git2::Repository;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Repositories fetched at once when the caller doesn't say.
+pub const DEFAULT_FETCH_CONCURRENCY: usize = 4;
+/// Remotes (and their rate limits) shouldn't see more than this from one machine.
+const MAX_FETCH_CONCURRENCY: usize = 16;
+
+/// A fast-forward only rewrites the working tree, but big checkouts take a moment.
+const MERGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Substrings of `git fetch` stderr that mean credentials were missing or
+/// rejected, as opposed to the remote being unreachable.
+const AUTH_ERROR_MARKERS: [&str; 9] = [
+    "authentication failed",
+    "could not read username",
+    "could not read password",
+    "terminal prompts disabled",
+    "invalid username or password",
+    "permission denied (publickey",
+    "host key verification failed",
+    "the requested url returned error: 401",
+    "the requested url returned error: 403",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FetchOutcome {
+    /// Fetched; the branch was left where it was
+    Fetched,
+    FastForwarded,
+    /// Behind, but local changes (or a merge, rebase, ...) kept it from being
+    /// fast-forwarded
+    SkippedDirty,
+    /// Behind with local commits of its own; needs a merge or rebase
+    Diverged,
+    NoRemote,
+    AuthFailed,
+    Failed,
+}
+
+impl FetchOutcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FetchOutcome::Fetched => "fetched",
+            FetchOutcome::FastForwarded => "fast-forwarded",
+            FetchOutcome::SkippedDirty => "skipped (dirty)",
+            FetchOutcome::Diverged => "diverged",
+            FetchOutcome::NoRemote => "no remote",
+            FetchOutcome::AuthFailed => "auth failed",
+            FetchOutcome::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoFetchResult {
+    pub project_path: String,
+    pub outcome: FetchOutcome,
+    pub branch: String,
+    /// Relative to the upstream after fetching (and fast-forwarding)
+    pub ahead: usize,
+    pub behind: usize,
+    /// Why it was skipped or what failed
+    pub message: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Sent as each repository finishes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub result: RepoFetchResult,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FetchSummary {
+    pub total: usize,
+    /// Repositories whose fetch succeeded, fast-forwarded or not
+    pub fetched: usize,
+    pub fast_forwarded: Vec<String>,
+    pub skipped_dirty: Vec<String>,
+    pub diverged: Vec<String>,
+    pub auth_errors: Vec<RepoFetchResult>,
+    pub failures: Vec<RepoFetchResult>,
+    /// Every repository, sorted by path
+    pub results: Vec<RepoFetchResult>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FetchOptions {
+    /// Fast-forward clean branches that are only behind their upstream
+    pub fast_forward: bool,
+    /// Repositories fetched at once, clamped to `1..=MAX_FETCH_CONCURRENCY`
+    pub concurrency: usize,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            fast_forward: false,
+            concurrency: DEFAULT_FETCH_CONCURRENCY,
+        }
+    }
+}
+
+/// Fetch every repository in `project_paths`, calling `on_progress` from the
+/// worker threads as each one finishes.
+pub fn fetch_all(
+    project_paths: &[String],
+    options: FetchOptions,
+    on_progress: impl Fn(&FetchProgress) + Sync,
+) -> FetchSummary {
+    let start = Instant::now();
+    let total = project_paths.len();
+    let completed = AtomicUsize::new(0);
+    let report = |result: RepoFetchResult| {
+        let progress = FetchProgress {
+            completed: completed.fetch_add(1, Ordering::SeqCst) + 1,
+            total,
+            result,
+        };
+        on_progress(&progress);
+        progress.result
+    };
+
+    // Group worktrees by their shared git directory so a repository is only
+    // fetched once; two fetches into one repository fight over ref locks.
+    let mut repos: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    let mut results = Vec::new();
+    for path in project_paths {
+        match Repository::open(path) {
+            Ok(repo) => {
+                let dir = repo.commondir().to_path_buf();
+                let key = dir.canonicalize().unwrap_or(dir);
+                repos.entry(key).or_default().push(path.clone());
+            }
+            Err(e) => results.push(report(failed(path, e.message().to_string(), start))),
+        }
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.concurrency.clamp(1, MAX_FETCH_CONCURRENCY))
+        .thread_name(|i| format!("houston-fetch-{}", i))
+        .build();
+    let fetch_repo = |worktrees: &Vec<String>| -> Vec<RepoFetchResult> {
+        let started = Instant::now();
+        let fetched = fetch(&worktrees[0]);
+        worktrees
+            .iter()
+            .map(|path| {
+                let result = match &fetched {
+                    Ok(()) => after_fetch(path, options.fast_forward, started),
+                    Err((outcome, message)) => RepoFetchResult {
+                        outcome: *outcome,
+                        message: Some(message.clone()),
+                        ..failed(path, String::new(), started)
+                    },
+                };
+                report(result)
+            })
+            .collect()
+    };
+    let fetched: Vec<RepoFetchResult> = match pool {
+        Ok(pool) => pool.install(|| {
+            repos
+                .par_iter()
+                .flat_map_iter(|(_, worktrees)| fetch_repo(worktrees))
+                .collect()
+        }),
+        Err(_) => repos.values().flat_map(fetch_repo).collect(),
+    };
+    results.extend(fetched);
+    results.sort_by(|a, b| a.project_path.cmp(&b.project_path));
+
+    let paths_with = |outcome: FetchOutcome| -> Vec<String> {
+        results
+            .iter()
+            .filter(|r| r.outcome == outcome)
+            .map(|r| r.project_path.clone())
+            .collect()
+    };
+    let results_with = |outcome: FetchOutcome| -> Vec<RepoFetchResult> {
+        results
+            .iter()
+            .filter(|r| r.outcome == outcome)
+            .cloned()
+            .collect()
+    };
+    FetchSummary {
+        total,
+        fetched: results
+            .iter()
+            .filter(|r| {
+                matches!(
+                    r.outcome,
+                    FetchOutcome::Fetched
+                        | FetchOutcome::FastForwarded
+                        | FetchOutcome::SkippedDirty
+                        | FetchOutcome::Diverged
+                )
+            })
+            .count(),
+        fast_forwarded: paths_with(FetchOutcome::FastForwarded),
+        skipped_dirty: paths_with(FetchOutcome::SkippedDirty),
+        diverged: paths_with(FetchOutcome::Diverged),
+        auth_errors: results_with(FetchOutcome::AuthFailed),
+        failures: results_with(FetchOutcome::Failed),
+        duration_ms: start.elapsed().as_millis() as u64,
+        results,
+    }
+}
+
+fn failed(path: &str, message: String, started: Instant) -> RepoFetchResult {
+    RepoFetchResult {
+        project_path: path.to_string(),
+        outcome: FetchOutcome::Failed,
+        branch: String::new(),
+        ahead: 0,
+        behind: 0,
+        message: Some(message),
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// `git fetch --all --prune` in `path`. Nothing may prompt: there's no
+/// terminal to answer, so missing credentials surface as an auth error.
+fn fetch(path: &str) -> Result<(), (FetchOutcome, String)> {
+    let has_remote = Repository::open(path)
+        .and_then(|repo| repo.remotes().map(|r| !r.is_empty()))
+        .unwrap_or(true);
+    if !has_remote {
+        return Err((FetchOutcome::NoRemote, "No remote configured".to_string()));
+    }
+
+    let output = noninteractive_git(path)
+        .args(["fetch", "--all", "--prune", "--quiet"])
+        .timeout(NETWORK_TIMEOUT)
+        .run_ok();
+    match output {
+        Ok(_) => Ok(()),
+        Err(ProcessError::Exit { stderr, .. }) if is_auth_error(&stderr) => {
+            Err((FetchOutcome::AuthFailed, stderr.trim().to_string()))
+        }
+        Err(e) => Err((FetchOutcome::Failed, e.to_string())),
+    }
+}
+
+/// `git` in `path` with credential prompts off. SSH runs in batch mode too,
+/// so a passphrase or host key question fails instead of hanging until the
+/// timeout, unless the user configured their own SSH command.
+fn noninteractive_git(path: &str) -> ProcessRunner {
+    let runner = ProcessRunner::new("git")
+        .current_dir(path)
+        .env("GIT_TERMINAL_PROMPT", "0");
+    if has_custom_ssh_command(path) {
+        runner
+    } else {
+        runner.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes")
+    }
+}
+
+/// Whether the user chose an SSH command of their own (`GIT_SSH_COMMAND`,
+/// `GIT_SSH` or `core.sshCommand`), which ours would otherwise replace.
+fn has_custom_ssh_command(path: &str) -> bool {
+    ["GIT_SSH_COMMAND", "GIT_SSH"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some())
+        || Repository::open(path)
+            .and_then(|repo| repo.config())
+            .and_then(|config| config.get_string("core.sshCommand"))
+            .is_ok()
+}
+
+fn is_auth_error(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    AUTH_ERROR_MARKERS
+        .iter()
+        .any(|marker| stderr.contains(marker))
+}
+
+/// Where the working tree at `path` stands now that its repository has been
+/// fetched, fast-forwarding it first if that's wanted and safe.
+fn after_fetch(path: &str, fast_forward: bool, started: Instant) -> RepoFetchResult {
+    let Some(status) = git::get_status(path) else {
+        return failed(path, "git is not installed".to_string(), started);
+    };
+    if let Some(error) = status.error {
+        return failed(path, error, started);
+    }
+
+    let mut result = RepoFetchResult {
+        project_path: path.to_string(),
+        outcome: FetchOutcome::Fetched,
+        branch: status.branch,
+        ahead: status.ahead,
+        behind: status.behind,
+        message: None,
+        duration_ms: 0,
+    };
+    if fast_forward && status.behind > 0 {
+        if status.operation != GitOperation::None {
+            result.outcome = FetchOutcome::SkippedDirty;
+            result.message = Some(format!("A {} is in progress", status.operation.label()));
+        } else if status.is_dirty {
+            result.outcome = FetchOutcome::SkippedDirty;
+            result.message = Some("Working tree has uncommitted changes".to_string());
+        } else if status.ahead > 0 {
+            result.outcome = FetchOutcome::Diverged;
+            result.message = Some(format!(
+                "{} local and {} upstream commits; merge or rebase by hand",
+                status.ahead, status.behind
+            ));
+        } else {
+            match noninteractive_git(path)
+                .args(["merge", "--ff-only", "--quiet", "@{upstream}"])
+                .timeout(MERGE_TIMEOUT)
+                .run_ok()
+            {
+                Ok(_) => {
+                    result.outcome = FetchOutcome::FastForwarded;
+                    result.behind = 0;
+                }
+                Err(e) => {
+                    result.outcome = FetchOutcome::Failed;
+                    result.message = Some(e.to_string());
+                }
+            }
+        }
+    }
+    result.duration_ms = started.elapsed().as_millis() as u64;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = ProcessRunner::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Houston")
+            .env("GIT_AUTHOR_EMAIL", "houston@example.com")
+            .env("GIT_COMMITTER_NAME", "Houston")
+            .env("GIT_COMMITTER_EMAIL", "houston@example.com")
+            .run_ok();
+        assert!(output.is_ok(), "git {:?}: {:?}", args, output.err());
+    }
+
+    fn commit(dir: &Path, name: &str, message: &str) {
+        fs::write(dir.join(name), message).unwrap();
+        git(dir, &["add", name]);
+        git(dir, &["commit", "-q", "-m", message]);
+    }
+
+    #[test]
+    fn test_fetch_all_file_remote() {
+        if super::super::process::which("git").is_none() {
+            return;
+        }
+        let tmp = TempDir::new().unwrap();
+        let origin = tmp.path().join("origin");
+        fs::create_dir(&origin).unwrap();
+        git(&origin, &["init", "-q"]);
+        commit(&origin, "README.md", "initial commit");
+
+        let url = format!("file://{}", origin.display());
+        let clone = |name: &str| {
+            git(tmp.path(), &["clone", "-q", &url, name]);
+            tmp.path().join(name).to_string_lossy().to_string()
+        };
+        let (clean, dirty, ahead) = (clone("clean"), clone("dirty"), clone("ahead"));
+        let local_only = tmp.path().join("local-only");
+        fs::create_dir(&local_only).unwrap();
+        git(&local_only, &["init", "-q"]);
+        commit(&local_only, "a.txt", "local");
+        let local_only = local_only.to_string_lossy().to_string();
+        let missing = tmp.path().join("unreachable").to_string_lossy().to_string();
+        let unreachable = clone("unreachable-origin");
+        git(
+            Path::new(&unreachable),
+            &[
+                "remote",
+                "set-url",
+                "origin",
+                &format!("file://{}", missing),
+            ],
+        );
+
+        commit(&origin, "new.txt", "upstream change");
+        fs::write(Path::new(&dirty).join("README.md"), "edited").unwrap();
+        commit(Path::new(&ahead), "mine.txt", "local change");
+
+        // Fetch alone only refreshes the counts
+        let paths = vec![clean.clone(), dirty.clone()];
+        let summary = fetch_all(&paths, FetchOptions::default(), |_| {});
+        assert_eq!(summary.fetched, 2);
+        assert!(summary.results.iter().all(|r| r.behind == 1));
+        assert!(git::get_status(&clean).unwrap().fetched_at.is_some());
+
+        let events = Mutex::new(Vec::new());
+        let paths = vec![
+            clean.clone(),
+            dirty.clone(),
+            ahead.clone(),
+            local_only.clone(),
+            unreachable.clone(),
+        ];
+        let options = FetchOptions {
+            fast_forward: true,
+            concurrency: 2,
+        };
+        let summary = fetch_all(&paths, options, |p| {
+            events.lock().unwrap().push(p.completed)
+        });
+
+        let mut completed = events.into_inner().unwrap();
+        completed.sort();
+        assert_eq!(completed, vec![1, 2, 3, 4, 5]);
+        assert_eq!(summary.total, 5);
+        assert_eq!(summary.fetched, 3);
+        assert_eq!(summary.fast_forwarded, vec![clean.clone()]);
+        assert_eq!(summary.skipped_dirty, vec![dirty]);
+        assert_eq!(summary.diverged, vec![ahead]);
+        assert_eq!(summary.failures.len(), 1);
+        assert_eq!(summary.failures[0].project_path, unreachable);
+        assert!(summary.auth_errors.is_empty());
+        let outcome = |path: &str| {
+            summary
+                .results
+                .iter()
+                .find(|r| r.project_path == path)
+                .unwrap()
+                .outcome
+        };
+        assert_eq!(outcome(&local_only), FetchOutcome::NoRemote);
+
+        let status = git::get_status(&clean).unwrap();
+        assert_eq!((status.ahead, status.behind), (0, 0));
+        assert_eq!(status.last_commit_message, "upstream change");
+    }
+
+    #[test]
+    fn test_auth_error_detection() {
+        assert!(is_auth_error(
+            "fatal: could not read Username for 'https://github.com': terminal prompts disabled"
+        ));
+        assert!(is_auth_error(
+            "git@github.com: Permission denied (publickey).\nfatal: Could not read from remote repository."
+        ));
+        assert!(!is_auth_error(
+            "fatal: '/tmp/gone' does not appear to be a git repository"
+        ));
+    }
+}
@@ -1,5 +1,71 @@
+//! Git status for workspace projects.
+//!
+//! Status is read in-process through libgit2 so a scan no longer spawns three
+//! `git` processes per repository. The `git` CLI is kept as a fallback for
+//! repositories libgit2 can't open (newer repository extensions, for one) and
+//! can be forced with `HOUSTON_GIT_BACKEND=cli`.
+
+use super::diagnostics::{DiagnosticItem, Severity};
+use super::process::{ProcessRunner, QUICK_TIMEOUT};
+use super::workspace::ProjectInfo;
+use git2::{BranchType, ErrorCode, Repository, StatusOptions};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// `git status` on a large repo with a cold cache can take a while.
+const STATUS_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Upper bound on repositories read at once, whichever backend is used.
+const MAX_GIT_WORKERS: usize = 8;
+
+/// An operation left in progress this long is reported as forgotten.
+const FORGOTTEN_OPERATION_SECS: i64 = 24 * 60 * 60;
+
+/// Multi-step command a working tree is in the middle of.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitOperation {
+    #[default]
+    None,
+    Merge,
+    Rebase,
+    /// `git am` (shares `rebase-apply/` with the old rebase backend)
+    Am,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl GitOperation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GitOperation::None => "none",
+            GitOperation::Merge => "merge",
+            GitOperation::Rebase => "rebase",
+            GitOperation::Am => "am",
+            GitOperation::CherryPick => "cherry-pick",
+            GitOperation::Revert => "revert",
+            GitOperation::Bisect => "bisect",
+        }
+    }
+
+    /// How to get out of it, for diagnostics.
+    fn resolution(&self) -> String {
+        match self {
+            GitOperation::None => String::new(),
+            GitOperation::Merge => "Commit the merge or run `git merge --abort`.".to_string(),
+            GitOperation::Bisect => "Run `git bisect reset` when you're done.".to_string(),
+            op => format!(
+                "Run `git {0} --continue` to finish or `git {0} --abort` to go back.",
+                op.label()
+            ),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitStatus {
@@ -15,32 +81,370 @@ pub struct GitStatus {
     pub last_commit_date: String,
     pub last_commit_epoch: Option<i64>,
     pub remote_url: String,
+    /// Set when `git status` failed or timed out; counts are then zero
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Merge, rebase, etc. in progress in this working tree
+    #[serde(default)]
+    pub operation: GitOperation,
+    /// When that operation started (its state file's mtime), as epoch seconds
+    #[serde(default)]
+    pub operation_since: Option<i64>,
+    /// Paths with unresolved merge conflicts
+    #[serde(default)]
+    pub conflicted_count: usize,
+    /// Abbreviated commit HEAD points at when no branch is checked out
+    #[serde(default)]
+    pub detached_at: Option<String>,
+    /// Tracking branch such as `origin/main`; empty when none is configured
+    #[serde(default)]
+    pub upstream: String,
+    /// Last `git fetch` (FETCH_HEAD's mtime) as epoch seconds; `None` if never fetched
+    #[serde(default)]
+    pub fetched_at: Option<i64>,
+}
+
+/// How `GitStatus` is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// In-process via libgit2, falling back to the CLI on errors
+    Library,
+    /// `git status` / `git log` / `git remote` subprocesses
+    Cli,
+}
+
+impl Backend {
+    /// `HOUSTON_GIT_BACKEND=cli` forces the subprocess backend.
+    pub fn from_env() -> Self {
+        match std::env::var("HOUSTON_GIT_BACKEND").as_deref() {
+            Ok("cli") => Backend::Cli,
+            _ => Backend::Library,
+        }
+    }
 }
 
 pub fn get_status(project_path: &str) -> Option<GitStatus> {
+    get_status_with(project_path, Backend::from_env())
+}
+
+pub fn get_status_with(project_path: &str, backend: Backend) -> Option<GitStatus> {
     // Check if it's a git repo
     let git_dir = std::path::Path::new(project_path).join(".git");
     if !git_dir.exists() {
         return None;
     }
 
-    let output = Command::new("git")
-        .args(["status", "--porcelain=v2", "--branch"])
+    match backend {
+        Backend::Library => match status_from_repo(project_path) {
+            Ok(status) => Some(status),
+            Err(e) => {
+                log::debug!("libgit2 status failed for {}: {}", project_path, e);
+                status_from_cli(project_path)
+            }
+        },
+        Backend::Cli => status_from_cli(project_path),
+    }
+}
+
+/// Read status through libgit2. Mirrors `git status --porcelain=v2 --branch`
+/// with default untracked handling: an untracked directory counts once.
+fn status_from_repo(project_path: &str) -> Result<GitStatus, git2::Error> {
+    let repo = Repository::open(project_path)?;
+
+    let mut ahead = 0;
+    let mut behind = 0;
+    let mut upstream_name = String::new();
+    let mut detached_at = None;
+    let branch = match repo.head() {
+        Ok(head) if head.is_branch() => {
+            let name = head.shorthand().unwrap_or_default().to_string();
+            let local = repo.find_branch(&name, BranchType::Local)?;
+            if let Ok(upstream) = local.upstream() {
+                upstream_name = upstream.name()?.unwrap_or_default().to_string();
+                if let (Some(oid), Some(up_oid)) = (head.target(), upstream.get().target()) {
+                    (ahead, behind) = repo.graph_ahead_behind(oid, up_oid)?;
+                }
+            }
+            name
+        }
+        Ok(head) => {
+            detached_at = head.target().map(|oid| short_oid(&oid.to_string()));
+            "(detached)".to_string()
+        }
+        // Fresh `git init`: HEAD points at a branch with no commits yet
+        Err(e) if e.code() == ErrorCode::UnbornBranch => repo
+            .find_reference("HEAD")?
+            .symbolic_target()
+            .and_then(|t| t.strip_prefix("refs/heads/"))
+            .unwrap_or_default()
+            .to_string(),
+        Err(e) => return Err(e),
+    };
+
+    // libgit2 only refreshes the index when asked to, so like
+    // `--no-optional-locks` this leaves .git/index (and the watcher) alone.
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .renames_head_to_index(true);
+    let statuses = repo.statuses(Some(&mut opts))?;
+
+    let mut modified_count = 0;
+    let mut untracked_count = 0;
+    let mut staged_count = 0;
+    let mut conflicted_count = 0;
+    for entry in statuses.iter() {
+        let s = entry.status();
+        if s.is_conflicted() {
+            conflicted_count += 1;
+            continue;
+        }
+        if s == git2::Status::WT_NEW {
+            untracked_count += 1;
+            continue;
+        }
+        if s.intersects(
+            git2::Status::INDEX_NEW
+                | git2::Status::INDEX_MODIFIED
+                | git2::Status::INDEX_DELETED
+                | git2::Status::INDEX_RENAMED
+                | git2::Status::INDEX_TYPECHANGE,
+        ) {
+            staged_count += 1;
+        }
+        if s.intersects(
+            git2::Status::WT_MODIFIED
+                | git2::Status::WT_DELETED
+                | git2::Status::WT_RENAMED
+                | git2::Status::WT_TYPECHANGE,
+        ) {
+            modified_count += 1;
+        }
+    }
+
+    let (last_commit_message, last_commit_date, last_commit_epoch) =
+        match repo.head().and_then(|h| h.peel_to_commit()) {
+            Ok(commit) => {
+                let epoch = commit.time().seconds();
+                (
+                    commit.summary().unwrap_or_default().to_string(),
+                    relative_date(epoch, chrono::Utc::now().timestamp()),
+                    Some(epoch),
+                )
+            }
+            Err(_) => Default::default(),
+        };
+
+    let remote_url = repo
+        .find_remote("origin")
+        .ok()
+        .and_then(|r| r.url().map(str::to_string))
+        .unwrap_or_default();
+
+    let is_dirty =
+        modified_count > 0 || untracked_count > 0 || staged_count > 0 || conflicted_count > 0;
+    let (operation, operation_since) = operation_in(repo.path());
+    let fetched_at = fetched_at_in(repo.path());
+
+    Ok(GitStatus {
+        project_path: project_path.to_string(),
+        branch,
+        is_dirty,
+        modified_count,
+        untracked_count,
+        staged_count,
+        ahead,
+        behind,
+        last_commit_message,
+        last_commit_date,
+        last_commit_epoch,
+        remote_url,
+        error: None,
+        operation,
+        operation_since,
+        conflicted_count,
+        detached_at,
+        upstream: upstream_name,
+        fetched_at,
+    })
+}
+
+fn short_oid(oid: &str) -> String {
+    oid.chars().take(7).collect()
+}
+
+/// The git directory of a working tree. Linked worktrees and submodules have
+/// a `.git` file pointing at theirs instead of a directory.
+fn git_dir(project_path: &Path) -> Option<PathBuf> {
+    let dot_git = project_path.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let target = content.trim().strip_prefix("gitdir:")?.trim();
+    Some(project_path.join(target))
+}
+
+/// Operation in progress according to the state files in `git_dir`, and when
+/// it started.
+fn operation_in(git_dir: &Path) -> (GitOperation, Option<i64>) {
+    const MARKERS: [(&str, GitOperation); 7] = [
+        ("rebase-merge", GitOperation::Rebase),
+        ("rebase-apply/applying", GitOperation::Am),
+        ("rebase-apply", GitOperation::Rebase),
+        ("MERGE_HEAD", GitOperation::Merge),
+        ("CHERRY_PICK_HEAD", GitOperation::CherryPick),
+        ("REVERT_HEAD", GitOperation::Revert),
+        ("BISECT_LOG", GitOperation::Bisect),
+    ];
+    for (marker, operation) in MARKERS {
+        if let Ok(meta) = fs::metadata(git_dir.join(marker)) {
+            return (operation, modified_epoch(&meta));
+        }
+    }
+    (GitOperation::None, None)
+}
+
+fn modified_epoch(meta: &fs::Metadata) -> Option<i64> {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+}
+
+/// The directory shared by all worktrees of a repository: a linked
+/// worktree's git dir names it in its `commondir` file.
+fn common_dir(git_dir: &Path) -> PathBuf {
+    fs::read_to_string(git_dir.join("commondir"))
+        .map(|target| git_dir.join(target.trim()))
+        .unwrap_or_else(|_| git_dir.to_path_buf())
+}
+
+/// When the repository was last fetched. Every `git fetch` rewrites
+/// FETCH_HEAD, even when nothing changed upstream. Each worktree writes its
+/// own, so the newest of the worktree's and the shared one wins.
+fn fetched_at_in(git_dir: &Path) -> Option<i64> {
+    [git_dir.to_path_buf(), common_dir(git_dir)]
+        .iter()
+        .filter_map(|dir| fs::metadata(dir.join("FETCH_HEAD")).ok())
+        .filter_map(|meta| modified_epoch(&meta))
+        .max()
+}
+
+/// Operation in progress in the working tree at `project_path`. Only looks at
+/// files, so it's cheap enough to run across every project.
+pub fn operation_state(project_path: &str) -> (GitOperation, Option<i64>) {
+    git_dir(Path::new(project_path))
+        .map(|dir| operation_in(&dir))
+        .unwrap_or((GitOperation::None, None))
+}
+
+/// Repos left in the middle of a rebase, merge, etc. for over a day.
+pub fn diagnostics(projects: &[ProjectInfo]) -> Vec<DiagnosticItem> {
+    let now = chrono::Utc::now().timestamp();
+    let mut items = Vec::new();
+    for project in projects.iter().filter(|p| p.has_git) {
+        let (operation, since) = operation_state(&project.path);
+        let Some(since) = since else { continue };
+        if operation == GitOperation::None || now - since < FORGOTTEN_OPERATION_SECS {
+            continue;
+        }
+        items.push(DiagnosticItem {
+            id: format!("git_operation_{}", project.path),
+            category: "git".to_string(),
+            severity: Severity::Warning,
+            title: format!("{}: {} in progress", project.name, operation.label()),
+            description: format!(
+                "A {} was started {} and never finished. {}",
+                operation.label(),
+                relative_date(since, now),
+                operation.resolution()
+            ),
+            details: Some(project.path.clone()),
+            fix_id: None,
+            fix_label: None,
+        });
+    }
+    items
+}
+
+/// Format `epoch` relative to `now` the way `git log --format=%ar` does.
+pub(crate) fn relative_date(epoch: i64, now: i64) -> String {
+    fn plural(n: i64, unit: &str) -> String {
+        if n == 1 {
+            format!("{} {}", n, unit)
+        } else {
+            format!("{} {}s", n, unit)
+        }
+    }
+
+    if epoch > now {
+        return "in the future".to_string();
+    }
+    let mut diff = now - epoch;
+    if diff < 90 {
+        return format!("{} ago", plural(diff, "second"));
+    }
+    diff = (diff + 30) / 60;
+    if diff < 90 {
+        return format!("{} ago", plural(diff, "minute"));
+    }
+    diff = (diff + 30) / 60;
+    if diff < 36 {
+        return format!("{} ago", plural(diff, "hour"));
+    }
+    // From here on `diff` is in days
+    diff = (diff + 12) / 24;
+    if diff < 14 {
+        return format!("{} ago", plural(diff, "day"));
+    }
+    if diff < 70 {
+        return format!("{} ago", plural((diff + 3) / 7, "week"));
+    }
+    if diff < 365 {
+        return format!("{} ago", plural((diff + 15) / 30, "month"));
+    }
+    if diff < 1825 {
+        let total_months = (diff * 12 * 2 + 365) / (365 * 2);
+        let (years, months) = (total_months / 12, total_months % 12);
+        if months > 0 {
+            return format!("{}, {} ago", plural(years, "year"), plural(months, "month"));
+        }
+        return format!("{} ago", plural(years, "year"));
+    }
+    format!("{} ago", plural((diff + 183) / 365, "year"))
+}
+
+fn status_from_cli(project_path: &str) -> Option<GitStatus> {
+    let output = ProcessRunner::new("git")
+        // No optional locks: don't rewrite .git/index, which would wake the watcher
+        .args(["--no-optional-locks", "status", "--porcelain=v2", "--branch"])
         .current_dir(project_path)
-        .output()
-        .ok()?;
+        .timeout(STATUS_TIMEOUT)
+        .run();
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (stdout, error) = match output {
+        Ok(o) => (o.stdout, None),
+        Err(e) if e.is_not_found() => return None,
+        Err(e) => (String::new(), Some(e.to_string())),
+    };
 
     let mut branch = String::new();
+    let mut head_oid = String::new();
+    let mut upstream = String::new();
     let mut ahead = 0;
     let mut behind = 0;
     let mut modified_count = 0;
     let mut untracked_count = 0;
     let mut staged_count = 0;
+    let mut conflicted_count = 0;
 
     for line in stdout.lines() {
-        if line.starts_with("# branch.head ") {
+        if let Some(oid) = line.strip_prefix("# branch.oid ") {
+            head_oid = oid.to_string();
+        } else if let Some(name) = line.strip_prefix("# branch.upstream ") {
+            upstream = name.to_string();
+        } else if line.starts_with("# branch.head ") {
             branch = line
                 .strip_prefix("# branch.head ")
                 .unwrap_or("")
@@ -66,37 +470,43 @@ pub fn get_status(project_path: &str) -> Option<GitStatus> {
             if y != '.' {
                 modified_count += 1;
             }
+        } else if line.starts_with("u ") {
+            conflicted_count += 1;
         } else if line.starts_with("? ") {
             untracked_count += 1;
         }
     }
 
     // Get last commit info
-    let log_output = Command::new("git")
+    let log_output = ProcessRunner::new("git")
         .args(["log", "-1", "--format=%s|%ar|%ct"])
         .current_dir(project_path)
-        .output()
+        .timeout(QUICK_TIMEOUT)
+        .run()
         .ok();
 
     let (last_commit_message, last_commit_date, last_commit_epoch) = log_output
-        .map(|o| {
-            let s = String::from_utf8_lossy(&o.stdout).trim().to_string();
-            parse_git_log_line(&s)
-        })
+        .map(|o| parse_git_log_line(o.stdout.trim()))
         .unwrap_or_default();
 
     // Get remote URL
-    let remote_output = Command::new("git")
+    let remote_output = ProcessRunner::new("git")
         .args(["remote", "get-url", "origin"])
         .current_dir(project_path)
-        .output()
+        .timeout(QUICK_TIMEOUT)
+        .run()
         .ok();
 
     let remote_url = remote_output
-        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .map(|o| o.stdout.trim().to_string())
         .unwrap_or_default();
 
-    let is_dirty = modified_count > 0 || untracked_count > 0 || staged_count > 0;
+    let is_dirty =
+        modified_count > 0 || untracked_count > 0 || staged_count > 0 || conflicted_count > 0;
+    let detached_at =
+        (branch == "(detached)" && !head_oid.is_empty()).then(|| short_oid(&head_oid));
+    let (operation, operation_since) = operation_state(project_path);
+    let fetched_at = git_dir(Path::new(project_path)).and_then(|dir| fetched_at_in(&dir));
 
     Some(GitStatus {
         project_path: project_path.to_string(),
@@ -111,23 +521,44 @@ pub fn get_status(project_path: &str) -> Option<GitStatus> {
         last_commit_date,
         last_commit_epoch,
         remote_url,
+        error,
+        operation,
+        operation_since,
+        conflicted_count,
+        detached_at,
+        upstream,
+        fetched_at,
+    })
+}
+
+/// Dedicated pool so a few hundred repos are read a handful at a time,
+/// without competing with the global rayon pool used by other scanners.
+pub(crate) fn git_pool() -> &'static rayon::ThreadPool {
+    static POOL: OnceLock<rayon::ThreadPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let workers = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4)
+            .min(MAX_GIT_WORKERS);
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(workers)
+            .thread_name(|i| format!("houston-git-{}", i))
+            .build()
+            .expect("failed to build git worker pool")
     })
 }
 
 pub fn get_statuses(project_paths: &[String]) -> Vec<GitStatus> {
-    let handles: Vec<_> = project_paths
-        .iter()
-        .map(|p| {
-            let path = p.clone();
-            std::thread::spawn(move || get_status(&path))
-        })
-        .collect();
+    get_statuses_with(project_paths, Backend::from_env())
+}
 
-    handles
-        .into_iter()
-        .filter_map(|h| h.join().ok())
-        .flatten()
-        .collect()
+pub fn get_statuses_with(project_paths: &[String], backend: Backend) -> Vec<GitStatus> {
+    git_pool().install(|| {
+        project_paths
+            .par_iter()
+            .filter_map(|p| get_status_with(p, backend))
+            .collect()
+    })
 }
 
 /// Parse a git log line in the format `message|relative_date|epoch`.
@@ -142,55 +573,293 @@ fn parse_git_log_line(line: &str) -> (String, String, Option<i64>) {
 }
 
 /// For each git-enabled project path, detect worktrees. Returns a mapping from
-/// project path to the main worktree path (shared ID) for projects that have
-/// multiple worktrees.
+/// project path to every worktree of its repository, main worktree first (its
+/// path doubles as the shared ID), for projects that have multiple worktrees.
 pub fn detect_worktree_groups(
     project_paths: &[String],
-) -> std::collections::HashMap<String, String> {
-    let handles: Vec<_> = project_paths
-        .iter()
-        .map(|path| {
-            let path = path.clone();
-            std::thread::spawn(move || {
-                let output = Command::new("git")
+) -> std::collections::HashMap<String, Vec<String>> {
+    git_pool().install(|| {
+        project_paths
+            .par_iter()
+            .filter_map(|path| {
+                let output = ProcessRunner::new("git")
                     .args(["worktree", "list", "--porcelain"])
-                    .current_dir(&path)
-                    .output();
-
-                let output = match output {
-                    Ok(o) if o.status.success() => o,
-                    _ => return None,
-                };
-
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let worktree_paths: Vec<String> = stdout
-                    .lines()
-                    .filter_map(|line| line.strip_prefix("worktree "))
-                    .map(|s| s.to_string())
+                    .current_dir(path)
+                    .timeout(QUICK_TIMEOUT)
+                    .run_ok()
+                    .ok()?;
+
+                let worktree_paths: Vec<String> = super::worktrees::parse_porcelain(&output.stdout)
+                    .into_iter()
+                    .map(|wt| wt.path)
                     .collect();
 
                 if worktree_paths.len() > 1 {
-                    let main_worktree = worktree_paths[0].clone();
-                    Some((path, main_worktree))
+                    Some((path.clone(), worktree_paths))
                 } else {
                     None
                 }
             })
-        })
-        .collect();
-
-    let mut result = std::collections::HashMap::new();
-    for h in handles {
-        if let Some(Some((path, main_wt))) = h.join().ok() {
-            result.insert(path, main_wt);
-        }
-    }
-    result
+            .collect()
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn commit_file(repo: &Repository, name: &str, content: &str, message: &str) {
+        fs::write(repo.workdir().unwrap().join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Houston", "houston@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap();
+    }
+
+    /// One commit ahead of its upstream, with one staged, one modified and
+    /// two untracked entries (a loose file and a directory).
+    fn fixture_repo(dir: &Path) {
+        let repo = Repository::init(dir).unwrap();
+        commit_file(&repo, "README.md", "# fixture\n", "initial commit");
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("base", &base, false).unwrap();
+        commit_file(&repo, "src.txt", "one\n", "add src");
+        let name = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.find_branch(&name, BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("base"))
+            .unwrap();
+
+        fs::write(dir.join("src.txt"), "two\n").unwrap();
+        fs::write(dir.join("staged.txt"), "new\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+        fs::create_dir(dir.join("notes")).unwrap();
+        fs::write(dir.join("notes/a.md"), "a").unwrap();
+        fs::write(dir.join("notes/b.md"), "b").unwrap();
+        fs::write(dir.join("scratch.txt"), "").unwrap();
+    }
+
+    #[test]
+    fn test_library_status_matches_cli() {
+        let tmp = TempDir::new().unwrap();
+        fixture_repo(tmp.path());
+        let path = tmp.path().to_string_lossy().to_string();
+
+        let lib = status_from_repo(&path).unwrap();
+        assert!(!lib.branch.is_empty());
+        assert_eq!((lib.ahead, lib.behind), (1, 0));
+        assert_eq!(lib.staged_count, 1);
+        assert_eq!(lib.modified_count, 1);
+        assert_eq!(lib.untracked_count, 2);
+        assert!(lib.is_dirty);
+        assert_eq!(lib.last_commit_message, "add src");
+        assert!(lib.last_commit_epoch.is_some());
+
+        if super::super::process::which("git").is_none() {
+            return;
+        }
+        let cli = get_status_with(&path, Backend::Cli).unwrap();
+        assert_eq!(cli.branch, lib.branch);
+        assert_eq!((cli.ahead, cli.behind), (lib.ahead, lib.behind));
+        assert_eq!(cli.staged_count, lib.staged_count);
+        assert_eq!(cli.modified_count, lib.modified_count);
+        assert_eq!(cli.untracked_count, lib.untracked_count);
+        assert_eq!(cli.last_commit_message, lib.last_commit_message);
+        assert_eq!(cli.last_commit_epoch, lib.last_commit_epoch);
+    }
+
+    #[test]
+    fn test_worktree_sees_shared_fetch_head() {
+        let tmp = TempDir::new().unwrap();
+        let main_dir = tmp.path().join("main");
+        let repo = Repository::init(&main_dir).unwrap();
+        commit_file(&repo, "a.txt", "a\n", "initial commit");
+        let linked = tmp.path().join("linked");
+        repo.worktree("linked", &linked, None).unwrap();
+        let path = linked.to_string_lossy().to_string();
+        assert_eq!(status_from_repo(&path).unwrap().fetched_at, None);
+
+        // Fetched from the main worktree
+        fs::write(main_dir.join(".git/FETCH_HEAD"), "").unwrap();
+        assert!(status_from_repo(&path).unwrap().fetched_at.is_some());
+
+        if super::super::process::which("git").is_none() {
+            return;
+        }
+        let cli = get_status_with(&path, Backend::Cli).unwrap();
+        assert!(cli.fetched_at.is_some());
+    }
+
+    #[test]
+    fn test_library_status_unborn_branch() {
+        let tmp = TempDir::new().unwrap();
+        Repository::init(tmp.path()).unwrap();
+        fs::write(tmp.path().join("new.txt"), "").unwrap();
+        let status = status_from_repo(&tmp.path().to_string_lossy()).unwrap();
+        assert!(!status.branch.is_empty());
+        assert_eq!(status.untracked_count, 1);
+        assert_eq!(status.last_commit_epoch, None);
+    }
+
+    #[test]
+    fn test_merge_conflict_and_upstream() {
+        let tmp = TempDir::new().unwrap();
+        let repo = Repository::init(tmp.path()).unwrap();
+        commit_file(&repo, "app.txt", "base\n", "initial commit");
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.branch("topic", &base, false).unwrap();
+        commit_file(&repo, "app.txt", "ours\n", "ours");
+        repo.set_head("refs/heads/topic").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit_file(&repo, "app.txt", "theirs\n", "theirs");
+        repo.find_branch("topic", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some(&main))
+            .unwrap();
+        let theirs = repo
+            .reference_to_annotated_commit(
+                &repo
+                    .find_reference(&format!("refs/heads/{}", main))
+                    .unwrap(),
+            )
+            .unwrap();
+        repo.merge(&[&theirs], None, None).unwrap();
+
+        let path = tmp.path().to_string_lossy().to_string();
+        let lib = status_from_repo(&path).unwrap();
+        assert_eq!(lib.operation, GitOperation::Merge);
+        assert!(lib.operation_since.is_some());
+        assert_eq!(lib.conflicted_count, 1);
+        assert!(lib.is_dirty);
+        assert_eq!(lib.upstream, main);
+        assert_eq!(lib.detached_at, None);
+
+        if super::super::process::which("git").is_none() {
+            return;
+        }
+        let cli = get_status_with(&path, Backend::Cli).unwrap();
+        assert_eq!(cli.operation, GitOperation::Merge);
+        assert_eq!(cli.conflicted_count, 1);
+        assert_eq!(cli.upstream, lib.upstream);
+    }
+
+    #[test]
+    fn test_detached_head() {
+        let tmp = TempDir::new().unwrap();
+        let repo = Repository::init(tmp.path()).unwrap();
+        commit_file(&repo, "a.txt", "a\n", "initial commit");
+        let oid = repo.head().unwrap().target().unwrap();
+        repo.set_head_detached(oid).unwrap();
+
+        let path = tmp.path().to_string_lossy().to_string();
+        let lib = status_from_repo(&path).unwrap();
+        assert_eq!(lib.branch, "(detached)");
+        assert_eq!(lib.detached_at, Some(oid.to_string()[..7].to_string()));
+        assert_eq!(lib.upstream, "");
+
+        if super::super::process::which("git").is_none() {
+            return;
+        }
+        let cli = get_status_with(&path, Backend::Cli).unwrap();
+        assert_eq!(cli.branch, "(detached)");
+        assert_eq!(cli.detached_at, lib.detached_at);
+    }
+
+    #[test]
+    fn test_forgotten_rebase_diagnostic() {
+        let tmp = TempDir::new().unwrap();
+        let old = std::time::SystemTime::now() - Duration::from_secs(3 * 24 * 60 * 60);
+        let make = |name: &str, marker: Option<&str>| {
+            let dir = tmp.path().join(name);
+            fs::create_dir_all(dir.join(".git")).unwrap();
+            if let Some(marker) = marker {
+                let state_dir = dir.join(".git").join(marker);
+                fs::create_dir(&state_dir).unwrap();
+                fs::write(state_dir.join("head-name"), "refs/heads/topic").unwrap();
+                fs::File::open(&state_dir)
+                    .unwrap()
+                    .set_modified(old)
+                    .unwrap();
+            }
+            ProjectInfo {
+                name: name.to_string(),
+                path: dir.to_string_lossy().to_string(),
+                has_git: true,
+                ..Default::default()
+            }
+        };
+        let projects = vec![make("clean", None), make("stuck", Some("rebase-merge"))];
+        // A rebase started just now is still being worked on
+        let fresh = make("fresh", None);
+        fs::create_dir_all(Path::new(&fresh.path).join(".git/rebase-merge")).unwrap();
+
+        assert_eq!(operation_state(&projects[1].path).0, GitOperation::Rebase);
+        let items = diagnostics(&[projects, vec![fresh]].concat());
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "stuck: rebase in progress");
+        assert!(items[0].description.contains("git rebase --abort"));
+    }
+
+    #[test]
+    fn test_relative_date_matches_git() {
+        let now = 1_700_000_000;
+        assert_eq!(relative_date(now - 1, now), "1 second ago");
+        assert_eq!(relative_date(now - 300, now), "5 minutes ago");
+        assert_eq!(relative_date(now - 3 * 3600, now), "3 hours ago");
+        assert_eq!(relative_date(now - 2 * 86400, now), "2 days ago");
+        assert_eq!(relative_date(now - 21 * 86400, now), "3 weeks ago");
+        assert_eq!(relative_date(now - 100 * 86400, now), "3 months ago");
+        assert_eq!(
+            relative_date(now - 500 * 86400, now),
+            "1 year, 4 months ago"
+        );
+        assert_eq!(relative_date(now - 3000 * 86400, now), "8 years ago");
+        assert_eq!(relative_date(now + 10, now), "in the future");
+    }
+
+    /// Compares both backends over a generated workspace. Run with
+    /// `cargo test --release bench_git_status -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_git_status_backends() {
+        const REPOS: usize = 150;
+        let tmp = TempDir::new().unwrap();
+        let paths: Vec<String> = (0..REPOS)
+            .map(|i| {
+                let dir = tmp.path().join(format!("repo-{:03}", i));
+                fs::create_dir(&dir).unwrap();
+                fixture_repo(&dir);
+                dir.to_string_lossy().to_string()
+            })
+            .collect();
+
+        for backend in [Backend::Library, Backend::Cli] {
+            let start = std::time::Instant::now();
+            let statuses = get_statuses_with(&paths, backend);
+            let elapsed = start.elapsed();
+            println!(
+                "{:?}: {} repos in {:?} ({:?}/repo)",
+                backend,
+                statuses.len(),
+                elapsed,
+                elapsed / REPOS as u32
+            );
+            assert_eq!(statuses.len(), REPOS);
+            assert!(statuses.iter().all(|s| s.error.is_none() && s.ahead == 1));
+        }
+    }
 
     #[test]
     fn test_parse_git_log_with_epoch() {
@@ -1,5 +1,5 @@
+use super::process::{self, ProcessRunner, QUICK_TIMEOUT};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LanguageInfo {
@@ -9,6 +9,9 @@ pub struct LanguageInfo {
     pub manager: String,
     pub installed: bool,
     pub icon: String,
+    /// Set when the binary exists but its version probe failed or hung
+    #[serde(default)]
+    pub error: Option<String>,
 }
 
 struct LanguageSpec {
@@ -25,7 +28,8 @@ fn parse_version_generic(output: &str) -> String {
     output
         .split_whitespace()
         .find(|word| {
-            word.chars()
+            word.trim_start_matches('v')
+                .chars()
                 .next()
                 .map(|c| c.is_ascii_digit())
                 .unwrap_or(false)
@@ -45,6 +49,28 @@ fn parse_java_version(output: &str) -> String {
         .unwrap_or_else(|| parse_version_generic(output))
 }
 
+fn parse_cc_version(output: &str) -> String {
+    // "cc (Ubuntu 13.2.0-4ubuntu3) 13.2.0" / "Apple clang version 15.0.0 (clang-1500.1.0.403.1)":
+    // drop the parenthesized vendor details before looking for a version
+    let first_line = output.lines().next().unwrap_or(output);
+    let mut depth = 0;
+    let stripped: String = first_line
+        .chars()
+        .filter(|c| match c {
+            '(' => {
+                depth += 1;
+                false
+            }
+            ')' => {
+                depth -= 1;
+                false
+            }
+            _ => depth == 0,
+        })
+        .collect();
+    parse_version_generic(&stripped)
+}
+
 fn detect_node_manager(path: &str) -> String {
     let p = path.to_lowercase();
     if p.contains("nvm") {
@@ -95,6 +121,8 @@ fn detect_generic_manager(path: &str) -> String {
         "homebrew".to_string()
     } else if p.contains("mise") || p.contains("asdf") {
         "mise/asdf".to_string()
+    } else if p.starts_with("/nix/") || p.contains(".nix-profile") {
+        "nix".to_string()
     } else if p.contains("\\scoop\\") {
         "scoop".to_string()
     } else if p.contains("\\chocolatey\\") {
@@ -106,6 +134,35 @@ fn detect_generic_manager(path: &str) -> String {
     }
 }
 
+fn detect_haskell_manager(path: &str) -> String {
+    if path.contains("ghcup") {
+        "ghcup".to_string()
+    } else if path.contains("stack") {
+        "stack".to_string()
+    } else {
+        detect_generic_manager(path)
+    }
+}
+
+fn detect_ocaml_manager(path: &str) -> String {
+    if path.contains("opam") {
+        "opam".to_string()
+    } else {
+        detect_generic_manager(path)
+    }
+}
+
+fn detect_scala_manager(path: &str) -> String {
+    let p = path.to_lowercase();
+    if p.contains("coursier") {
+        "coursier".to_string()
+    } else if p.contains("sdkman") {
+        "sdkman".to_string()
+    } else {
+        detect_generic_manager(path)
+    }
+}
+
 fn detect_rust_manager(_path: &str) -> String {
     "rustup".to_string()
 }
@@ -177,6 +234,70 @@ const LANGUAGES: &[LanguageSpec] = &[
         manager_detector: detect_generic_manager,
         icon: "php",
     },
+    LanguageSpec {
+        name: "Zig",
+        binary: "zig",
+        version_args: &["version"],
+        version_parser: parse_version_generic,
+        manager_detector: detect_generic_manager,
+        icon: "zig",
+    },
+    LanguageSpec {
+        name: "Haskell",
+        binary: "ghc",
+        version_args: &["--numeric-version"],
+        version_parser: parse_version_generic,
+        manager_detector: detect_haskell_manager,
+        icon: "haskell",
+    },
+    LanguageSpec {
+        name: "OCaml",
+        binary: "ocaml",
+        version_args: &["-version"],
+        version_parser: parse_version_generic,
+        manager_detector: detect_ocaml_manager,
+        icon: "ocaml",
+    },
+    LanguageSpec {
+        name: "Scala",
+        binary: "scala",
+        version_args: &["-version"],
+        version_parser: parse_version_generic,
+        manager_detector: detect_scala_manager,
+        icon: "scala",
+    },
+    LanguageSpec {
+        name: "Clojure",
+        binary: "clojure",
+        version_args: &["--version"],
+        version_parser: parse_version_generic,
+        manager_detector: detect_generic_manager,
+        icon: "clojure",
+    },
+    LanguageSpec {
+        name: "Nix",
+        binary: "nix",
+        version_args: &["--version"],
+        version_parser: parse_version_generic,
+        manager_detector: detect_generic_manager,
+        icon: "nix",
+    },
+    LanguageSpec {
+        name: "Terraform",
+        binary: "terraform",
+        version_args: &["version"],
+        version_parser: parse_version_generic,
+        manager_detector: detect_generic_manager,
+        icon: "terraform",
+    },
+    LanguageSpec {
+        name: "C/C++",
+        binary: "cc",
+        version_args: &["--version"],
+        version_parser: parse_cc_version,
+        manager_detector: detect_generic_manager,
+        icon: "c",
+    },
     LanguageSpec {
         name: "Deno",
         binary: "deno",
@@ -195,27 +316,6 @@ const LANGUAGES: &[LanguageSpec] = &[
     },
 ];
 
-fn which(binary: &str) -> Option<String> {
-    #[cfg(unix)]
-    let cmd = "which";
-    #[cfg(windows)]
-    let cmd = "where.exe";
-
-    Command::new(cmd)
-        .arg(binary)
-        .output()
-        .ok()
-        .and_then(|o| {
-            if o.status.success() {
-                // `where.exe` returns multiple lines; take first
-                let out = String::from_utf8_lossy(&o.stdout);
-                Some(out.lines().next()?.trim().to_string())
-            } else {
-                None
-            }
-        })
-}
-
 pub fn scan() -> Vec<LanguageInfo> {
     let handles: Vec<_> = LANGUAGES
         .iter()
@@ -227,54 +327,48 @@ pub fn scan() -> Vec<LanguageInfo> {
             let manager_detector = spec.manager_detector;
             let icon = spec.icon;
             std::thread::spawn(move || {
-                if let Some(binary_path) = which(binary) {
-                    let output = Command::new(binary).args(version_args).output();
+                let missing = || LanguageInfo {
+                    name: name.to_string(),
+                    version: String::new(),
+                    binary_path: String::new(),
+                    manager: String::new(),
+                    installed: false,
+                    icon: icon.to_string(),
+                    error: None,
+                };
 
-                    let (installed, version) = match output {
-                        Ok(o) if o.status.success() => {
-                            let stdout = String::from_utf8_lossy(&o.stdout).to_string();
-                            let stderr = String::from_utf8_lossy(&o.stderr).to_string();
-                            let raw = if stdout.trim().is_empty() {
-                                &stderr
-                            } else {
-                                &stdout
-                            };
-                            let ver = version_parser(raw.trim());
-                            (true, ver)
-                        }
-                        _ => (false, String::new()),
-                    };
+                let Some(binary_path) = process::which(binary) else {
+                    return missing();
+                };
 
-                    if !installed {
-                        return LanguageInfo {
-                            name: name.to_string(),
-                            version: String::new(),
-                            binary_path: String::new(),
-                            manager: String::new(),
-                            installed: false,
-                            icon: icon.to_string(),
+                let output = ProcessRunner::new(binary)
+                    .args(version_args)
+                    .timeout(QUICK_TIMEOUT)
+                    .run_ok();
+
+                let (version, error) = match output {
+                    Ok(o) => {
+                        let raw = if o.stdout.trim().is_empty() {
+                            &o.stderr
+                        } else {
+                            &o.stdout
                         };
+                        (version_parser(raw.trim()), None)
                     }
+                    // A shim that hangs (e.g. an uninitialized version manager)
+                    // is still installed; say so rather than hiding it
+                    Err(e) if e.is_timeout() => (String::new(), Some(e.to_string())),
+                    Err(_) => return missing(),
+                };
 
-                    let manager = manager_detector(&binary_path);
-
-                    LanguageInfo {
-                        name: name.to_string(),
-                        version,
-                        binary_path,
-                        manager,
-                        installed: true,
-                        icon: icon.to_string(),
-                    }
-                } else {
-                    LanguageInfo {
-                        name: name.to_string(),
-                        version: String::new(),
-                        binary_path: String::new(),
-                        manager: String::new(),
-                        installed: false,
-                        icon: icon.to_string(),
-                    }
+                LanguageInfo {
+                    name: name.to_string(),
+                    version,
+                    manager: manager_detector(&binary_path),
+                    binary_path,
+                    installed: true,
+                    icon: icon.to_string(),
+                    error,
                 }
             })
         })
@@ -285,3 +379,17 @@ pub fn scan() -> Vec<LanguageInfo> {
         .filter_map(|h| h.join().ok())
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cc_version_skips_vendor_details() {
+        assert_eq!(parse_cc_version("cc (Ubuntu 13.2.0-4ubuntu3) 13.2.0\nCopyright"), "13.2.0");
+        assert_eq!(
+            parse_cc_version("Apple clang version 15.0.0 (clang-1500.1.0.403.1)\nTarget: arm64"),
+            "15.0.0"
+        );
+    }
+}
@@ -0,0 +1,480 @@
+//! Machine manifest: a versioned snapshot of a known-good machine.
+//!
+//! Built from the regular scanner structs, written as pretty JSON, and
+//! compared against the local machine to list what's missing or at a
+//! different version (the onboarding "does my laptop match?" check).
+
+use super::ai_tools::{self, AiToolInfo};
+use super::claude::McpServer;
+use super::languages::LanguageInfo;
+use super::packages::PackageList;
+use super::path::PathEntry;
+use super::system::SystemInfo;
+use super::{languages, packages, path, system};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Bumped when a field is removed or changes meaning. New optional fields
+/// don't need a bump.
+pub const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MachineManifest {
+    pub manifest_version: u32,
+    pub houston_version: String,
+    pub exported_at: String,
+    pub system: SystemInfo,
+    pub languages: Vec<LanguageInfo>,
+    pub path: Vec<PathEntry>,
+    pub packages: PackageList,
+    pub ai_tools: Vec<AiToolInfo>,
+    /// MCP servers keyed by the tool whose config declares them
+    #[serde(default)]
+    pub mcp_servers: BTreeMap<String, Vec<McpServer>>,
+}
+
+impl MachineManifest {
+    /// Assemble a manifest from scan results, keeping only what's installed.
+    pub fn new(
+        system: SystemInfo,
+        languages: Vec<LanguageInfo>,
+        path: Vec<PathEntry>,
+        packages: PackageList,
+        ai_tools: Vec<AiToolInfo>,
+        mcp_servers: BTreeMap<String, Vec<McpServer>>,
+    ) -> Self {
+        Self {
+            manifest_version: MANIFEST_VERSION,
+            houston_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            system,
+            languages: languages.into_iter().filter(|l| l.installed).collect(),
+            path,
+            packages,
+            ai_tools: ai_tools
+                .into_iter()
+                .filter(|t| t.installed || t.app_installed)
+                .collect(),
+            mcp_servers,
+        }
+    }
+
+    /// Run every scanner the manifest needs (used by the CLI, which has no caches).
+    pub fn capture() -> Self {
+        let langs = std::thread::spawn(languages::scan);
+        let pkgs = std::thread::spawn(packages::scan);
+        let tools = std::thread::spawn(ai_tools::scan);
+
+        Self::new(
+            system::scan(),
+            langs.join().unwrap_or_default(),
+            path::scan(),
+            pkgs.join().unwrap_or_default(),
+            tools.join().map(|r| r.tools).unwrap_or_default(),
+            scan_mcp_servers(),
+        )
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize manifest: {}", e))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let manifest: Self =
+            serde_json::from_str(json).map_err(|e| format!("Invalid manifest: {}", e))?;
+        if manifest.manifest_version > MANIFEST_VERSION {
+            return Err(format!(
+                "Manifest version {} is newer than this version of Houston supports ({})",
+                manifest.manifest_version, MANIFEST_VERSION
+            ));
+        }
+        Ok(manifest)
+    }
+}
+
+/// MCP servers for every tool with a known config location.
+pub fn scan_mcp_servers() -> BTreeMap<String, Vec<McpServer>> {
+    ai_tools::MCP_TOOLS
+        .iter()
+        .map(|tool| (tool.to_string(), ai_tools::scan_mcp_servers(tool)))
+        .filter(|(_, servers)| !servers.is_empty())
+        .collect()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Comparison
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GapKind {
+    Missing,
+    VersionMismatch,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestGap {
+    pub kind: GapKind,
+    /// system, language, path, package, ai_tool or mcp_server
+    pub category: String,
+    /// Package manager or tool the entry belongs to
+    pub group: Option<String>,
+    pub name: String,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestComparison {
+    pub reference_hostname: String,
+    pub reference_exported_at: String,
+    pub gaps: Vec<ManifestGap>,
+    pub missing_count: usize,
+    pub mismatch_count: usize,
+}
+
+/// List what `local` is missing, or has at a different version, compared
+/// to `reference`. Extra things installed locally are not reported.
+pub fn compare(reference: &MachineManifest, local: &MachineManifest) -> ManifestComparison {
+    let mut gaps = Vec::new();
+
+    compare_system(&reference.system, &local.system, &mut gaps);
+
+    compare_versions(
+        "language",
+        None,
+        reference
+            .languages
+            .iter()
+            .map(|l| (l.name.as_str(), l.version.as_str())),
+        local
+            .languages
+            .iter()
+            .map(|l| (l.name.as_str(), l.version.as_str())),
+        &mut gaps,
+    );
+
+    // Home directories differ between machines, so compare with `~`
+    let local_path: HashSet<String> = local
+        .path
+        .iter()
+        .map(|e| tilde(&e.path, &local.system.home_dir))
+        .collect();
+    for entry in reference.path.iter().filter(|e| e.exists) {
+        let p = tilde(&entry.path, &reference.system.home_dir);
+        if !local_path.contains(&p) {
+            gaps.push(gap(GapKind::Missing, "path", None, &p, None, None));
+        }
+    }
+
+    // (group, source name used in `PackageList::errors`, expected, actual)
+    let package_groups = [
+        (
+            "npm",
+            "npm",
+            &reference.packages.npm_global,
+            &local.packages.npm_global,
+        ),
+        (
+            "brew",
+            "brew",
+            &reference.packages.brew,
+            &local.packages.brew,
+        ),
+        ("pip", "pip", &reference.packages.pip, &local.packages.pip),
+        (
+            "cargo",
+            "cargo",
+            &reference.packages.cargo,
+            &local.packages.cargo,
+        ),
+        (
+            "scoop",
+            "scoop",
+            &reference.packages.scoop,
+            &local.packages.scoop,
+        ),
+        (
+            "chocolatey",
+            "choco",
+            &reference.packages.chocolatey,
+            &local.packages.chocolatey,
+        ),
+    ];
+    let failed = |list: &PackageList, source: &str| list.errors.iter().any(|e| e.source == source);
+    for (group, source, expected, actual) in package_groups {
+        // A manager that couldn't be listed would make every package look missing
+        if failed(&reference.packages, source) || failed(&local.packages, source) {
+            continue;
+        }
+        compare_versions(
+            "package",
+            Some(group),
+            expected
+                .iter()
+                .map(|p| (p.name.as_str(), p.version.as_str())),
+            actual.iter().map(|p| (p.name.as_str(), p.version.as_str())),
+            &mut gaps,
+        );
+    }
+
+    let tool_version = |t: &AiToolInfo| {
+        t.version
+            .clone()
+            .or_else(|| t.app_version.clone())
+            .unwrap_or_default()
+    };
+    let ref_tools: Vec<(String, String)> = reference
+        .ai_tools
+        .iter()
+        .map(|t| (t.name.clone(), tool_version(t)))
+        .collect();
+    let local_tools: Vec<(String, String)> = local
+        .ai_tools
+        .iter()
+        .map(|t| (t.name.clone(), tool_version(t)))
+        .collect();
+    compare_versions(
+        "ai_tool",
+        None,
+        ref_tools.iter().map(|(n, v)| (n.as_str(), v.as_str())),
+        local_tools.iter().map(|(n, v)| (n.as_str(), v.as_str())),
+        &mut gaps,
+    );
+
+    for (tool, servers) in &reference.mcp_servers {
+        let local_servers = local.mcp_servers.get(tool);
+        for server in servers {
+            let found = local_servers.and_then(|s| s.iter().find(|l| l.name == server.name));
+            let expected = mcp_command(server);
+            match found {
+                None => gaps.push(gap(
+                    GapKind::Missing,
+                    "mcp_server",
+                    Some(tool),
+                    &server.name,
+                    Some(expected),
+                    None,
+                )),
+                Some(l) if mcp_command(l) != expected => gaps.push(gap(
+                    GapKind::VersionMismatch,
+                    "mcp_server",
+                    Some(tool),
+                    &server.name,
+                    Some(expected),
+                    Some(mcp_command(l)),
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+
+    let missing_count = gaps.iter().filter(|g| g.kind == GapKind::Missing).count();
+    ManifestComparison {
+        reference_hostname: reference.system.hostname.clone(),
+        reference_exported_at: reference.exported_at.clone(),
+        mismatch_count: gaps.len() - missing_count,
+        missing_count,
+        gaps,
+    }
+}
+
+fn compare_system(reference: &SystemInfo, local: &SystemInfo, gaps: &mut Vec<ManifestGap>) {
+    let fields = [
+        ("os", &reference.os_name, &local.os_name),
+        ("architecture", &reference.architecture, &local.architecture),
+        ("shell", &reference.shell, &local.shell),
+    ];
+    for (name, expected, actual) in fields {
+        if expected != actual {
+            gaps.push(gap(
+                GapKind::VersionMismatch,
+                "system",
+                None,
+                name,
+                Some(expected.clone()),
+                Some(actual.clone()),
+            ));
+        }
+    }
+}
+
+fn compare_versions<'a>(
+    category: &str,
+    group: Option<&str>,
+    expected: impl Iterator<Item = (&'a str, &'a str)>,
+    actual: impl Iterator<Item = (&'a str, &'a str)>,
+    gaps: &mut Vec<ManifestGap>,
+) {
+    let actual: HashMap<&str, &str> = actual.collect();
+    for (name, version) in expected {
+        match actual.get(name) {
+            None => gaps.push(gap(
+                GapKind::Missing,
+                category,
+                group,
+                name,
+                Some(version.to_string()),
+                None,
+            )),
+            Some(v) if !version.is_empty() && *v != version => gaps.push(gap(
+                GapKind::VersionMismatch,
+                category,
+                group,
+                name,
+                Some(version.to_string()),
+                Some(v.to_string()),
+            )),
+            Some(_) => {}
+        }
+    }
+}
+
+fn gap(
+    kind: GapKind,
+    category: &str,
+    group: Option<&str>,
+    name: &str,
+    expected: Option<String>,
+    actual: Option<String>,
+) -> ManifestGap {
+    ManifestGap {
+        kind,
+        category: category.to_string(),
+        group: group.map(|g| g.to_string()),
+        name: name.to_string(),
+        expected,
+        actual,
+    }
+}
+
+fn mcp_command(server: &McpServer) -> String {
+    format!("{} {}", server.command, server.args.join(" "))
+        .trim()
+        .to_string()
+}
+
+fn tilde(path: &str, home: &str) -> String {
+    match path.strip_prefix(home) {
+        // Only whole components: /Users/refother is not under /Users/ref
+        Some(rest) if !home.is_empty() && (rest.is_empty() || rest.starts_with(['/', '\\'])) => {
+            format!("~{}", rest)
+        }
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanners::packages::PackageInfo;
+    use crate::scanners::process::ScanError;
+
+    fn manifest(home: &str) -> MachineManifest {
+        let system: SystemInfo = serde_json::from_value(serde_json::json!({
+            "os_name": "macOS", "os_version": "15.1", "kernel_version": "24.1.0",
+            "architecture": "aarch64", "hostname": "ref", "shell": "zsh",
+            "shell_version": "5.9", "cpu_brand": "M3", "memory_gb": "36",
+            "username": "dev", "home_dir": home, "binary_architecture": "aarch64",
+            "architecture_mismatch": false
+        }))
+        .unwrap();
+        MachineManifest::new(
+            system,
+            vec![],
+            vec![],
+            PackageList::default(),
+            vec![],
+            BTreeMap::new(),
+        )
+    }
+
+    fn lang(name: &str, version: &str) -> LanguageInfo {
+        LanguageInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+            binary_path: String::new(),
+            manager: String::new(),
+            installed: true,
+            icon: String::new(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_reports_missing_and_mismatched() {
+        let mut reference = manifest("/Users/ref");
+        reference.languages = vec![lang("Node.js", "22.1.0"), lang("Go", "1.23.0")];
+        reference.packages.brew = vec![PackageInfo {
+            name: "jq".to_string(),
+            version: "1.7.1".to_string(),
+        }];
+
+        let mut local = manifest("/home/new");
+        local.languages = vec![lang("Node.js", "20.11.0"), lang("Rust", "1.80.0")];
+
+        let result = compare(&reference, &local);
+        assert_eq!(result.missing_count, 2);
+        assert_eq!(result.mismatch_count, 1);
+
+        let node = result.gaps.iter().find(|g| g.name == "Node.js").unwrap();
+        assert_eq!(node.kind, GapKind::VersionMismatch);
+        assert_eq!(node.actual.as_deref(), Some("20.11.0"));
+        let jq = result.gaps.iter().find(|g| g.name == "jq").unwrap();
+        assert_eq!(jq.group.as_deref(), Some("brew"));
+
+        // brew timed out locally: its packages aren't known to be missing
+        local.packages.errors.push(ScanError {
+            source: "brew".to_string(),
+            kind: "timed_out".to_string(),
+            message: "timed out".to_string(),
+        });
+        let result = compare(&reference, &local);
+        assert!(result.gaps.iter().all(|g| g.name != "jq"));
+    }
+
+    #[test]
+    fn test_path_compared_relative_to_home() {
+        let entry = |p: &str| PathEntry {
+            path: p.to_string(),
+            exists: true,
+            is_duplicate: false,
+            index: 0,
+            category: "Other".to_string(),
+        };
+        let mut reference = manifest("/Users/ref");
+        reference.path = vec![entry("/Users/ref/.cargo/bin"), entry("/opt/tools/bin")];
+        let mut local = manifest("/home/new");
+        local.path = vec![entry("/home/new/.cargo/bin")];
+
+        let result = compare(&reference, &local);
+        let paths: Vec<&str> = result
+            .gaps
+            .iter()
+            .filter(|g| g.category == "path")
+            .map(|g| g.name.as_str())
+            .collect();
+        assert_eq!(paths, vec!["/opt/tools/bin"]);
+
+        assert_eq!(tilde("/Users/ref", "/Users/ref"), "~");
+        assert_eq!(tilde("/Users/ref/bin", "/Users/ref"), "~/bin");
+        assert_eq!(
+            tilde("/Users/refother/bin", "/Users/ref"),
+            "/Users/refother/bin"
+        );
+        assert_eq!(tilde("C:\\Users\\ref\\bin", "C:\\Users\\ref"), "~\\bin");
+    }
+
+    #[test]
+    fn test_rejects_newer_manifest_version() {
+        let mut m = manifest("/Users/ref");
+        m.manifest_version = MANIFEST_VERSION + 1;
+        let json = serde_json::to_string(&m).unwrap();
+        assert!(MachineManifest::from_json(&json).is_err());
+
+        m.manifest_version = MANIFEST_VERSION;
+        let json = m.to_json().unwrap();
+        assert!(MachineManifest::from_json(&json).is_ok());
+    }
+}
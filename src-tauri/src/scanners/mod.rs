@@ -1,14 +1,28 @@
 pub mod ai_tools;
+pub mod branches;
 pub mod claude;
+pub mod compose;
+pub mod dev_commands;
 pub mod dev_servers;
 pub mod diagnostics;
 pub mod docker;
 pub mod environment;
+pub mod fetch;
 pub mod git;
 pub mod languages;
+pub mod manifest;
 pub mod outdated_cache;
 pub mod packages;
 pub mod path;
+pub mod policy;
+pub mod ports;
+pub mod process;
+#[cfg(target_os = "linux")]
+pub mod procfs;
 pub mod project_analysis;
+pub mod scan_diff;
 pub mod system;
+pub mod version_check;
+pub mod version_req;
 pub mod workspace;
+pub mod worktrees;
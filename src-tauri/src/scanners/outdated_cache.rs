@@ -9,13 +9,15 @@
 //! commands run at most once per 10 minutes regardless of how many
 //! consumers need the data.
 
+use super::process::{ProcessError, ProcessRunner, NETWORK_TIMEOUT};
 use serde_json::Value;
-use std::process::Command;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+type OutdatedResult = Result<Value, ProcessError>;
+
 struct CachedResult {
-    data: Value,
+    data: OutdatedResult,
     fetched_at: Instant,
 }
 
@@ -25,7 +27,12 @@ static PIP_CACHE: Mutex<Option<CachedResult>> = Mutex::new(None);
 
 const CACHE_TTL: Duration = Duration::from_secs(600); // 10 minutes
 
-fn get_or_fetch(cache: &Mutex<Option<CachedResult>>, fetch: impl FnOnce() -> Value) -> Value {
+/// Errors are cached too: a `brew` that timed out will most likely time out
+/// again, and retrying on every scan would stall each one for a minute.
+fn get_or_fetch(
+    cache: &Mutex<Option<CachedResult>>,
+    fetch: impl FnOnce() -> OutdatedResult,
+) -> OutdatedResult {
     let mut guard = cache.lock().unwrap();
     if let Some(ref cached) = *guard {
         if cached.fetched_at.elapsed() < CACHE_TTL {
@@ -40,53 +47,55 @@ fn get_or_fetch(cache: &Mutex<Option<CachedResult>>, fetch: impl FnOnce() -> Val
     data
 }
 
-/// Get brew outdated JSON (cached). Returns `{"formulae": [...], "casks": [...]}` or null.
-pub fn brew_outdated_json() -> Value {
+/// Get brew outdated JSON (cached). Returns `{"formulae": [...], "casks": [...]}`,
+/// null if brew gave no usable output, or the error that stopped it.
+pub fn brew_outdated() -> OutdatedResult {
     get_or_fetch(&BREW_CACHE, || {
         if cfg!(target_os = "windows") {
-            return Value::Null;
+            return Ok(Value::Null);
         }
-        let output = Command::new("brew")
+        let output = ProcessRunner::new("brew")
             .args(["outdated", "--json"])
-            .output();
-        match output {
-            Ok(o) if o.status.success() => {
-                let stdout = String::from_utf8_lossy(&o.stdout);
-                serde_json::from_str(&stdout).unwrap_or(Value::Null)
-            }
-            _ => Value::Null,
-        }
+            .timeout(NETWORK_TIMEOUT)
+            .run_ok()?;
+        Ok(serde_json::from_str(&output.stdout).unwrap_or(Value::Null))
     })
 }
 
 /// Get npm outdated JSON (cached). Returns `{"pkg": {"current": ..., "latest": ...}}` or null.
-pub fn npm_outdated_json() -> Value {
+pub fn npm_outdated() -> OutdatedResult {
     get_or_fetch(&NPM_CACHE, || {
-        let output = Command::new("npm")
+        // Exits 1 whenever something is outdated, so don't treat that as failure
+        let output = ProcessRunner::new("npm")
             .args(["outdated", "-g", "--json"])
-            .output();
-        match output {
-            Ok(o) => {
-                let stdout = String::from_utf8_lossy(&o.stdout);
-                serde_json::from_str(&stdout).unwrap_or(Value::Null)
-            }
-            _ => Value::Null,
-        }
+            .timeout(NETWORK_TIMEOUT)
+            .run()?;
+        Ok(serde_json::from_str(&output.stdout).unwrap_or(Value::Null))
     })
 }
 
 /// Get pip outdated JSON (cached). Returns `[{"name": ..., "version": ..., "latest_version": ...}]` or null.
-pub fn pip_outdated_json() -> Value {
+pub fn pip_outdated() -> OutdatedResult {
     get_or_fetch(&PIP_CACHE, || {
-        let output = Command::new("pip3")
+        let output = ProcessRunner::new("pip3")
             .args(["list", "--outdated", "--format=json"])
-            .output();
-        match output {
-            Ok(o) if o.status.success() => {
-                let stdout = String::from_utf8_lossy(&o.stdout);
-                serde_json::from_str(&stdout).unwrap_or(Value::Null)
-            }
-            _ => Value::Null,
-        }
+            .timeout(NETWORK_TIMEOUT)
+            .run_ok()?;
+        Ok(serde_json::from_str(&output.stdout).unwrap_or(Value::Null))
     })
 }
+
+/// [`brew_outdated`] for callers that only care about the data.
+pub fn brew_outdated_json() -> Value {
+    brew_outdated().unwrap_or(Value::Null)
+}
+
+/// [`npm_outdated`] for callers that only care about the data.
+pub fn npm_outdated_json() -> Value {
+    npm_outdated().unwrap_or(Value::Null)
+}
+
+/// [`pip_outdated`] for callers that only care about the data.
+pub fn pip_outdated_json() -> Value {
+    pip_outdated().unwrap_or(Value::Null)
+}
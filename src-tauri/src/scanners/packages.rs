@@ -1,5 +1,9 @@
+use super::process::{ProcessError, ProcessRunner, ScanError};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::time::Duration;
+
+/// Listing installed packages is local, but `brew`/`pip` can still be slow.
+const LIST_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageInfo {
@@ -7,7 +11,7 @@ pub struct PackageInfo {
     pub version: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PackageList {
     pub npm_global: Vec<PackageInfo>,
     pub brew: Vec<PackageInfo>,
@@ -15,194 +19,171 @@ pub struct PackageList {
     pub cargo: Vec<PackageInfo>,
     pub scoop: Vec<PackageInfo>,
     pub chocolatey: Vec<PackageInfo>,
+    /// Package managers that are installed but couldn't be listed
+    #[serde(default)]
+    pub errors: Vec<ScanError>,
 }
 
-fn scan_npm_global() -> Vec<PackageInfo> {
-    let output = Command::new("npm")
+fn scan_npm_global() -> Result<Vec<PackageInfo>, ProcessError> {
+    let output = ProcessRunner::new("npm")
         .args(["list", "-g", "--depth=0", "--json"])
-        .output();
-
-    match output {
-        Ok(o) if o.status.success() => {
-            let stdout = String::from_utf8_lossy(&o.stdout);
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&stdout) {
-                if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
-                    return deps
-                        .iter()
-                        .map(|(name, info)| PackageInfo {
-                            name: name.clone(),
-                            version: info
-                                .get("version")
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("unknown")
-                                .to_string(),
-                        })
-                        .collect();
-                }
-            }
-            Vec::new()
+        .timeout(LIST_TIMEOUT)
+        .run_ok()?;
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(&output.stdout) {
+        if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
+            return Ok(deps
+                .iter()
+                .map(|(name, info)| PackageInfo {
+                    name: name.clone(),
+                    version: info
+                        .get("version")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                })
+                .collect());
         }
-        _ => Vec::new(),
     }
+    Ok(Vec::new())
 }
 
-fn scan_brew() -> Vec<PackageInfo> {
+fn scan_brew() -> Result<Vec<PackageInfo>, ProcessError> {
     // Homebrew exists on macOS and Linux, but not Windows
     if cfg!(target_os = "windows") {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
-    let output = Command::new("brew").args(["list", "--versions"]).output();
-
-    match output {
-        Ok(o) if o.status.success() => {
-            let stdout = String::from_utf8_lossy(&o.stdout);
-            stdout
-                .lines()
-                .filter_map(|line| {
-                    let parts: Vec<&str> = line.splitn(2, ' ').collect();
-                    if parts.len() == 2 {
-                        Some(PackageInfo {
-                            name: parts[0].to_string(),
-                            version: parts[1].trim().to_string(),
-                        })
-                    } else {
-                        None
-                    }
+    let output = ProcessRunner::new("brew")
+        .args(["list", "--versions"])
+        .timeout(LIST_TIMEOUT)
+        .run_ok()?;
+    Ok(output
+        .stdout
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.splitn(2, ' ').collect();
+            if parts.len() == 2 {
+                Some(PackageInfo {
+                    name: parts[0].to_string(),
+                    version: parts[1].trim().to_string(),
                 })
-                .collect()
-        }
-        _ => Vec::new(),
-    }
+            } else {
+                None
+            }
+        })
+        .collect())
 }
 
-fn scan_pip() -> Vec<PackageInfo> {
-    let output = Command::new("pip3")
+fn scan_pip() -> Result<Vec<PackageInfo>, ProcessError> {
+    let output = ProcessRunner::new("pip3")
         .args(["list", "--format=json"])
-        .output();
-
-    match output {
-        Ok(o) if o.status.success() => {
-            let stdout = String::from_utf8_lossy(&o.stdout);
-            if let Ok(packages) = serde_json::from_str::<Vec<serde_json::Value>>(&stdout) {
-                return packages
-                    .iter()
-                    .filter_map(|pkg| {
-                        let name = pkg.get("name")?.as_str()?.to_string();
-                        let version = pkg.get("version")?.as_str()?.to_string();
-                        Some(PackageInfo { name, version })
-                    })
-                    .collect();
-            }
-            Vec::new()
-        }
-        _ => Vec::new(),
+        .timeout(LIST_TIMEOUT)
+        .run_ok()?;
+    if let Ok(packages) = serde_json::from_str::<Vec<serde_json::Value>>(&output.stdout) {
+        return Ok(packages
+            .iter()
+            .filter_map(|pkg| {
+                let name = pkg.get("name")?.as_str()?.to_string();
+                let version = pkg.get("version")?.as_str()?.to_string();
+                Some(PackageInfo { name, version })
+            })
+            .collect());
     }
+    Ok(Vec::new())
 }
 
-fn scan_cargo() -> Vec<PackageInfo> {
-    let output = Command::new("cargo").args(["install", "--list"]).output();
-
-    match output {
-        Ok(o) if o.status.success() => {
-            let stdout = String::from_utf8_lossy(&o.stdout);
-            stdout
-                .lines()
-                .filter(|line| !line.starts_with(' '))
-                .filter_map(|line| {
-                    // Format: "package_name v0.1.0:"
-                    let parts: Vec<&str> = line.splitn(2, ' ').collect();
-                    if parts.len() == 2 {
-                        Some(PackageInfo {
-                            name: parts[0].to_string(),
-                            version: parts[1]
-                                .trim_end_matches(':')
-                                .trim_start_matches('v')
-                                .to_string(),
-                        })
-                    } else {
-                        None
-                    }
+fn scan_cargo() -> Result<Vec<PackageInfo>, ProcessError> {
+    let output = ProcessRunner::new("cargo")
+        .args(["install", "--list"])
+        .timeout(LIST_TIMEOUT)
+        .run_ok()?;
+    Ok(output
+        .stdout
+        .lines()
+        .filter(|line| !line.starts_with(' '))
+        .filter_map(|line| {
+            // Format: "package_name v0.1.0:"
+            let parts: Vec<&str> = line.splitn(2, ' ').collect();
+            if parts.len() == 2 {
+                Some(PackageInfo {
+                    name: parts[0].to_string(),
+                    version: parts[1]
+                        .trim_end_matches(':')
+                        .trim_start_matches('v')
+                        .to_string(),
                 })
-                .collect()
-        }
-        _ => Vec::new(),
-    }
+            } else {
+                None
+            }
+        })
+        .collect())
 }
 
-fn scan_scoop() -> Vec<PackageInfo> {
+fn scan_scoop() -> Result<Vec<PackageInfo>, ProcessError> {
     if !cfg!(target_os = "windows") {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
     // `scoop list` outputs a table; parse lines after the header separator
-    let output = Command::new("scoop").args(["list"]).output();
-
-    match output {
-        Ok(o) if o.status.success() => {
-            let stdout = String::from_utf8_lossy(&o.stdout);
-            let mut started = false;
-            stdout
-                .lines()
-                .filter_map(|line| {
-                    let trimmed = line.trim();
-                    if trimmed.starts_with("----") || trimmed.starts_with("Name") {
-                        started = true;
-                        return None;
-                    }
-                    if !started || trimmed.is_empty() {
-                        return None;
-                    }
-                    let parts: Vec<&str> = trimmed.split_whitespace().collect();
-                    if parts.len() >= 2 {
-                        Some(PackageInfo {
-                            name: parts[0].to_string(),
-                            version: parts[1].to_string(),
-                        })
-                    } else {
-                        None
-                    }
+    let output = ProcessRunner::new("scoop")
+        .args(["list"])
+        .timeout(LIST_TIMEOUT)
+        .run_ok()?;
+    let mut started = false;
+    Ok(output
+        .stdout
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            if trimmed.starts_with("----") || trimmed.starts_with("Name") {
+                started = true;
+                return None;
+            }
+            if !started || trimmed.is_empty() {
+                return None;
+            }
+            let parts: Vec<&str> = trimmed.split_whitespace().collect();
+            if parts.len() >= 2 {
+                Some(PackageInfo {
+                    name: parts[0].to_string(),
+                    version: parts[1].to_string(),
                 })
-                .collect()
-        }
-        _ => Vec::new(),
-    }
+            } else {
+                None
+            }
+        })
+        .collect())
 }
 
-fn scan_chocolatey() -> Vec<PackageInfo> {
+fn scan_chocolatey() -> Result<Vec<PackageInfo>, ProcessError> {
     if !cfg!(target_os = "windows") {
-        return Vec::new();
+        return Ok(Vec::new());
     }
 
-    let output = Command::new("choco")
+    let output = ProcessRunner::new("choco")
         .args(["list", "--local-only"])
-        .output();
-
-    match output {
-        Ok(o) if o.status.success() => {
-            let stdout = String::from_utf8_lossy(&o.stdout);
-            stdout
-                .lines()
-                .filter_map(|line| {
-                    let trimmed = line.trim();
-                    // Skip summary line like "42 packages installed."
-                    if trimmed.contains("packages installed") || trimmed.is_empty() {
-                        return None;
-                    }
-                    let parts: Vec<&str> = trimmed.splitn(2, ' ').collect();
-                    if parts.len() == 2 {
-                        Some(PackageInfo {
-                            name: parts[0].to_string(),
-                            version: parts[1].trim().to_string(),
-                        })
-                    } else {
-                        None
-                    }
+        .timeout(LIST_TIMEOUT)
+        .run_ok()?;
+    Ok(output
+        .stdout
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            // Skip summary line like "42 packages installed."
+            if trimmed.contains("packages installed") || trimmed.is_empty() {
+                return None;
+            }
+            let parts: Vec<&str> = trimmed.splitn(2, ' ').collect();
+            if parts.len() == 2 {
+                Some(PackageInfo {
+                    name: parts[0].to_string(),
+                    version: parts[1].trim().to_string(),
                 })
-                .collect()
-        }
-        _ => Vec::new(),
-    }
+            } else {
+                None
+            }
+        })
+        .collect())
 }
 
 pub fn scan() -> PackageList {
@@ -213,12 +194,32 @@ pub fn scan() -> PackageList {
     let scoop = std::thread::spawn(scan_scoop);
     let choco = std::thread::spawn(scan_chocolatey);
 
+    let mut errors = Vec::new();
+    let mut collect = |source: &str, handle: std::thread::JoinHandle<_>| -> Vec<PackageInfo> {
+        match handle.join() {
+            Ok(Ok(packages)) => packages,
+            Ok(Err(e)) => {
+                errors.extend(ScanError::reportable(source, &e));
+                Vec::new()
+            }
+            Err(_) => Vec::new(),
+        }
+    };
+
+    let npm_global = collect("npm", npm);
+    let brew = collect("brew", brew);
+    let pip = collect("pip", pip);
+    let cargo = collect("cargo", cargo);
+    let scoop = collect("scoop", scoop);
+    let chocolatey = collect("choco", choco);
+
     PackageList {
-        npm_global: npm.join().unwrap_or_default(),
-        brew: brew.join().unwrap_or_default(),
-        pip: pip.join().unwrap_or_default(),
-        cargo: cargo.join().unwrap_or_default(),
-        scoop: scoop.join().unwrap_or_default(),
-        chocolatey: choco.join().unwrap_or_default(),
+        npm_global,
+        brew,
+        pip,
+        cargo,
+        scoop,
+        chocolatey,
+        errors,
     }
 }
@@ -0,0 +1,604 @@
+//! Team baseline policy, evaluated as a diagnostics check group.
+//!
+//! A policy is a TOML file declaring required tools (optionally with a
+//! version range), required environment variables, banned global packages
+//! and PATH ordering rules. It's read from `~/.config/houston/policy.toml`
+//! and from a `houston.toml` at the root of any workspace or project.
+//!
+//! ```toml
+//! [tools.node]
+//! version = ">=20 <23"
+//! severity = "error"
+//!
+//! [tools.docker]
+//!
+//! [env]
+//! required = ["AWS_PROFILE"]
+//!
+//! [packages]
+//! banned = ["npm:left-pad", "pycrypto"]
+//!
+//! [path]
+//! order = [{ before = "/opt/homebrew/bin", after = "/usr/bin" }]
+//! ```
+
+use super::diagnostics::{DiagnosticItem, ProjectScope, Severity};
+use super::languages::LanguageInfo;
+use super::packages::{PackageInfo, PackageList};
+use super::path::PathEntry;
+use super::process::{self, ProcessRunner, QUICK_TIMEOUT};
+use super::version_req;
+use super::{languages, packages, path};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Repo- or workspace-level policy file name.
+pub const POLICY_FILE_NAME: &str = "houston.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub tools: BTreeMap<String, ToolRule>,
+    #[serde(default)]
+    pub env: EnvRule,
+    #[serde(default)]
+    pub packages: PackageRule,
+    #[serde(default)]
+    pub path: PathRule,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ToolRule {
+    /// npm-style range, e.g. ">=20 <23" or "^3.11"
+    pub version: Option<String>,
+    pub severity: Option<Severity>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnvRule {
+    #[serde(default)]
+    pub required: Vec<String>,
+    pub severity: Option<Severity>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackageRule {
+    /// `name` (any manager) or `manager:name`, e.g. "npm:left-pad"
+    #[serde(default)]
+    pub banned: Vec<String>,
+    pub severity: Option<Severity>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathRule {
+    #[serde(default)]
+    pub order: Vec<PathOrder>,
+    pub severity: Option<Severity>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathOrder {
+    pub before: String,
+    pub after: String,
+}
+
+/// A parsed policy and the file it came from.
+#[derive(Debug, Clone)]
+pub struct LoadedPolicy {
+    pub source: String,
+    pub policy: Policy,
+}
+
+pub fn user_policy_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".config").join("houston").join("policy.toml"))
+}
+
+/// Every policy file that applies: the user's own (`user_policy`), then
+/// `houston.toml` in each of `roots`. Files that fail to parse are reported
+/// as diagnostics.
+pub fn load(
+    user_policy: Option<&Path>,
+    roots: &[String],
+) -> (Vec<LoadedPolicy>, Vec<DiagnosticItem>) {
+    let mut candidates: Vec<PathBuf> = user_policy.map(Path::to_path_buf).into_iter().collect();
+    candidates.extend(roots.iter().map(|r| Path::new(r).join(POLICY_FILE_NAME)));
+
+    let mut seen = HashSet::new();
+    let mut policies = Vec::new();
+    let mut items = Vec::new();
+
+    for file in candidates {
+        if !file.is_file() || !seen.insert(file.clone()) {
+            continue;
+        }
+        let source = file.to_string_lossy().to_string();
+        let parsed = std::fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|content| toml::from_str::<Policy>(&content).map_err(|e| e.to_string()));
+
+        match parsed {
+            Ok(policy) => policies.push(LoadedPolicy { source, policy }),
+            Err(e) => items.push(DiagnosticItem {
+                id: format!("policy_invalid_{}", source),
+                category: "policy".to_string(),
+                severity: Severity::Error,
+                title: "Invalid policy file".to_string(),
+                description: format!("{} could not be read", source),
+                details: Some(e),
+                fix_id: None,
+                fix_label: None,
+            }),
+        }
+    }
+
+    (policies, items)
+}
+
+/// Load and evaluate all policies for `scope`. Scanners are only run for
+/// sections some policy actually uses, and only if the scope doesn't carry
+/// their results already.
+pub fn check(scope: &ProjectScope) -> Vec<DiagnosticItem> {
+    let (policies, mut items) = load(user_policy_path().as_deref(), &scope.roots);
+    if policies.is_empty() {
+        return items;
+    }
+
+    let needs = |f: fn(&Policy) -> bool| policies.iter().any(|p| f(&p.policy));
+    let facts = Facts {
+        languages: if needs(|p| !p.tools.is_empty()) {
+            scope.languages.clone().unwrap_or_else(languages::scan)
+        } else {
+            Vec::new()
+        },
+        path: if needs(|p| !p.path.order.is_empty()) {
+            path::scan()
+        } else {
+            Vec::new()
+        },
+        packages: if needs(|p| !p.packages.banned.is_empty()) {
+            Some(scope.packages.clone().unwrap_or_else(packages::scan))
+        } else {
+            None
+        },
+        home: dirs::home_dir().map(|h| h.to_string_lossy().to_string()),
+    };
+
+    items.extend(evaluate(&policies, &facts, &SystemProbe));
+    items
+}
+
+/// What the policy is checked against.
+pub struct Facts {
+    pub languages: Vec<LanguageInfo>,
+    pub path: Vec<PathEntry>,
+    pub packages: Option<PackageList>,
+    pub home: Option<String>,
+}
+
+/// Lookups that touch the live system, split out so tests can fake them.
+pub trait Probe {
+    /// Version of a tool that isn't one of the scanned languages:
+    /// `None` if not installed, `Some("")` if installed but unversioned.
+    fn tool_version(&self, binary: &str) -> Option<String>;
+    fn env_var(&self, name: &str) -> Option<String>;
+}
+
+struct SystemProbe;
+
+impl Probe for SystemProbe {
+    fn tool_version(&self, binary: &str) -> Option<String> {
+        if !is_bare_tool_name(binary) {
+            return None;
+        }
+        let resolved = process::which(binary)?;
+        let output = ProcessRunner::new(resolved)
+            .arg("--version")
+            .timeout(QUICK_TIMEOUT)
+            .run()
+            .ok();
+        let version = output
+            .map(|o| format!("{}\n{}", o.stdout, o.stderr))
+            .and_then(|text| {
+                text.split_whitespace()
+                    .find(|w| w.contains('.') && version_req::parse_version(w).is_some())
+                    .map(|w| w.trim_start_matches('v').to_string())
+            });
+        Some(version.unwrap_or_default())
+    }
+
+    fn env_var(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok()
+    }
+}
+
+pub fn evaluate(
+    policies: &[LoadedPolicy],
+    facts: &Facts,
+    probe: &dyn Probe,
+) -> Vec<DiagnosticItem> {
+    let mut items: Vec<DiagnosticItem> = Vec::new();
+    let mut seen = HashSet::new();
+
+    for loaded in policies {
+        let policy = &loaded.policy;
+        let source = match &policy.name {
+            Some(name) => format!("{} ({})", name, loaded.source),
+            None => loaded.source.clone(),
+        };
+        let mut push =
+            |id: String, severity: &Option<Severity>, title: String, description: String| {
+                // The first policy to flag something wins (user policy before repo ones)
+                if seen.insert(id.clone()) {
+                    items.push(DiagnosticItem {
+                        id,
+                        category: "policy".to_string(),
+                        severity: severity.clone().unwrap_or(Severity::Warning),
+                        title,
+                        description,
+                        details: Some(format!("Required by {}", source)),
+                        fix_id: None,
+                        fix_label: None,
+                    });
+                }
+            };
+
+        for (tool, rule) in &policy.tools {
+            if !is_bare_tool_name(tool) {
+                push(
+                    format!("policy_tool_name_{}", tool),
+                    &Some(Severity::Error),
+                    format!("Invalid tool name: {}", tool),
+                    "Tools are looked up on PATH by name; paths are not run".to_string(),
+                );
+                continue;
+            }
+            let installed = find_language(&facts.languages, tool)
+                .map(|l| l.version.clone())
+                .or_else(|| probe.tool_version(tool));
+
+            let Some(version) = installed else {
+                push(
+                    format!("policy_tool_missing_{}", tool),
+                    &rule.severity,
+                    format!("{} is not installed", tool),
+                    match &rule.version {
+                        Some(range) => format!("The team policy requires {} {}", tool, range),
+                        None => format!("The team policy requires {}", tool),
+                    },
+                );
+                continue;
+            };
+
+            if let Some(range) = &rule.version {
+                match version_req::satisfies(&version, range) {
+                    Ok(true) => {}
+                    Ok(false) => push(
+                        format!("policy_tool_version_{}", tool),
+                        &rule.severity,
+                        format!(
+                            "{} {} does not satisfy {}",
+                            tool,
+                            display_version(&version),
+                            range
+                        ),
+                        format!("The team policy requires {} {}", tool, range),
+                    ),
+                    Err(e) => push(
+                        format!("policy_tool_range_{}", tool),
+                        &Some(Severity::Error),
+                        format!("Invalid version range for {}", tool),
+                        e,
+                    ),
+                }
+            }
+        }
+
+        for var in &policy.env.required {
+            if probe.env_var(var).is_none_or(|v| v.is_empty()) {
+                push(
+                    format!("policy_env_{}", var),
+                    &policy.env.severity,
+                    format!("{} is not set", var),
+                    format!("The team policy requires the {} environment variable", var),
+                );
+            }
+        }
+
+        if let Some(list) = &facts.packages {
+            for banned in &policy.packages.banned {
+                for (manager, pkg) in find_banned(list, banned) {
+                    push(
+                        format!("policy_banned_{}_{}", manager, pkg.name),
+                        &policy.packages.severity,
+                        format!("Banned package installed: {}", pkg.name),
+                        format!(
+                            "{} {} is installed globally via {} but is banned by the team policy",
+                            pkg.name, pkg.version, manager
+                        ),
+                    );
+                }
+            }
+        }
+
+        for (i, rule) in policy.path.order.iter().enumerate() {
+            let position = |pattern: &str| {
+                let pattern = expand_home(pattern, facts.home.as_deref());
+                facts
+                    .path
+                    .iter()
+                    .find(|e| e.path.trim_end_matches('/') == pattern.trim_end_matches('/'))
+                    .map(|e| e.index)
+            };
+            if let (Some(before), Some(after)) = (position(&rule.before), position(&rule.after)) {
+                if after < before {
+                    push(
+                        format!("policy_path_order_{}_{}", i, rule.before),
+                        &policy.path.severity,
+                        "PATH order violates team policy".to_string(),
+                        format!("{} must come before {} in PATH", rule.before, rule.after),
+                    );
+                }
+            }
+        }
+    }
+
+    items
+}
+
+/// A command name to look up on PATH, like "node" or "docker-compose".
+/// Policies come from any repository in a workspace, so anything that could
+/// point at a file (`./scripts/x`, `/tmp/x`, `..\x`) is refused.
+fn is_bare_tool_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(['.', '-'])
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '+'))
+}
+
+fn find_language<'a>(langs: &'a [LanguageInfo], tool: &str) -> Option<&'a LanguageInfo> {
+    let tool = tool.to_lowercase();
+    let alias = match tool.as_str() {
+        "node" | "nodejs" => "node.js",
+        "python3" => "python",
+        "golang" => "go",
+        "rustc" | "cargo" => "rust",
+        other => other,
+    };
+    langs
+        .iter()
+        .filter(|l| l.installed)
+        .find(|l| l.name.to_lowercase() == alias)
+}
+
+fn find_banned<'a>(list: &'a PackageList, banned: &str) -> Vec<(&'static str, &'a PackageInfo)> {
+    let (manager, name) = match banned.split_once(':') {
+        Some((m, n)) => (Some(m), n),
+        None => (None, banned),
+    };
+    let sources: [(&'static str, &[&str], &Vec<PackageInfo>); 6] = [
+        ("npm", &["npm"], &list.npm_global),
+        ("brew", &["brew", "homebrew"], &list.brew),
+        ("pip", &["pip", "pip3"], &list.pip),
+        ("cargo", &["cargo"], &list.cargo),
+        ("scoop", &["scoop"], &list.scoop),
+        ("choco", &["choco", "chocolatey"], &list.chocolatey),
+    ];
+    sources
+        .into_iter()
+        .filter(|(_, aliases, _)| manager.is_none_or(|m| aliases.contains(&m)))
+        .flat_map(|(source, _, pkgs)| {
+            pkgs.iter()
+                .filter(|p| p.name.eq_ignore_ascii_case(name))
+                .map(move |p| (source, p))
+        })
+        .collect()
+}
+
+fn expand_home(pattern: &str, home: Option<&str>) -> String {
+    match (pattern.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) => format!("{}{}", home, rest),
+        _ => pattern.to_string(),
+    }
+}
+
+fn display_version(version: &str) -> &str {
+    if version.is_empty() {
+        "(unknown version)"
+    } else {
+        version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct FakeProbe {
+        tools: HashMap<&'static str, &'static str>,
+        env: HashMap<&'static str, &'static str>,
+    }
+
+    impl Probe for FakeProbe {
+        fn tool_version(&self, binary: &str) -> Option<String> {
+            self.tools.get(binary).map(|v| v.to_string())
+        }
+        fn env_var(&self, name: &str) -> Option<String> {
+            self.env.get(name).map(|v| v.to_string())
+        }
+    }
+
+    fn lang(name: &str, version: &str) -> LanguageInfo {
+        LanguageInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+            binary_path: String::new(),
+            manager: String::new(),
+            installed: true,
+            icon: String::new(),
+            error: None,
+        }
+    }
+
+    fn path_entry(path: &str, index: usize) -> PathEntry {
+        PathEntry {
+            path: path.to_string(),
+            exists: true,
+            is_duplicate: false,
+            index,
+            category: "Other".to_string(),
+        }
+    }
+
+    fn loaded(toml: &str) -> Vec<LoadedPolicy> {
+        vec![LoadedPolicy {
+            source: "/tmp/houston.toml".to_string(),
+            policy: toml::from_str(toml).unwrap(),
+        }]
+    }
+
+    #[test]
+    fn test_tools_env_packages_and_path() {
+        let policies = loaded(
+            r#"
+            name = "Acme"
+
+            [tools.node]
+            version = ">=20 <23"
+            severity = "error"
+
+            [tools.docker]
+
+            [tools.terraform]
+            version = "^1.6"
+
+            [env]
+            required = ["AWS_PROFILE", "GOPATH"]
+
+            [packages]
+            banned = ["npm:left-pad", "pycrypto"]
+            severity = "info"
+
+            [path]
+            order = [{ before = "~/.cargo/bin", after = "/usr/bin" }]
+            "#,
+        );
+        let mut list = PackageList::default();
+        list.npm_global.push(PackageInfo {
+            name: "left-pad".to_string(),
+            version: "1.3.0".to_string(),
+        });
+        list.pip.push(PackageInfo {
+            name: "pycrypto".to_string(),
+            version: "2.6.1".to_string(),
+        });
+        let facts = Facts {
+            languages: vec![lang("Node.js", "18.19.0")],
+            path: vec![
+                path_entry("/usr/bin", 0),
+                path_entry("/home/dev/.cargo/bin", 1),
+            ],
+            packages: Some(list),
+            home: Some("/home/dev".to_string()),
+        };
+        let probe = FakeProbe {
+            tools: HashMap::from([("terraform", "1.7.2")]),
+            env: HashMap::from([("GOPATH", "/home/dev/go")]),
+        };
+
+        let items = evaluate(&policies, &facts, &probe);
+        let ids: Vec<&str> = items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "policy_tool_missing_docker",
+                "policy_tool_version_node",
+                "policy_env_AWS_PROFILE",
+                "policy_banned_npm_left-pad",
+                "policy_banned_pip_pycrypto",
+                "policy_path_order_0_~/.cargo/bin",
+            ]
+        );
+        assert!(items.iter().all(|i| i.category == "policy"));
+        let node = items
+            .iter()
+            .find(|i| i.id == "policy_tool_version_node")
+            .unwrap();
+        assert!(matches!(node.severity, Severity::Error));
+        assert_eq!(
+            node.details.as_deref(),
+            Some("Required by Acme (/tmp/houston.toml)")
+        );
+        let banned = items
+            .iter()
+            .find(|i| i.id.starts_with("policy_banned"))
+            .unwrap();
+        assert!(matches!(banned.severity, Severity::Info));
+    }
+
+    /// Fails the test if anything is looked up on the system.
+    struct NoProbe;
+
+    impl Probe for NoProbe {
+        fn tool_version(&self, binary: &str) -> Option<String> {
+            panic!("probed {}", binary);
+        }
+        fn env_var(&self, _name: &str) -> Option<String> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_path_like_tool_names_are_not_run() {
+        let policies = loaded(
+            r#"
+            [tools."./scripts/evil"]
+            [tools."/tmp/evil"]
+            [tools.'..\evil']
+            [tools."-rf"]
+            "#,
+        );
+        let facts = Facts {
+            languages: Vec::new(),
+            path: Vec::new(),
+            packages: None,
+            home: None,
+        };
+        let items = evaluate(&policies, &facts, &NoProbe);
+        assert_eq!(items.len(), 4);
+        assert!(items.iter().all(|i| i.id.starts_with("policy_tool_name_")));
+        assert!(SystemProbe.tool_version("./scripts/evil").is_none());
+        assert!(is_bare_tool_name("docker-compose"));
+        assert!(is_bare_tool_name("python3.12"));
+    }
+
+    #[test]
+    fn test_invalid_policy_file_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let user_policy = dir.path().join("policy.toml");
+        std::fs::write(&user_policy, "[env]\nrequired = [\"HOME\"]\n").unwrap();
+        let repo = dir.path().join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        std::fs::write(
+            repo.join(POLICY_FILE_NAME),
+            "[tools.node]\nversoin = \"20\"\n",
+        )
+        .unwrap();
+        let root = repo.to_string_lossy().to_string();
+
+        let (policies, items) = load(Some(&user_policy), std::slice::from_ref(&root));
+        assert_eq!(policies.len(), 1);
+        assert_eq!(policies[0].source, user_policy.to_string_lossy());
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "Invalid policy file");
+    }
+}
@@ -0,0 +1,701 @@
+//! Port map: who owns which port.
+//!
+//! Merges live listeners (dev servers and published container ports) with
+//! the ports projects declare in their own config (vite config, `.env`
+//! `PORT`, `--port` flags in dev scripts, compose files), so a collision
+//! shows up before the second server fails to bind. Dev servers still
+//! listening from a deleted directory are reported as zombies.
+
+use super::compose;
+use super::dev_commands::DEV_SCRIPTS;
+use super::dev_servers::{DevServer, DevServerReport};
+use super::diagnostics::{DiagnosticItem, Severity};
+use super::docker::DockerStatus;
+use super::workspace::ProjectInfo;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+const VITE_CONFIGS: &[&str] = &[
+    "vite.config.ts",
+    "vite.config.js",
+    "vite.config.mts",
+    "vite.config.mjs",
+];
+
+/// Env files read by the common dev tooling, in load order
+const ENV_FILES: &[&str] = &[
+    ".env",
+    ".env.local",
+    ".env.development",
+    ".env.development.local",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortOwnerKind {
+    DevServer,
+    Container,
+    /// Any other listening process (only from a direct port lookup)
+    Process,
+    /// Configured in a project but not necessarily bound
+    Declared,
+}
+
+impl PortOwnerKind {
+    fn is_live(self) -> bool {
+        self != PortOwnerKind::Declared
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortOwner {
+    pub port: u16,
+    pub kind: PortOwnerKind,
+    /// Project, container or process name
+    pub name: String,
+    pub project_path: Option<String>,
+    pub pid: Option<u32>,
+    pub container_id: Option<String>,
+    /// Where a declared port comes from (e.g. "vite.config.ts"), or a live
+    /// listener's working directory
+    pub source: Option<String>,
+}
+
+impl PortOwner {
+    fn label(&self) -> String {
+        match (self.kind, self.pid) {
+            (PortOwnerKind::Container, _) => format!("container {}", self.name),
+            (PortOwnerKind::Declared, _) => format!(
+                "{} ({})",
+                self.name,
+                self.source.as_deref().unwrap_or("config")
+            ),
+            (_, Some(pid)) => format!("{} (PID {})", self.name, pid),
+            (_, None) => self.name.clone(),
+        }
+    }
+
+    /// Same listener, reported twice (e.g. a process and a dev server)
+    fn same_listener(&self, other: &PortOwner) -> bool {
+        (self.pid.is_some() && self.pid == other.pid)
+            || (self.container_id.is_some() && self.container_id == other.container_id)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortConflict {
+    pub port: u16,
+    pub owners: Vec<PortOwner>,
+    /// Whether two things are actually competing for the port right now, as
+    /// opposed to projects that would collide once both are started
+    pub live: bool,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortMap {
+    /// Every owner, sorted by port
+    pub owners: Vec<PortOwner>,
+    pub conflicts: Vec<PortConflict>,
+    /// Dev servers whose working directory no longer exists
+    pub zombies: Vec<PortOwner>,
+    pub scanned_at: String,
+}
+
+impl PortMap {
+    pub fn owners_of(&self, port: u16) -> Vec<PortOwner> {
+        self.owners
+            .iter()
+            .filter(|o| o.port == port)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Build the map from scans that already ran.
+pub fn build(projects: &[ProjectInfo], dev: &DevServerReport, docker: &DockerStatus) -> PortMap {
+    let mut owners = Vec::new();
+    let mut zombies = Vec::new();
+
+    for server in dev.servers.iter().filter(|s| s.port > 0) {
+        let owner = dev_server_owner(server, projects);
+        if is_zombie(server) {
+            zombies.push(owner.clone());
+        }
+        owners.push(owner);
+    }
+
+    let compose_paths: HashMap<&str, &str> = docker
+        .compose_projects
+        .iter()
+        .filter_map(|p| Some((p.name.as_str(), p.project_path.as_deref()?)))
+        .collect();
+    for container in docker.containers.iter().filter(|c| c.status == "running") {
+        let project_path = container
+            .compose_project
+            .as_deref()
+            .and_then(|name| compose_paths.get(name))
+            .map(|p| p.to_string());
+        let mut published: Vec<u16> = container.ports.iter().filter_map(|p| p.host_port).collect();
+        // IPv4 and IPv6 bindings of the same port
+        published.sort_unstable();
+        published.dedup();
+        for port in published {
+            owners.push(PortOwner {
+                port,
+                kind: PortOwnerKind::Container,
+                name: container.name.clone(),
+                project_path: project_path.clone(),
+                pid: None,
+                container_id: Some(container.id.clone()),
+                source: None,
+            });
+        }
+    }
+
+    for project in projects {
+        owners.extend(declared_ports(project));
+    }
+
+    owners.sort_by(|a, b| {
+        a.port
+            .cmp(&b.port)
+            .then(b.kind.is_live().cmp(&a.kind.is_live()))
+    });
+    let conflicts = find_conflicts(&owners);
+    PortMap {
+        owners,
+        conflicts,
+        zombies,
+        scanned_at: chrono::Local::now().to_rfc3339(),
+    }
+}
+
+fn dev_server_owner(server: &DevServer, projects: &[ProjectInfo]) -> PortOwner {
+    let project_name = server
+        .project_path
+        .as_deref()
+        .and_then(|path| projects.iter().find(|p| p.path == path))
+        .map(|p| p.name.clone());
+    PortOwner {
+        port: server.port,
+        kind: PortOwnerKind::DevServer,
+        name: project_name.unwrap_or_else(|| server.process_name.clone()),
+        project_path: server.project_path.clone(),
+        pid: Some(server.pid),
+        container_id: None,
+        source: server.cwd.clone(),
+    }
+}
+
+/// Still listening, but from a directory that has been deleted.
+fn is_zombie(server: &DevServer) -> bool {
+    server.cwd.as_deref().is_some_and(|cwd| {
+        // Linux marks a removed cwd in the /proc link itself
+        cwd.ends_with(" (deleted)") || !Path::new(cwd).exists()
+    })
+}
+
+fn find_conflicts(owners: &[PortOwner]) -> Vec<PortConflict> {
+    let mut by_port: BTreeMap<u16, Vec<&PortOwner>> = BTreeMap::new();
+    for owner in owners {
+        by_port.entry(owner.port).or_default().push(owner);
+    }
+
+    let mut conflicts = Vec::new();
+    for (port, group) in by_port {
+        let mut live: Vec<&PortOwner> = Vec::new();
+        for owner in group.iter().filter(|o| o.kind.is_live()) {
+            if !live.iter().any(|l| l.same_listener(owner)) {
+                live.push(owner);
+            }
+        }
+        let declared: Vec<&PortOwner> = group
+            .iter()
+            .copied()
+            .filter(|o| !o.kind.is_live())
+            .collect();
+
+        let (live_conflict, reason) = if live.len() > 1 {
+            let names: Vec<String> = live.iter().map(|o| o.label()).collect();
+            (true, format!("Bound by {}", names.join(" and ")))
+        } else if let Some(holder) = live.first() {
+            // A project expecting the port while something else holds it
+            let Some(waiting) = declared
+                .iter()
+                .find(|d| holder.project_path.is_none() || d.project_path != holder.project_path)
+            else {
+                continue;
+            };
+            (
+                true,
+                format!(
+                    "{} expects port {}, but {} is using it",
+                    waiting.label(),
+                    port,
+                    holder.label()
+                ),
+            )
+        } else {
+            let mut projects: Vec<&PortOwner> = Vec::new();
+            for owner in &declared {
+                if !projects
+                    .iter()
+                    .any(|p| p.project_path == owner.project_path)
+                {
+                    projects.push(owner);
+                }
+            }
+            if projects.len() < 2 {
+                continue;
+            }
+            let names: Vec<String> = projects.iter().map(|o| o.label()).collect();
+            (
+                false,
+                format!("{} are all set to use port {}", names.join(", "), port),
+            )
+        };
+
+        conflicts.push(PortConflict {
+            port,
+            owners: group.into_iter().cloned().collect(),
+            live: live_conflict,
+            reason,
+        });
+    }
+    conflicts
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Ports declared in project config
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Ports a project is configured to listen on, one owner per port.
+pub fn declared_ports(project: &ProjectInfo) -> Vec<PortOwner> {
+    let dir = Path::new(&project.path);
+    let mut found: Vec<(u16, String)> = Vec::new();
+
+    for file in VITE_CONFIGS {
+        if let Ok(content) = fs::read_to_string(dir.join(file)) {
+            found.extend(
+                vite_ports(&content)
+                    .into_iter()
+                    .map(|p| (p, file.to_string())),
+            );
+        }
+    }
+
+    for file in ENV_FILES {
+        let env = compose::read_dotenv(&dir.join(file));
+        if let Some(port) = env.get("PORT").and_then(|p| p.parse().ok()) {
+            found.push((port, file.to_string()));
+        }
+    }
+
+    if let Ok(content) = fs::read_to_string(dir.join("package.json")) {
+        found.extend(
+            script_ports(&content)
+                .into_iter()
+                .map(|(port, script)| (port, format!("package.json \"{}\"", script))),
+        );
+    }
+
+    if let Some(file) = &project.compose {
+        let name = Path::new(&file.path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "compose".to_string());
+        for service in &file.services {
+            found.extend(
+                service
+                    .ports
+                    .iter()
+                    .map(|p| (p.published, format!("{} {}", name, service.name))),
+            );
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    found
+        .into_iter()
+        .filter(|(port, _)| *port > 0 && seen.insert(*port))
+        .map(|(port, source)| PortOwner {
+            port,
+            kind: PortOwnerKind::Declared,
+            name: project.name.clone(),
+            project_path: Some(project.path.clone()),
+            pid: None,
+            container_id: None,
+            source: Some(source),
+        })
+        .collect()
+}
+
+/// `port: 5173` entries in a vite config (`server.port`, `preview.port`).
+fn vite_ports(content: &str) -> Vec<u16> {
+    let mut ports = Vec::new();
+    let mut rest = content;
+    while let Some(i) = rest.find("port") {
+        let before = rest[..i].chars().next_back();
+        let after = rest[i + 4..].trim_start();
+        rest = &rest[i + 4..];
+        // Whole identifier only: not `strictPort`, `clientPort`, `portal`
+        if before.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.') {
+            continue;
+        }
+        let Some(value) = after.strip_prefix(':') else {
+            continue;
+        };
+        let digits: String = value
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if let Ok(port) = digits.parse() {
+            ports.push(port);
+        }
+    }
+    ports
+}
+
+/// `--port 3001`, `--port=3001` and `-p 3001` in the usual dev scripts.
+fn script_ports(package_json: &str) -> Vec<(u16, String)> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(package_json) else {
+        return Vec::new();
+    };
+    let Some(scripts) = json.get("scripts").and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+    DEV_SCRIPTS
+        .iter()
+        .filter_map(|name| {
+            let script = scripts.get(*name)?.as_str()?;
+            let mut words = script.split_whitespace();
+            while let Some(word) = words.next() {
+                let value = match word {
+                    "--port" | "-p" => words.next(),
+                    _ => word.strip_prefix("--port="),
+                };
+                if let Some(port) = value.and_then(|v| v.parse().ok()) {
+                    return Some((port, name.to_string()));
+                }
+            }
+            None
+        })
+        .collect()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Direct lookup
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Everything on `port`: the map's owners plus any other process listening
+/// there (a native Postgres, a stray `python -m http.server`, ...), which
+/// the dev server scan filters out.
+pub fn owners_of(map: &PortMap, port: u16) -> Vec<PortOwner> {
+    let mut owners = map.owners_of(port);
+    for (pid, name, cwd) in listeners_on(port) {
+        let process = PortOwner {
+            port,
+            kind: PortOwnerKind::Process,
+            name,
+            project_path: None,
+            pid: Some(pid),
+            container_id: None,
+            source: cwd,
+        };
+        if !owners.iter().any(|o| o.same_listener(&process)) {
+            owners.push(process);
+        }
+    }
+    owners
+}
+
+/// (pid, name, cwd) of processes listening on a TCP port.
+fn listeners_on(port: u16) -> Vec<(u32, String, Option<String>)> {
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(listeners) = super::procfs::listeners() {
+            return listeners
+                .into_iter()
+                .filter(|l| l.ports.contains(&port))
+                .map(|l| (l.pid, l.name, l.cwd))
+                .collect();
+        }
+    }
+    #[cfg(unix)]
+    {
+        use super::process::{ProcessRunner, QUICK_TIMEOUT};
+        // Exits non-zero when nothing matches
+        let Ok(output) = ProcessRunner::new("lsof")
+            .args(["-nP", "-sTCP:LISTEN", "-Fpc"])
+            .arg(format!("-iTCP:{}", port))
+            .timeout(QUICK_TIMEOUT)
+            .run()
+        else {
+            return Vec::new();
+        };
+        parse_lsof_processes(&output.stdout)
+    }
+    #[cfg(windows)]
+    {
+        let _ = port;
+        Vec::new()
+    }
+}
+
+/// `p<pid>` / `c<command>` records from `lsof -Fpc`.
+#[cfg(unix)]
+fn parse_lsof_processes(output: &str) -> Vec<(u32, String, Option<String>)> {
+    let mut found: Vec<(u32, String, Option<String>)> = Vec::new();
+    for line in output.lines() {
+        if let Some(pid) = line.strip_prefix('p').and_then(|p| p.parse().ok()) {
+            found.push((pid, String::new(), None));
+        } else if let (Some(name), Some(last)) = (line.strip_prefix('c'), found.last_mut()) {
+            last.1 = name.to_string();
+        }
+    }
+    found
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Diagnostics
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Live dev servers and containers, then the map. For callers without
+/// cached scans (the diagnostics scan, the CLI).
+pub fn scan(workspaces: &[String], projects: &[ProjectInfo]) -> PortMap {
+    let project_paths: Vec<String> = projects.iter().map(|p| p.path.clone()).collect();
+    let dev = std::thread::spawn(move || super::dev_servers::scan(&project_paths));
+    let mut docker = super::docker::scan();
+    compose::link_projects(&mut docker.compose_projects, workspaces, projects);
+    let dev = dev.join().unwrap_or_else(|_| DevServerReport {
+        servers: Vec::new(),
+        scanned_at: String::new(),
+        errors: Vec::new(),
+    });
+    build(projects, &dev, &docker)
+}
+
+pub fn diagnostics(map: &PortMap) -> Vec<DiagnosticItem> {
+    let mut items = Vec::new();
+    for conflict in &map.conflicts {
+        let details = conflict
+            .owners
+            .iter()
+            .map(|o| o.label())
+            .collect::<Vec<_>>()
+            .join("\n");
+        items.push(DiagnosticItem {
+            id: format!("port_conflict_{}", conflict.port),
+            category: "ports".to_string(),
+            severity: if conflict.live {
+                Severity::Warning
+            } else {
+                Severity::Info
+            },
+            title: format!("Port {} conflict", conflict.port),
+            description: conflict.reason.clone(),
+            details: Some(details),
+            fix_id: None,
+            fix_label: None,
+        });
+    }
+    for zombie in &map.zombies {
+        let Some(pid) = zombie.pid else {
+            continue;
+        };
+        let cwd = zombie
+            .source
+            .as_deref()
+            .unwrap_or("")
+            .trim_end_matches(" (deleted)");
+        items.push(DiagnosticItem {
+            id: format!("zombie_listener_{}_{}", pid, zombie.port),
+            category: "ports".to_string(),
+            severity: Severity::Warning,
+            title: format!("{} is still serving port {}", zombie.label(), zombie.port),
+            description: format!(
+                "Its working directory {} no longer exists, so this is probably a leftover dev server.",
+                cwd
+            ),
+            details: Some(cwd.to_string()),
+            fix_id: Some(format!("kill_process:{}", pid)),
+            fix_label: Some("Stop process".to_string()),
+        });
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanners::compose::{ComposeFile, ComposePort, ComposeService};
+    use crate::scanners::docker::{ComposeProject, ContainerInfo};
+
+    fn dev_server(pid: u32, port: u16, project: Option<&str>, cwd: &str) -> DevServer {
+        DevServer {
+            pid,
+            port,
+            process_name: "node".to_string(),
+            framework: None,
+            project_path: project.map(str::to_string),
+            uptime_secs: None,
+            cwd: Some(cwd.to_string()),
+            managed_id: None,
+        }
+    }
+
+    fn docker_with(containers: Vec<ContainerInfo>, compose: Vec<ComposeProject>) -> DockerStatus {
+        DockerStatus {
+            available: true,
+            version: None,
+            containers,
+            compose_projects: compose,
+            total_running: 0,
+            total_stopped: 0,
+            scanned_at: String::new(),
+            errors: Vec::new(),
+            endpoint: None,
+            engine: None,
+        }
+    }
+
+    fn container(id: &str, name: &str, port: u16, compose_project: Option<&str>) -> ContainerInfo {
+        let mut value = serde_json::json!({
+            "id": id, "name": name, "image": "img", "status": "running",
+            "state_detail": "Up", "created": "", "cpu_percent": 0.0,
+            "memory_bytes": 0, "memory_limit": 0, "service_name": "",
+            "category": "", "icon": "", "compose_project": compose_project,
+            "compose_service": null,
+        });
+        value["ports"] = serde_json::json!([
+            { "host_port": port, "container_port": port, "protocol": "tcp", "host_ip": "0.0.0.0" },
+            { "host_port": port, "container_port": port, "protocol": "tcp", "host_ip": "::" },
+        ]);
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_declared_ports() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("vite.config.ts"),
+            "export default defineConfig({\n  server: { port: 5180, strictPort: true },\n  preview: { port:4180 },\n})\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join(".env"), "PORT=3005\nDB_PORT=5432\n").unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{ "scripts": { "dev": "next dev -p 3001", "start": "next start --port=3002", "build": "tsc -p 3" } }"#,
+        )
+        .unwrap();
+        let project = ProjectInfo {
+            name: "web".to_string(),
+            path: dir.path().to_string_lossy().to_string(),
+            compose: Some(ComposeFile {
+                path: dir
+                    .path()
+                    .join("compose.yaml")
+                    .to_string_lossy()
+                    .to_string(),
+                project_name: "web".to_string(),
+                services: vec![ComposeService {
+                    name: "db".to_string(),
+                    image: Some("postgres".to_string()),
+                    build: false,
+                    container_name: None,
+                    ports: vec![ComposePort {
+                        host_ip: None,
+                        published: 5433,
+                        target: 5432,
+                        protocol: "tcp".to_string(),
+                    }],
+                }],
+            }),
+            ..Default::default()
+        };
+
+        let ports: Vec<(u16, String)> = declared_ports(&project)
+            .into_iter()
+            .map(|o| (o.port, o.source.unwrap()))
+            .collect();
+        assert_eq!(
+            ports,
+            vec![
+                (5180, "vite.config.ts".to_string()),
+                (4180, "vite.config.ts".to_string()),
+                (3005, ".env".to_string()),
+                (3001, "package.json \"dev\"".to_string()),
+                (3002, "package.json \"start\"".to_string()),
+                (5433, "compose.yaml db".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conflicts_and_zombies() {
+        let live_dir = tempfile::tempdir().unwrap();
+        let other_dir = tempfile::tempdir().unwrap();
+        let live = live_dir.path().to_string_lossy().to_string();
+        let other = other_dir.path().to_string_lossy().to_string();
+        fs::write(other_dir.path().join(".env"), "PORT=3000\n").unwrap();
+        fs::write(live_dir.path().join(".env"), "PORT=3000\n").unwrap();
+        let projects = vec![
+            ProjectInfo {
+                name: "live".to_string(),
+                path: live.clone(),
+                ..Default::default()
+            },
+            ProjectInfo {
+                name: "other".to_string(),
+                path: other.clone(),
+                ..Default::default()
+            },
+        ];
+        let dev = DevServerReport {
+            servers: vec![
+                dev_server(10, 3000, Some(&live), &live),
+                dev_server(11, 8080, None, "/gone/project (deleted)"),
+            ],
+            scanned_at: String::new(),
+            errors: Vec::new(),
+        };
+        let docker = docker_with(
+            vec![container("c1", "api-1", 8080, Some("api"))],
+            vec![ComposeProject {
+                name: "api".to_string(),
+                container_count: 1,
+                running_count: 1,
+                containers: vec!["c1".to_string()],
+                working_dir: None,
+                config_files: Vec::new(),
+                services: Vec::new(),
+                project_path: None,
+                project_name: None,
+            }],
+        );
+
+        let map = build(&projects, &dev, &docker);
+        assert_eq!(map.owners_of(3000).len(), 3);
+
+        let by_port: HashMap<u16, &PortConflict> =
+            map.conflicts.iter().map(|c| (c.port, c)).collect();
+        // `other` wants 3000 while `live` runs there; `live` declaring its own port is fine
+        assert!(by_port[&3000].live);
+        assert!(by_port[&3000]
+            .reason
+            .starts_with("other (.env) expects port 3000"));
+        // The container is published once despite the IPv4 and IPv6 bindings
+        assert!(by_port[&8080].reason.contains("container api-1"));
+        assert_eq!(map.conflicts.len(), 2);
+
+        assert_eq!(map.zombies.len(), 1);
+        assert_eq!(map.zombies[0].pid, Some(11));
+        let items = diagnostics(&map);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[2].fix_id.as_deref(), Some("kill_process:11"));
+    }
+}
@@ -0,0 +1,393 @@
+//! Shared runner for external commands.
+//!
+//! `Command::output()` blocks until the child exits, so one hung `brew` or a
+//! shell prompting for input used to freeze a scan thread forever. Everything
+//! the scanners spawn goes through [`ProcessRunner`] instead, which:
+//!
+//! - closes stdin so nothing can wait on input,
+//! - enforces a timeout and kills the whole process group when it expires
+//!   (package managers like to spawn helpers that would otherwise linger),
+//! - always captures stderr, and
+//! - reports failures as a structured [`ProcessError`].
+
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Used when a call site doesn't pick its own timeout.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+/// `--version` style probes should answer almost immediately.
+pub const QUICK_TIMEOUT: Duration = Duration::from_secs(5);
+/// Commands that may hit the network (outdated checks, `brew doctor`).
+pub const NETWORK_TIMEOUT: Duration = Duration::from_secs(60);
+/// User-triggered upgrades (`brew upgrade`, `npm update -g`) can legitimately
+/// take minutes, but should still not hang forever.
+pub const INSTALL_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// How long to keep draining output pipes after the child is gone. A
+/// daemonized grandchild can hold them open indefinitely.
+const PIPE_GRACE: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ProcessError {
+    #[error("`{program}` is not installed or not on PATH")]
+    NotFound { program: String },
+    #[error("failed to start `{program}`: {message}")]
+    Spawn { program: String, message: String },
+    #[error("`{program}` timed out after {secs}s", secs = .timeout.as_secs())]
+    TimedOut {
+        program: String,
+        timeout: Duration,
+        stderr: String,
+    },
+    #[error("`{program}` exited with {status}: {stderr}", status = exit_label(*.code))]
+    Exit {
+        program: String,
+        code: Option<i32>,
+        stderr: String,
+    },
+}
+
+fn exit_label(code: Option<i32>) -> String {
+    match code {
+        Some(c) => format!("code {}", c),
+        None => "a signal".to_string(),
+    }
+}
+
+impl ProcessError {
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ProcessError::NotFound { .. })
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, ProcessError::TimedOut { .. })
+    }
+
+    /// Stable identifier for the frontend: `not_found`, `spawn`, `timed_out`, `exit`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ProcessError::NotFound { .. } => "not_found",
+            ProcessError::Spawn { .. } => "spawn",
+            ProcessError::TimedOut { .. } => "timed_out",
+            ProcessError::Exit { .. } => "exit",
+        }
+    }
+}
+
+/// A failed external command, recorded on a scanner's result so the UI can
+/// show "brew timed out" instead of an empty list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanError {
+    /// What was being scanned, e.g. "brew" or "npm outdated".
+    pub source: String,
+    /// One of [`ProcessError::kind`], or `api`/`connection` for Docker Engine API calls.
+    pub kind: String,
+    pub message: String,
+}
+
+impl ScanError {
+    pub fn new(source: &str, err: &ProcessError) -> Self {
+        Self {
+            source: source.to_string(),
+            kind: err.kind().to_string(),
+            message: err.to_string(),
+        }
+    }
+
+    /// Like [`ScanError::new`], but a missing tool isn't an error worth
+    /// reporting: most machines don't have scoop, choco and brew all at once.
+    pub fn reportable(source: &str, err: &ProcessError) -> Option<Self> {
+        if err.is_not_found() {
+            None
+        } else {
+            Some(Self::new(source, err))
+        }
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        self.kind == "timed_out"
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProcessOutput {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ProcessOutput {
+    pub fn success(&self) -> bool {
+        self.status.success()
+    }
+}
+
+/// Builder around `std::process::Command` with a timeout.
+pub struct ProcessRunner {
+    command: Command,
+    program: String,
+    timeout: Duration,
+}
+
+impl ProcessRunner {
+    pub fn new(program: impl AsRef<OsStr>) -> Self {
+        let program = program.as_ref();
+        Self {
+            command: Command::new(program),
+            program: program.to_string_lossy().to_string(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.command.arg(arg);
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.command.args(args);
+        self
+    }
+
+    pub fn current_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.command.current_dir(dir);
+        self
+    }
+
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.command.env(key, value);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run to completion. A non-zero exit is *not* an error here since many
+    /// tools (`npm outdated`, `brew doctor`) use it to report findings.
+    pub fn run(mut self) -> Result<ProcessOutput, ProcessError> {
+        self.command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        // Own process group, so a timeout can take down helpers too
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            self.command.process_group(0);
+        }
+
+        let mut child = self.command.spawn().map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                ProcessError::NotFound {
+                    program: self.program.clone(),
+                }
+            } else {
+                ProcessError::Spawn {
+                    program: self.program.clone(),
+                    message: e.to_string(),
+                }
+            }
+        })?;
+
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        let started = Instant::now();
+        let mut poll = Duration::from_millis(5);
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Ok(None) if started.elapsed() >= self.timeout => break None,
+                Ok(None) => {
+                    std::thread::sleep(poll);
+                    poll = (poll * 2).min(Duration::from_millis(50));
+                }
+                Err(e) => {
+                    kill_tree(&mut child);
+                    return Err(ProcessError::Spawn {
+                        program: self.program,
+                        message: e.to_string(),
+                    });
+                }
+            }
+        };
+
+        let Some(status) = status else {
+            kill_tree(&mut child);
+            let _ = child.wait();
+            return Err(ProcessError::TimedOut {
+                program: self.program,
+                timeout: self.timeout,
+                stderr: collect(&stderr.0, stderr.1),
+            });
+        };
+
+        Ok(ProcessOutput {
+            status,
+            stdout: collect(&stdout.0, stdout.1),
+            stderr: collect(&stderr.0, stderr.1),
+        })
+    }
+
+    /// Run and treat a non-zero exit as [`ProcessError::Exit`].
+    pub fn run_ok(self) -> Result<ProcessOutput, ProcessError> {
+        let program = self.program.clone();
+        let output = self.run()?;
+        if output.success() {
+            Ok(output)
+        } else {
+            Err(ProcessError::Exit {
+                program,
+                code: output.status.code(),
+                stderr: output.stderr.trim().to_string(),
+            })
+        }
+    }
+}
+
+type PipeBuffer = (Arc<Mutex<Vec<u8>>>, Option<std::thread::JoinHandle<()>>);
+
+/// Read a pipe on its own thread so a chatty child can't fill the pipe
+/// buffer and block while we're waiting on it.
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> PipeBuffer {
+    let buffer = Arc::new(Mutex::new(Vec::new()));
+    let handle = pipe.map(|mut pipe| {
+        let buffer = buffer.clone();
+        std::thread::spawn(move || {
+            let mut chunk = [0u8; 8192];
+            while let Ok(n) = pipe.read(&mut chunk) {
+                if n == 0 {
+                    break;
+                }
+                buffer.lock().unwrap().extend_from_slice(&chunk[..n]);
+            }
+        })
+    });
+    (buffer, handle)
+}
+
+fn collect(buffer: &Arc<Mutex<Vec<u8>>>, handle: Option<std::thread::JoinHandle<()>>) -> String {
+    if let Some(handle) = handle {
+        let deadline = Instant::now() + PIPE_GRACE;
+        while !handle.is_finished() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        if handle.is_finished() {
+            let _ = handle.join();
+        }
+    }
+    let bytes = buffer.lock().unwrap();
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+/// Kill the child and everything it spawned.
+fn kill_tree(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // The child leads its own group (process_group(0)), so -pid hits all of it
+        if let Ok(pid) = i32::try_from(child.id()) {
+            unsafe {
+                libc::kill(-pid, libc::SIGKILL);
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &child.id().to_string()])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    let _ = child.kill();
+}
+
+/// Locate a binary on PATH (`which`/`where.exe`), returning the first match.
+pub fn which(binary: &str) -> Option<String> {
+    #[cfg(unix)]
+    let cmd = "which";
+    #[cfg(windows)]
+    let cmd = "where.exe";
+
+    let output = ProcessRunner::new(cmd)
+        .arg(binary)
+        .timeout(QUICK_TIMEOUT)
+        .run_ok()
+        .ok()?;
+    // `where.exe` returns multiple lines; take first
+    Some(output.stdout.lines().next()?.trim().to_string())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captures_stdout_stderr_and_status() {
+        let output = ProcessRunner::new("sh")
+            .args(["-c", "echo out; echo err >&2; exit 3"])
+            .run()
+            .unwrap();
+        assert_eq!(output.stdout.trim(), "out");
+        assert_eq!(output.stderr.trim(), "err");
+        assert_eq!(output.status.code(), Some(3));
+
+        let err = ProcessRunner::new("sh")
+            .args(["-c", "echo nope >&2; exit 2"])
+            .run_ok()
+            .unwrap_err();
+        assert_eq!(err.kind(), "exit");
+        assert!(err.to_string().contains("nope"));
+    }
+
+    #[test]
+    fn test_timeout_kills_process_group() {
+        let tmp = tempfile::tempdir().unwrap();
+        let marker = tmp.path().join("survived");
+        // The grandchild would create the marker if it outlived the timeout
+        let script = format!("(sleep 1; touch '{}') & sleep 30", marker.display());
+
+        let started = Instant::now();
+        let err = ProcessRunner::new("sh")
+            .args(["-c", &script])
+            .timeout(Duration::from_millis(200))
+            .run()
+            .unwrap_err();
+        assert!(err.is_timeout());
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        std::thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists(), "grandchild outlived the timeout");
+    }
+
+    #[test]
+    fn test_missing_binary_and_closed_stdin() {
+        let err = ProcessRunner::new("houston-definitely-not-a-binary")
+            .run()
+            .unwrap_err();
+        assert!(err.is_not_found());
+        assert!(ScanError::reportable("x", &err).is_none());
+
+        // `cat` would wait forever on an inherited terminal
+        let output = ProcessRunner::new("cat")
+            .timeout(Duration::from_secs(5))
+            .run()
+            .unwrap();
+        assert!(output.success());
+    }
+}
@@ -0,0 +1,195 @@
+//! Listening sockets and process details straight from `/proc` (Linux).
+//!
+//! One pass over `/proc/net/tcp{,6}` and `/proc/<pid>/fd` replaces `lsof`
+//! plus a `ps`/`lsof -p` round trip per PID, and works on minimal images
+//! where neither is installed.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// TCP state code for LISTEN in `/proc/net/tcp`
+const TCP_LISTEN: &str = "0A";
+
+/// A process with a listening TCP socket
+#[derive(Debug, Clone)]
+pub struct Listener {
+    pub pid: u32,
+    /// Listening ports the process holds, ascending
+    pub ports: Vec<u16>,
+    /// `/proc/<pid>/comm`, the same short name `ps -o comm=` prints
+    pub name: String,
+    pub cmdline: Vec<String>,
+    pub cwd: Option<String>,
+    pub uptime_secs: Option<u64>,
+}
+
+/// Every process with a listening TCP socket. Processes owned by other users
+/// are skipped, since their fds can't be read without root.
+pub fn listeners() -> io::Result<Vec<Listener>> {
+    let mut sockets = parse_net_tcp(&fs::read_to_string("/proc/net/tcp")?);
+    // IPv6 may be disabled entirely
+    if let Ok(tcp6) = fs::read_to_string("/proc/net/tcp6") {
+        sockets.extend(parse_net_tcp(&tcp6));
+    }
+    if sockets.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let boot = Boot::read();
+    let mut listeners = Vec::new();
+    for entry in fs::read_dir("/proc")?.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|s| s.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let proc_dir = entry.path();
+        let mut ports: Vec<u16> = socket_inodes(&proc_dir)
+            .filter_map(|inode| sockets.get(&inode).copied())
+            .collect();
+        if ports.is_empty() {
+            continue;
+        }
+        ports.sort_unstable();
+        ports.dedup();
+
+        listeners.push(Listener {
+            pid,
+            ports,
+            name: fs::read_to_string(proc_dir.join("comm"))
+                .map(|s| s.trim_end().to_string())
+                .unwrap_or_else(|_| "unknown".to_string()),
+            cmdline: fs::read(proc_dir.join("cmdline"))
+                .map(|raw| parse_cmdline(&raw))
+                .unwrap_or_default(),
+            cwd: fs::read_link(proc_dir.join("cwd"))
+                .ok()
+                .map(|p| p.to_string_lossy().to_string()),
+            uptime_secs: fs::read_to_string(proc_dir.join("stat"))
+                .ok()
+                .and_then(|stat| boot.uptime_of(&stat)),
+        });
+    }
+    Ok(listeners)
+}
+
+/// Listening sockets in a `/proc/net/tcp` table: inode → local port.
+fn parse_net_tcp(table: &str) -> HashMap<u64, u16> {
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            // sl local_address rem_address st tx:rx tr:when retrnsmt uid timeout inode
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.get(3) != Some(&TCP_LISTEN) {
+                return None;
+            }
+            let (_, port) = fields.get(1)?.rsplit_once(':')?;
+            let port = u16::from_str_radix(port, 16).ok()?;
+            let inode = fields.get(9)?.parse::<u64>().ok()?;
+            (inode != 0).then_some((inode, port))
+        })
+        .collect()
+}
+
+/// Socket inodes among a process's open fds (`socket:[12345]` links).
+fn socket_inodes(proc_dir: &Path) -> impl Iterator<Item = u64> {
+    fs::read_dir(proc_dir.join("fd"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|fd| {
+            let target = fs::read_link(fd.path()).ok()?;
+            let target = target.to_str()?;
+            target
+                .strip_prefix("socket:[")?
+                .strip_suffix(']')?
+                .parse()
+                .ok()
+        })
+}
+
+fn parse_cmdline(raw: &[u8]) -> Vec<String> {
+    raw.split(|b| *b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).to_string())
+        .collect()
+}
+
+/// What's needed to turn a process start time into an age.
+struct Boot {
+    uptime_secs: Option<f64>,
+    ticks_per_sec: u64,
+}
+
+impl Boot {
+    fn read() -> Self {
+        let uptime_secs = fs::read_to_string("/proc/uptime")
+            .ok()
+            .and_then(|s| s.split_whitespace().next()?.parse().ok());
+        let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+        Self {
+            uptime_secs,
+            ticks_per_sec: if ticks > 0 { ticks as u64 } else { 100 },
+        }
+    }
+
+    /// Seconds since the process in `/proc/<pid>/stat` started.
+    fn uptime_of(&self, stat: &str) -> Option<u64> {
+        let started = start_ticks(stat)? / self.ticks_per_sec;
+        Some((self.uptime_secs? as u64).saturating_sub(started))
+    }
+}
+
+/// Field 22 of `/proc/<pid>/stat`: start time in clock ticks after boot.
+/// The command name (field 2) is in parentheses and may contain spaces, so
+/// count from the last `)`.
+fn start_ticks(stat: &str) -> Option<u64> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    // rest starts at field 3 (state)
+    rest.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_net_tcp_listeners() {
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0BB8 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 48213 1 0000000000000000 100 0 0 10 0
+   1: 00000000:1538 00000000:0000 0A 00000000:00000000 00:00000000 00000000   999        0 39911 1 0000000000000000 100 0 0 10 0
+   2: 0100007F:0BB8 0100007F:D4F2 01 00000000:00000000 00:00000000 00000000  1000        0 50122 1 0000000000000000 20 4 30 10 -1
+";
+        let sockets = parse_net_tcp(tcp);
+        assert_eq!(sockets.len(), 2, "established connections are skipped");
+        assert_eq!(sockets.get(&48213), Some(&3000));
+        assert_eq!(sockets.get(&39911), Some(&5432));
+
+        let tcp6 = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:1F90 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 61777 1 0000000000000000 100 0 0 10 0
+";
+        assert_eq!(parse_net_tcp(tcp6).get(&61777), Some(&8080));
+    }
+
+    #[test]
+    fn test_stat_start_time_and_cmdline() {
+        let stat = "4312 (next-server (v1) S 4300 4300 4300 0 -1 4194304 9123 0 0 0 512 88 0 0 20 0 11 0 1234567 1298432000 65536 18446744073709551615";
+        assert_eq!(start_ticks(stat), Some(1234567));
+
+        let boot = Boot {
+            uptime_secs: Some(20000.5),
+            ticks_per_sec: 100,
+        };
+        assert_eq!(boot.uptime_of(stat), Some(20000 - 12345));
+
+        assert_eq!(
+            parse_cmdline(b"node\0node_modules/.bin/vite\0--port=3000\0"),
+            vec!["node", "node_modules/.bin/vite", "--port=3000"]
+        );
+    }
+}
@@ -1,15 +1,22 @@
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
-use std::process::Command;
 use walkdir::WalkDir;
 
-use crate::registry::{detect, get_storage_dirs_by_type};
+use super::process::ProcessRunner;
+use crate::registry::{
+    detect, detect_stacks, get_storage_dirs_by_type, DetectedStack, SKIP_DIRS, SOURCE_LANGUAGES,
+};
 
 /// Maximum number of files to scan per directory to prevent slowdowns
 const MAX_FILES_PER_DIR: usize = 100_000;
 
+/// Source files larger than this are skipped when counting lines (bundles,
+/// generated code)
+const MAX_LINE_COUNT_FILE_BYTES: u64 = 1024 * 1024;
+
 // ============================================================================
 // Data Structures
 // ============================================================================
@@ -78,6 +85,14 @@ pub struct ExtendedGitInfo {
     pub stash_count: usize,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineCount {
+    pub language: String,
+    pub files: usize,
+    /// Non-blank lines
+    pub lines: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectAnalysis {
     pub path: String,
@@ -87,6 +102,10 @@ pub struct ProjectAnalysis {
     pub total_size: SizeInfo,
     pub code_size: SizeInfo,
     pub language_details: LanguageDetails,
+    /// Every stack in the project, primary first
+    pub stacks: Vec<DetectedStack>,
+    /// Per-language source line counts, most lines first
+    pub line_counts: Vec<LineCount>,
     pub storage_breakdown: Vec<DirectorySize>,
     pub git_info: Option<ExtendedGitInfo>,
     pub analyzed_at: String,
@@ -151,6 +170,71 @@ fn calculate_optional_dir_size(path: &Path) -> Option<SizeInfo> {
     }
 }
 
+// ============================================================================
+// Line Counts
+// ============================================================================
+
+/// Non-blank lines per source language (by extension, see `SOURCE_LANGUAGES`),
+/// skipping dependency/build directories and hidden ones.
+fn count_lines(path: &Path) -> Vec<LineCount> {
+    let by_extension: HashMap<&str, &str> = SOURCE_LANGUAGES
+        .iter()
+        .flat_map(|l| l.extensions.iter().map(move |ext| (*ext, l.name)))
+        .collect();
+
+    let files: Vec<(&str, std::path::PathBuf)> = WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            e.depth() == 0
+                || !e.file_type().is_dir()
+                || !(name.starts_with('.') || SKIP_DIRS.contains(&name.as_ref()))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .take(MAX_FILES_PER_DIR)
+        .filter_map(|e| {
+            let ext = e.path().extension()?.to_str()?.to_ascii_lowercase();
+            let language = by_extension.get(ext.as_str())?;
+            Some((*language, e.into_path()))
+        })
+        .collect();
+
+    let counted: Vec<(&str, u64)> = files
+        .par_iter()
+        .filter_map(|(language, file)| {
+            if fs::metadata(file).ok()?.len() > MAX_LINE_COUNT_FILE_BYTES {
+                return None;
+            }
+            let content = fs::read(file).ok()?;
+            let lines = content
+                .split(|b| *b == b'\n')
+                .filter(|line| line.iter().any(|b| !b.is_ascii_whitespace()))
+                .count() as u64;
+            Some((*language, lines))
+        })
+        .collect();
+
+    let mut totals: HashMap<&str, LineCount> = HashMap::new();
+    for (language, lines) in counted {
+        let entry = totals.entry(language).or_insert_with(|| LineCount {
+            language: language.to_string(),
+            files: 0,
+            lines: 0,
+        });
+        entry.files += 1;
+        entry.lines += lines;
+    }
+
+    let mut counts: Vec<LineCount> = totals.into_values().collect();
+    counts.sort_by(|a, b| {
+        b.lines
+            .cmp(&a.lines)
+            .then_with(|| a.language.cmp(&b.language))
+    });
+    counts
+}
+
 // ============================================================================
 // Directory Discovery (using registry)
 // ============================================================================
@@ -405,13 +489,12 @@ fn parse_go_project(path: &Path) -> GoDetails {
 // ============================================================================
 
 fn run_git_command(path: &Path, args: &[&str]) -> Option<String> {
-    Command::new("git")
+    ProcessRunner::new("git")
         .args(args)
         .current_dir(path)
-        .output()
+        .run_ok()
         .ok()
-        .filter(|output| output.status.success())
-        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .map(|output| output.stdout.trim().to_string())
 }
 
 fn get_extended_git_info(path: &Path) -> Option<ExtendedGitInfo> {
@@ -544,6 +627,9 @@ pub fn analyze_project(project_path: &str) -> Result<ProjectAnalysis, String> {
         _ => LanguageDetails::Other,
     };
 
+    let stacks = detect_stacks(path);
+    let line_counts = count_lines(path);
+
     // Get extended git info
     let git_info = get_extended_git_info(path);
 
@@ -557,8 +643,82 @@ pub fn analyze_project(project_path: &str) -> Result<ProjectAnalysis, String> {
         total_size: make_size_info(total_bytes),
         code_size: make_size_info(code_bytes),
         language_details,
+        stacks,
+        line_counts,
         storage_breakdown,
         git_info,
         analyzed_at,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polyglot_stacks_and_line_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"api\"\n[dependencies]\naxum = \"0.7\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/main.rs"), "fn main() {\n\n    run();\n}\n").unwrap();
+        fs::create_dir_all(root.join("web/src")).unwrap();
+        fs::write(
+            root.join("web/package.json"),
+            r#"{ "devDependencies": { "vite": "^6", "react": "^19" } }"#,
+        )
+        .unwrap();
+        fs::write(root.join("web/pnpm-lock.yaml"), "").unwrap();
+        fs::write(
+            root.join("web/src/App.tsx"),
+            "export const App = () => null;\n",
+        )
+        .unwrap();
+        // Dependencies and samples don't count
+        fs::create_dir_all(root.join("web/node_modules/react")).unwrap();
+        fs::write(root.join("web/node_modules/react/index.js"), "a\nb\nc\n").unwrap();
+        fs::create_dir_all(root.join("examples/demo")).unwrap();
+        fs::write(root.join("examples/demo/go.mod"), "module demo\n").unwrap();
+
+        let analysis = analyze_project(&root.to_string_lossy()).unwrap();
+        let stacks: Vec<(&str, &str, &str, &str)> = analysis
+            .stacks
+            .iter()
+            .map(|s| {
+                (
+                    s.subdir.as_str(),
+                    s.detection.language_display.as_str(),
+                    s.detection.framework.as_str(),
+                    s.detection.package_manager_display.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            stacks,
+            vec![
+                ("", "Rust", "Axum", "cargo"),
+                ("web", "JavaScript/TypeScript", "React + Vite", "pnpm"),
+            ]
+        );
+
+        assert_eq!(
+            analysis.line_counts,
+            vec![
+                LineCount {
+                    language: "Rust".to_string(),
+                    files: 1,
+                    lines: 3
+                },
+                LineCount {
+                    language: "TypeScript".to_string(),
+                    files: 1,
+                    lines: 1
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,522 @@
+//! Compare two `scan_history` snapshots of the same scanner.
+//!
+//! Each scanner's JSON blob is parsed back into its own struct and compared
+//! by a natural key (PATH entry, language name, package name, env var key...),
+//! so the result reads like "node 20.11.0 → 22.1.0" rather than a JSON patch.
+
+use super::ai_tools::AiToolsReport;
+use super::claude::ClaudeConfig;
+use super::diagnostics::DiagnosticReport;
+use super::docker::DockerStatus;
+use super::environment::EnvVarInfo;
+use super::languages::LanguageInfo;
+use super::packages::{PackageInfo, PackageList};
+use super::path::PathEntry;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffEntry {
+    pub kind: ChangeKind,
+    /// Sub-list the entry belongs to, e.g. "brew" for packages
+    pub group: Option<String>,
+    pub key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanDiff {
+    pub scanner: String,
+    pub from_id: i64,
+    pub to_id: i64,
+    pub from_scanned_at: String,
+    pub to_scanned_at: String,
+    pub changes: Vec<DiffEntry>,
+    pub summary: DiffSummary,
+}
+
+/// One side of the comparison, as stored in `scan_history`.
+pub struct Snapshot<'a> {
+    pub id: i64,
+    pub scanned_at: &'a str,
+    pub data_json: &'a str,
+}
+
+pub fn diff(scanner: &str, from: Snapshot, to: Snapshot) -> Result<ScanDiff, String> {
+    let changes = match scanner {
+        "path" => {
+            let (a, b) = parse_pair::<Vec<PathEntry>>(&from, &to)?;
+            diff_path(&a, &b)
+        }
+        "languages" => {
+            let (a, b) = parse_pair::<Vec<LanguageInfo>>(&from, &to)?;
+            diff_languages(&a, &b)
+        }
+        "environment" => {
+            let (a, b) = parse_pair::<Vec<EnvVarInfo>>(&from, &to)?;
+            diff_environment(&a, &b)
+        }
+        "packages" => {
+            let (a, b) = parse_pair::<PackageList>(&from, &to)?;
+            diff_packages(&a, &b)
+        }
+        "ai_tools" => {
+            let (a, b) = parse_pair::<AiToolsReport>(&from, &to)?;
+            diff_ai_tools(&a, &b)
+        }
+        "claude" => {
+            let (a, b) = parse_pair::<ClaudeConfig>(&from, &to)?;
+            diff_claude(&a, &b)
+        }
+        "diagnostics" => {
+            let (a, b) = parse_pair::<DiagnosticReport>(&from, &to)?;
+            diff_diagnostics(&a, &b)
+        }
+        "docker" => {
+            let (a, b) = parse_pair::<DockerStatus>(&from, &to)?;
+            diff_docker(&a, &b)
+        }
+        // `system` and anything added later: compare top-level fields
+        _ => {
+            let (a, b) = parse_pair::<Value>(&from, &to)?;
+            diff_fields(None, &a, &b)
+        }
+    };
+
+    let mut summary = DiffSummary::default();
+    for change in &changes {
+        match change.kind {
+            ChangeKind::Added => summary.added += 1,
+            ChangeKind::Removed => summary.removed += 1,
+            ChangeKind::Changed => summary.changed += 1,
+        }
+    }
+
+    Ok(ScanDiff {
+        scanner: scanner.to_string(),
+        from_id: from.id,
+        to_id: to.id,
+        from_scanned_at: from.scanned_at.to_string(),
+        to_scanned_at: to.scanned_at.to_string(),
+        changes,
+        summary,
+    })
+}
+
+fn parse_pair<T: DeserializeOwned>(from: &Snapshot, to: &Snapshot) -> Result<(T, T), String> {
+    let parse = |json: &str| {
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse scan data: {}", e))
+    };
+    Ok((parse(from.data_json)?, parse(to.data_json)?))
+}
+
+/// Match `from` and `to` by key and emit added/removed/changed entries.
+/// Entries keep the order of `to`, with removals appended in `from` order.
+fn diff_keyed<T>(
+    group: Option<&str>,
+    from: &[T],
+    to: &[T],
+    key: impl Fn(&T) -> String,
+    value: impl Fn(&T) -> String,
+) -> Vec<DiffEntry> {
+    let before: HashMap<String, String> = from.iter().map(|t| (key(t), value(t))).collect();
+    let after_keys: HashSet<String> = to.iter().map(&key).collect();
+    let mut changes = Vec::new();
+
+    for item in to {
+        let k = key(item);
+        let v = value(item);
+        match before.get(&k) {
+            None => changes.push(entry(ChangeKind::Added, group, k, None, Some(v))),
+            Some(old) if *old != v => changes.push(entry(
+                ChangeKind::Changed,
+                group,
+                k,
+                Some(old.clone()),
+                Some(v),
+            )),
+            Some(_) => {}
+        }
+    }
+
+    for item in from {
+        let k = key(item);
+        if !after_keys.contains(&k) {
+            let v = value(item);
+            changes.push(entry(ChangeKind::Removed, group, k, Some(v), None));
+        }
+    }
+
+    changes
+}
+
+fn entry(
+    kind: ChangeKind,
+    group: Option<&str>,
+    key: String,
+    before: Option<String>,
+    after: Option<String>,
+) -> DiffEntry {
+    DiffEntry {
+        kind,
+        group: group.map(|g| g.to_string()),
+        key,
+        before,
+        after,
+    }
+}
+
+fn diff_path(from: &[PathEntry], to: &[PathEntry]) -> Vec<DiffEntry> {
+    let status = |e: &PathEntry| if e.exists { "exists" } else { "missing" }.to_string();
+    let mut changes = diff_keyed(None, from, to, |e| e.path.clone(), status);
+
+    // Reordering matters for PATH, but an insert near the front shifts every
+    // index after it. Among the entries present in both, the ones outside the
+    // longest common subsequence are the ones that actually moved.
+    let from_keys: HashSet<&str> = from.iter().map(|e| e.path.as_str()).collect();
+    let to_keys: HashSet<&str> = to.iter().map(|e| e.path.as_str()).collect();
+    let common = |entries: &[PathEntry]| -> Vec<String> {
+        entries
+            .iter()
+            .map(|e| e.path.as_str())
+            .filter(|p| from_keys.contains(p) && to_keys.contains(p))
+            .map(|p| p.to_string())
+            .collect()
+    };
+    let (old_order, new_order) = (common(from), common(to));
+    let stayed = longest_common_subsequence(&old_order, &new_order);
+
+    for e in to {
+        if new_order.contains(&e.path) && !stayed.contains(&e.path) {
+            let old = from.iter().find(|f| f.path == e.path).map(|f| f.index);
+            changes.push(entry(
+                ChangeKind::Changed,
+                None,
+                e.path.clone(),
+                old.map(|i| format!("position {}", i + 1)),
+                Some(format!("position {}", e.index + 1)),
+            ));
+        }
+    }
+
+    changes
+}
+
+fn longest_common_subsequence(a: &[String], b: &[String]) -> HashSet<String> {
+    let mut table = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = HashSet::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            result.insert(a[i].clone());
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+fn diff_languages(from: &[LanguageInfo], to: &[LanguageInfo]) -> Vec<DiffEntry> {
+    let installed = |langs: &[LanguageInfo]| -> Vec<LanguageInfo> {
+        langs.iter().filter(|l| l.installed).cloned().collect()
+    };
+    diff_keyed(
+        None,
+        &installed(from),
+        &installed(to),
+        |l| l.name.clone(),
+        |l| {
+            if l.manager.is_empty() {
+                l.version.clone()
+            } else {
+                format!("{} ({})", l.version, l.manager)
+            }
+        },
+    )
+}
+
+fn diff_environment(from: &[EnvVarInfo], to: &[EnvVarInfo]) -> Vec<DiffEntry> {
+    // PATH has its own scanner and diff
+    let vars = |v: &[EnvVarInfo]| -> Vec<EnvVarInfo> {
+        v.iter().filter(|e| e.key != "PATH").cloned().collect()
+    };
+    diff_keyed(
+        None,
+        &vars(from),
+        &vars(to),
+        |e| e.key.clone(),
+        |e| e.value.clone(),
+    )
+}
+
+fn diff_packages(from: &PackageList, to: &PackageList) -> Vec<DiffEntry> {
+    // (group, source name used in `PackageList::errors`, before, after)
+    let groups = [
+        ("npm", "npm", &from.npm_global, &to.npm_global),
+        ("brew", "brew", &from.brew, &to.brew),
+        ("pip", "pip", &from.pip, &to.pip),
+        ("cargo", "cargo", &from.cargo, &to.cargo),
+        ("scoop", "scoop", &from.scoop, &to.scoop),
+        ("chocolatey", "choco", &from.chocolatey, &to.chocolatey),
+    ];
+
+    let failed = |list: &PackageList, source: &str| list.errors.iter().any(|e| e.source == source);
+
+    groups
+        .into_iter()
+        // A manager that timed out on either side would look like a mass uninstall
+        .filter(|(_, source, _, _)| !failed(from, source) && !failed(to, source))
+        .flat_map(|(group, _, before, after)| {
+            diff_keyed(
+                Some(group),
+                before,
+                after,
+                |p: &PackageInfo| p.name.clone(),
+                |p| p.version.clone(),
+            )
+        })
+        .collect()
+}
+
+fn diff_ai_tools(from: &AiToolsReport, to: &AiToolsReport) -> Vec<DiffEntry> {
+    let installed = |r: &AiToolsReport| -> Vec<_> {
+        r.tools
+            .iter()
+            .filter(|t| t.installed || t.app_installed)
+            .cloned()
+            .collect()
+    };
+    diff_keyed(
+        None,
+        &installed(from),
+        &installed(to),
+        |t| t.name.clone(),
+        |t| {
+            t.version
+                .clone()
+                .or_else(|| t.app_version.clone())
+                .unwrap_or_else(|| "installed".to_string())
+        },
+    )
+}
+
+fn diff_claude(from: &ClaudeConfig, to: &ClaudeConfig) -> Vec<DiffEntry> {
+    let mut changes = diff_keyed(
+        Some("mcp_servers"),
+        &from.mcp_servers,
+        &to.mcp_servers,
+        |s| s.name.clone(),
+        |s| {
+            format!("{} {}", s.command, s.args.join(" "))
+                .trim()
+                .to_string()
+        },
+    );
+    changes.extend(diff_keyed(
+        Some("settings"),
+        &from.settings,
+        &to.settings,
+        |s| s.key.clone(),
+        |s| s.value.clone(),
+    ));
+    changes
+}
+
+fn diff_diagnostics(from: &DiagnosticReport, to: &DiagnosticReport) -> Vec<DiffEntry> {
+    diff_keyed(
+        None,
+        &from.items,
+        &to.items,
+        |i| i.id.clone(),
+        |i| format!("{}: {}", i.severity.as_str(), i.title),
+    )
+}
+
+fn diff_docker(from: &DockerStatus, to: &DockerStatus) -> Vec<DiffEntry> {
+    diff_keyed(
+        None,
+        &from.containers,
+        &to.containers,
+        |c| c.name.clone(),
+        |c| format!("{} ({})", c.image, c.status),
+    )
+}
+
+/// Field-by-field comparison of two JSON objects (used for `system`).
+fn diff_fields(group: Option<&str>, from: &Value, to: &Value) -> Vec<DiffEntry> {
+    let (Some(from), Some(to)) = (from.as_object(), to.as_object()) else {
+        return if from == to {
+            Vec::new()
+        } else {
+            vec![entry(
+                ChangeKind::Changed,
+                group,
+                "value".to_string(),
+                Some(display(from)),
+                Some(display(to)),
+            )]
+        };
+    };
+
+    let mut changes = Vec::new();
+    for (key, new) in to {
+        match from.get(key) {
+            None => changes.push(entry(
+                ChangeKind::Added,
+                group,
+                key.clone(),
+                None,
+                Some(display(new)),
+            )),
+            Some(old) if old != new => changes.push(entry(
+                ChangeKind::Changed,
+                group,
+                key.clone(),
+                Some(display(old)),
+                Some(display(new)),
+            )),
+            Some(_) => {}
+        }
+    }
+    for (key, old) in from {
+        if !to.contains_key(key) {
+            changes.push(entry(
+                ChangeKind::Removed,
+                group,
+                key.clone(),
+                Some(display(old)),
+                None,
+            ));
+        }
+    }
+    changes
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_entry(path: &str, index: usize) -> PathEntry {
+        PathEntry {
+            path: path.to_string(),
+            exists: true,
+            is_duplicate: false,
+            index,
+            category: "Other".to_string(),
+        }
+    }
+
+    fn snapshot(id: i64, json: &str) -> Snapshot<'_> {
+        Snapshot {
+            id,
+            scanned_at: "2026-01-01T00:00:00Z",
+            data_json: json,
+        }
+    }
+
+    #[test]
+    fn test_path_insert_does_not_report_shifted_entries() {
+        let from = vec![path_entry("/usr/bin", 0), path_entry("/bin", 1)];
+        let to = vec![
+            path_entry("/opt/new/bin", 0),
+            path_entry("/usr/bin", 1),
+            path_entry("/bin", 2),
+        ];
+        let changes = diff_path(&from, &to);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ChangeKind::Added);
+        assert_eq!(changes[0].key, "/opt/new/bin");
+    }
+
+    #[test]
+    fn test_path_reorder_is_reported() {
+        let from = vec![
+            path_entry("/usr/bin", 0),
+            path_entry("/opt/homebrew/bin", 1),
+        ];
+        let to = vec![
+            path_entry("/opt/homebrew/bin", 0),
+            path_entry("/usr/bin", 1),
+        ];
+        let changes = diff_path(&from, &to);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ChangeKind::Changed);
+        assert_eq!(changes[0].key, "/usr/bin");
+        assert_eq!(changes[0].before.as_deref(), Some("position 1"));
+        assert_eq!(changes[0].after.as_deref(), Some("position 2"));
+    }
+
+    #[test]
+    fn test_package_diff_and_summary() {
+        let from = r#"{"npm_global":[{"name":"typescript","version":"5.7.2"},{"name":"eslint","version":"9.0.0"}],
+            "brew":[],"pip":[],"cargo":[],"scoop":[],"chocolatey":[]}"#;
+        let to = r#"{"npm_global":[{"name":"typescript","version":"5.8.0"},{"name":"vercel","version":"40.0.0"}],
+            "brew":[],"pip":[],"cargo":[],"scoop":[],"chocolatey":[]}"#;
+        let diff = diff("packages", snapshot(1, from), snapshot(2, to)).unwrap();
+
+        assert_eq!(diff.summary.added, 1);
+        assert_eq!(diff.summary.removed, 1);
+        assert_eq!(diff.summary.changed, 1);
+        let upgraded = diff.changes.iter().find(|c| c.key == "typescript").unwrap();
+        assert_eq!(upgraded.group.as_deref(), Some("npm"));
+        assert_eq!(upgraded.before.as_deref(), Some("5.7.2"));
+        assert_eq!(upgraded.after.as_deref(), Some("5.8.0"));
+    }
+
+    #[test]
+    fn test_timed_out_package_manager_is_skipped() {
+        let from = r#"{"npm_global":[],"brew":[{"name":"git","version":"2.44"}],"pip":[],"cargo":[],"scoop":[],"chocolatey":[]}"#;
+        let to = r#"{"npm_global":[],"brew":[],"pip":[],"cargo":[],"scoop":[],"chocolatey":[],
+            "errors":[{"source":"brew","kind":"timed_out","message":"`brew` timed out after 30s"}]}"#;
+        let diff = diff("packages", snapshot(1, from), snapshot(2, to)).unwrap();
+        assert!(diff.changes.is_empty());
+    }
+
+    #[test]
+    fn test_system_field_diff() {
+        let from = r#"{"os_version":"14.2","hostname":"mbp"}"#;
+        let to = r#"{"os_version":"14.3","hostname":"mbp"}"#;
+        let diff = diff("system", snapshot(1, from), snapshot(2, to)).unwrap();
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].key, "os_version");
+    }
+}
@@ -1,5 +1,5 @@
+use super::process::{ProcessRunner, QUICK_TIMEOUT};
 use serde::{Deserialize, Serialize};
-use std::process::Command;
 use sysinfo::System;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,15 +29,16 @@ fn detect_shell() -> (String, String) {
             .unwrap_or("unknown")
             .to_string();
 
-        let version = Command::new(&shell_path)
+        let version = ProcessRunner::new(&shell_path)
             .arg("--version")
-            .output()
+            .timeout(QUICK_TIMEOUT)
+            .run()
             .map(|o| {
-                let stdout = String::from_utf8_lossy(&o.stdout).trim().to_string();
+                let stdout = o.stdout.trim();
                 if stdout.is_empty() {
-                    String::from_utf8_lossy(&o.stderr).trim().to_string()
+                    o.stderr.trim().to_string()
                 } else {
-                    stdout
+                    stdout.to_string()
                 }
             })
             .unwrap_or_default();
@@ -59,16 +60,18 @@ fn detect_shell() -> (String, String) {
 
         let version = if shell == "cmd" {
             // cmd doesn't have a clean version output; use ver
-            Command::new("cmd")
+            ProcessRunner::new("cmd")
                 .args(["/C", "ver"])
-                .output()
-                .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                .timeout(QUICK_TIMEOUT)
+                .run()
+                .map(|o| o.stdout.trim().to_string())
                 .unwrap_or_default()
         } else {
-            Command::new("powershell")
+            ProcessRunner::new("powershell")
                 .args(["-NoProfile", "-Command", "$PSVersionTable.PSVersion.ToString()"])
-                .output()
-                .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+                .timeout(QUICK_TIMEOUT)
+                .run()
+                .map(|o| o.stdout.trim().to_string())
                 .unwrap_or_default()
         };
 
@@ -79,10 +82,11 @@ fn detect_shell() -> (String, String) {
 #[cfg(target_os = "macos")]
 fn detect_rosetta(binary_arch: &str) -> bool {
     if binary_arch == "x86_64" {
-        Command::new("sysctl")
+        ProcessRunner::new("sysctl")
             .args(["-n", "hw.optional.arm64"])
-            .output()
-            .map(|o| String::from_utf8_lossy(&o.stdout).trim() == "1")
+            .timeout(QUICK_TIMEOUT)
+            .run()
+            .map(|o| o.stdout.trim() == "1")
             .unwrap_or(false)
     } else {
         false
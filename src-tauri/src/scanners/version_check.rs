@@ -0,0 +1,419 @@
+//! Compare a project's version files (`.nvmrc`, `.python-version`,
+//! `rust-toolchain.toml`, …) with the runtimes that would actually run in it.
+//!
+//! For shim-based managers (pyenv, rbenv, rustup) that means the version the
+//! manager would pick inside the project directory; nvm only switches on
+//! `nvm use`, so there the active `node` on PATH is what counts.
+
+use super::diagnostics::{DiagnosticItem, Severity};
+use super::languages::LanguageInfo;
+use super::version_req;
+use super::workspace::{ProjectInfo, VersionFile};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionStatus {
+    Ok,
+    /// A matching version is installed but isn't the one that would run
+    Mismatch,
+    NotInstalled,
+    /// The file names an alias we couldn't resolve (e.g. `lts/*` without nvm)
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionCheck {
+    pub file: String,
+    pub language: String,
+    pub expected: String,
+    /// Version of the runtime currently on PATH
+    pub active: Option<String>,
+    /// Version the manager would pick in this project
+    pub resolved: Option<String>,
+    pub manager: Option<String>,
+    pub status: VersionStatus,
+    /// Command that fixes it, e.g. "pyenv install 3.11"
+    pub fix: Option<String>,
+}
+
+/// Install roots of the version managers we know how to ask.
+#[derive(Debug, Clone, Default)]
+pub struct Managers {
+    pub nvm: Option<PathBuf>,
+    pub pyenv: Option<PathBuf>,
+    pub rbenv: Option<PathBuf>,
+    pub rustup: Option<PathBuf>,
+}
+
+impl Managers {
+    pub fn detect() -> Self {
+        let home = dirs::home_dir();
+        let root = |var: &str, default: &str| {
+            std::env::var_os(var)
+                .map(PathBuf::from)
+                .or_else(|| home.as_ref().map(|h| h.join(default)))
+                .filter(|p| p.is_dir())
+        };
+        Self {
+            nvm: root("NVM_DIR", ".nvm"),
+            pyenv: root("PYENV_ROOT", ".pyenv"),
+            rbenv: root("RBENV_ROOT", ".rbenv"),
+            rustup: root("RUSTUP_HOME", ".rustup"),
+        }
+    }
+}
+
+fn list_dir(dir: &Path) -> Vec<String> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Highest installed version matching `spec`, the way nvm/pyenv/rbenv
+/// resolve a partial version.
+fn best_match(installed: &[String], spec: &str) -> Option<String> {
+    installed
+        .iter()
+        .filter(|v| version_req::matches_spec(v, spec) == Some(true))
+        .max_by_key(|v| version_req::parse_version(v))
+        .cloned()
+}
+
+/// Follow nvm aliases (`lts/*` → `lts/iron` → `v20.18.0`) to something
+/// comparable.
+fn resolve_nvm_alias(nvm: &Path, spec: &str) -> Option<String> {
+    let mut current = spec.to_string();
+    for _ in 0..5 {
+        if matches!(current.as_str(), "node" | "stable") {
+            return Some("*".to_string());
+        }
+        if version_req::matches_spec("0", &current).is_some() {
+            return Some(current);
+        }
+        current = std::fs::read_to_string(nvm.join("alias").join(&current))
+            .ok()?
+            .trim()
+            .to_string();
+    }
+    None
+}
+
+fn display_name(language: &str) -> &str {
+    match language {
+        "node" => "Node.js",
+        "python" => "Python",
+        "ruby" => "Ruby",
+        "rust" => "Rust",
+        other => other,
+    }
+}
+
+pub fn check_files(
+    files: &[VersionFile],
+    langs: &[LanguageInfo],
+    managers: &Managers,
+) -> Vec<VersionCheck> {
+    files
+        .iter()
+        .map(|file| check_file(file, langs, managers))
+        .collect()
+}
+
+fn check_file(file: &VersionFile, langs: &[LanguageInfo], managers: &Managers) -> VersionCheck {
+    // .python-version may list several versions; the first one wins
+    let spec = file
+        .expected_version
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let lang = langs
+        .iter()
+        .find(|l| l.installed && l.name == display_name(&file.language));
+    let active = lang.map(|l| l.version.clone());
+    let path_manager = lang.map(|l| l.manager.as_str()).unwrap_or_default();
+
+    let mut check = VersionCheck {
+        file: file.name.clone(),
+        language: file.language.clone(),
+        expected: file.expected_version.clone(),
+        active: active.clone(),
+        resolved: None,
+        manager: None,
+        status: compare_active(active.as_deref(), &spec),
+        fix: None,
+    };
+
+    match file.language.as_str() {
+        "node" => {
+            let Some(nvm) = &managers.nvm else {
+                return check;
+            };
+            check.manager = Some("nvm".to_string());
+            let Some(resolved_spec) = resolve_nvm_alias(nvm, &spec) else {
+                check.status = VersionStatus::Unknown;
+                return check;
+            };
+            check.status = compare_active(active.as_deref(), &resolved_spec);
+            if check.status == VersionStatus::Ok {
+                return check;
+            }
+            let installed: Vec<String> = list_dir(&nvm.join("versions").join("node"))
+                .into_iter()
+                .map(|v| v.trim_start_matches('v').to_string())
+                .collect();
+            match best_match(&installed, &resolved_spec) {
+                Some(version) => {
+                    check.resolved = Some(version);
+                    check.status = VersionStatus::Mismatch;
+                    check.fix = Some("nvm use".to_string());
+                }
+                None => {
+                    check.status = VersionStatus::NotInstalled;
+                    check.fix = Some(format!("nvm install {}", spec));
+                }
+            }
+        }
+        "python" | "ruby" => {
+            let (name, root) = if file.language == "python" {
+                ("pyenv", &managers.pyenv)
+            } else {
+                ("rbenv", &managers.rbenv)
+            };
+            // Only authoritative when its shims are what's on PATH
+            let Some(root) = root.as_ref().filter(|_| path_manager == name) else {
+                return check;
+            };
+            check.manager = Some(name.to_string());
+            if spec == "system" {
+                check.status = VersionStatus::Ok;
+                return check;
+            }
+            match best_match(&list_dir(&root.join("versions")), &spec) {
+                Some(version) => {
+                    check.resolved = Some(version);
+                    check.status = VersionStatus::Ok;
+                }
+                None => {
+                    check.status = VersionStatus::NotInstalled;
+                    check.fix = Some(format!("{} install {}", name, spec));
+                }
+            }
+        }
+        "rust" => {
+            let Some(rustup) = &managers.rustup else {
+                return check;
+            };
+            check.manager = Some("rustup".to_string());
+            // Toolchain dirs are "<channel>-<host triple>", e.g. "1.79.0-x86_64-apple-darwin"
+            let numeric = version_req::matches_spec("0", &spec).is_some();
+            let toolchain = list_dir(&rustup.join("toolchains"))
+                .into_iter()
+                .filter(|t| {
+                    if numeric {
+                        let channel = t.split('-').next().unwrap_or_default();
+                        version_req::matches_spec(channel, &spec) == Some(true)
+                    } else {
+                        t == &spec || t.starts_with(&format!("{}-", spec))
+                    }
+                })
+                .max_by_key(|t| version_req::parse_version(t));
+            match toolchain {
+                Some(t) => {
+                    check.resolved = Some(t);
+                    check.status = VersionStatus::Ok;
+                }
+                None => {
+                    check.status = VersionStatus::NotInstalled;
+                    check.fix = Some(format!("rustup toolchain install {}", spec));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    check
+}
+
+fn compare_active(active: Option<&str>, spec: &str) -> VersionStatus {
+    let Some(active) = active else {
+        return VersionStatus::NotInstalled;
+    };
+    match version_req::matches_spec(active, spec) {
+        Some(true) => VersionStatus::Ok,
+        Some(false) => VersionStatus::Mismatch,
+        None => VersionStatus::Unknown,
+    }
+}
+
+/// Fill in `version_checks` for every project that has version files.
+pub fn annotate(projects: &mut [ProjectInfo], langs: &[LanguageInfo]) {
+    let managers = Managers::detect();
+    for project in projects.iter_mut() {
+        project.version_checks = check_files(&project.version_files, langs, &managers);
+    }
+}
+
+/// Diagnostics for projects already run through [`annotate`].
+pub fn diagnostics(projects: &[ProjectInfo]) -> Vec<DiagnosticItem> {
+    let mut items = Vec::new();
+    for project in projects {
+        for check in &project.version_checks {
+            let lang = display_name(&check.language);
+            let (title, description) = match check.status {
+                VersionStatus::Ok | VersionStatus::Unknown => continue,
+                VersionStatus::Mismatch => (
+                    format!("{}: wrong {} version", project.name, lang),
+                    format!(
+                        "{} expects {} but {} is active",
+                        check.file,
+                        check.expected,
+                        check.active.as_deref().unwrap_or("nothing")
+                    ),
+                ),
+                VersionStatus::NotInstalled => (
+                    format!(
+                        "{}: {} {} not installed",
+                        project.name, lang, check.expected
+                    ),
+                    format!("{} expects {} {}", check.file, lang, check.expected),
+                ),
+            };
+            let fix = check
+                .fix
+                .as_ref()
+                .map(|f| format!(" Run `{}` in {}.", f, project.path))
+                .unwrap_or_default();
+            items.push(DiagnosticItem {
+                id: format!("version_mismatch_{}_{}", project.path, check.file),
+                category: "versions".to_string(),
+                severity: Severity::Warning,
+                title,
+                description: format!("{}.{}", description, fix),
+                details: Some(project.path.clone()),
+                fix_id: None,
+                fix_label: None,
+            });
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn lang(name: &str, version: &str, manager: &str) -> LanguageInfo {
+        LanguageInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+            binary_path: String::new(),
+            manager: manager.to_string(),
+            installed: true,
+            icon: String::new(),
+            error: None,
+        }
+    }
+
+    fn vf(name: &str, expected: &str, language: &str) -> VersionFile {
+        VersionFile {
+            name: name.to_string(),
+            expected_version: expected.to_string(),
+            language: language.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_without_managers_compares_active() {
+        let langs = vec![lang("Node.js", "20.11.1", "homebrew")];
+        let checks = check_files(
+            &[
+                vf(".nvmrc", "20", "node"),
+                vf(".node-version", ">=22", "node"),
+                vf(".ruby-version", "3.3", "ruby"),
+                vf(".nvmrc", "lts/iron", "node"),
+            ],
+            &langs,
+            &Managers::default(),
+        );
+        let statuses: Vec<VersionStatus> = checks.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                VersionStatus::Ok,
+                VersionStatus::Mismatch,
+                VersionStatus::NotInstalled,
+                VersionStatus::Unknown,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_manager_resolution() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        for dir in [
+            ".nvm/versions/node/v18.20.4",
+            ".nvm/versions/node/v20.18.0",
+            ".nvm/alias/lts",
+            ".pyenv/versions/3.11.2",
+            ".pyenv/versions/3.11.9",
+            ".rustup/toolchains/1.79.0-x86_64-unknown-linux-gnu",
+            ".rustup/toolchains/stable-x86_64-unknown-linux-gnu",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(".nvm/alias/lts/*"), "lts/iron\n").unwrap();
+        fs::write(root.join(".nvm/alias/lts/iron"), "v20.18.0\n").unwrap();
+        let managers = Managers {
+            nvm: Some(root.join(".nvm")),
+            pyenv: Some(root.join(".pyenv")),
+            rbenv: None,
+            rustup: Some(root.join(".rustup")),
+        };
+        let langs = vec![
+            lang("Node.js", "22.0.0", "nvm"),
+            lang("Python", "3.12.1", "pyenv"),
+            lang("Rust", "1.80.0", "rustup"),
+        ];
+
+        let checks = check_files(
+            &[
+                vf(".nvmrc", "lts/*", "node"),
+                vf(".nvmrc", "16", "node"),
+                vf(".python-version", "3.11\n3.10", "python"),
+                vf(".python-version", "3.9", "python"),
+                vf("rust-toolchain.toml", "1.79", "rust"),
+                vf("rust-toolchain.toml", "nightly", "rust"),
+            ],
+            &langs,
+            &managers,
+        );
+
+        assert_eq!(checks[0].status, VersionStatus::Mismatch);
+        assert_eq!(checks[0].resolved.as_deref(), Some("20.18.0"));
+        assert_eq!(checks[0].fix.as_deref(), Some("nvm use"));
+        assert_eq!(checks[1].status, VersionStatus::NotInstalled);
+        assert_eq!(checks[1].fix.as_deref(), Some("nvm install 16"));
+        assert_eq!(checks[2].status, VersionStatus::Ok);
+        assert_eq!(checks[2].resolved.as_deref(), Some("3.11.9"));
+        assert_eq!(checks[3].status, VersionStatus::NotInstalled);
+        assert_eq!(checks[3].fix.as_deref(), Some("pyenv install 3.9"));
+        assert_eq!(checks[4].status, VersionStatus::Ok);
+        assert_eq!(checks[5].status, VersionStatus::NotInstalled);
+        assert_eq!(
+            checks[5].fix.as_deref(),
+            Some("rustup toolchain install nightly")
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Lenient version parsing and range matching.
+//!
+//! Tool versions come from `--version` output and version files, so they're
+//! rarely clean semver ("3.12", "v20.11.1", "1.8.0_392"). Ranges follow npm
+//! conventions (`>=20 <23`, `^18 || ^20`, `20.x`, `1.2 - 1.4`) and are
+//! translated to the `semver` crate's syntax.
+
+use semver::{Version, VersionReq};
+
+/// Parse the leading `major[.minor[.patch]]` of a version string, padding
+/// missing parts with zero. Returns `None` if it doesn't start with a number.
+pub fn parse_version(raw: &str) -> Option<Version> {
+    let s = raw.trim().trim_start_matches(['v', 'V']);
+    let numeric: String = s
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let mut parts = numeric
+        .split('.')
+        .filter(|p| !p.is_empty())
+        .map(|p| p.parse::<u64>().ok());
+
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some(Version::new(major, minor, patch))
+}
+
+/// A parsed range: any of the alternatives (`||`) may match.
+#[derive(Debug, Clone)]
+pub struct Range {
+    alternatives: Vec<VersionReq>,
+}
+
+impl Range {
+    pub fn parse(range: &str) -> Result<Self, String> {
+        let alternatives = range
+            .split("||")
+            .map(|alt| {
+                let normalized = normalize(alt.trim());
+                VersionReq::parse(&normalized)
+                    .map_err(|e| format!("Invalid version range `{}`: {}", range.trim(), e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { alternatives })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.alternatives.iter().any(|req| req.matches(version))
+    }
+}
+
+/// Does `version` (lenient) satisfy `range` (npm-style)?
+pub fn satisfies(version: &str, range: &str) -> Result<bool, String> {
+    let range = Range::parse(range)?;
+    Ok(parse_version(version).is_some_and(|v| range.matches(&v)))
+}
+
+/// Match a version-file entry against a version. A plain version is a
+/// prefix ("20" matches 20.11.1, "3.11.4" only 3.11.4); anything with an
+/// operator or wildcard is a range. `None` for aliases like `lts/iron` or
+/// `system` that can't be compared without asking the version manager.
+pub fn matches_spec(version: &str, spec: &str) -> Option<bool> {
+    let spec = spec.trim().trim_start_matches(['v', 'V']);
+    if spec.is_empty() {
+        return None;
+    }
+    if spec.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        let want = components(spec);
+        let have = components(version);
+        return Some(!want.is_empty() && have.starts_with(&want));
+    }
+    let is_range = spec.starts_with(['<', '>', '=', '~', '^', '*'])
+        || spec.contains(".x")
+        || spec.contains("||")
+        || spec.contains(" - ");
+    if !is_range {
+        return None;
+    }
+    let range = Range::parse(spec).ok()?;
+    Some(parse_version(version).is_some_and(|v| range.matches(&v)))
+}
+
+fn components(version: &str) -> Vec<u64> {
+    version
+        .trim()
+        .trim_start_matches(['v', 'V'])
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect::<String>()
+        .split('.')
+        .map_while(|p| p.parse().ok())
+        .collect()
+}
+
+/// npm-style → `semver` crate syntax: comparators separated by commas,
+/// `a - b` hyphen ranges expanded, and `latest`/empty meaning anything.
+fn normalize(range: &str) -> String {
+    if range.is_empty() || range == "*" || range.eq_ignore_ascii_case("latest") {
+        return "*".to_string();
+    }
+    if let Some((low, high)) = range.split_once(" - ") {
+        return format!(">={}, <={}", low.trim(), high.trim());
+    }
+    if range.contains(',') {
+        return range.to_string();
+    }
+
+    // Join operators to their versions (">= 20" → ">=20") before splitting
+    let mut tokens: Vec<String> = Vec::new();
+    for token in range.split_whitespace() {
+        match tokens.last_mut() {
+            Some(last) if last.chars().all(|c| "<>=~^".contains(c)) => last.push_str(token),
+            _ => tokens.push(token.to_string()),
+        }
+    }
+    tokens
+        .iter()
+        .map(|t| t.trim_start_matches(['v', 'V']).replace(".X", ".x"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_lenient() {
+        assert_eq!(parse_version("v20.11.1"), Some(Version::new(20, 11, 1)));
+        assert_eq!(parse_version("3.12"), Some(Version::new(3, 12, 0)));
+        assert_eq!(parse_version("1.8.0_392"), Some(Version::new(1, 8, 0)));
+        assert_eq!(parse_version("22"), Some(Version::new(22, 0, 0)));
+        assert_eq!(parse_version("lts/iron"), None);
+    }
+
+    #[test]
+    fn test_npm_style_ranges() {
+        assert!(satisfies("20.11.1", ">=20 <23").unwrap());
+        assert!(!satisfies("23.0.0", ">=20 <23").unwrap());
+        assert!(satisfies("18.19.0", "^16 || ^18").unwrap());
+        assert!(satisfies("20.3.0", "20.x").unwrap());
+        assert!(satisfies("1.3.9", "1.2 - 1.4").unwrap());
+        assert!(satisfies("3.12.1", ">= 3.11").unwrap());
+        assert!(satisfies("9.9.9", "*").unwrap());
+        assert!(Range::parse(">=banana").is_err());
+    }
+
+    #[test]
+    fn test_version_file_specs() {
+        assert_eq!(matches_spec("20.11.1", "20"), Some(true));
+        assert_eq!(matches_spec("20.11.1", "v20.11"), Some(true));
+        assert_eq!(matches_spec("3.11.9", "3.11.4"), Some(false));
+        assert_eq!(matches_spec("3.12-dev", "3.12"), Some(true));
+        assert_eq!(matches_spec("2.0.0", "20"), Some(false));
+        assert_eq!(matches_spec("18.19.0", ">=20"), Some(false));
+        assert_eq!(matches_spec("20.3.0", "20.x"), Some(true));
+        assert_eq!(matches_spec("20.3.0", "lts/iron"), None);
+        assert_eq!(matches_spec("3.12.1", "system"), None);
+    }
+}
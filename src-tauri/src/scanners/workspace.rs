@@ -1,11 +1,20 @@
+use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::registry::{detect, get_storage_dirs_by_type, is_project_dir, SKIP_DIRS};
+use super::compose::{self, ComposeFile};
+use super::version_check::VersionCheck;
+use crate::registry::{
+    detect, detect_stacks, get_storage_dirs_by_type, is_project_dir, DetectedStack, SKIP_DIRS,
+};
 
 pub const DEFAULT_MAX_SCAN_DEPTH: usize = 5;
 
+/// How far below a project root deep discovery looks for nested manifests.
+pub const DEEP_DISCOVERY_DEPTH: usize = 4;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectHealthScore {
     pub grade: String,
@@ -26,7 +35,7 @@ pub struct VersionFile {
     pub language: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProjectInfo {
     pub name: String,
     pub path: String,
@@ -43,6 +52,15 @@ pub struct ProjectInfo {
     pub health_score: Option<ProjectHealthScore>,
     pub has_build_artifacts: bool,
     pub version_files: Vec<VersionFile>,
+    /// Filled in by `version_check::annotate` once installed runtimes are known
+    #[serde(default)]
+    pub version_checks: Vec<VersionCheck>,
+    /// `compose.yaml` / `docker-compose.yml` in the project root
+    #[serde(default)]
+    pub compose: Option<ComposeFile>,
+    /// Every stack in the project, primary first (see `registry::detect_stacks`)
+    #[serde(default)]
+    pub stacks: Vec<DetectedStack>,
 }
 
 fn get_project_name(path: &Path) -> Option<String> {
@@ -203,12 +221,18 @@ fn detect_version_files(path: &Path) -> Vec<VersionFile> {
         (".node-version", "node"),
         (".python-version", "python"),
         (".ruby-version", "ruby"),
+        ("rust-toolchain.toml", "rust"),
+        ("rust-toolchain", "rust"),
     ];
     candidates
         .iter()
         .filter_map(|(file, lang)| {
             let content = fs::read_to_string(path.join(file)).ok()?;
-            let version = content.trim().to_string();
+            let version = if *lang == "rust" {
+                toolchain_channel(&content)
+            } else {
+                content.trim().to_string()
+            };
             if version.is_empty() {
                 return None;
             }
@@ -221,6 +245,20 @@ fn detect_version_files(path: &Path) -> Vec<VersionFile> {
         .collect()
 }
 
+/// `rust-toolchain` is either a bare channel name or the same TOML as
+/// `rust-toolchain.toml` (`[toolchain] channel = "1.79"`).
+fn toolchain_channel(content: &str) -> String {
+    match toml::from_str::<toml::Value>(content) {
+        Ok(value) => value
+            .get("toolchain")
+            .and_then(|t| t.get("channel"))
+            .and_then(|c| c.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        Err(_) => content.trim().to_string(),
+    }
+}
+
 fn make_project(path: &Path, group: &str, group_type: &str) -> Option<ProjectInfo> {
     // Use the registry to detect project type
     let detection = detect(path)?;
@@ -254,6 +292,9 @@ fn make_project(path: &Path, group: &str, group_type: &str) -> Option<ProjectInf
         health_score,
         has_build_artifacts: build_artifacts,
         version_files,
+        version_checks: Vec::new(),
+        compose: compose::parse_project(path),
+        stacks: detect_stacks(path),
     })
 }
 
@@ -264,6 +305,7 @@ fn scan_group(
     projects: &mut Vec<ProjectInfo>,
     current_depth: usize,
     max_depth: usize,
+    deep: bool,
 ) {
     let entries = match fs::read_dir(dir) {
         Ok(e) => e,
@@ -282,7 +324,7 @@ fn scan_group(
 
         if is_project_dir(&path) {
             // Check for monorepo
-            if let Some(pkg_paths) = detect_monorepo_packages(&path) {
+            if let Some(pkg_paths) = nested_packages(&path, deep) {
                 if let Some(mut root_proj) = make_project(&path, group_name, "monorepo") {
                     root_proj.is_monorepo_root = true;
                     projects.push(root_proj);
@@ -298,7 +340,14 @@ fn scan_group(
         } else if current_depth < max_depth {
             // Not a project — recurse deeper with breadcrumb group name
             let child_group = format!("{}/{}", group_name, dir_name);
-            scan_group(&path, &child_group, projects, current_depth + 1, max_depth);
+            scan_group(
+                &path,
+                &child_group,
+                projects,
+                current_depth + 1,
+                max_depth,
+                deep,
+            );
         }
     }
 }
@@ -427,7 +476,7 @@ fn detect_cargo_workspace(root: &Path) -> Option<Vec<String>> {
 
 /// Expand glob-style patterns like `packages/*` by listing directory children
 /// that match and are valid project directories.
-fn resolve_globs(root: &Path, patterns: &[String]) -> Vec<std::path::PathBuf> {
+fn resolve_globs(root: &Path, patterns: &[String]) -> Vec<PathBuf> {
     let mut results = Vec::new();
 
     for pattern in patterns {
@@ -473,7 +522,7 @@ fn resolve_globs(root: &Path, patterns: &[String]) -> Vec<std::path::PathBuf> {
 }
 
 /// Detect monorepo workspace packages. Returns resolved package paths or None.
-fn detect_monorepo_packages(root: &Path) -> Option<Vec<std::path::PathBuf>> {
+fn detect_monorepo_packages(root: &Path) -> Option<Vec<PathBuf>> {
     // Try each strategy in order
     let patterns = detect_pnpm_workspace(root)
         .or_else(|| detect_npm_workspaces(root))
@@ -487,7 +536,47 @@ fn detect_monorepo_packages(root: &Path) -> Option<Vec<std::path::PathBuf>> {
     }
 }
 
-pub fn scan_directory(workspace_path: &str, max_depth: usize) -> Vec<ProjectInfo> {
+/// Find project directories nested anywhere under `root` (up to
+/// `DEEP_DISCOVERY_DEPTH` levels), for repos that keep e.g. `backend/` and
+/// `frontend/` side by side without declaring a package-manager workspace.
+/// Honours `.gitignore`/`.ignore` files and skips hidden dirs and `SKIP_DIRS`.
+fn discover_nested_projects(root: &Path) -> Option<Vec<PathBuf>> {
+    let walker = WalkBuilder::new(root)
+        .max_depth(Some(DEEP_DISCOVERY_DEPTH))
+        .require_git(false)
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !SKIP_DIRS.contains(&name.as_ref())
+        })
+        .build();
+
+    let mut results: Vec<PathBuf> = walker
+        .flatten()
+        .filter(|entry| entry.depth() > 0 && entry.file_type().is_some_and(|t| t.is_dir()))
+        .map(|entry| entry.into_path())
+        .filter(|path| is_project_dir(path))
+        .collect();
+
+    if results.is_empty() {
+        return None;
+    }
+    results.sort();
+    Some(results)
+}
+
+/// Declared workspace packages, or — when deep discovery is on — any nested
+/// projects, so both end up grouped under the parent repo.
+fn nested_packages(root: &Path, deep: bool) -> Option<Vec<PathBuf>> {
+    detect_monorepo_packages(root).or_else(|| {
+        if deep {
+            discover_nested_projects(root)
+        } else {
+            None
+        }
+    })
+}
+
+pub fn scan_directory(workspace_path: &str, max_depth: usize, deep: bool) -> Vec<ProjectInfo> {
     let mut projects = Vec::new();
     let workspace = Path::new(workspace_path);
 
@@ -513,7 +602,7 @@ pub fn scan_directory(workspace_path: &str, max_depth: usize) -> Vec<ProjectInfo
 
         if is_project_dir(&path) {
             // Check if it's a monorepo
-            if let Some(pkg_paths) = detect_monorepo_packages(&path) {
+            if let Some(pkg_paths) = nested_packages(&path, deep) {
                 // Add the root project
                 if let Some(mut root_proj) = make_project(&path, &dir_name, "monorepo") {
                     root_proj.is_monorepo_root = true;
@@ -533,7 +622,7 @@ pub fn scan_directory(workspace_path: &str, max_depth: usize) -> Vec<ProjectInfo
             }
         } else {
             // Not a project — treat as a group folder, scan its children
-            scan_group(&path, &dir_name, &mut projects, 2, max_depth);
+            scan_group(&path, &dir_name, &mut projects, 2, max_depth, deep);
         }
     }
 
@@ -546,6 +635,172 @@ pub fn scan_directory(workspace_path: &str, max_depth: usize) -> Vec<ProjectInfo
     projects
 }
 
+/// A linked worktree found through `git worktree list` rather than the
+/// directory walk. Monorepo checkouts are marked as roots so they consolidate
+/// with their main worktree like scanned ones do.
+fn worktree_project(path: &Path) -> Option<ProjectInfo> {
+    let dir_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if nested_packages(path, false).is_some() {
+        let mut root = make_project(path, &dir_name, "monorepo")?;
+        root.is_monorepo_root = true;
+        Some(root)
+    } else {
+        make_project(path, "", "")
+    }
+}
+
+/// Scan every workspace root and group worktrees of the same repository.
+/// `deep_discovery` lists the workspaces that opted into nested discovery.
+/// Shared by the `scan_projects` command and the headless CLI.
+pub fn scan_workspaces(
+    workspace_paths: &[String],
+    max_depth: usize,
+    deep_discovery: &[String],
+) -> Vec<ProjectInfo> {
+    let mut all_projects = Vec::new();
+
+    for ws_path in workspace_paths {
+        let projects = scan_directory(ws_path, max_depth, deep_discovery.contains(ws_path));
+        all_projects.extend(projects);
+    }
+
+    // Worktree post-processing: detect worktree groups across all projects
+    let git_paths: Vec<String> = all_projects
+        .iter()
+        .filter(|p| p.has_git)
+        .map(|p| p.path.clone())
+        .collect();
+
+    let worktree_lists = super::git::detect_worktree_groups(&git_paths);
+
+    // Linked worktrees the directory walk didn't reach, such as ones created
+    // in a sibling `<repo>.worktrees/` outside every workspace root, still
+    // belong with their repository.
+    let known: HashSet<PathBuf> = all_projects
+        .iter()
+        .map(|p| {
+            Path::new(&p.path)
+                .canonicalize()
+                .unwrap_or_else(|_| PathBuf::from(&p.path))
+        })
+        .collect();
+    let mut worktree_map: HashMap<String, String> = HashMap::new();
+    let mut linked = Vec::new();
+    for (path, worktrees) in &worktree_lists {
+        worktree_map.insert(path.clone(), worktrees[0].clone());
+        for wt in worktrees {
+            let wt_path = Path::new(wt);
+            let canonical = wt_path
+                .canonicalize()
+                .unwrap_or_else(|_| wt_path.to_path_buf());
+            if known.contains(&canonical) || worktree_map.contains_key(wt) {
+                continue;
+            }
+            if let Some(project) = worktree_project(wt_path) {
+                worktree_map.insert(wt.clone(), worktrees[0].clone());
+                linked.push(project);
+            }
+        }
+    }
+    all_projects.extend(linked);
+
+    for project in &mut all_projects {
+        if let Some(main_wt) = worktree_map.get(&project.path) {
+            // Only apply worktree grouping if not already in a monorepo group
+            if project.group_type != "monorepo" {
+                project.worktree_id = main_wt.clone();
+                // Derive a group name from the main worktree path
+                let repo_name = std::path::Path::new(main_wt)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| main_wt.clone());
+                project.group = format!("{} (worktrees)", repo_name);
+                project.group_type = "worktree".to_string();
+            }
+        }
+    }
+
+    // Monorepo-worktree consolidation: when multiple monorepo roots are worktrees
+    // of each other, collapse them into a single worktree group card.
+    {
+        // Step 1: Find monorepo roots that appear in the worktree map
+        let mut main_wt_to_roots: HashMap<String, Vec<String>> = HashMap::new();
+        for project in all_projects.iter() {
+            if project.is_monorepo_root {
+                if let Some(main_wt) = worktree_map.get(&project.path) {
+                    main_wt_to_roots
+                        .entry(main_wt.clone())
+                        .or_default()
+                        .push(project.path.clone());
+                }
+            }
+        }
+
+        // Step 2: For groups with 2+ roots, collect their monorepo group names
+        let mut affected_groups: HashSet<String> = HashSet::new();
+        let mut roots_to_update: HashSet<String> = HashSet::new();
+        let mut group_name_for_root: HashMap<String, String> = HashMap::new();
+
+        for (main_wt, root_paths) in &main_wt_to_roots {
+            if root_paths.len() < 2 {
+                continue; // Solo monorepo, leave as-is
+            }
+
+            let main_wt_dirname = std::path::Path::new(main_wt)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| main_wt.clone());
+            let wt_group_name = format!("{} (worktrees)", main_wt_dirname);
+
+            for root_path in root_paths {
+                if let Some(proj) = all_projects.iter().find(|p| p.path == *root_path) {
+                    affected_groups.insert(proj.group.clone());
+                }
+                roots_to_update.insert(root_path.clone());
+                group_name_for_root.insert(root_path.clone(), wt_group_name.clone());
+            }
+        }
+
+        if !affected_groups.is_empty() {
+            // Remove sub-packages (non-root entries) from affected monorepo groups
+            all_projects.retain(|p| !affected_groups.contains(&p.group) || p.is_monorepo_root);
+
+            // Convert each monorepo root into a worktree entry
+            for project in &mut all_projects {
+                if roots_to_update.contains(&project.path) {
+                    let folder_name = std::path::Path::new(&project.path)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| project.path.clone());
+                    project.name = folder_name;
+                    project.group = group_name_for_root
+                        .get(&project.path)
+                        .cloned()
+                        .unwrap_or_default();
+                    project.group_type = "worktree".to_string();
+                    // Keep is_monorepo_root = true so the frontend knows
+                    // these worktrees can be drilled into for packages
+                    project.worktree_id =
+                        worktree_map.get(&project.path).cloned().unwrap_or_default();
+                }
+            }
+        }
+    }
+
+    // Re-sort
+    all_projects.sort_by(|a, b| {
+        a.group
+            .to_lowercase()
+            .cmp(&b.group.to_lowercase())
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    all_projects
+}
+
 pub fn scan_monorepo_packages(root_path: &str) -> Vec<ProjectInfo> {
     let root = Path::new(root_path);
     let group_name = root
@@ -553,7 +808,9 @@ pub fn scan_monorepo_packages(root_path: &str) -> Vec<ProjectInfo> {
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_default();
 
-    match detect_monorepo_packages(root) {
+    // Roots only get here when the scan marked them, so falling back to
+    // nested discovery is safe even if the workspace setting changed since.
+    match nested_packages(root, true) {
         Some(pkg_paths) => {
             let mut packages = Vec::new();
             for pkg_path in &pkg_paths {
@@ -623,4 +880,102 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].expected_version, "20");
     }
+
+    #[test]
+    fn test_detect_version_files_rust_toolchain() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.79\"\ncomponents = [\"clippy\"]\n",
+        )
+        .unwrap();
+        fs::write(tmp.path().join("rust-toolchain"), "nightly-2024-06-01\n").unwrap();
+        let files = detect_version_files(tmp.path());
+        let channels: Vec<&str> = files.iter().map(|f| f.expected_version.as_str()).collect();
+        assert_eq!(channels, vec!["1.79", "nightly-2024-06-01"]);
+        assert!(files.iter().all(|f| f.language == "rust"));
+    }
+
+    #[test]
+    fn test_deep_discovery_groups_nested_projects() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        for dir in [
+            "backend",
+            "frontend",
+            "vendor/lib",
+            "node_modules/dep",
+            "scratch",
+        ] {
+            fs::create_dir_all(repo.join(dir)).unwrap();
+        }
+        fs::write(repo.join("README.md"), "# repo").unwrap();
+        fs::write(repo.join("Makefile"), "all:").unwrap();
+        fs::write(
+            repo.join("backend/pyproject.toml"),
+            "[project]\nname = \"api\"\n",
+        )
+        .unwrap();
+        fs::write(repo.join("frontend/package.json"), r#"{"name": "web"}"#).unwrap();
+        fs::write(
+            repo.join("node_modules/dep/package.json"),
+            r#"{"name": "dep"}"#,
+        )
+        .unwrap();
+        fs::write(repo.join("scratch/package.json"), r#"{"name": "scratch"}"#).unwrap();
+        fs::write(repo.join(".gitignore"), "scratch/\n").unwrap();
+        fs::write(repo.join("package.json"), r#"{"name": "repo"}"#).unwrap();
+        let ws = tmp.path().to_string_lossy().to_string();
+
+        let shallow = scan_directory(&ws, DEFAULT_MAX_SCAN_DEPTH, false);
+        assert_eq!(shallow.len(), 1);
+        assert!(!shallow[0].is_monorepo_root);
+
+        let deep = scan_directory(&ws, DEFAULT_MAX_SCAN_DEPTH, true);
+        let mut names: Vec<&str> = deep.iter().map(|p| p.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["backend", "repo", "web"]);
+        assert!(deep
+            .iter()
+            .all(|p| p.group == "repo" && p.group_type == "monorepo"));
+        assert!(deep.iter().any(|p| p.name == "repo" && p.is_monorepo_root));
+    }
+
+    #[test]
+    fn test_linked_worktree_outside_workspace_is_grouped() {
+        if super::super::process::which("git").is_none() {
+            return;
+        }
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path().join("code");
+        let app = ws.join("app");
+        fs::create_dir_all(&app).unwrap();
+        fs::write(app.join("package.json"), r#"{"name": "app"}"#).unwrap();
+        let repo = git2::Repository::init(&app).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("package.json")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Houston", "houston@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "initial commit", &tree, &[])
+            .unwrap();
+
+        let app_path = app.to_string_lossy().to_string();
+        let elsewhere = tmp.path().join("trees").join("{repo}");
+        let linked = super::super::worktrees::create(
+            &app_path,
+            "feature",
+            true,
+            None,
+            &elsewhere.to_string_lossy(),
+        )
+        .unwrap();
+
+        let ws_path = ws.to_string_lossy().to_string();
+        let projects = scan_workspaces(&[ws_path], DEFAULT_MAX_SCAN_DEPTH, &[]);
+        assert_eq!(projects.len(), 2);
+        assert!(projects.iter().any(|p| p.path == linked.path));
+        assert!(projects
+            .iter()
+            .all(|p| p.group == "app (worktrees)" && p.group_type == "worktree"));
+    }
 }
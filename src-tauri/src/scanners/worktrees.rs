@@ -0,0 +1,331 @@
+//! Linked worktrees of a repository: listing them with their dirty, locked
+//! and prunable state, and creating, removing and pruning them through
+//! `git worktree`.
+
+use super::git::{self, git_pool, GitStatus};
+use super::process::{ProcessRunner, QUICK_TIMEOUT};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where new worktrees go unless the `worktree_directory` setting says
+/// otherwise. Relative to the main worktree's parent, so `~/code/app` gets
+/// its worktrees in `~/code/app.worktrees/<branch>`.
+pub const DEFAULT_WORKTREE_DIR: &str = "{repo}.worktrees";
+
+/// `git worktree add` checks out the whole tree.
+const CHECKOUT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// One stanza of `git worktree list --porcelain`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct WorktreeEntry {
+    pub path: String,
+    pub head: String,
+    /// Short branch name; `None` when detached
+    pub branch: Option<String>,
+    pub bare: bool,
+    /// `Some` (possibly empty) when locked, with the reason given
+    pub locked: Option<String>,
+    pub prunable: Option<String>,
+}
+
+pub(crate) fn parse_porcelain(output: &str) -> Vec<WorktreeEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<WorktreeEntry> = None;
+    for line in output.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            entries.extend(current.take());
+            current = Some(WorktreeEntry {
+                path: path.to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(entry) = current.as_mut() else {
+            continue;
+        };
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "HEAD" => entry.head = value.to_string(),
+            "branch" => {
+                entry.branch = Some(
+                    value
+                        .strip_prefix("refs/heads/")
+                        .unwrap_or(value)
+                        .to_string(),
+                )
+            }
+            "bare" => entry.bare = true,
+            "locked" => entry.locked = Some(value.to_string()),
+            "prunable" => entry.prunable = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    entries.extend(current);
+    entries
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorktreeInfo {
+    pub path: String,
+    /// `None` when HEAD is detached
+    pub branch: Option<String>,
+    /// Abbreviated commit checked out
+    pub head: String,
+    pub is_main: bool,
+    pub locked: bool,
+    pub lock_reason: Option<String>,
+    /// Its directory is gone; `git worktree prune` will clean it up
+    pub prunable: bool,
+    pub prune_reason: Option<String>,
+    /// Dirty state and counts; `None` for prunable worktrees
+    pub status: Option<GitStatus>,
+}
+
+fn list_entries(project_path: &str) -> Result<Vec<WorktreeEntry>, String> {
+    let output = ProcessRunner::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .current_dir(project_path)
+        .timeout(QUICK_TIMEOUT)
+        .run_ok()
+        .map_err(|e| e.to_string())?;
+    Ok(parse_porcelain(&output.stdout))
+}
+
+/// Every worktree of the repository `project_path` belongs to, main first.
+pub fn list(project_path: &str) -> Result<Vec<WorktreeInfo>, String> {
+    let entries = list_entries(project_path)?;
+    let worktrees = git_pool().install(|| {
+        entries
+            .par_iter()
+            .enumerate()
+            .filter(|(_, e)| !e.bare)
+            .map(|(i, e)| WorktreeInfo {
+                path: e.path.clone(),
+                branch: e.branch.clone(),
+                head: e.head.chars().take(7).collect(),
+                is_main: i == 0,
+                locked: e.locked.is_some(),
+                lock_reason: e.locked.clone().filter(|r| !r.is_empty()),
+                prunable: e.prunable.is_some(),
+                prune_reason: e.prunable.clone().filter(|r| !r.is_empty()),
+                status: if e.prunable.is_none() && Path::new(&e.path).is_dir() {
+                    git::get_status(&e.path)
+                } else {
+                    None
+                },
+            })
+            .collect()
+    });
+    Ok(worktrees)
+}
+
+/// Directory a new worktree for `branch` goes in. `template` may use
+/// `{repo}` for the main worktree's folder name; relative templates are
+/// resolved next to the main worktree.
+pub fn worktree_path(main_worktree: &Path, template: &str, branch: &str) -> PathBuf {
+    let repo = main_worktree
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let dir = PathBuf::from(template.replace("{repo}", &repo));
+    let dir = if dir.is_absolute() {
+        dir
+    } else {
+        main_worktree.parent().unwrap_or(main_worktree).join(dir)
+    };
+    dir.join(branch.replace('/', "-"))
+}
+
+/// Check out `branch` in a new worktree under `template` (see
+/// [`worktree_path`]). With `new_branch` the branch is created from `base`
+/// (default: HEAD); otherwise it must exist locally or on exactly one remote.
+pub fn create(
+    project_path: &str,
+    branch: &str,
+    new_branch: bool,
+    base: Option<&str>,
+    template: &str,
+) -> Result<WorktreeInfo, String> {
+    let branch = branch.trim();
+    if branch.is_empty() || branch.starts_with('-') {
+        return Err(format!("Invalid branch name: {:?}", branch));
+    }
+    if base.is_some_and(|b| b.starts_with('-')) {
+        return Err(format!("Invalid base: {:?}", base.unwrap_or_default()));
+    }
+
+    let entries = list_entries(project_path)?;
+    let main = entries
+        .first()
+        .map(|e| PathBuf::from(&e.path))
+        .ok_or("Not a git repository")?;
+    let target = worktree_path(&main, template, branch);
+    if target.exists() {
+        return Err(format!("{} already exists", target.display()));
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let target_arg = target.to_string_lossy().to_string();
+    let mut args = vec!["worktree", "add"];
+    if new_branch {
+        args.extend(["-b", branch, target_arg.as_str()]);
+        args.extend(base);
+    } else {
+        args.extend([target_arg.as_str(), branch]);
+    }
+    ProcessRunner::new("git")
+        .args(&args)
+        .current_dir(project_path)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .timeout(CHECKOUT_TIMEOUT)
+        .run_ok()
+        .map_err(|e| e.to_string())?;
+
+    // git reports the canonical path, which may differ through symlinks
+    let canonical = target.canonicalize().unwrap_or(target);
+    list(project_path)?
+        .into_iter()
+        .find(|wt| Path::new(&wt.path).canonicalize().ok().as_ref() == Some(&canonical))
+        .ok_or_else(|| format!("Created {} but git doesn't list it", canonical.display()))
+}
+
+/// Delete a linked worktree's directory and its administrative files. Without
+/// `force`, git refuses when it has uncommitted or untracked changes; locked
+/// worktrees have to be unlocked first either way.
+pub fn remove(project_path: &str, worktree_path: &str, force: bool) -> Result<(), String> {
+    let mut args = vec!["worktree", "remove"];
+    if force {
+        args.push("--force");
+    }
+    // The path is never read as an option, even if it starts with '-'
+    args.extend(["--", worktree_path]);
+    ProcessRunner::new("git")
+        .args(&args)
+        .current_dir(project_path)
+        .timeout(CHECKOUT_TIMEOUT)
+        .run_ok()
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Forget worktrees whose directories were deleted by hand, returning their
+/// paths. `dry_run` only lists them.
+pub fn prune(project_path: &str, dry_run: bool) -> Result<Vec<String>, String> {
+    let prunable: Vec<String> = list_entries(project_path)?
+        .into_iter()
+        .filter(|e| e.prunable.is_some())
+        .map(|e| e.path)
+        .collect();
+    if !dry_run && !prunable.is_empty() {
+        ProcessRunner::new("git")
+            .args(["worktree", "prune"])
+            .current_dir(project_path)
+            .timeout(QUICK_TIMEOUT)
+            .run_ok()
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(prunable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = ProcessRunner::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Houston")
+            .env("GIT_AUTHOR_EMAIL", "houston@example.com")
+            .env("GIT_COMMITTER_NAME", "Houston")
+            .env("GIT_COMMITTER_EMAIL", "houston@example.com")
+            .run_ok();
+        assert!(output.is_ok(), "git {:?}: {:?}", args, output.err());
+    }
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output = "worktree /code/app\nHEAD 1111111111111111111111111111111111111111\nbranch refs/heads/main\n\n\
+                      worktree /code/app.worktrees/fix\nHEAD 2222222222222222222222222222222222222222\ndetached\nlocked on a USB drive\n\n\
+                      worktree /code/app.worktrees/gone\nHEAD 3333333333333333333333333333333333333333\nbranch refs/heads/feature/gone\nprunable gitdir file points to non-existent location\n";
+        let entries = parse_porcelain(output);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].branch.as_deref(), Some("main"));
+        assert_eq!(entries[0].locked, None);
+        assert_eq!(entries[1].branch, None);
+        assert_eq!(entries[1].locked.as_deref(), Some("on a USB drive"));
+        assert_eq!(entries[2].branch.as_deref(), Some("feature/gone"));
+        assert!(entries[2].prunable.is_some());
+    }
+
+    #[test]
+    fn test_worktree_path() {
+        let main = Path::new("/code/app");
+        assert_eq!(
+            worktree_path(main, DEFAULT_WORKTREE_DIR, "feature/login"),
+            PathBuf::from("/code/app.worktrees/feature-login")
+        );
+        assert_eq!(
+            worktree_path(main, "/tmp/trees/{repo}", "fix"),
+            PathBuf::from("/tmp/trees/app/fix")
+        );
+    }
+
+    #[test]
+    fn test_create_list_remove_prune() {
+        if super::super::process::which("git").is_none() {
+            return;
+        }
+        let tmp = TempDir::new().unwrap();
+        let main = tmp.path().join("app");
+        fs::create_dir(&main).unwrap();
+        git(&main, &["init", "-q"]);
+        fs::write(main.join("README.md"), "# app\n").unwrap();
+        git(&main, &["add", "README.md"]);
+        git(&main, &["commit", "-q", "-m", "initial commit"]);
+        git(&main, &["branch", "existing"]);
+        let repo = main.to_string_lossy().to_string();
+
+        let created = create(&repo, "feature/new", true, None, DEFAULT_WORKTREE_DIR).unwrap();
+        assert_eq!(created.branch.as_deref(), Some("feature/new"));
+        assert!(created.path.ends_with("app.worktrees/feature-new"));
+        assert!(!created.is_main);
+        let existing = create(&repo, "existing", false, None, DEFAULT_WORKTREE_DIR).unwrap();
+        assert!(create(&repo, "existing", false, None, DEFAULT_WORKTREE_DIR).is_err());
+        assert!(create(&repo, "--force", true, None, DEFAULT_WORKTREE_DIR).is_err());
+
+        fs::write(Path::new(&existing.path).join("scratch.txt"), "").unwrap();
+        git(Path::new(&repo), &["worktree", "lock", &created.path]);
+        let worktrees = list(&repo).unwrap();
+        assert_eq!(worktrees.len(), 3);
+        assert!(worktrees[0].is_main);
+        let find = |path: &str| worktrees.iter().find(|w| w.path == path).unwrap();
+        assert!(find(&created.path).locked);
+        assert!(find(&existing.path).status.as_ref().unwrap().is_dirty);
+        assert!(!find(&created.path).status.as_ref().unwrap().is_dirty);
+
+        // Dirty worktrees need force; locked ones stay put
+        assert!(remove(&repo, &existing.path, false).is_err());
+        remove(&repo, &existing.path, true).unwrap();
+        assert!(!Path::new(&existing.path).exists());
+        assert!(remove(&repo, &created.path, true).is_err());
+
+        git(Path::new(&repo), &["worktree", "unlock", &created.path]);
+        fs::remove_dir_all(&created.path).unwrap();
+        let gone = list(&repo).unwrap();
+        let stale = gone.iter().find(|w| w.path == created.path).unwrap();
+        assert!(stale.prunable);
+        assert!(stale.status.is_none());
+        assert_eq!(prune(&repo, true).unwrap(), vec![created.path.clone()]);
+        assert_eq!(list(&repo).unwrap().len(), 2);
+        assert_eq!(prune(&repo, false).unwrap(), vec![created.path]);
+        assert_eq!(list(&repo).unwrap().len(), 1);
+    }
+}
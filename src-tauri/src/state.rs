@@ -108,10 +108,13 @@ pub struct AppStatsSnapshot {
 
 use serde::Serialize as SerializeTrait;
 
+use crate::api_server::ApiServerHandle;
 use crate::db::Database;
+use crate::supervisor::Supervisor;
+use crate::watcher::WatcherHandle;
 use crate::scanners::{
     ai_tools::AiToolsReport, claude::ClaudeConfig, dev_servers::DevServerReport,
-    diagnostics::DiagnosticReport, docker::DockerStatus, environment::EnvVarInfo,
+    diagnostics::DiagnosticReport, docker::{DockerStatus, LogStream}, environment::EnvVarInfo,
     git::GitStatus, languages::LanguageInfo, packages::PackageList, path::PathEntry,
     system::SystemInfo, workspace::ProjectInfo,
 };
@@ -148,6 +151,14 @@ pub struct AppState {
     pub docker_fingerprint: Mutex<Option<(usize, usize)>>,
     // Throttle scan record writes: scanner_name → last write instant
     pub scan_record_timestamps: Mutex<HashMap<String, Instant>>,
+    // Localhost API server, when running
+    pub api_server: Mutex<Option<ApiServerHandle>>,
+    // Filesystem watcher, when running
+    pub watcher: Mutex<Option<WatcherHandle>>,
+    // Followed container logs: container_id → (subscription id, stream); dropping one stops it
+    pub log_streams: Mutex<HashMap<String, (u64, LogStream)>>,
+    // Dev servers started from Houston
+    pub supervisor: Supervisor,
     // App-level
     pub startup_instant: Instant,
 }
@@ -209,10 +220,21 @@ impl AppState {
             dev_server_stats: ScanStats::new(),
             docker_fingerprint: Mutex::new(None),
             scan_record_timestamps: Mutex::new(HashMap::new()),
+            api_server: Mutex::new(None),
+            watcher: Mutex::new(None),
+            log_streams: Mutex::new(HashMap::new()),
+            supervisor: Supervisor::new(),
             startup_instant: Instant::now(),
         }
     }
 
+    /// Ask the filesystem watcher (if running) to rebuild its watch list.
+    pub fn resync_watcher(&self) {
+        if let Some(watcher) = self.watcher.lock().unwrap().as_ref() {
+            watcher.resync();
+        }
+    }
+
     /// Write a scan record to the DB only if at least 5 minutes have passed
     /// since the last write for this scanner. Refresh commands bypass throttling.
     pub fn throttled_record_scan<T: SerializeTrait>(&self, scanner: &str, data: &T) {
@@ -0,0 +1,832 @@
+//! Dev servers that Houston starts itself.
+//!
+//! The supervisor keeps each `Child`, reads its stdout/stderr into a ring
+//! buffer, and pushes every line to the UI as a `devserver://output/<id>`
+//! event. A monitor thread per server watches for the bound port (in the
+//! whole process tree, since `npm run dev` listens from a grandchild), notices
+//! exits, and restarts crashed servers when auto-restart is on. Every status
+//! change is emitted as `devserver://status`.
+//!
+//! Servers run in their own process group so a stop reaches the whole tree.
+//! They outlive Houston if it quits; the regular dev server scan still finds
+//! them.
+
+use crate::scanners::dev_servers::DevServer;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// Lines of output kept per server.
+const LOG_CAPACITY: usize = 2000;
+/// How often the monitor checks for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// How often to look for a listening socket until one turns up.
+const PORT_PROBE_INTERVAL: Duration = Duration::from_secs(1);
+/// A server that hasn't bound a port by now is probably not an HTTP server;
+/// call it running anyway.
+const STARTUP_GRACE: Duration = Duration::from_secs(15);
+/// Time between SIGTERM and SIGKILL on stop.
+const STOP_GRACE: Duration = Duration::from_secs(5);
+/// Crash restarts in a row before giving up, and the delay before the first
+/// (doubled each time).
+const MAX_AUTO_RESTARTS: u32 = 5;
+const RESTART_BACKOFF: Duration = Duration::from_secs(1);
+/// Running this long resets the crash counter.
+const HEALTHY_UPTIME: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerStatus {
+    /// Spawned, port not seen yet
+    Starting,
+    Running,
+    Stopping,
+    Stopped,
+    Crashed,
+}
+
+/// A supervised dev server, as shown to the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct ManagedServer {
+    pub id: u32,
+    pub project_path: String,
+    pub command: String,
+    pub pid: Option<u32>,
+    pub port: Option<u16>,
+    pub status: ServerStatus,
+    pub auto_restart: bool,
+    /// Automatic restarts since it last ran healthily
+    pub restarts: u32,
+    pub exit_code: Option<i32>,
+    pub started_at: Option<String>,
+}
+
+/// One captured line of output
+#[derive(Debug, Clone, Serialize)]
+pub struct OutputLine {
+    /// "stdout", "stderr", or "system" for the supervisor's own notes
+    pub stream: String,
+    pub line: String,
+    pub at: String,
+}
+
+struct Entry {
+    info: Mutex<ManagedServer>,
+    child: Mutex<Option<Child>>,
+    logs: Mutex<VecDeque<OutputLine>>,
+    stop_requested: AtomicBool,
+    /// Held from the "already running?" check through the spawn, so two
+    /// starts for the same project can't both launch a process
+    lifecycle: Mutex<()>,
+    /// Bumped on every spawn so a stale monitor thread knows to quit
+    generation: AtomicU64,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: Mutex<HashMap<u32, Arc<Entry>>>,
+    next_id: AtomicU32,
+    app: OnceLock<AppHandle>,
+}
+
+/// Lives in `AppState::supervisor`.
+#[derive(Default)]
+pub struct Supervisor {
+    inner: Arc<Inner>,
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start emitting events. Called once from `setup`, after `AppState`
+    /// is managed.
+    pub fn attach(&self, app: AppHandle) {
+        let _ = self.inner.app.set(app);
+    }
+
+    /// Start `command` in `project_path`. A project has at most one managed
+    /// server; starting it again reuses its id once the old one has exited.
+    pub fn start(
+        &self,
+        project_path: &str,
+        command: &str,
+        auto_restart: bool,
+    ) -> Result<ManagedServer, String> {
+        if command.trim().is_empty() {
+            return Err("Empty command".to_string());
+        }
+        let entry = self.find_or_insert(project_path, command, auto_restart);
+        let _lifecycle = entry.lifecycle.lock().unwrap();
+        {
+            let mut info = entry.info.lock().unwrap();
+            if info.pid.is_some() {
+                return Err(format!(
+                    "A dev server is already running for {}",
+                    project_path
+                ));
+            }
+            info.command = command.to_string();
+            info.auto_restart = auto_restart;
+            info.restarts = 0;
+        }
+
+        spawn(&self.inner, &entry)?;
+        let info = entry.info.lock().unwrap().clone();
+        Ok(info)
+    }
+
+    /// SIGTERM the server's process group, then SIGKILL it if it hasn't
+    /// exited after a grace period.
+    pub fn stop(&self, id: u32) -> Result<ManagedServer, String> {
+        let entry = self.get(id)?;
+        stop_entry(&self.inner, &entry);
+        let info = entry.info.lock().unwrap().clone();
+        Ok(info)
+    }
+
+    /// Stop (if running) and start again with the same command.
+    pub fn restart(&self, id: u32) -> Result<ManagedServer, String> {
+        let entry = self.get(id)?;
+        let _lifecycle = entry.lifecycle.lock().unwrap();
+        stop_entry(&self.inner, &entry);
+        entry.info.lock().unwrap().restarts = 0;
+        spawn(&self.inner, &entry)?;
+        let info = entry.info.lock().unwrap().clone();
+        Ok(info)
+    }
+
+    pub fn set_auto_restart(&self, id: u32, enabled: bool) -> Result<ManagedServer, String> {
+        let entry = self.get(id)?;
+        let info = {
+            let mut info = entry.info.lock().unwrap();
+            info.auto_restart = enabled;
+            info.clone()
+        };
+        emit_status(&self.inner, &info);
+        Ok(info)
+    }
+
+    pub fn list(&self) -> Vec<ManagedServer> {
+        let mut servers: Vec<ManagedServer> = self
+            .inner
+            .entries
+            .lock()
+            .unwrap()
+            .values()
+            .map(|e| e.info.lock().unwrap().clone())
+            .collect();
+        servers.sort_by_key(|s| s.id);
+        servers
+    }
+
+    pub fn logs(&self, id: u32) -> Result<Vec<OutputLine>, String> {
+        let entry = self.get(id)?;
+        let logs = entry.logs.lock().unwrap().iter().cloned().collect();
+        Ok(logs)
+    }
+
+    /// The managed server a PID belongs to, counting its descendants.
+    pub fn owner_of(&self, pid: u32) -> Option<ManagedServer> {
+        let running = self.running();
+        if running.is_empty() {
+            return None;
+        }
+        let parents = process_parents();
+        running.into_iter().find(|server| {
+            server
+                .pid
+                .is_some_and(|root| descends_from(&parents, pid, root))
+        })
+    }
+
+    /// Tag scanned servers that Houston started, and add managed ones the
+    /// scan didn't see (still starting, or not listening on a port yet).
+    pub fn merge_into(&self, servers: &mut Vec<DevServer>) {
+        let running = self.running();
+        if running.is_empty() {
+            return;
+        }
+        let parents = process_parents();
+        for server in servers.iter_mut() {
+            server.managed_id = running
+                .iter()
+                .find(|m| {
+                    m.pid
+                        .is_some_and(|root| descends_from(&parents, server.pid, root))
+                })
+                .map(|m| m.id);
+        }
+        for managed in running {
+            if servers.iter().any(|s| s.managed_id == Some(managed.id)) {
+                continue;
+            }
+            let Some(pid) = managed.pid else {
+                continue;
+            };
+            servers.push(DevServer {
+                pid,
+                port: managed.port.unwrap_or(0),
+                process_name: managed
+                    .command
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or("sh")
+                    .to_string(),
+                framework: None,
+                project_path: Some(managed.project_path.clone()),
+                uptime_secs: managed
+                    .started_at
+                    .as_deref()
+                    .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                    .map(|t| {
+                        (chrono::Local::now().fixed_offset() - t)
+                            .num_seconds()
+                            .max(0) as u64
+                    }),
+                cwd: Some(managed.project_path.clone()),
+                managed_id: Some(managed.id),
+            });
+        }
+    }
+
+    fn running(&self) -> Vec<ManagedServer> {
+        self.list()
+            .into_iter()
+            .filter(|s| s.pid.is_some())
+            .collect()
+    }
+
+    fn get(&self, id: u32) -> Result<Arc<Entry>, String> {
+        self.inner
+            .entries
+            .lock()
+            .unwrap()
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| format!("No managed dev server with id {}", id))
+    }
+
+    /// The project's entry, created (stopped) if it has none. Looked up and
+    /// inserted under one lock so concurrent starts share an entry.
+    fn find_or_insert(&self, project_path: &str, command: &str, auto_restart: bool) -> Arc<Entry> {
+        let mut entries = self.inner.entries.lock().unwrap();
+        if let Some(entry) = entries
+            .values()
+            .find(|e| e.info.lock().unwrap().project_path == project_path)
+        {
+            return entry.clone();
+        }
+        let id = self.inner.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let entry = Arc::new(Entry {
+            info: Mutex::new(ManagedServer {
+                id,
+                project_path: project_path.to_string(),
+                command: command.to_string(),
+                pid: None,
+                port: None,
+                status: ServerStatus::Stopped,
+                auto_restart,
+                restarts: 0,
+                exit_code: None,
+                started_at: None,
+            }),
+            child: Mutex::new(None),
+            logs: Mutex::new(VecDeque::new()),
+            stop_requested: AtomicBool::new(false),
+            lifecycle: Mutex::new(()),
+            generation: AtomicU64::new(0),
+        });
+        entries.insert(id, entry.clone());
+        entry
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Process lifecycle
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Run through the shell so commands like `PORT=3001 npm run dev` work.
+fn shell_command(command: &str) -> Command {
+    #[cfg(unix)]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    }
+}
+
+fn spawn(inner: &Arc<Inner>, entry: &Arc<Entry>) -> Result<(), String> {
+    let (project_path, command) = {
+        let info = entry.info.lock().unwrap();
+        (info.project_path.clone(), info.command.clone())
+    };
+
+    let mut cmd = shell_command(&command);
+    cmd.current_dir(&project_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Colored output is stripped anyway; ask tools to keep it simple
+        .env("FORCE_COLOR", "0");
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to start: {}", e))?;
+
+    let generation = entry.generation.fetch_add(1, Ordering::SeqCst) + 1;
+    entry.stop_requested.store(false, Ordering::SeqCst);
+    read_output(inner, entry, child.stdout.take(), "stdout");
+    read_output(inner, entry, child.stderr.take(), "stderr");
+
+    let info = {
+        let mut info = entry.info.lock().unwrap();
+        info.pid = Some(child.id());
+        info.port = None;
+        info.status = ServerStatus::Starting;
+        info.exit_code = None;
+        info.started_at = Some(chrono::Local::now().to_rfc3339());
+        info.clone()
+    };
+    *entry.child.lock().unwrap() = Some(child);
+    push_line(inner, entry, "system", format!("$ {}", command));
+    emit_status(inner, &info);
+
+    let inner = inner.clone();
+    let entry = entry.clone();
+    std::thread::spawn(move || monitor(inner, entry, generation));
+    Ok(())
+}
+
+fn read_output<R: Read + Send + 'static>(
+    inner: &Arc<Inner>,
+    entry: &Arc<Entry>,
+    pipe: Option<R>,
+    stream: &'static str,
+) {
+    let Some(pipe) = pipe else {
+        return;
+    };
+    let inner = inner.clone();
+    let entry = entry.clone();
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut buf = Vec::new();
+        while let Ok(n) = reader.read_until(b'\n', &mut buf) {
+            if n == 0 {
+                break;
+            }
+            let line = strip_ansi(String::from_utf8_lossy(&buf).trim_end());
+            buf.clear();
+            if line.is_empty() {
+                continue;
+            }
+            // Dev servers announce their URL long before a socket probe would notice
+            if let Some(port) = port_from_output(&line) {
+                set_port(&inner, &entry, port);
+            }
+            push_line(&inner, &entry, stream, line);
+        }
+    });
+}
+
+fn monitor(inner: Arc<Inner>, entry: Arc<Entry>, generation: u64) {
+    let started = Instant::now();
+    let mut last_probe: Option<Instant> = None;
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if entry.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+
+        let exited = {
+            let mut child = entry.child.lock().unwrap();
+            match child.as_mut().map(|c| c.try_wait()) {
+                Some(Ok(Some(status))) => {
+                    child.take();
+                    Some(status)
+                }
+                Some(_) => None,
+                None => return,
+            }
+        };
+        if let Some(status) = exited {
+            on_exit(&inner, &entry, status, started.elapsed());
+            return;
+        }
+
+        let (pid, port, state) = {
+            let info = entry.info.lock().unwrap();
+            (info.pid, info.port, info.status)
+        };
+        if port.is_none() && last_probe.is_none_or(|t| t.elapsed() >= PORT_PROBE_INTERVAL) {
+            last_probe = Some(Instant::now());
+            if let Some(port) =
+                pid.and_then(|pid| listening_ports(&process_tree(pid)).into_iter().min())
+            {
+                set_port(&inner, &entry, port);
+            } else if state == ServerStatus::Starting && started.elapsed() >= STARTUP_GRACE {
+                let info = {
+                    let mut info = entry.info.lock().unwrap();
+                    // Unless a stop began since we looked
+                    if info.status != ServerStatus::Starting {
+                        continue;
+                    }
+                    info.status = ServerStatus::Running;
+                    info.clone()
+                };
+                emit_status(&inner, &info);
+            }
+        }
+    }
+}
+
+fn on_exit(inner: &Arc<Inner>, entry: &Arc<Entry>, status: ExitStatus, uptime: Duration) {
+    let stopping = entry.stop_requested.load(Ordering::SeqCst);
+    let crashed = !stopping && !status.success();
+    let (info, restart_delay) = {
+        let mut info = entry.info.lock().unwrap();
+        info.pid = None;
+        info.port = None;
+        info.exit_code = status.code();
+        info.status = if crashed {
+            ServerStatus::Crashed
+        } else {
+            ServerStatus::Stopped
+        };
+        if uptime >= HEALTHY_UPTIME {
+            info.restarts = 0;
+        }
+        let delay = (crashed && info.auto_restart && info.restarts < MAX_AUTO_RESTARTS)
+            .then(|| RESTART_BACKOFF * 2u32.pow(info.restarts));
+        (info.clone(), delay)
+    };
+
+    let code = status
+        .code()
+        .map(|c| format!("code {}", c))
+        .unwrap_or_else(|| "a signal".to_string());
+    push_line(inner, entry, "system", format!("Exited with {}", code));
+    emit_status(inner, &info);
+
+    let Some(delay) = restart_delay else {
+        return;
+    };
+    push_line(
+        inner,
+        entry,
+        "system",
+        format!("Restarting in {}s", delay.as_secs()),
+    );
+    let generation = entry.generation.load(Ordering::SeqCst);
+    std::thread::sleep(delay);
+    let _lifecycle = entry.lifecycle.lock().unwrap();
+    // Stopped, restarted or started by hand while we waited
+    if entry.generation.load(Ordering::SeqCst) != generation
+        || entry.stop_requested.load(Ordering::SeqCst)
+    {
+        return;
+    }
+    entry.info.lock().unwrap().restarts += 1;
+    if let Err(e) = spawn(inner, entry) {
+        push_line(inner, entry, "system", e);
+    }
+}
+
+fn stop_entry(inner: &Arc<Inner>, entry: &Arc<Entry>) {
+    // Set even when nothing is running, so a crash restart waiting out its
+    // backoff is called off too
+    entry.stop_requested.store(true, Ordering::SeqCst);
+    let (pid, info) = {
+        let mut info = entry.info.lock().unwrap();
+        let Some(pid) = info.pid else {
+            return;
+        };
+        info.status = ServerStatus::Stopping;
+        (pid, info.clone())
+    };
+    emit_status(inner, &info);
+
+    // The monitor reaps the child before it records the exit, so go by the
+    // status rather than the child handle
+    let exited = || entry.info.lock().unwrap().status != ServerStatus::Stopping;
+    signal_group(pid, false);
+    let deadline = Instant::now() + STOP_GRACE;
+    while Instant::now() < deadline {
+        if exited() {
+            return;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    push_line(
+        inner,
+        entry,
+        "system",
+        "Still running after SIGTERM, killing".to_string(),
+    );
+    signal_group(pid, true);
+    // Give the monitor a moment to reap it and report the exit
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while !exited() && Instant::now() < deadline {
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Signal the server's whole process group (it leads its own).
+fn signal_group(pid: u32, kill: bool) {
+    #[cfg(unix)]
+    {
+        if let Ok(pid) = i32::try_from(pid) {
+            let signal = if kill { libc::SIGKILL } else { libc::SIGTERM };
+            unsafe {
+                libc::kill(-pid, signal);
+            }
+        }
+    }
+    #[cfg(windows)]
+    {
+        let pid = pid.to_string();
+        let mut args = vec!["/T", "/PID", pid.as_str()];
+        if kill {
+            args.insert(0, "/F");
+        }
+        let _ = Command::new("taskkill")
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Output and events
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn push_line(inner: &Arc<Inner>, entry: &Arc<Entry>, stream: &str, line: String) {
+    let line = OutputLine {
+        stream: stream.to_string(),
+        line,
+        at: chrono::Local::now().to_rfc3339(),
+    };
+    {
+        let mut logs = entry.logs.lock().unwrap();
+        if logs.len() == LOG_CAPACITY {
+            logs.pop_front();
+        }
+        logs.push_back(line.clone());
+    }
+    if let Some(app) = inner.app.get() {
+        let id = entry.info.lock().unwrap().id;
+        let _ = app.emit(&format!("devserver://output/{}", id), line);
+    }
+}
+
+fn set_port(inner: &Arc<Inner>, entry: &Arc<Entry>, port: u16) {
+    let info = {
+        let mut info = entry.info.lock().unwrap();
+        if info.port.is_some() || info.pid.is_none() {
+            return;
+        }
+        info.port = Some(port);
+        // A stop in progress waits for the status to change
+        if info.status != ServerStatus::Stopping {
+            info.status = ServerStatus::Running;
+        }
+        info.clone()
+    };
+    emit_status(inner, &info);
+}
+
+fn emit_status(inner: &Arc<Inner>, info: &ManagedServer) {
+    if let Some(app) = inner.app.get() {
+        let _ = app.emit("devserver://status", info);
+    }
+}
+
+/// Drop terminal escape sequences (colors, cursor movement).
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'[') {
+            chars.next();
+            // Parameters, then a final byte in @..~
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        } else {
+            chars.next();
+        }
+    }
+    out
+}
+
+/// The port in a local URL a dev server prints on startup, e.g.
+/// "➜  Local:   http://localhost:5173/".
+fn port_from_output(line: &str) -> Option<u16> {
+    const HOSTS: &[&str] = &["localhost:", "127.0.0.1:", "0.0.0.0:", "[::1]:", "[::]:"];
+    HOSTS.iter().find_map(|host| {
+        let start = line.find(&format!("://{}", host))? + 3 + host.len();
+        let digits: String = line[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse().ok().filter(|p| *p > 0)
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Process tree and sockets
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn process_parents() -> HashMap<u32, u32> {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+    sys.processes()
+        .iter()
+        .filter_map(|(pid, process)| Some((pid.as_u32(), process.parent()?.as_u32())))
+        .collect()
+}
+
+/// Whether `pid` is `root` or one of its descendants.
+fn descends_from(parents: &HashMap<u32, u32>, pid: u32, root: u32) -> bool {
+    let mut current = Some(pid);
+    // Bounded walk in case a racy snapshot contains a cycle
+    for _ in 0..64 {
+        match current {
+            Some(p) if p == root => return true,
+            Some(p) => current = parents.get(&p).copied(),
+            None => break,
+        }
+    }
+    false
+}
+
+/// `root` and all its descendants.
+fn process_tree(root: u32) -> Vec<u32> {
+    let parents = process_parents();
+    let mut tree = vec![root];
+    let mut i = 0;
+    while i < tree.len() {
+        let parent = tree[i];
+        tree.extend(
+            parents
+                .iter()
+                .filter(|(_, p)| **p == parent)
+                .map(|(pid, _)| *pid),
+        );
+        i += 1;
+    }
+    tree
+}
+
+fn listening_ports(pids: &[u32]) -> Vec<u16> {
+    #[cfg(target_os = "linux")]
+    {
+        crate::scanners::procfs::listeners()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|l| pids.contains(&l.pid))
+            .flat_map(|l| l.ports)
+            .collect()
+    }
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        use crate::scanners::process::{ProcessRunner, QUICK_TIMEOUT};
+        let pid_list = pids
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        ProcessRunner::new("lsof")
+            .args(["-a", "-nP", "-iTCP", "-sTCP:LISTEN", "-Fn", "-p", &pid_list])
+            .timeout(QUICK_TIMEOUT)
+            .run()
+            .map(|o| {
+                o.stdout
+                    .lines()
+                    .filter_map(|l| l.strip_prefix('n')?.rsplit(':').next()?.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+    #[cfg(windows)]
+    {
+        // The URL printed on startup is all we go by here
+        let _ = pids;
+        Vec::new()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_parsing() {
+        assert_eq!(
+            strip_ansi("\u{1b}[32m➜\u{1b}[39m  Local:   http://localhost:\u{1b}[1m5173\u{1b}[22m/"),
+            "➜  Local:   http://localhost:5173/"
+        );
+        assert_eq!(
+            port_from_output("➜  Local:   http://localhost:5173/"),
+            Some(5173)
+        );
+        assert_eq!(
+            port_from_output("- Local: http://127.0.0.1:3000"),
+            Some(3000)
+        );
+        assert_eq!(
+            port_from_output("Listening on http://[::]:8080"),
+            Some(8080)
+        );
+        assert_eq!(port_from_output("Compiled in 2.3s"), None);
+    }
+
+    #[test]
+    fn test_crash_is_captured() {
+        let dir = tempfile::tempdir().unwrap();
+        let supervisor = Supervisor::new();
+        let server = supervisor
+            .start(
+                &dir.path().to_string_lossy(),
+                "echo booting; echo oops >&2; exit 3",
+                false,
+            )
+            .unwrap();
+        assert_eq!(server.status, ServerStatus::Starting);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let server = loop {
+            let server = supervisor.list().remove(0);
+            if server.status == ServerStatus::Crashed || Instant::now() > deadline {
+                break server;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        };
+        assert_eq!(server.status, ServerStatus::Crashed);
+        assert_eq!(server.exit_code, Some(3));
+        assert_eq!(server.pid, None);
+
+        let logs = supervisor.logs(server.id).unwrap();
+        assert!(logs
+            .iter()
+            .any(|l| l.stream == "stdout" && l.line == "booting"));
+        assert!(logs
+            .iter()
+            .any(|l| l.stream == "stderr" && l.line == "oops"));
+
+        // Starting the same project again reuses the entry
+        let again = supervisor
+            .start(&dir.path().to_string_lossy(), "sleep 5", false)
+            .unwrap();
+        assert_eq!(again.id, server.id);
+        let stopped = supervisor.stop(server.id).unwrap();
+        assert_eq!(stopped.status, ServerStatus::Stopped);
+        assert_eq!(supervisor.list().len(), 1);
+    }
+
+    #[test]
+    fn test_stop_cancels_pending_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let supervisor = Supervisor::new();
+        let server = supervisor
+            .start(&dir.path().to_string_lossy(), "exit 1", true)
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while supervisor.list()[0].status != ServerStatus::Crashed && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        // Stopped while waiting out the restart backoff
+        supervisor.stop(server.id).unwrap();
+        std::thread::sleep(RESTART_BACKOFF + Duration::from_millis(500));
+
+        let after = supervisor.list().remove(0);
+        assert_eq!(after.pid, None);
+        assert_eq!(after.restarts, 0);
+    }
+}
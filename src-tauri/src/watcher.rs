@@ -0,0 +1,418 @@
+//! Filesystem watcher that pushes scanner updates instead of waiting for TTLs.
+//!
+//! Watches (non-recursively) the workspace roots, each repo's git dir
+//! (`HEAD`, `index`, `logs/HEAD`), `~/.claude`, the MCP config files of the
+//! known AI tools, and every PATH directory. A burst of changes is debounced,
+//! mapped to the scanners it affects, and each affected cache is invalidated
+//! and rescanned. Afterwards a `scanner://updated/<kind>` event is emitted so
+//! the UI can refetch from the now-warm cache.
+
+use crate::commands::{ai_tools_cmds, claude_cmds, language_cmds, system_cmds, workspace_cmds};
+use crate::scanners::ai_tools;
+use crate::state::AppState;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Quiet period after the last change before rescanning.
+const DEBOUNCE: Duration = Duration::from_millis(400);
+/// Upper bound on how long a continuous stream of changes can delay a rescan.
+const MAX_DELAY: Duration = Duration::from_secs(3);
+
+/// Files in a git dir whose changes mean the status may have changed.
+const GIT_STATE_FILES: &[&str] = &[
+    "HEAD",
+    "index",
+    "ORIG_HEAD",
+    "MERGE_HEAD",
+    "FETCH_HEAD",
+    "packed-refs",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ScanKind {
+    // Order matters: projects are rescanned before git so new repos are included
+    Projects,
+    Git,
+    Claude,
+    AiTools,
+    Path,
+    Languages,
+}
+
+impl ScanKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScanKind::Projects => "projects",
+            ScanKind::Git => "git",
+            ScanKind::Claude => "claude",
+            ScanKind::AiTools => "ai_tools",
+            ScanKind::Path => "path",
+            ScanKind::Languages => "languages",
+        }
+    }
+
+    pub fn event_name(&self) -> String {
+        format!("scanner://updated/{}", self.as_str())
+    }
+}
+
+enum Msg {
+    Fs(notify::Result<notify::Event>),
+    Resync,
+}
+
+/// Handle kept in `AppState::watcher` so commands can ask for the watch set
+/// to be rebuilt (e.g. after a workspace is added).
+pub struct WatcherHandle {
+    tx: Sender<Msg>,
+}
+
+impl WatcherHandle {
+    pub fn resync(&self) {
+        let _ = self.tx.send(Msg::Resync);
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Watch targets
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Default)]
+pub struct WatchTargets {
+    pub workspace_roots: Vec<PathBuf>,
+    pub git_dirs: Vec<PathBuf>,
+    pub claude_dir: Option<PathBuf>,
+    pub mcp_configs: Vec<PathBuf>,
+    pub path_dirs: Vec<PathBuf>,
+}
+
+impl WatchTargets {
+    /// Directories to register with the OS watcher. Files are watched through
+    /// their parent so atomic replace-on-save doesn't drop the watch.
+    pub fn dirs(&self) -> BTreeSet<PathBuf> {
+        let mut dirs = BTreeSet::new();
+        dirs.extend(self.workspace_roots.iter().cloned());
+        for git_dir in &self.git_dirs {
+            dirs.insert(git_dir.clone());
+            dirs.insert(git_dir.join("logs"));
+        }
+        dirs.extend(self.claude_dir.iter().cloned());
+        dirs.extend(
+            self.mcp_configs
+                .iter()
+                .filter_map(|f| f.parent().map(Path::to_path_buf)),
+        );
+        dirs.extend(self.path_dirs.iter().cloned());
+        dirs.retain(|d| d.is_dir());
+        dirs
+    }
+
+    /// Map a changed path to the scanners whose results it can affect.
+    pub fn classify(&self, path: &Path) -> Vec<ScanKind> {
+        let mut kinds = Vec::new();
+        let Some(name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            return kinds;
+        };
+        // Lock files come and go around every git/editor write
+        if name.ends_with(".lock") {
+            return kinds;
+        }
+        let parent = path.parent().unwrap_or(Path::new(""));
+
+        let in_git_dir = self.git_dirs.iter().any(|g| g == parent);
+        let in_git_logs = self.git_dirs.iter().any(|g| g.join("logs") == parent);
+        if (in_git_dir && GIT_STATE_FILES.contains(&name.as_str()))
+            || (in_git_logs && name == "HEAD")
+        {
+            kinds.push(ScanKind::Git);
+        }
+
+        if self.workspace_roots.iter().any(|r| r == parent) && !name.starts_with('.') {
+            kinds.push(ScanKind::Projects);
+        }
+
+        if self.claude_dir.as_deref() == Some(parent) && name == "settings.json" {
+            kinds.push(ScanKind::Claude);
+        }
+
+        if self.mcp_configs.iter().any(|f| f == path) {
+            kinds.push(ScanKind::AiTools);
+        }
+
+        if self.path_dirs.iter().any(|d| d == parent) {
+            kinds.push(ScanKind::Path);
+            kinds.push(ScanKind::Languages);
+        }
+
+        kinds
+    }
+}
+
+/// The directory git keeps HEAD and index in. For linked worktrees `.git` is
+/// a file pointing at `<main>/.git/worktrees/<name>`.
+pub fn resolve_git_dir(project_path: &Path) -> Option<PathBuf> {
+    let dot_git = project_path.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let content = std::fs::read_to_string(&dot_git).ok()?;
+    let target = content.trim().strip_prefix("gitdir:")?.trim();
+    let target = PathBuf::from(target);
+    Some(if target.is_absolute() {
+        target
+    } else {
+        project_path.join(target)
+    })
+}
+
+fn collect_targets(app: &AppHandle) -> WatchTargets {
+    let state = app.state::<AppState>();
+
+    // Make sure there's a project list to derive git dirs from
+    let cached = state.project_cache.lock().unwrap().get();
+    let projects = match cached {
+        Some(p) => p,
+        None => workspace_cmds::scan_projects(app.state::<AppState>()),
+    };
+
+    let workspace_roots = state
+        .workspace_paths
+        .lock()
+        .unwrap()
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+    let mut git_dirs: Vec<PathBuf> = projects
+        .iter()
+        .filter(|p| p.has_git)
+        .filter_map(|p| resolve_git_dir(Path::new(&p.path)))
+        .collect();
+    git_dirs.sort();
+    git_dirs.dedup();
+
+    let separator = if cfg!(windows) { ';' } else { ':' };
+    let path_dirs = std::env::var("PATH")
+        .unwrap_or_default()
+        .split(separator)
+        .filter(|s| !s.is_empty())
+        .map(PathBuf::from)
+        .collect();
+
+    WatchTargets {
+        workspace_roots,
+        git_dirs,
+        claude_dir: dirs::home_dir().map(|h| h.join(".claude")),
+        mcp_configs: ai_tools::mcp_config_paths(),
+        path_dirs,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Lifecycle
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Whether the `watcher_enabled` setting allows the watcher (on by default).
+pub fn is_enabled(app: &AppHandle) -> bool {
+    let state = app.state::<AppState>();
+    let db = state.db.lock().unwrap();
+    db.get_setting("watcher_enabled")
+        .ok()
+        .flatten()
+        .map(|v| v != "false")
+        .unwrap_or(true)
+}
+
+/// Start the watcher thread and register its handle in `AppState`.
+pub fn start(app: &AppHandle) -> Result<(), String> {
+    let (tx, rx) = mpsc::channel();
+    let fs_tx = tx.clone();
+    let watcher = notify::recommended_watcher(move |res| {
+        let _ = fs_tx.send(Msg::Fs(res));
+    })
+    .map_err(|e| format!("Failed to create filesystem watcher: {}", e))?;
+
+    let app = app.clone();
+    app.state::<AppState>()
+        .watcher
+        .lock()
+        .unwrap()
+        .replace(WatcherHandle { tx: tx.clone() });
+
+    std::thread::spawn(move || run(app, watcher, rx));
+    let _ = tx.send(Msg::Resync);
+    Ok(())
+}
+
+fn run(app: AppHandle, mut watcher: RecommendedWatcher, rx: Receiver<Msg>) {
+    let mut targets = WatchTargets::default();
+    let mut watched: BTreeSet<PathBuf> = BTreeSet::new();
+
+    loop {
+        // Block until something happens
+        let Ok(first) = rx.recv() else { return };
+
+        let mut kinds: BTreeSet<ScanKind> = BTreeSet::new();
+        let mut resync = false;
+        let mut handle = |msg: Msg, targets: &WatchTargets| match msg {
+            Msg::Resync => resync = true,
+            Msg::Fs(Ok(event)) => {
+                if matches!(event.kind, EventKind::Access(_)) {
+                    return;
+                }
+                for path in &event.paths {
+                    kinds.extend(targets.classify(path));
+                }
+            }
+            Msg::Fs(Err(e)) => log::debug!("watcher error: {}", e),
+        };
+        handle(first, &targets);
+
+        // Debounce: keep draining until things go quiet (or MAX_DELAY passes)
+        let started = Instant::now();
+        while started.elapsed() < MAX_DELAY {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(msg) => handle(msg, &targets),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        for kind in &kinds {
+            rescan(&app, *kind);
+        }
+
+        // New or removed projects change which git dirs need watching
+        if resync || kinds.contains(&ScanKind::Projects) {
+            targets = collect_targets(&app);
+            let wanted = targets.dirs();
+            for dir in watched.difference(&wanted) {
+                let _ = watcher.unwatch(dir);
+            }
+            for dir in wanted.difference(&watched) {
+                if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                    log::debug!("failed to watch {}: {}", dir.display(), e);
+                }
+            }
+            watched = wanted;
+        }
+    }
+}
+
+/// Invalidate the cache behind `kind`, rescan through the regular command so
+/// stats and history stay consistent, and tell the frontend.
+fn rescan(app: &AppHandle, kind: ScanKind) {
+    let state = app.state::<AppState>();
+    match kind {
+        ScanKind::Projects => {
+            state.project_cache.lock().unwrap().invalidate();
+            state.git_cache.lock().unwrap().invalidate();
+            workspace_cmds::scan_projects(app.state::<AppState>());
+            workspace_cmds::get_all_git_statuses(app.state::<AppState>());
+            let _ = app.emit(&ScanKind::Git.event_name(), ());
+        }
+        ScanKind::Git => {
+            state.git_cache.lock().unwrap().invalidate();
+            workspace_cmds::get_all_git_statuses(app.state::<AppState>());
+        }
+        ScanKind::Claude => {
+            state.claude_cache.lock().unwrap().invalidate();
+            claude_cmds::get_claude_config(app.state::<AppState>());
+        }
+        ScanKind::AiTools => {
+            state.ai_tools_cache.lock().unwrap().invalidate();
+            ai_tools_cmds::get_ai_tools(app.state::<AppState>());
+        }
+        ScanKind::Path => {
+            state.path_cache.lock().unwrap().invalidate();
+            system_cmds::get_path_entries(app.state::<AppState>());
+        }
+        ScanKind::Languages => {
+            state.language_cache.lock().unwrap().invalidate();
+            language_cmds::get_languages(app.state::<AppState>());
+        }
+    }
+    let _ = app.emit(&kind.event_name(), ());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets() -> WatchTargets {
+        WatchTargets {
+            workspace_roots: vec![PathBuf::from("/ws")],
+            git_dirs: vec![PathBuf::from("/ws/app/.git")],
+            claude_dir: Some(PathBuf::from("/home/me/.claude")),
+            mcp_configs: vec![
+                PathBuf::from("/home/me/.claude/settings.json"),
+                PathBuf::from("/home/me/.cursor/mcp.json"),
+            ],
+            path_dirs: vec![PathBuf::from("/usr/local/bin")],
+        }
+    }
+
+    #[test]
+    fn test_classify_git_changes() {
+        let t = targets();
+        assert_eq!(
+            t.classify(Path::new("/ws/app/.git/HEAD")),
+            vec![ScanKind::Git]
+        );
+        assert_eq!(
+            t.classify(Path::new("/ws/app/.git/index")),
+            vec![ScanKind::Git]
+        );
+        assert_eq!(
+            t.classify(Path::new("/ws/app/.git/logs/HEAD")),
+            vec![ScanKind::Git]
+        );
+        assert!(t.classify(Path::new("/ws/app/.git/index.lock")).is_empty());
+        assert!(t.classify(Path::new("/ws/app/.git/config")).is_empty());
+    }
+
+    #[test]
+    fn test_classify_other_sources() {
+        let t = targets();
+        assert_eq!(
+            t.classify(Path::new("/ws/new-project")),
+            vec![ScanKind::Projects]
+        );
+        assert!(t.classify(Path::new("/ws/.DS_Store")).is_empty());
+        assert_eq!(
+            t.classify(Path::new("/home/me/.claude/settings.json")),
+            vec![ScanKind::Claude, ScanKind::AiTools]
+        );
+        assert_eq!(
+            t.classify(Path::new("/home/me/.cursor/mcp.json")),
+            vec![ScanKind::AiTools]
+        );
+        assert_eq!(
+            t.classify(Path::new("/usr/local/bin/node")),
+            vec![ScanKind::Path, ScanKind::Languages]
+        );
+    }
+
+    #[test]
+    fn test_resolve_git_dir_for_worktree() {
+        let tmp = tempfile::tempdir().unwrap();
+        let main = tmp.path().join("main");
+        let linked = tmp.path().join("linked");
+        std::fs::create_dir_all(main.join(".git/worktrees/linked")).unwrap();
+        std::fs::create_dir_all(&linked).unwrap();
+        let gitdir = main.join(".git/worktrees/linked");
+        std::fs::write(
+            linked.join(".git"),
+            format!("gitdir: {}\n", gitdir.display()),
+        )
+        .unwrap();
+
+        assert_eq!(resolve_git_dir(&main), Some(main.join(".git")));
+        assert_eq!(resolve_git_dir(&linked), Some(gitdir));
+        assert_eq!(resolve_git_dir(tmp.path()), None);
+    }
+}
@@ -8,6 +8,7 @@ import { useSetting } from "@/hooks/use-settings";
 import { useWhatsNewCheck } from "@/hooks/use-whats-new";
 import { useOnboardingCheck } from "@/hooks/use-onboarding";
 import { useUpdateChecker } from "@/hooks/use-update-checker";
+import { useScannerEvents } from "@/hooks/use-scanner-events";
 import { VisibilityContext, useVisibilityState } from "@/hooks/use-visibility";
 
 const queryClient = new QueryClient({
@@ -115,6 +116,11 @@ function UpdateChecker() {
   return null;
 }
 
+function ScannerEvents() {
+  useScannerEvents();
+  return null;
+}
+
 function ThemeProvider({ children }: { children: React.ReactNode }) {
   const { theme } = useTheme();
   return (
@@ -156,6 +162,7 @@ export default function App() {
           <WhatsNewCheck />
           <OnboardingCheck />
           <UpdateChecker />
+          <ScannerEvents />
           <KeyboardShortcuts />
           <AppShell />
         </ThemeProvider>
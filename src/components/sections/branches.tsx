@@ -0,0 +1,309 @@
+import { useState } from "react";
+import { ArrowDown, ArrowUp, GitBranch, RefreshCw, Trash2, Upload } from "lucide-react";
+import { commands, type BranchInfo, type DeleteMergedResult } from "@/lib/commands";
+import {
+  useBranchReport,
+  useDeleteMergedBranches,
+  useFetchAllRepos,
+  useProjectBranches,
+} from "@/hooks/use-branches";
+import { StatusBadge } from "@/components/shared/status-badge";
+
+function hasUnpushedWork(branch: BranchInfo) {
+  return branch.ahead > 0 || branch.unique_commits > 0;
+}
+
+function repoName(path: string) {
+  return path.split("/").filter(Boolean).pop() ?? path;
+}
+
+function BranchFlags({ branch }: { branch: BranchInfo }) {
+  return (
+    <>
+      {branch.is_default && <StatusBadge variant="info">default</StatusBadge>}
+      {branch.unique_commits > 0 && (
+        <StatusBadge variant="warning">
+          {branch.unique_commits >= 1000 ? "1000+" : branch.unique_commits} local-only
+        </StatusBadge>
+      )}
+      {branch.upstream_gone && <StatusBadge variant="warning">upstream gone</StatusBadge>}
+      {branch.merged && <StatusBadge variant="success">merged</StatusBadge>}
+      {branch.stale && <StatusBadge variant="neutral">stale</StatusBadge>}
+    </>
+  );
+}
+
+function AheadBehind({ branch }: { branch: BranchInfo }) {
+  return (
+    <span className="flex items-center gap-1.5 text-xs">
+      {branch.ahead > 0 && (
+        <span className="flex items-center gap-0.5 text-info">
+          <ArrowUp className="h-3 w-3" />
+          {branch.ahead}
+        </span>
+      )}
+      {branch.behind > 0 && (
+        <span className="flex items-center gap-0.5 text-warning">
+          <ArrowDown className="h-3 w-3" />
+          {branch.behind}
+        </span>
+      )}
+    </span>
+  );
+}
+
+/** Preview merged branches with a dry run, then delete those (and only those) on confirm */
+function DeleteMergedAction({ projectPath }: { projectPath: string | null }) {
+  const [preview, setPreview] = useState<DeleteMergedResult | null>(null);
+  const [previewing, setPreviewing] = useState(false);
+  const deleteMerged = useDeleteMergedBranches();
+
+  const loadPreview = async () => {
+    setPreviewing(true);
+    try {
+      setPreview(await commands.deleteMergedBranches(projectPath, true));
+    } finally {
+      setPreviewing(false);
+    }
+  };
+
+  if (!preview) {
+    return (
+      <button
+        onClick={loadPreview}
+        disabled={previewing}
+        className="inline-flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1 text-xs font-medium transition-colors hover:bg-accent disabled:opacity-50"
+      >
+        <Trash2 className="h-3 w-3" />
+        {previewing ? "Checking..." : "Delete merged..."}
+      </button>
+    );
+  }
+
+  return (
+    <div className="w-full space-y-2 rounded-md border border-border p-3">
+      {preview.deleted.length === 0 ? (
+        <p className="text-xs text-muted-foreground">No merged branches to delete.</p>
+      ) : (
+        <>
+          <p className="text-xs text-muted-foreground">
+            These branches are fully merged into their default branch:
+          </p>
+          <ul className="space-y-0.5">
+            {preview.deleted.map((b) => (
+              <li key={`${b.project_path}:${b.name}`} className="font-mono text-xs">
+                {projectPath === null && (
+                  <span className="text-muted-foreground">{repoName(b.project_path)} / </span>
+                )}
+                {b.name} <span className="text-muted-foreground">({b.commit})</span>
+              </li>
+            ))}
+          </ul>
+        </>
+      )}
+      {preview.errors.length > 0 && (
+        <p className="whitespace-pre-wrap text-xs text-destructive">{preview.errors.join("\n")}</p>
+      )}
+      <div className="flex gap-2">
+        {preview.deleted.length > 0 && (
+          <button
+            onClick={() =>
+              deleteMerged.mutate(
+                {
+                  projectPath,
+                  branches: preview.deleted.map(({ project_path, name }) => ({
+                    project_path,
+                    name,
+                  })),
+                },
+                { onSuccess: () => setPreview(null) },
+              )
+            }
+            disabled={deleteMerged.isPending}
+            className="rounded-md border border-border px-2.5 py-1 text-xs font-medium text-destructive transition-colors hover:bg-destructive/10 disabled:opacity-50"
+          >
+            Delete {preview.deleted.length} branch{preview.deleted.length === 1 ? "" : "es"}
+          </button>
+        )}
+        <button
+          onClick={() => setPreview(null)}
+          className="rounded-md border border-border px-2.5 py-1 text-xs font-medium transition-colors hover:bg-accent"
+        >
+          Cancel
+        </button>
+      </div>
+    </div>
+  );
+}
+
+/** Fetch every repo, optionally fast-forwarding the ones that are only behind */
+function FetchAllAction() {
+  const [fastForward, setFastForward] = useState(false);
+  const fetchAll = useFetchAllRepos();
+  const { progress } = fetchAll;
+
+  return (
+    <div className="flex items-center gap-3">
+      <label className="flex items-center gap-1.5 text-xs text-muted-foreground">
+        <input
+          type="checkbox"
+          checked={fastForward}
+          onChange={(e) => setFastForward(e.target.checked)}
+          disabled={fetchAll.isPending}
+        />
+        Fast-forward clean repos
+      </label>
+      <button
+        onClick={() => fetchAll.mutate({ fastForward })}
+        disabled={fetchAll.isPending}
+        title={progress ? progress.result.project_path : "git fetch every workspace repo"}
+        className="inline-flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1 text-xs font-medium transition-colors hover:bg-accent disabled:opacity-50"
+      >
+        <RefreshCw className={`h-3 w-3 ${fetchAll.isPending ? "animate-spin" : ""}`} />
+        {fetchAll.isPending
+          ? progress
+            ? `Fetching ${progress.completed}/${progress.total}`
+            : "Fetching..."
+          : "Fetch all"}
+      </button>
+    </div>
+  );
+}
+
+/** "Where do I have unpushed work?" across every workspace repo */
+export function UnpushedWorkPanel() {
+  const { data: report } = useBranchReport();
+  if (!report || report.repos.length === 0) return null;
+
+  const unreadable = report.repos.filter((repo) => repo.error);
+
+  const unpushed = report.repos.flatMap((repo) =>
+    repo.branches
+      .filter(hasUnpushedWork)
+      .map((branch) => ({ repo: repo.project_path, hasRemote: repo.has_remote, branch })),
+  );
+
+  return (
+    <div className="rounded-lg border border-border bg-card">
+      <div className="flex items-center justify-between border-b border-border px-4 py-3">
+        <div className="flex items-center gap-2">
+          <Upload className="h-4 w-4 text-muted-foreground" />
+          <h3 className="text-sm font-medium">Unpushed Work</h3>
+          <StatusBadge variant={report.unpushed_count > 0 ? "warning" : "success"}>
+            {report.unpushed_count} branch{report.unpushed_count === 1 ? "" : "es"}
+          </StatusBadge>
+          {report.stale_count > 0 && (
+            <StatusBadge variant="neutral">{report.stale_count} stale</StatusBadge>
+          )}
+        </div>
+        <FetchAllAction />
+      </div>
+      {unpushed.length > 0 ? (
+        <div className="divide-y divide-border">
+          {unpushed.map(({ repo, hasRemote, branch }) => (
+            <div
+              key={`${repo}:${branch.name}`}
+              className="flex items-center justify-between gap-3 px-4 py-2.5"
+            >
+              <div className="flex min-w-0 items-center gap-2 text-xs">
+                <span className="truncate text-muted-foreground" title={repo}>
+                  {repoName(repo)}
+                </span>
+                <GitBranch className="h-3 w-3 shrink-0 text-muted-foreground" />
+                <span className="truncate font-mono">{branch.name}</span>
+              </div>
+              <div className="flex shrink-0 items-center gap-2">
+                <AheadBehind branch={branch} />
+                <BranchFlags branch={branch} />
+                {!hasRemote && <StatusBadge variant="error">no remote</StatusBadge>}
+                <span className="text-[10px] text-muted-foreground">
+                  {branch.last_commit_date}
+                </span>
+              </div>
+            </div>
+          ))}
+        </div>
+      ) : (
+        <div className="px-4 py-6 text-center text-sm text-muted-foreground">
+          Every branch is pushed
+        </div>
+      )}
+      {unreadable.length > 0 && (
+        <div className="space-y-0.5 border-t border-border px-4 py-2.5">
+          {unreadable.map((repo) => (
+            <p
+              key={repo.project_path}
+              className="truncate text-xs text-destructive"
+              title={repo.project_path}
+            >
+              Couldn't read {repoName(repo.project_path)}: {repo.error}
+            </p>
+          ))}
+        </div>
+      )}
+      {report.deletable_count > 0 && (
+        <div className="flex items-center gap-3 border-t border-border px-4 py-2.5">
+          <span className="text-xs text-muted-foreground">
+            {report.deletable_count} merged branch{report.deletable_count === 1 ? "" : "es"} can
+            be deleted
+          </span>
+          <DeleteMergedAction projectPath={null} />
+        </div>
+      )}
+    </div>
+  );
+}
+
+export function ProjectBranchesCard({ projectPath }: { projectPath: string }) {
+  const { data: repo } = useProjectBranches(projectPath);
+  if (!repo || (repo.branches.length === 0 && !repo.error)) return null;
+
+  const deletable = repo.branches.filter((b) => b.merged && !b.is_default && !b.checked_out);
+
+  return (
+    <div className="rounded-lg border border-border bg-card p-4">
+      <div className="mb-3 flex items-center gap-2">
+        <GitBranch className="h-4 w-4 text-muted-foreground" />
+        <h3 className="text-sm font-medium">Branches</h3>
+        <span className="text-xs text-muted-foreground">
+          {repo.branches.length} local
+          {repo.default_branch && `, merged into ${repo.default_branch}`}
+        </span>
+      </div>
+      {repo.error ? (
+        <p className="text-xs text-destructive">{repo.error}</p>
+      ) : (
+        <div className="divide-y divide-border">
+          {repo.branches.map((branch) => (
+            <div key={branch.name} className="flex items-center justify-between gap-3 py-2">
+              <div className="min-w-0">
+                <div className="flex items-center gap-2">
+                  <span className={`truncate font-mono text-xs ${branch.is_head ? "font-semibold" : ""}`}>
+                    {branch.name}
+                  </span>
+                  {branch.upstream && (
+                    <span className="truncate text-[10px] text-muted-foreground">
+                      → {branch.upstream}
+                    </span>
+                  )}
+                </div>
+                <p className="truncate text-[10px] text-muted-foreground">
+                  {branch.last_commit_message} · {branch.last_commit_date}
+                </p>
+              </div>
+              <div className="flex shrink-0 items-center gap-2">
+                <AheadBehind branch={branch} />
+                <BranchFlags branch={branch} />
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+      {deletable.length > 0 && (
+        <div className="mt-3 flex border-t border-border pt-3">
+          <DeleteMergedAction projectPath={projectPath} />
+        </div>
+      )}
+    </div>
+  );
+}
@@ -25,8 +25,16 @@ import {
   BookOpen,
   Loader2,
   RefreshCw,
+  Download,
+  FolderOpen,
 } from "lucide-react";
-import { useDockerStatus, useContainerActions, useContainerLogs } from "@/hooks/use-docker";
+import {
+  useDockerStatus,
+  useContainerActions,
+  useComposeActions,
+  useContainerLogs,
+  useContainerLogStream,
+} from "@/hooks/use-docker";
 import { SectionHeader } from "@/components/shared/section-header";
 import { SearchInput } from "@/components/shared/search-input";
 import { StatusBadge } from "@/components/shared/status-badge";
@@ -85,6 +93,7 @@ function getStatusVariant(status: string): "success" | "warning" | "error" | "ne
     case "running":
       return "success";
     case "paused":
+    case "restarting":
       return "warning";
     case "exited":
     case "dead":
@@ -96,6 +105,12 @@ function getStatusVariant(status: string): "success" | "warning" | "error" | "ne
 
 // HTTP-likely ports that should get clickable links
 const HTTP_PORTS = new Set([80, 443, 3000, 3001, 4000, 5000, 5173, 8000, 8080, 8443, 8888, 9000]);
+function getHealthVariant(health: string): "success" | "warning" | "error" {
+  if (health === "healthy") return "success";
+  if (health === "unhealthy") return "error";
+  return "warning";
+}
+
 function isHttpLikelyPort(containerPort: number): boolean {
   return HTTP_PORTS.has(containerPort);
 }
@@ -151,6 +166,11 @@ function ContainerCard({ container }: { container: ContainerInfo }) {
               <StatusBadge variant={getStatusVariant(container.status)}>
                 {container.status}
               </StatusBadge>
+              {container.health && (
+                <StatusBadge variant={getHealthVariant(container.health)}>
+                  {container.health}
+                </StatusBadge>
+              )}
             </div>
             <div className="text-xs text-muted-foreground truncate mt-0.5">
               {container.image}
@@ -244,24 +264,79 @@ function ComposeProjectGroup({
   defaultExpanded?: boolean;
 }) {
   const [expanded, setExpanded] = useState(defaultExpanded);
+  const composeAction = useComposeActions();
   const projectContainers = containers.filter((c) => project.containers.includes(c.id));
+  const canManage = !!project.working_dir;
+  const run = (action: "up" | "down" | "restart" | "pull") =>
+    composeAction.mutate({ project: project.name, action });
 
   return (
     <div className="space-y-2">
-      <button
-        onClick={() => setExpanded(!expanded)}
-        className="flex w-full items-center gap-2 text-sm font-medium hover:text-primary transition-colors"
-      >
-        {expanded ? (
-          <ChevronDown className="h-4 w-4 text-muted-foreground" />
-        ) : (
-          <ChevronRight className="h-4 w-4 text-muted-foreground" />
+      <div className="group flex items-center justify-between gap-2">
+        <button
+          onClick={() => setExpanded(!expanded)}
+          className="flex items-center gap-2 text-sm font-medium hover:text-primary transition-colors"
+        >
+          {expanded ? (
+            <ChevronDown className="h-4 w-4 text-muted-foreground" />
+          ) : (
+            <ChevronRight className="h-4 w-4 text-muted-foreground" />
+          )}
+          <span>{project.name}</span>
+          <span className="text-xs text-muted-foreground">
+            ({project.running_count}/{project.container_count} running)
+          </span>
+          {project.project_name && (
+            <span
+              className="flex items-center gap-1 text-xs text-muted-foreground"
+              title={project.project_path ?? undefined}
+            >
+              <FolderOpen className="h-3 w-3" />
+              {project.project_name}
+            </span>
+          )}
+        </button>
+
+        {canManage && (
+          <div className="flex items-center gap-1 opacity-0 group-hover:opacity-100 transition-opacity">
+            {composeAction.isPending && <Loader2 className="h-3.5 w-3.5 animate-spin text-muted-foreground" />}
+            {project.running_count < project.container_count && (
+              <button
+                onClick={() => run("up")}
+                disabled={composeAction.isPending}
+                className="p-1.5 rounded hover:bg-muted transition-colors"
+                title="docker compose up"
+              >
+                <Play className="h-3.5 w-3.5 text-muted-foreground" />
+              </button>
+            )}
+            <button
+              onClick={() => run("restart")}
+              disabled={composeAction.isPending}
+              className="p-1.5 rounded hover:bg-muted transition-colors"
+              title="docker compose restart"
+            >
+              <RotateCcw className="h-3.5 w-3.5 text-muted-foreground" />
+            </button>
+            <button
+              onClick={() => run("pull")}
+              disabled={composeAction.isPending}
+              className="p-1.5 rounded hover:bg-muted transition-colors"
+              title="docker compose pull"
+            >
+              <Download className="h-3.5 w-3.5 text-muted-foreground" />
+            </button>
+            <button
+              onClick={() => run("down")}
+              disabled={composeAction.isPending}
+              className="p-1.5 rounded hover:bg-muted transition-colors"
+              title="docker compose down"
+            >
+              <Square className="h-3.5 w-3.5 text-muted-foreground" />
+            </button>
+          </div>
         )}
-        <span>{project.name}</span>
-        <span className="text-xs text-muted-foreground">
-          ({project.running_count}/{project.container_count} running)
-        </span>
-      </button>
+      </div>
 
       {expanded && (
         <div className="grid gap-3 pl-6 sm:grid-cols-2 lg:grid-cols-3">
@@ -279,8 +354,15 @@ function ComposeProjectGroup({
 function ContainerDetailView({ containerId }: { containerId: string }) {
   const setDetailContext = useNavigationStore((s) => s.setDetailContext);
   const { data: dockerStatus, isLoading } = useDockerStatus();
-  const { data: logs, isLoading: logsLoading, refetch: refetchLogs } = useContainerLogs(containerId);
+  const [followLogs, setFollowLogs] = useState(false);
+  const [showTimestamps, setShowTimestamps] = useState(false);
+  const { data: logs, isLoading: logsLoading, refetch: refetchLogs } = useContainerLogs(containerId, !followLogs);
+  const logStream = useContainerLogStream(containerId, {
+    enabled: followLogs,
+    timestamps: showTimestamps,
+  });
   const { startContainer, stopContainer, restartContainer } = useContainerActions();
+  const composeAction = useComposeActions();
   const queryClient = useQueryClient();
 
   const container = dockerStatus?.containers.find((c) => c.id === containerId);
@@ -359,6 +441,23 @@ function ContainerDetailView({ containerId }: { containerId: string }) {
               Start
             </button>
           )}
+          {container.compose_project && container.compose_service && (
+            <button
+              onClick={() =>
+                composeAction.mutate({
+                  project: container.compose_project!,
+                  action: "recreate",
+                  service: container.compose_service!,
+                })
+              }
+              disabled={isPending || composeAction.isPending}
+              className="flex items-center gap-1.5 rounded-md bg-muted px-3 py-1.5 text-sm hover:bg-muted/80 transition-colors"
+              title="Recreate this service's containers (docker compose up --force-recreate)"
+            >
+              {composeAction.isPending ? <Loader2 className="h-4 w-4 animate-spin" /> : <RefreshCw className="h-4 w-4" />}
+              Recreate
+            </button>
+          )}
         </div>
       </div>
 
@@ -384,6 +483,34 @@ function ContainerDetailView({ containerId }: { containerId: string }) {
               <dt className="text-muted-foreground">Created</dt>
               <dd className="text-xs">{container.created}</dd>
             </div>
+            {container.health && (
+              <div className="flex justify-between">
+                <dt className="text-muted-foreground">Health</dt>
+                <dd>
+                  <StatusBadge variant={getHealthVariant(container.health)}>
+                    {container.health}
+                  </StatusBadge>
+                </dd>
+              </div>
+            )}
+            {(container.restart_count ?? 0) > 0 && (
+              <div className="flex justify-between">
+                <dt className="text-muted-foreground">Restarts</dt>
+                <dd>{container.restart_count}</dd>
+              </div>
+            )}
+            {container.exit_code != null && (
+              <div className="flex justify-between">
+                <dt className="text-muted-foreground">Exit code</dt>
+                <dd className="font-mono text-xs">{container.exit_code}</dd>
+              </div>
+            )}
+            {container.networks && container.networks.length > 0 && (
+              <div className="flex justify-between gap-4">
+                <dt className="text-muted-foreground">Networks</dt>
+                <dd className="truncate font-mono text-xs">{container.networks.join(", ")}</dd>
+              </div>
+            )}
           </dl>
         </div>
 
@@ -440,6 +567,26 @@ function ContainerDetailView({ containerId }: { containerId: string }) {
           </div>
         )}
 
+        {/* Volumes */}
+        {container.volumes && container.volumes.length > 0 && (
+          <div className="rounded-lg border border-border bg-card p-4">
+            <h3 className="text-sm font-medium mb-3">Volumes</h3>
+            <div className="space-y-1.5">
+              {container.volumes.map((v) => (
+                <div key={v.destination} className="flex items-center justify-between gap-3 text-sm">
+                  <span className="truncate font-mono text-xs" title={v.source}>
+                    {v.name ?? v.source}
+                  </span>
+                  <span className="shrink-0 text-xs text-muted-foreground">
+                    {v.destination}
+                    {v.read_only && " (ro)"}
+                  </span>
+                </div>
+              ))}
+            </div>
+          </div>
+        )}
+
         {/* Compose Info */}
         {container.compose_project && (
           <div className="rounded-lg border border-border bg-card p-4">
@@ -468,17 +615,57 @@ function ContainerDetailView({ containerId }: { containerId: string }) {
               <Terminal className="h-4 w-4 text-muted-foreground" />
               <h3 className="text-sm font-medium">Logs</h3>
             </div>
-            <button
-              onClick={() => refetchLogs()}
-              disabled={logsLoading}
-              className="p-1.5 rounded hover:bg-muted transition-colors"
-              title="Refresh logs"
-            >
-              <RefreshCw className={`h-3.5 w-3.5 text-muted-foreground ${logsLoading ? "animate-spin" : ""}`} />
-            </button>
+            <div className="flex items-center gap-1">
+              {followLogs && (
+                <button
+                  onClick={() => setShowTimestamps((v) => !v)}
+                  className={`rounded px-2 py-1 text-xs transition-colors ${
+                    showTimestamps ? "bg-muted text-foreground" : "text-muted-foreground hover:bg-muted"
+                  }`}
+                  title="Show timestamps"
+                >
+                  Timestamps
+                </button>
+              )}
+              <button
+                onClick={() => setFollowLogs((v) => !v)}
+                className={`rounded px-2 py-1 text-xs transition-colors ${
+                  followLogs ? "bg-muted text-foreground" : "text-muted-foreground hover:bg-muted"
+                }`}
+                title="Stream new lines as they are written"
+              >
+                Follow
+              </button>
+              {!followLogs && (
+                <button
+                  onClick={() => refetchLogs()}
+                  disabled={logsLoading}
+                  className="p-1.5 rounded hover:bg-muted transition-colors"
+                  title="Refresh logs"
+                >
+                  <RefreshCw className={`h-3.5 w-3.5 text-muted-foreground ${logsLoading ? "animate-spin" : ""}`} />
+                </button>
+              )}
+            </div>
           </div>
           <div className="max-h-64 overflow-auto p-4">
-            {logsLoading ? (
+            {followLogs ? (
+              logStream.lines.length > 0 ? (
+                <pre className="font-mono text-xs whitespace-pre-wrap">
+                  {logStream.lines.map((line, i) => (
+                    <div
+                      key={i}
+                      className={line.stream === "stderr" ? "text-destructive" : "text-muted-foreground"}
+                    >
+                      {line.timestamp && <span className="opacity-60">{line.timestamp} </span>}
+                      {line.message}
+                    </div>
+                  ))}
+                </pre>
+              ) : (
+                <p className="text-sm text-muted-foreground text-center py-4">Waiting for output...</p>
+              )
+            ) : logsLoading ? (
               <div className="flex items-center justify-center py-8 text-muted-foreground">
                 <Loader2 className="h-4 w-4 animate-spin mr-2" />
                 Loading logs...
@@ -490,6 +677,11 @@ function ContainerDetailView({ containerId }: { containerId: string }) {
             ) : (
               <p className="text-sm text-muted-foreground text-center py-4">No logs available</p>
             )}
+            {followLogs && logStream.ended !== undefined && (
+              <p className="mt-2 text-xs text-muted-foreground">
+                {logStream.ended ? `Stream ended: ${logStream.ended}` : "Stream ended"}
+              </p>
+            )}
           </div>
         </div>
       )}
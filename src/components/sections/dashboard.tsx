@@ -20,7 +20,7 @@ import { usePathEntries } from "@/hooks/use-path-entries";
 import { useLanguages } from "@/hooks/use-languages";
 import { useAllGitStatuses } from "@/hooks/use-git-status";
 import { useProjects } from "@/hooks/use-workspaces";
-import { usePortMap } from "@/hooks/use-port-map";
+import { usePortMap, usePortMapDetails, usePortOwners } from "@/hooks/use-port-map";
 import { useVersionMismatches } from "@/hooks/use-version-mismatches";
 import { useQuery } from "@tanstack/react-query";
 import { commands } from "@/lib/commands";
@@ -28,6 +28,69 @@ import { StatusDot } from "@/components/shared/status-dot";
 import { StatusBadge } from "@/components/shared/status-badge";
 import { StatCardSkeleton, InfoCardSkeleton } from "@/components/shared/skeleton";
 import { useNavigationStore } from "@/stores/navigation";
+import { UnpushedWorkPanel } from "./branches";
+
+const portOwnerLabels = {
+  dev_server: "dev server",
+  container: "docker",
+  process: "process",
+  declared: "configured",
+} as const;
+
+/** "Who is using port X?" */
+function PortLookup() {
+  const [input, setInput] = useState("");
+  const [port, setPort] = useState<number | null>(null);
+  const { data: owners, isFetching } = usePortOwners(port);
+
+  return (
+    <div className="border-t border-border px-4 py-3">
+      <form
+        className="flex items-center gap-2"
+        onSubmit={(e) => {
+          e.preventDefault();
+          const n = Number(input);
+          setPort(Number.isInteger(n) && n > 0 && n < 65536 ? n : null);
+        }}
+      >
+        <input
+          value={input}
+          onChange={(e) => setInput(e.target.value)}
+          placeholder="Who is using port…"
+          inputMode="numeric"
+          className="w-44 rounded-md border border-border bg-background px-2.5 py-1 font-mono text-xs focus:border-primary focus:outline-none"
+        />
+        <button
+          type="submit"
+          className="rounded px-2 py-1 text-xs text-muted-foreground transition-colors hover:bg-muted"
+        >
+          Look up
+        </button>
+        {isFetching && <span className="text-xs text-muted-foreground">Checking…</span>}
+      </form>
+      {port != null && owners && (
+        <div className="mt-2 space-y-1 text-xs">
+          {owners.length === 0 ? (
+            <span className="text-muted-foreground">Nothing is using port {port}</span>
+          ) : (
+            owners.map((owner, i) => (
+              <div key={i} className="flex items-center gap-2">
+                <StatusBadge variant={owner.kind === "declared" ? "neutral" : "info"}>
+                  {portOwnerLabels[owner.kind]}
+                </StatusBadge>
+                <span className="font-medium">{owner.name}</span>
+                {owner.pid != null && <span className="text-muted-foreground">PID {owner.pid}</span>}
+                {owner.source && (
+                  <span className="truncate font-mono text-muted-foreground">{owner.source}</span>
+                )}
+              </div>
+            ))
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
 
 function MetricCard({
   icon: Icon,
@@ -85,6 +148,8 @@ export function Dashboard() {
   const portStats = usePortMap();
   const { totalCount: versionMismatchCount } = useVersionMismatches();
   const [showPortMap, setShowPortMap] = useState(false);
+  const { data: portMap } = usePortMapDetails();
+  const portProblems = (portMap?.conflicts.length ?? 0) + (portMap?.zombies.length ?? 0);
 
   const pathWarnings =
     paths?.filter((p) => !p.exists || p.is_duplicate).length ?? 0;
@@ -273,9 +338,17 @@ export function Dashboard() {
           icon={Network}
           label="Active Ports"
           value={portStats.totalPorts}
-          status={portStats.hasConflicts ? "warning" : portStats.totalPorts > 0 ? "info" : "success"}
+          status={
+            portStats.hasConflicts || portProblems > 0
+              ? "warning"
+              : portStats.totalPorts > 0
+                ? "info"
+                : "success"
+          }
           detail={
-            portStats.hasConflicts
+            portProblems > 0
+              ? `${portProblems} port problem${portProblems !== 1 ? "s" : ""}`
+              : portStats.hasConflicts
               ? `${portStats.conflicts.length} port conflict${portStats.conflicts.length !== 1 ? "s" : ""}`
               : portStats.totalPorts > 0
                 ? `${portStats.totalPorts} port${portStats.totalPorts !== 1 ? "s" : ""} in use`
@@ -353,9 +426,36 @@ export function Dashboard() {
               No active ports detected
             </div>
           )}
+          {portMap && portProblems > 0 && (
+            <div className="divide-y divide-border border-t border-border">
+              {portMap.conflicts.map((conflict) => (
+                <div key={`conflict-${conflict.port}`} className="flex items-start gap-3 px-4 py-2.5">
+                  <span className="font-mono text-sm font-semibold">:{conflict.port}</span>
+                  <span className="flex-1 text-xs text-muted-foreground">{conflict.reason}</span>
+                  <StatusBadge variant={conflict.live ? "warning" : "neutral"}>
+                    {conflict.live ? "conflict" : "would collide"}
+                  </StatusBadge>
+                </div>
+              ))}
+              {portMap.zombies.map((zombie) => (
+                <div key={`zombie-${zombie.pid}-${zombie.port}`} className="flex items-start gap-3 px-4 py-2.5">
+                  <span className="font-mono text-sm font-semibold">:{zombie.port}</span>
+                  <span className="flex-1 text-xs text-muted-foreground">
+                    {zombie.name} (PID {zombie.pid}) is serving a deleted directory
+                    {zombie.source && <span className="font-mono"> {zombie.source}</span>}
+                  </span>
+                  <StatusBadge variant="warning">zombie</StatusBadge>
+                </div>
+              ))}
+            </div>
+          )}
+          <PortLookup />
         </div>
       )}
 
+      {/* Unpushed and mergeable branches across repos */}
+      {gitStatuses && gitStatuses.length > 0 && <UnpushedWorkPanel />}
+
       {/* System overview + Languages */}
       <div className="grid grid-cols-2 gap-4">
         {/* System card */}
@@ -48,9 +48,15 @@ export function LanguagesSection() {
                 <div className="flex items-start justify-between">
                   <div>
                     <h3 className="text-sm font-medium">{lang.name}</h3>
-                    <p className="mt-0.5 font-mono text-lg font-semibold tracking-tight">
-                      {lang.version}
-                    </p>
+                    {lang.error ? (
+                      <p className="mt-0.5 text-xs text-warning" title={lang.error}>
+                        Version check timed out
+                      </p>
+                    ) : (
+                      <p className="mt-0.5 font-mono text-lg font-semibold tracking-tight">
+                        {lang.version}
+                      </p>
+                    )}
                   </div>
                   <CheckCircle2 className="h-4 w-4 text-success" />
                 </div>
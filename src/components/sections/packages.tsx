@@ -7,6 +7,7 @@ import { CopyButton } from "@/components/shared/copy-button";
 import { EmptyState } from "@/components/shared/empty-state";
 import { TableSkeleton } from "@/components/shared/skeleton";
 import { IssueLinkBadge } from "@/components/shared/issue-link-badge";
+import { ErrorBanner } from "@/components/shared/error-banner";
 import { useQueryClient } from "@tanstack/react-query";
 import type { PackageInfo } from "@/lib/commands";
 
@@ -115,6 +116,15 @@ export function PackagesSection() {
 
       <IssueLinkBadge section="packages" />
 
+      {/* Package managers that timed out or failed to list */}
+      {packages?.errors?.map((err) => (
+        <ErrorBanner
+          key={err.source}
+          message={err.message}
+          onRetry={() => queryClient.invalidateQueries({ queryKey: ["global-packages"] })}
+        />
+      ))}
+
       {/* Content */}
       {isLoading ? (
         <TableSkeleton rows={8} />
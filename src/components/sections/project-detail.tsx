@@ -1,3 +1,4 @@
+import { useState } from "react";
 import {
   ArrowLeft,
   Terminal,
@@ -22,15 +23,30 @@ import {
   XCircle,
   FileText,
   AlertTriangle,
+  Container,
+  RotateCw,
+  Layers,
 } from "lucide-react";
 import { useProjectAnalysis } from "@/hooks/use-project-analysis";
 import { useProjects } from "@/hooks/use-workspaces";
-import { useDevServers, useStopDevServer, useStartDevServer } from "@/hooks/use-dev-servers";
+import {
+  useDevServers,
+  useStopDevServer,
+  useStartDevServer,
+  useRestartDevServer,
+  useSetDevServerAutoRestart,
+  useManagedServers,
+  useDevServerOutput,
+  useDevCommands,
+} from "@/hooks/use-dev-servers";
 import { useVersionMismatches } from "@/hooks/use-version-mismatches";
+import { useComposeStacks, useComposeActions } from "@/hooks/use-docker";
 import { useNavigationStore } from "@/stores/navigation";
 import { commands } from "@/lib/commands";
 import type {
   ProjectAnalysis,
+  DetectedStack,
+  LineCount,
   DirectorySize,
   LanguageDetails,
   DevServer,
@@ -41,6 +57,8 @@ import { StatusBadge } from "@/components/shared/status-badge";
 import { CopyButton } from "@/components/shared/copy-button";
 import { InfoCardSkeleton } from "@/components/shared/skeleton";
 import { useQueryClient } from "@tanstack/react-query";
+import { ProjectBranchesCard } from "./branches";
+import { ProjectWorktreesCard } from "./worktrees";
 import { useSettings, getSettingValue } from "@/hooks/use-settings";
 import { getToolLabel } from "@/lib/tool-filters";
 
@@ -53,6 +71,40 @@ function formatUptime(secs: number): string {
   return `${s}s`;
 }
 
+const managedStatusVariant = {
+  starting: "info",
+  running: "success",
+  stopping: "warning",
+  stopped: "neutral",
+  crashed: "error",
+} as const;
+
+function DevServerOutput({ id }: { id: number }) {
+  const lines = useDevServerOutput(id);
+  return (
+    <div className="mt-3 max-h-64 overflow-auto rounded-md bg-muted/50 p-2 font-mono text-xs">
+      {lines.length === 0 ? (
+        <span className="text-muted-foreground">No output yet</span>
+      ) : (
+        lines.map((line, i) => (
+          <div
+            key={i}
+            className={
+              line.stream === "stderr"
+                ? "text-destructive"
+                : line.stream === "system"
+                  ? "italic text-muted-foreground/70"
+                  : "text-muted-foreground"
+            }
+          >
+            {line.line}
+          </div>
+        ))
+      )}
+    </div>
+  );
+}
+
 function DevServerCard({
   server,
   projectPath,
@@ -62,32 +114,87 @@ function DevServerCard({
 }) {
   const stopServer = useStopDevServer();
   const startServer = useStartDevServer();
-  const isPending = stopServer.isPending || startServer.isPending;
+  const restartServer = useRestartDevServer();
+  const setAutoRestart = useSetDevServerAutoRestart();
+  const { data: managedServers } = useManagedServers();
+  const { data: devCommands } = useDevCommands(projectPath);
+  const [showOutput, setShowOutput] = useState(false);
+  const [pickedCommand, setPickedCommand] = useState<string | null>(null);
+  const isPending = stopServer.isPending || startServer.isPending || restartServer.isPending;
+
+  const managed = managedServers?.find((m) =>
+    server?.managed_id != null ? m.id === server.managed_id : m.project_path === projectPath,
+  );
+
+  const outputToggle = managed && (
+    <button
+      onClick={() => setShowOutput((v) => !v)}
+      className={`rounded px-2 py-1 text-xs transition-colors ${
+        showOutput ? "bg-muted text-foreground" : "text-muted-foreground hover:bg-muted"
+      }`}
+      title="Show captured stdout/stderr"
+    >
+      Output
+    </button>
+  );
 
   if (!server) {
+    const crashed = managed?.status === "crashed";
+    const candidates = devCommands?.candidates ?? [];
+    const saved = devCommands?.saved ?? null;
+    const command = pickedCommand ?? saved ?? candidates[0]?.command ?? null;
     return (
       <div className="rounded-lg border border-border bg-card p-4">
         <div className="mb-3 flex items-center gap-2">
           <Radio className="h-4 w-4 text-muted-foreground" />
           <h3 className="text-sm font-medium">Dev Server</h3>
+          {managed && (
+            <StatusBadge variant={managedStatusVariant[managed.status]}>
+              {managed.status}
+            </StatusBadge>
+          )}
         </div>
         <div className="flex items-center justify-between">
           <span className="text-sm text-muted-foreground">
-            No dev server running
+            {crashed
+              ? `\`${managed.command}\` exited with code ${managed.exit_code ?? "?"}`
+              : "No dev server running"}
           </span>
-          <button
-            onClick={() => startServer.mutate({ projectPath })}
-            disabled={isPending}
-            className="inline-flex items-center gap-1.5 rounded-md bg-primary px-3 py-1.5 text-xs font-medium text-primary-foreground transition-colors hover:bg-primary/90 disabled:opacity-50"
-          >
-            {isPending ? (
-              <Loader2 className="h-3 w-3 animate-spin" />
-            ) : (
-              <Play className="h-3 w-3" />
+          <div className="flex items-center gap-2">
+            {(candidates.length > 0 || saved) && (
+              <select
+                value={command ?? ""}
+                onChange={(e) => setPickedCommand(e.target.value)}
+                className="max-w-64 rounded-md border border-border bg-background px-2 py-1 font-mono text-xs focus:border-primary focus:outline-none"
+                title="Start command (remembered for this project)"
+              >
+                {saved && !candidates.some((c) => c.command === saved) && (
+                  <option value={saved}>{saved} (saved)</option>
+                )}
+                {candidates.map((c) => (
+                  <option key={c.command} value={c.command}>
+                    {c.command} · {c.source}
+                  </option>
+                ))}
+              </select>
             )}
-            Start
-          </button>
+            {outputToggle}
+            <button
+              onClick={() => command && startServer.mutate({ projectPath, command })}
+              disabled={isPending || !command}
+              title={command ? undefined : "No start command found for this project"}
+              className="inline-flex items-center gap-1.5 rounded-md bg-primary px-3 py-1.5 text-xs font-medium text-primary-foreground transition-colors hover:bg-primary/90 disabled:opacity-50"
+            >
+              {isPending ? (
+                <Loader2 className="h-3 w-3 animate-spin" />
+              ) : (
+                <Play className="h-3 w-3" />
+              )}
+              Start
+            </button>
+          </div>
         </div>
+        {showOutput && managed && <DevServerOutput id={managed.id} />}
       </div>
     );
   }
@@ -101,20 +208,54 @@ function DevServerCard({
             <span className="relative inline-flex h-2.5 w-2.5 rounded-full bg-success" />
           </span>
           <h3 className="text-sm font-medium">Dev Server</h3>
-          <StatusBadge variant="success">running</StatusBadge>
-        </div>
-        <button
-          onClick={() => stopServer.mutate(server.pid)}
-          disabled={isPending}
-          className="inline-flex items-center gap-1.5 rounded-md border border-border bg-card px-3 py-1.5 text-xs font-medium transition-colors hover:bg-accent disabled:opacity-50"
-        >
-          {isPending ? (
-            <Loader2 className="h-3 w-3 animate-spin" />
+          {managed ? (
+            <StatusBadge variant={managedStatusVariant[managed.status]}>
+              {managed.status}
+            </StatusBadge>
           ) : (
-            <Square className="h-3 w-3" />
+            <StatusBadge variant="success">running</StatusBadge>
           )}
-          Stop
-        </button>
+        </div>
+        <div className="flex items-center gap-2">
+          {managed && (
+            <button
+              onClick={() =>
+                setAutoRestart.mutate({ id: managed.id, enabled: !managed.auto_restart })
+              }
+              className={`rounded px-2 py-1 text-xs transition-colors ${
+                managed.auto_restart
+                  ? "bg-muted text-foreground"
+                  : "text-muted-foreground hover:bg-muted"
+              }`}
+              title="Restart automatically if it crashes"
+            >
+              Auto-restart
+            </button>
+          )}
+          {outputToggle}
+          {managed && (
+            <button
+              onClick={() => restartServer.mutate(managed.id)}
+              disabled={isPending}
+              className="inline-flex items-center gap-1.5 rounded-md border border-border bg-card px-3 py-1.5 text-xs font-medium transition-colors hover:bg-accent disabled:opacity-50"
+            >
+              <RotateCw className="h-3 w-3" />
+              Restart
+            </button>
+          )}
+          <button
+            onClick={() => stopServer.mutate(server.pid)}
+            disabled={isPending}
+            className="inline-flex items-center gap-1.5 rounded-md border border-border bg-card px-3 py-1.5 text-xs font-medium transition-colors hover:bg-accent disabled:opacity-50"
+          >
+            {isPending ? (
+              <Loader2 className="h-3 w-3 animate-spin" />
+            ) : (
+              <Square className="h-3 w-3" />
+            )}
+            Stop
+          </button>
+        </div>
       </div>
       <div className="grid grid-cols-3 gap-4 text-sm">
         <div>
@@ -134,6 +275,7 @@ function DevServerCard({
           </p>
         </div>
       </div>
+      {showOutput && managed && <DevServerOutput id={managed.id} />}
     </div>
   );
 }
@@ -423,6 +565,74 @@ function GoDetailsCard({ details }: { details: LanguageDetails & { type: "Go" }
   );
 }
 
+const lineCountColors = [
+  "bg-emerald-500",
+  "bg-blue-500",
+  "bg-amber-500",
+  "bg-purple-500",
+  "bg-pink-500",
+  "bg-cyan-500",
+];
+
+function StacksCard({ stacks, lineCounts }: { stacks: DetectedStack[]; lineCounts: LineCount[] }) {
+  const totalLines = lineCounts.reduce((sum, c) => sum + c.lines, 0);
+  const shown = lineCounts.slice(0, lineCountColors.length);
+
+  return (
+    <div className="rounded-lg border border-border bg-card p-4">
+      <div className="mb-3 flex items-center gap-2">
+        <Layers className="h-4 w-4 text-muted-foreground" />
+        <h3 className="text-sm font-medium">Stacks</h3>
+      </div>
+
+      <div className="space-y-1.5">
+        {stacks.map((stack, i) => (
+          <div key={`${stack.subdir}:${stack.language_display}:${stack.framework}`} className="flex items-center gap-2 text-xs">
+            <span className="w-28 shrink-0 truncate font-mono text-muted-foreground" title={stack.subdir || "root"}>
+              {stack.subdir ? `${stack.subdir}/` : "root"}
+            </span>
+            <StatusBadge variant={i === 0 ? "info" : "neutral"}>{stack.language_display}</StatusBadge>
+            {stack.framework !== stack.language_display && (
+              <span className="font-medium">{stack.framework}</span>
+            )}
+            {stack.package_manager !== "unknown" && (
+              <span className="text-muted-foreground">{stack.package_manager_display}</span>
+            )}
+          </div>
+        ))}
+      </div>
+
+      {totalLines > 0 && (
+        <div className="mt-4">
+          <div className="mb-2 flex h-3 w-full overflow-hidden rounded-full bg-muted">
+            {shown.map((count, i) => (
+              <div
+                key={count.language}
+                className={lineCountColors[i]}
+                style={{ width: `${(count.lines / totalLines) * 100}%` }}
+                title={`${count.language}: ${count.lines.toLocaleString()} lines`}
+              />
+            ))}
+          </div>
+          <div className="flex flex-wrap gap-3 text-[10px]">
+            {shown.map((count, i) => (
+              <div key={count.language} className="flex items-center gap-1">
+                <div className={`h-2 w-2 rounded-full ${lineCountColors[i]}`} />
+                <span className="text-muted-foreground">
+                  {count.language} {count.lines.toLocaleString()}
+                </span>
+              </div>
+            ))}
+            {lineCounts.length > shown.length && (
+              <span className="text-muted-foreground">+{lineCounts.length - shown.length} more</span>
+            )}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
 function LanguageDetailsCard({ details }: { details: LanguageDetails }) {
   switch (details.type) {
     case "JavaScript":
@@ -697,6 +907,9 @@ function VersionMismatchCard({ projectPath }: { projectPath: string }) {
                 <XCircle className="h-3.5 w-3.5 text-warning" />
               )}
               <span className="font-mono text-xs">{m.file.name}</span>
+              {!m.matches && m.fix && (
+                <code className="rounded bg-muted px-1.5 py-0.5 text-[10px]">{m.fix}</code>
+              )}
             </div>
             <div className="flex items-center gap-3 text-xs text-muted-foreground">
               <span>
@@ -713,6 +926,80 @@ function VersionMismatchCard({ projectPath }: { projectPath: string }) {
   );
 }
 
+const COLLISION_LABELS: Record<string, string> = {
+  container: "container",
+  dev_server: "dev server",
+  stack: "stack",
+};
+
+function ComposeStackCard({ projectPath }: { projectPath: string }) {
+  const { data: stacks } = useComposeStacks();
+  const composeAction = useComposeActions();
+  const stack = stacks?.find((s) => s.project_path === projectPath);
+
+  if (!stack) return null;
+
+  const total = stack.services.length;
+  const hasCollisions = stack.collisions.length > 0;
+
+  return (
+    <div
+      className={`rounded-lg border p-4 ${
+        hasCollisions ? "border-warning/25 bg-warning/5" : "border-border bg-card"
+      }`}
+    >
+      <div className="mb-3 flex items-center justify-between gap-2">
+        <div className="flex items-center gap-2">
+          <Container className="h-4 w-4 text-muted-foreground" />
+          <h3 className="text-sm font-medium">Compose Stack</h3>
+          <span className="text-xs text-muted-foreground">
+            {total} {total === 1 ? "service" : "services"}, {stack.running_count} running
+          </span>
+          {hasCollisions && <StatusBadge variant="warning">port conflict</StatusBadge>}
+        </div>
+        {stack.running_count < total && (
+          <button
+            onClick={() => composeAction.mutate({ project: stack.compose_project, action: "up" })}
+            disabled={composeAction.isPending}
+            className="flex items-center gap-1.5 rounded-md bg-muted px-2.5 py-1 text-xs hover:bg-muted/80 transition-colors"
+            title={hasCollisions ? "Some ports are already taken" : "docker compose up"}
+          >
+            {composeAction.isPending ? <Loader2 className="h-3 w-3 animate-spin" /> : <Play className="h-3 w-3" />}
+            Up
+          </button>
+        )}
+      </div>
+
+      <div className="space-y-1.5">
+        {stack.services.map((service) => (
+          <div key={service.name} className="flex items-center justify-between text-sm">
+            <div className="flex items-center gap-2">
+              <span className={`h-1.5 w-1.5 rounded-full ${service.running ? "bg-success" : "bg-muted-foreground/40"}`} />
+              <span className="font-mono text-xs">{service.name}</span>
+              {service.image && <span className="text-xs text-muted-foreground">{service.image}</span>}
+            </div>
+            <span className="font-mono text-xs text-muted-foreground">
+              {service.ports.map((p) => p.published).join(", ")}
+            </span>
+          </div>
+        ))}
+      </div>
+
+      {hasCollisions && (
+        <div className="mt-3 space-y-1 border-t border-border pt-3">
+          {stack.collisions.map((c) => (
+            <p key={`${c.service}-${c.port}-${c.owner}`} className="text-xs text-warning">
+              :{c.port} for {c.service} is taken by {COLLISION_LABELS[c.kind] ?? c.kind} {c.owner}
+            </p>
+          ))}
+        </div>
+      )}
+
+      <p className="mt-3 font-mono text-[10px] text-muted-foreground">{stack.file}</p>
+    </div>
+  );
+}
+
 export function ProjectDetail({ projectPath, projectName }: ProjectDetailProps) {
   const setDetailContext = useNavigationStore((s) => s.setDetailContext);
   const queryClient = useQueryClient();
@@ -789,14 +1076,24 @@ export function ProjectDetail({ projectPath, projectName }: ProjectDetailProps)
           {/* Version mismatch */}
           <VersionMismatchCard projectPath={projectPath} />
 
+          {/* Compose stack */}
+          <ComposeStackCard projectPath={projectPath} />
+
           {/* Top row: Storage + Language Details */}
           <div className="grid gap-4 md:grid-cols-2">
             <StorageCard analysis={analysis} />
             <LanguageDetailsCard details={analysis.language_details} />
           </div>
 
+          {/* Stacks and line counts */}
+          {(analysis.stacks.length > 1 || analysis.line_counts.length > 0) && (
+            <StacksCard stacks={analysis.stacks} lineCounts={analysis.line_counts} />
+          )}
+
           {/* Git info */}
           {analysis.git_info && <GitInfoCard gitInfo={analysis.git_info} />}
+          {analysis.git_info && <ProjectBranchesCard projectPath={projectPath} />}
+          {analysis.git_info && <ProjectWorktreesCard projectPath={projectPath} />}
 
           {/* Directory breakdown */}
           <DirectoryBreakdownTable directories={analysis.storage_breakdown} />
@@ -19,6 +19,8 @@ import {
   Sparkles,
   FolderSearch,
   Code2,
+  FileJson,
+  Boxes,
 } from "lucide-react";
 import { useAppStats } from "@/hooks/use-app-stats";
 import { SectionHeader } from "@/components/shared/section-header";
@@ -30,10 +32,11 @@ import { check } from "@tauri-apps/plugin-updater";
 import { relaunch } from "@tauri-apps/plugin-process";
 import { useSettings, useSetSetting, getSettingValue } from "@/hooks/use-settings";
 import { appDataDir } from "@tauri-apps/api/path";
-import { useQuery } from "@tanstack/react-query";
+import { useMutation, useQuery } from "@tanstack/react-query";
 import { ReleaseNotesSection } from "./release-notes";
 import { useUpdateStore, type UpdateStatus } from "@/stores/update";
-import { commands } from "@/lib/commands";
+import { commands, type ManifestComparison } from "@/lib/commands";
+import { open, save } from "@tauri-apps/plugin-dialog";
 import { filterToolsByCategory } from "@/lib/tool-filters";
 
 function formatUptime(secs: number): string {
@@ -119,6 +122,7 @@ function GeneralTabContent() {
   const autoScan = getSettingValue(settings, "auto_scan_on_startup", "true");
   const snarkyEnabled = getSettingValue(settings, "snarky_comments", "true");
   const scanDepth = getSettingValue(settings, "scan_max_depth", "5");
+  const worktreeDirectory = getSettingValue(settings, "worktree_directory", "{repo}.worktrees");
 
   const selectClass = "w-full rounded-md border border-border bg-background px-2.5 py-1.5 text-sm focus:border-primary focus:outline-none";
 
@@ -265,6 +269,26 @@ function GeneralTabContent() {
               </select>
             </div>
           </div>
+          <div>
+            <label className="mb-1 block text-xs text-muted-foreground">
+              New Worktrees Go In
+            </label>
+            <input
+              key={worktreeDirectory}
+              defaultValue={worktreeDirectory}
+              onBlur={(e) => {
+                const value = e.target.value.trim();
+                if (value && value !== worktreeDirectory) {
+                  setSetting.mutate({ key: "worktree_directory", value });
+                }
+              }}
+              placeholder="{repo}.worktrees"
+              className={cn(selectClass, "font-mono")}
+            />
+            <p className="mt-1 text-[10px] text-muted-foreground">
+              Relative to the repository&apos;s parent folder; {"{repo}"} is the repository name.
+            </p>
+          </div>
           <div className="flex items-center justify-between">
             <label className="text-xs text-muted-foreground">
               Auto-scan on startup
@@ -545,6 +569,174 @@ function DatabaseCard() {
   );
 }
 
+function MachineManifestCard() {
+  const [busy, setBusy] = useState(false);
+  const [message, setMessage] = useState("");
+  const [comparison, setComparison] = useState<ManifestComparison | null>(null);
+
+  const handleExport = async () => {
+    const path = await save({
+      defaultPath: "houston-manifest.json",
+      filters: [{ name: "Houston manifest", extensions: ["json"] }],
+    });
+    if (!path) return;
+    setBusy(true);
+    try {
+      await commands.exportManifest(path);
+      setComparison(null);
+      setMessage(`Exported to ${path}`);
+    } catch (e) {
+      setMessage(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleCompare = async () => {
+    const path = await open({
+      multiple: false,
+      filters: [{ name: "Houston manifest", extensions: ["json"] }],
+    });
+    if (typeof path !== "string") return;
+    setBusy(true);
+    try {
+      const result = await commands.compareManifest(path);
+      setComparison(result);
+      setMessage(
+        result.gaps.length === 0
+          ? `Matches ${result.reference_hostname}`
+          : `${result.missing_count} missing, ${result.mismatch_count} different vs ${result.reference_hostname}`,
+      );
+    } catch (e) {
+      setComparison(null);
+      setMessage(String(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="rounded-lg border border-border bg-card p-4">
+      <div className="mb-3 flex items-center justify-between">
+        <div className="flex items-center gap-2 text-sm font-medium">
+          <FileJson className="h-4 w-4 text-muted-foreground" />
+          Machine Manifest
+        </div>
+        <div className="flex gap-2">
+          <button
+            onClick={handleExport}
+            disabled={busy}
+            className="rounded-md border border-border px-2.5 py-1 text-xs font-medium transition-colors hover:bg-accent disabled:opacity-50"
+          >
+            Export
+          </button>
+          <button
+            onClick={handleCompare}
+            disabled={busy}
+            className="rounded-md border border-border px-2.5 py-1 text-xs font-medium transition-colors hover:bg-accent disabled:opacity-50"
+          >
+            Compare…
+          </button>
+        </div>
+      </div>
+      <p className="text-xs text-muted-foreground">
+        Export languages, PATH, global packages, AI tools and MCP servers, or
+        compare this machine against a teammate's manifest.
+      </p>
+      {busy && <Loader2 className="mt-3 h-4 w-4 animate-spin text-muted-foreground" />}
+      {message && !busy && <p className="mt-3 text-xs text-foreground">{message}</p>}
+      {comparison && comparison.gaps.length > 0 && (
+        <div className="mt-3 max-h-64 space-y-1 overflow-y-auto text-xs">
+          {comparison.gaps.map((gap) => (
+            <div
+              key={`${gap.category}:${gap.group ?? ""}:${gap.name}`}
+              className="flex items-center justify-between gap-2"
+            >
+              <span className="truncate font-mono">
+                {gap.group ? `${gap.group}/` : ""}
+                {gap.name}
+              </span>
+              <span
+                className={cn(
+                  "shrink-0 font-mono",
+                  gap.kind === "missing" ? "text-destructive" : "text-warning",
+                )}
+              >
+                {gap.kind === "missing"
+                  ? `missing${gap.expected ? ` (${gap.expected})` : ""}`
+                  : `${gap.actual ?? "?"} → ${gap.expected ?? "?"}`}
+              </span>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
+
+function DetectionRegistryCard() {
+  const queryClient = useQueryClient();
+  const { data: status } = useQuery({
+    queryKey: ["detection-registry"],
+    queryFn: commands.getDetectionRegistry,
+  });
+  const reload = useMutation({
+    mutationFn: commands.reloadDetectionRegistry,
+    onSuccess: (next) => {
+      queryClient.setQueryData(["detection-registry"], next);
+      queryClient.invalidateQueries({ queryKey: ["projects"] });
+      queryClient.invalidateQueries({ queryKey: ["diagnostics"] });
+    },
+  });
+
+  return (
+    <div className="rounded-lg border border-border bg-card p-4">
+      <div className="mb-3 flex items-center justify-between">
+        <div className="flex items-center gap-2 text-sm font-medium">
+          <Boxes className="h-4 w-4 text-muted-foreground" />
+          Project Detection
+        </div>
+        <button
+          onClick={() => reload.mutate()}
+          disabled={reload.isPending}
+          className="rounded-md border border-border px-2.5 py-1 text-xs font-medium transition-colors hover:bg-accent disabled:opacity-50"
+        >
+          Reload
+        </button>
+      </div>
+      <p className="text-xs text-muted-foreground">
+        Add languages, frameworks and storage directories to the built-in detection rules
+        from a TOML or JSON file. Changes apply after reloading.
+      </p>
+      {status && (
+        <div className="mt-3 space-y-2 text-xs">
+          {status.paths.map((path) => (
+            <div key={path} className="flex items-center justify-between gap-2">
+              <span className="truncate font-mono text-foreground" title={path}>
+                {path}
+              </span>
+              <span className="shrink-0 text-muted-foreground">
+                {status.loaded.includes(path) ? "loaded" : "not found"}
+              </span>
+            </div>
+          ))}
+          {status.loaded.length > 0 && (
+            <div className="text-muted-foreground">
+              {status.languages} languages, {status.frameworks} frameworks,{" "}
+              {status.storage_dirs} storage dirs added
+            </div>
+          )}
+          {status.errors.map((error, i) => (
+            <div key={i} className="text-destructive" title={error.source}>
+              {error.message}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
+
 interface SoftwareUpdateCardProps {
   updateStatus: UpdateStatus;
   updateVersion: string;
@@ -671,6 +863,9 @@ function SystemTabContent({
           onInstallUpdate={onInstallUpdate}
         />
       </div>
+
+      <MachineManifestCard />
+      <DetectionRegistryCard />
     </div>
   );
 }
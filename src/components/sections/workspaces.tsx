@@ -2,6 +2,7 @@ import { useCallback, useEffect, useMemo, useState } from "react";
 import {
   FolderPlus,
   FolderX,
+  FolderTree,
   GitBranch,
   GitCommit,
   ArrowUp,
@@ -26,12 +27,14 @@ import {
   useAddWorkspace,
   useRemoveWorkspace,
   useWorkspacePaths,
+  useDeepDiscoveryWorkspaces,
+  useSetWorkspaceDeepDiscovery,
 } from "@/hooks/use-workspaces";
 import { useGitStatus, useAllGitStatuses } from "@/hooks/use-git-status";
 import { useDevServers } from "@/hooks/use-dev-servers";
 import { useSetting, useSetSetting, useSettings, getSettingValue } from "@/hooks/use-settings";
 import { getToolLabel } from "@/lib/tool-filters";
-import { commands, type ProjectInfo } from "@/lib/commands";
+import { commands, type GitOperation, type ProjectInfo } from "@/lib/commands";
 import { useNavigationStore } from "@/stores/navigation";
 import { SectionHeader } from "@/components/shared/section-header";
 import { StatusBadge } from "@/components/shared/status-badge";
@@ -46,8 +49,31 @@ import { useVersionMismatches } from "@/hooks/use-version-mismatches";
 import { groupBySubfolder } from "@/lib/group-by-subfolder";
 import { ProjectDetail } from "./project-detail";
 
+const gitOperationLabels: Record<GitOperation, string> = {
+  none: "",
+  merge: "Merge",
+  rebase: "Rebase",
+  am: "git am",
+  cherry_pick: "Cherry-pick",
+  revert: "Revert",
+  bisect: "Bisect",
+};
+
+/** Fetches older than this get called out on the card */
+const STALE_FETCH_SECS = 24 * 60 * 60;
+
+/** How long ago the repo was fetched, or null while that's still recent */
+function staleFetchLabel(fetchedAt: number | null): string | null {
+  if (fetchedAt === null) return "never fetched";
+  const age = Date.now() / 1000 - fetchedAt;
+  if (age < STALE_FETCH_SECS) return null;
+  const days = Math.floor(age / STALE_FETCH_SECS);
+  return `fetched ${days}d ago`;
+}
+
 function ProjectCard({ project }: { project: ProjectInfo }) {
   const { data: git } = useGitStatus(project.has_git ? project.path : "");
+  const fetchAge = git?.remote_url ? staleFetchLabel(git.fetched_at ?? null) : null;
   const { data: devServerReport } = useDevServers();
   const hasDevServer = devServerReport?.servers.some(
     (s) => s.project_path === project.path
@@ -131,16 +157,40 @@ function ProjectCard({ project }: { project: ProjectInfo }) {
           <StatusBadge variant="neutral">{project.framework}</StatusBadge>
         )}
         <StatusBadge variant="neutral">{project.package_manager}</StatusBadge>
+        {(project.stacks ?? []).slice(1).map((stack) => (
+          <span
+            key={`${stack.subdir}:${stack.language_display}:${stack.framework}`}
+            className="truncate text-[10px] text-muted-foreground"
+            title={`${stack.subdir || "root"}: ${stack.language_display}, ${stack.framework}`}
+          >
+            + {stack.framework}
+          </span>
+        ))}
       </div>
 
       {/* Git status */}
       {git && (
         <div className="mt-3 flex items-center gap-3 text-xs text-muted-foreground">
-          <div className="flex items-center gap-1">
+          <div
+            className="flex items-center gap-1"
+            title={git.upstream ? `Tracking ${git.upstream}` : "No upstream branch"}
+          >
             <GitBranch className="h-3 w-3" />
-            <span className="font-mono">{git.branch}</span>
+            <span className="font-mono">
+              {git.detached_at ? `detached @ ${git.detached_at}` : git.branch}
+            </span>
           </div>
+          {git.operation && git.operation !== "none" && (
+            <StatusBadge variant="error">
+              {gitOperationLabels[git.operation]} in progress
+            </StatusBadge>
+          )}
           {git.is_dirty && <StatusDot status="warning" />}
+          {git.conflicted_count > 0 && (
+            <span className="text-destructive" title="Conflicted files">
+              {git.conflicted_count}C
+            </span>
+          )}
           {git.modified_count > 0 && (
             <span className="text-warning">{git.modified_count}M</span>
           )}
@@ -164,6 +214,11 @@ function ProjectCard({ project }: { project: ProjectInfo }) {
               {git.behind}
             </span>
           )}
+          {fetchAge && (
+            <span className="text-[10px]" title="Behind counts are only as fresh as the last fetch">
+              {fetchAge}
+            </span>
+          )}
         </div>
       )}
       {git && git.last_commit_message && (
@@ -599,6 +654,8 @@ export function WorkspacesSection() {
   const { data: projects, isLoading, isFetching } = useProjects();
   const addWorkspace = useAddWorkspace();
   const removeWorkspace = useRemoveWorkspace();
+  const { data: deepWorkspaces } = useDeepDiscoveryWorkspaces();
+  const setDeepDiscovery = useSetWorkspaceDeepDiscovery();
   const queryClient = useQueryClient();
   const groups = useGroupedProjects(projects);
   const detailContext = useNavigationStore((s) => s.detailContext);
@@ -828,22 +885,38 @@ export function WorkspacesSection() {
       {/* Workspace paths */}
       {workspacePaths && workspacePaths.length > 0 && (
         <div className="flex flex-wrap gap-2">
-          {workspacePaths.map((path) => (
-            <div
-              key={path}
-              className="flex items-center gap-1.5 rounded-md border border-border bg-card px-2.5 py-1 text-xs"
-            >
-              <FolderOpen className="h-3 w-3 text-muted-foreground" />
-              <span className="font-mono text-muted-foreground">{path}</span>
-              <button
-                onClick={() => removeWorkspace.mutate(path)}
-                className="ml-1 text-muted-foreground hover:text-destructive"
-                title="Remove workspace"
+          {workspacePaths.map((path) => {
+            const deep = deepWorkspaces?.includes(path) ?? false;
+            return (
+              <div
+                key={path}
+                className="flex items-center gap-1.5 rounded-md border border-border bg-card px-2.5 py-1 text-xs"
               >
-                <FolderX className="h-3 w-3" />
-              </button>
-            </div>
-          ))}
+                <FolderOpen className="h-3 w-3 text-muted-foreground" />
+                <span className="font-mono text-muted-foreground">{path}</span>
+                <button
+                  onClick={() => setDeepDiscovery.mutate({ path, enabled: !deep })}
+                  disabled={setDeepDiscovery.isPending}
+                  className={`ml-1 hover:text-foreground disabled:opacity-50 ${deep ? "text-primary" : "text-muted-foreground"}`}
+                  title={
+                    deep
+                      ? "Nested discovery on: projects inside projects are listed too"
+                      : "Find nested projects (e.g. backend/ + frontend/) inside each project"
+                  }
+                  aria-pressed={deep}
+                >
+                  <FolderTree className="h-3 w-3" />
+                </button>
+                <button
+                  onClick={() => removeWorkspace.mutate(path)}
+                  className="ml-1 text-muted-foreground hover:text-destructive"
+                  title="Remove workspace"
+                >
+                  <FolderX className="h-3 w-3" />
+                </button>
+              </div>
+            );
+          })}
         </div>
       )}
 
@@ -0,0 +1,237 @@
+import { useState } from "react";
+import { FolderGit2, Lock, Plus, Trash2 } from "lucide-react";
+import type { WorktreeInfo } from "@/lib/commands";
+import {
+  useCreateWorktree,
+  usePruneWorktrees,
+  useRemoveWorktree,
+  useWorktrees,
+} from "@/hooks/use-worktrees";
+import { StatusBadge } from "@/components/shared/status-badge";
+
+const buttonClass =
+  "inline-flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1 text-xs font-medium transition-colors hover:bg-accent disabled:opacity-50";
+const inputClass =
+  "rounded-md border border-border bg-background px-2 py-1 text-xs focus:border-primary focus:outline-none";
+
+function folderName(path: string) {
+  return path.split("/").filter(Boolean).pop() ?? path;
+}
+
+function WorktreeState({ worktree }: { worktree: WorktreeInfo }) {
+  const status = worktree.status;
+  return (
+    <>
+      {worktree.is_main && <StatusBadge variant="info">main</StatusBadge>}
+      {worktree.locked && (
+        <span title={worktree.lock_reason ?? "Locked"}>
+          <StatusBadge variant="warning">
+            <Lock className="h-2.5 w-2.5" />
+            locked
+          </StatusBadge>
+        </span>
+      )}
+      {worktree.prunable && (
+        <span title={worktree.prune_reason ?? "Directory is gone"}>
+          <StatusBadge variant="error">prunable</StatusBadge>
+        </span>
+      )}
+      {status &&
+        (status.is_dirty ? (
+          <StatusBadge variant="warning">
+            {status.staged_count + status.modified_count + status.untracked_count +
+              status.conflicted_count}{" "}
+            changed
+          </StatusBadge>
+        ) : (
+          <StatusBadge variant="success">clean</StatusBadge>
+        ))}
+    </>
+  );
+}
+
+/** Remove a linked worktree, asking again before throwing away local changes */
+function RemoveWorktreeAction({
+  projectPath,
+  worktree,
+}: {
+  projectPath: string;
+  worktree: WorktreeInfo;
+}) {
+  const [confirming, setConfirming] = useState(false);
+  const remove = useRemoveWorktree(projectPath);
+  const dirty = worktree.status?.is_dirty ?? false;
+
+  if (!confirming) {
+    return (
+      <button
+        onClick={() => setConfirming(true)}
+        disabled={worktree.locked}
+        title={worktree.locked ? "Unlock it with `git worktree unlock` first" : "Remove worktree"}
+        className="rounded p-1 text-muted-foreground transition-colors hover:bg-accent hover:text-destructive disabled:opacity-50"
+      >
+        <Trash2 className="h-3 w-3" />
+      </button>
+    );
+  }
+
+  return (
+    <div className="flex items-center gap-1.5">
+      <button
+        onClick={() =>
+          remove.mutate(
+            { worktreePath: worktree.path, force: dirty },
+            { onSettled: () => setConfirming(false) },
+          )
+        }
+        disabled={remove.isPending}
+        className="rounded-md border border-border px-2 py-0.5 text-xs font-medium text-destructive transition-colors hover:bg-destructive/10 disabled:opacity-50"
+      >
+        {dirty ? "Discard changes and remove" : "Remove"}
+      </button>
+      <button
+        onClick={() => setConfirming(false)}
+        className="rounded-md border border-border px-2 py-0.5 text-xs font-medium transition-colors hover:bg-accent"
+      >
+        Cancel
+      </button>
+    </div>
+  );
+}
+
+function CreateWorktreeForm({
+  projectPath,
+  onDone,
+}: {
+  projectPath: string;
+  onDone: () => void;
+}) {
+  const [branch, setBranch] = useState("");
+  const [newBranch, setNewBranch] = useState(true);
+  const [base, setBase] = useState("");
+  const create = useCreateWorktree(projectPath);
+
+  const submit = () => {
+    if (!branch.trim()) return;
+    create.mutate(
+      { branch: branch.trim(), newBranch, base: newBranch && base.trim() ? base.trim() : undefined },
+      { onSuccess: onDone },
+    );
+  };
+
+  return (
+    <div className="space-y-2 rounded-md border border-border p-3">
+      <div className="flex flex-wrap items-center gap-2">
+        <input
+          value={branch}
+          onChange={(e) => setBranch(e.target.value)}
+          onKeyDown={(e) => e.key === "Enter" && submit()}
+          placeholder="Branch name"
+          className={`${inputClass} flex-1 font-mono`}
+          autoFocus
+        />
+        {newBranch && (
+          <input
+            value={base}
+            onChange={(e) => setBase(e.target.value)}
+            placeholder="From (default: HEAD)"
+            className={`${inputClass} w-40 font-mono`}
+          />
+        )}
+      </div>
+      <div className="flex items-center justify-between">
+        <label className="flex items-center gap-1.5 text-xs text-muted-foreground">
+          <input
+            type="checkbox"
+            checked={newBranch}
+            onChange={(e) => setNewBranch(e.target.checked)}
+          />
+          Create a new branch
+        </label>
+        <div className="flex gap-2">
+          <button
+            onClick={submit}
+            disabled={!branch.trim() || create.isPending}
+            className={buttonClass}
+          >
+            {create.isPending ? "Creating..." : "Create"}
+          </button>
+          <button onClick={onDone} className={buttonClass}>
+            Cancel
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export function ProjectWorktreesCard({ projectPath }: { projectPath: string }) {
+  const [creating, setCreating] = useState(false);
+  const { data: worktrees } = useWorktrees(projectPath);
+  const prune = usePruneWorktrees(projectPath);
+  if (!worktrees) return null;
+
+  const prunable = worktrees.filter((w) => w.prunable).length;
+
+  return (
+    <div className="rounded-lg border border-border bg-card p-4">
+      <div className="mb-3 flex items-center justify-between gap-2">
+        <div className="flex items-center gap-2">
+          <FolderGit2 className="h-4 w-4 text-muted-foreground" />
+          <h3 className="text-sm font-medium">Worktrees</h3>
+          <span className="text-xs text-muted-foreground">{worktrees.length}</span>
+        </div>
+        <div className="flex gap-2">
+          {prunable > 0 && (
+            <button
+              onClick={() => prune.mutate()}
+              disabled={prune.isPending}
+              className={buttonClass}
+            >
+              Prune {prunable}
+            </button>
+          )}
+          {!creating && (
+            <button onClick={() => setCreating(true)} className={buttonClass}>
+              <Plus className="h-3 w-3" />
+              New worktree
+            </button>
+          )}
+        </div>
+      </div>
+      {creating && (
+        <div className="mb-3">
+          <CreateWorktreeForm projectPath={projectPath} onDone={() => setCreating(false)} />
+        </div>
+      )}
+      <div className="divide-y divide-border">
+        {worktrees.map((worktree) => (
+          <div key={worktree.path} className="flex items-center justify-between gap-3 py-2">
+            <div className="min-w-0">
+              <div className="flex items-center gap-2">
+                <span
+                  className={`truncate text-xs ${worktree.path === projectPath ? "font-semibold" : ""}`}
+                  title={worktree.path}
+                >
+                  {folderName(worktree.path)}
+                </span>
+                <span className="truncate font-mono text-[10px] text-muted-foreground">
+                  {worktree.branch ?? `detached @ ${worktree.head}`}
+                </span>
+              </div>
+              <p className="truncate font-mono text-[10px] text-muted-foreground">
+                {worktree.path}
+              </p>
+            </div>
+            <div className="flex shrink-0 items-center gap-2">
+              <WorktreeState worktree={worktree} />
+              {!worktree.is_main && !worktree.prunable && (
+                <RemoveWorktreeAction projectPath={projectPath} worktree={worktree} />
+              )}
+            </div>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,119 @@
+import { useState } from "react";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { toast } from "sonner";
+import { commands, type BranchRef, type FetchProgress, type FetchSummary } from "@/lib/commands";
+import { useSmartQuery } from "./use-smart-query";
+
+/** Every local branch across workspace repos */
+export function useBranchReport() {
+  return useSmartQuery({
+    queryKey: ["branch-report"],
+    queryFn: commands.getBranchReport,
+    activeStaleTime: 300_000,
+    hiddenStaleTime: Infinity,
+  });
+}
+
+export function useProjectBranches(projectPath: string) {
+  return useQuery({
+    queryKey: ["project-branches", projectPath],
+    queryFn: () => commands.getProjectBranches(projectPath),
+    enabled: !!projectPath,
+    staleTime: 60_000,
+  });
+}
+
+/** Deletes the previewed branches still merged; `projectPath: null` covers every repo */
+export function useDeleteMergedBranches() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({
+      projectPath,
+      branches,
+    }: {
+      projectPath: string | null;
+      branches: BranchRef[];
+    }) => commands.deleteMergedBranches(projectPath, false, branches),
+    onSuccess: (result) => {
+      if (result.errors.length > 0) {
+        toast.error(`${result.errors.length} branch(es) could not be deleted`, {
+          description: result.errors.join("\n"),
+        });
+      }
+      if (result.deleted.length > 0) {
+        toast.success(
+          `Deleted ${result.deleted.length} merged branch${result.deleted.length === 1 ? "" : "es"}`,
+        );
+      }
+      queryClient.invalidateQueries({ queryKey: ["branch-report"] });
+      queryClient.invalidateQueries({ queryKey: ["project-branches"] });
+      queryClient.invalidateQueries({ queryKey: ["all-git-statuses"] });
+    },
+    onError: () => {
+      toast.error("Failed to delete merged branches");
+    },
+  });
+}
+
+function fetchSummaryText(summary: FetchSummary) {
+  const parts = [`${summary.fetched} of ${summary.total} fetched`];
+  if (summary.fast_forwarded.length > 0) {
+    parts.push(`${summary.fast_forwarded.length} fast-forwarded`);
+  }
+  if (summary.skipped_dirty.length > 0) {
+    parts.push(`${summary.skipped_dirty.length} skipped with local changes`);
+  }
+  if (summary.diverged.length > 0) {
+    parts.push(`${summary.diverged.length} diverged`);
+  }
+  return parts.join(", ");
+}
+
+/**
+ * Fetches every workspace repo, optionally fast-forwarding clean branches
+ * that are only behind. `progress` follows `git://fetch-progress` while the
+ * fetch runs.
+ */
+export function useFetchAllRepos() {
+  const queryClient = useQueryClient();
+  const [progress, setProgress] = useState<FetchProgress | null>(null);
+
+  const mutation = useMutation({
+    mutationFn: async ({ fastForward }: { fastForward: boolean }) => {
+      setProgress(null);
+      const { listen } = await import("@tauri-apps/api/event");
+      const unlisten = await listen<FetchProgress>("git://fetch-progress", (event) => {
+        setProgress(event.payload);
+      });
+      try {
+        return await commands.fetchAllRepos(fastForward);
+      } finally {
+        unlisten();
+        setProgress(null);
+      }
+    },
+    onSuccess: (summary) => {
+      const problems = [...summary.auth_errors, ...summary.failures];
+      if (problems.length > 0) {
+        toast.error(
+          `${summary.auth_errors.length} auth error(s), ${summary.failures.length} failure(s)`,
+          {
+            description: problems
+              .map((r) => `${r.project_path}: ${r.message ?? r.outcome}`)
+              .join("\n"),
+          },
+        );
+      }
+      toast.success(fetchSummaryText(summary));
+      queryClient.invalidateQueries({ queryKey: ["all-git-statuses"] });
+      queryClient.invalidateQueries({ queryKey: ["git-status"] });
+      queryClient.invalidateQueries({ queryKey: ["branch-report"] });
+      queryClient.invalidateQueries({ queryKey: ["project-branches"] });
+    },
+    onError: (e) => {
+      toast.error("Fetch failed", { description: String(e) });
+    },
+  });
+
+  return { ...mutation, progress };
+}
@@ -1,5 +1,6 @@
-import { useMutation, useQueryClient } from "@tanstack/react-query";
-import { commands } from "@/lib/commands";
+import { useEffect, useState } from "react";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { commands, type DevServerOutputLine, type ManagedServer } from "@/lib/commands";
 import { useSmartQuery } from "./use-smart-query";
 import { toast } from "sonner";
 
@@ -35,16 +36,133 @@ export function useStartDevServer() {
     mutationFn: ({
       projectPath,
       command,
+      autoRestart,
     }: {
       projectPath: string;
       command?: string;
-    }) => commands.startDevServer(projectPath, command),
+      autoRestart?: boolean;
+    }) => commands.startDevServer(projectPath, command, autoRestart),
     onSuccess: (_data, { projectPath }) => {
       toast.success("Starting dev server...");
       queryClient.invalidateQueries({ queryKey: ["dev-servers"] });
+      queryClient.invalidateQueries({ queryKey: ["managed-servers"] });
+      queryClient.invalidateQueries({ queryKey: ["dev-commands", projectPath] });
     },
-    onError: () => {
-      toast.error("Failed to start dev server");
+    onError: (e) => {
+      toast.error(`Failed to start dev server: ${e}`);
+    },
+  });
+}
+
+export function useRestartDevServer() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: number) => commands.restartDevServer(id),
+    onSuccess: () => {
+      toast.success("Restarting dev server...");
+      queryClient.invalidateQueries({ queryKey: ["dev-servers"] });
+      queryClient.invalidateQueries({ queryKey: ["managed-servers"] });
+    },
+    onError: (e) => {
+      toast.error(`Failed to restart dev server: ${e}`);
+    },
+  });
+}
+
+export function useSetDevServerAutoRestart() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ id, enabled }: { id: number; enabled: boolean }) =>
+      commands.setDevServerAutoRestart(id, enabled),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["managed-servers"] });
     },
   });
 }
+
+/** Start command candidates for a project, plus the saved choice */
+export function useDevCommands(projectPath: string) {
+  return useQuery({
+    queryKey: ["dev-commands", projectPath],
+    queryFn: () => commands.getDevCommands(projectPath),
+    staleTime: 60_000,
+  });
+}
+
+/** Servers started from Houston, kept current by `devserver://status` events */
+export function useManagedServers() {
+  const queryClient = useQueryClient();
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+    import("@tauri-apps/api/event").then(async ({ listen }) => {
+      const u = await listen<ManagedServer>("devserver://status", (event) => {
+        queryClient.setQueryData<ManagedServer[]>(["managed-servers"], (prev = []) => {
+          const rest = prev.filter((s) => s.id !== event.payload.id);
+          return [...rest, event.payload].sort((a, b) => a.id - b.id);
+        });
+        // Port and pid changes show up in the scanned list too
+        queryClient.invalidateQueries({ queryKey: ["dev-servers"] });
+      });
+      if (cancelled) u();
+      else unlisten = u;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [queryClient]);
+
+  return useQuery({
+    queryKey: ["managed-servers"],
+    queryFn: commands.getManagedServers,
+    staleTime: Infinity,
+  });
+}
+
+const MAX_OUTPUT_LINES = 1000;
+
+/** Captured output of a managed server: the buffered backlog, then live lines */
+export function useDevServerOutput(id: number | null | undefined, enabled = true) {
+  const [lines, setLines] = useState<DevServerOutputLine[]>([]);
+
+  useEffect(() => {
+    if (!enabled || id == null) return;
+    setLines([]);
+
+    let cancelled = false;
+    let unlisten: (() => void) | undefined;
+    import("@tauri-apps/api/event").then(async ({ listen }) => {
+      const pending: DevServerOutputLine[] = [];
+      let loaded = false;
+      const u = await listen<DevServerOutputLine>(`devserver://output/${id}`, (event) => {
+        if (loaded) {
+          setLines((prev) => [...prev, event.payload].slice(-MAX_OUTPUT_LINES));
+        } else {
+          pending.push(event.payload);
+        }
+      });
+      if (cancelled) {
+        u();
+        return;
+      }
+      unlisten = u;
+      const backlog = await commands.getDevServerOutput(id).catch(() => []);
+      if (cancelled) return;
+      // Lines that arrived while the backlog loaded may already be in it
+      const last = backlog[backlog.length - 1]?.at ?? "";
+      loaded = true;
+      setLines(
+        [...backlog, ...pending.filter((l) => l.at > last)].slice(-MAX_OUTPUT_LINES),
+      );
+    });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [id, enabled]);
+
+  return lines;
+}
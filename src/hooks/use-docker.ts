@@ -1,5 +1,6 @@
+import { useEffect, useState } from "react";
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
-import { commands } from "@/lib/commands";
+import { commands, type ComposeAction, type LogFollowOptions, type LogLine } from "@/lib/commands";
 import { useSmartQuery } from "./use-smart-query";
 import { toast } from "sonner";
 
@@ -62,6 +63,41 @@ export function useContainerActions() {
   return { startContainer, stopContainer, restartContainer };
 }
 
+const COMPOSE_ACTION_LABELS: Record<ComposeAction, string> = {
+  up: "started",
+  down: "taken down",
+  restart: "restarted",
+  pull: "images pulled",
+  recreate: "service recreated",
+};
+
+export function useComposeActions() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ project, action, service }: { project: string; action: ComposeAction; service?: string }) =>
+      commands.runComposeAction(project, action, service),
+    onSuccess: (_, { project, action }) => {
+      toast.success(`${project}: ${COMPOSE_ACTION_LABELS[action]}`);
+      queryClient.invalidateQueries({ queryKey: ["docker-status"] });
+      queryClient.invalidateQueries({ queryKey: ["compose-stacks"] });
+    },
+    onError: (error, { project }) => toast.error(`Compose failed for ${project}`, { description: String(error) }),
+  });
+}
+
+export function useComposeStacks() {
+  return useSmartQuery({
+    queryKey: ["compose-stacks"],
+    queryFn: commands.getComposeStacks,
+    activeStaleTime: 10_000,
+    hiddenStaleTime: 60_000,
+    activeInterval: 30_000,
+    hiddenInterval: 120_000,
+    deepIdleInterval: false,
+  });
+}
+
 export function useContainerLogs(containerId: string, enabled: boolean = true) {
   return useQuery({
     queryKey: ["container-logs", containerId],
@@ -70,3 +106,49 @@ export function useContainerLogs(containerId: string, enabled: boolean = true) {
     staleTime: 5_000,
   });
 }
+
+const MAX_STREAMED_LINES = 1000;
+
+/** Follow a container's logs over `docker://logs/<id>` events while mounted */
+export function useContainerLogStream(
+  containerId: string,
+  opts: LogFollowOptions & { enabled?: boolean } = {},
+) {
+  const { enabled = true, stdout, stderr, timestamps, since, tail } = opts;
+  const [lines, setLines] = useState<LogLine[]>([]);
+  const [ended, setEnded] = useState<string | null | undefined>(undefined);
+
+  useEffect(() => {
+    if (!enabled || !containerId) return;
+    setLines([]);
+    setEnded(undefined);
+
+    let cancelled = false;
+    const unlisteners: (() => void)[] = [];
+    import("@tauri-apps/api/event").then(async ({ listen }) => {
+      const onLines = await listen<LogLine[]>(`docker://logs/${containerId}`, (event) => {
+        setLines((prev) => [...prev, ...event.payload].slice(-MAX_STREAMED_LINES));
+      });
+      const onEnded = await listen<string | null>(`docker://logs-ended/${containerId}`, (event) => {
+        setEnded(event.payload);
+      });
+      unlisteners.push(onLines, onEnded);
+      if (cancelled) {
+        unlisteners.forEach((u) => u());
+        return;
+      }
+      commands
+        .subscribeContainerLogs(containerId, { stdout, stderr, timestamps, since, tail })
+        .catch((e) => setEnded(String(e)));
+    });
+
+    return () => {
+      cancelled = true;
+      unlisteners.forEach((u) => u());
+      commands.unsubscribeContainerLogs(containerId).catch(() => {});
+    };
+  }, [containerId, enabled, stdout, stderr, timestamps, since, tail]);
+
+  /** `undefined` while streaming, otherwise `null` or the error it ended with */
+  return { lines, ended };
+}
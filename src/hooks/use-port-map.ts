@@ -1,7 +1,10 @@
 import { useMemo } from "react";
+import { useQuery } from "@tanstack/react-query";
 import { useDevServers } from "./use-dev-servers";
 import { useDockerStatusCached } from "./use-docker";
+import { useSmartQuery } from "./use-smart-query";
 import { aggregatePorts } from "@/lib/port-map";
+import { commands } from "@/lib/commands";
 
 export function usePortMap() {
   const { data: devServers } = useDevServers();
@@ -11,3 +14,27 @@ export function usePortMap() {
     [devServers, dockerStatus],
   );
 }
+
+/** Backend port map: live listeners plus ports declared in project config */
+export function usePortMapDetails(enabled = true) {
+  return useSmartQuery({
+    queryKey: ["port-map"],
+    queryFn: commands.getPortMap,
+    enabled,
+    activeStaleTime: 15_000,
+    hiddenStaleTime: 60_000,
+    activeInterval: 15_000,
+    hiddenInterval: 60_000,
+    deepIdleInterval: false,
+  });
+}
+
+/** Who is on a port, including processes that aren't dev servers */
+export function usePortOwners(port: number | null) {
+  return useQuery({
+    queryKey: ["port-owners", port],
+    queryFn: () => commands.findPortOwners(port!),
+    enabled: port != null,
+    staleTime: 5_000,
+  });
+}
@@ -16,3 +16,16 @@ export function useLatestScan(scanner: string) {
     staleTime: 30_000,
   });
 }
+
+export function useScanDiff(
+  scanner: string,
+  fromId: number | null,
+  toId: number | null,
+) {
+  return useQuery({
+    queryKey: ["scan-diff", scanner, fromId, toId],
+    queryFn: () => commands.diffScans(scanner, fromId!, toId!),
+    enabled: fromId !== null && toId !== null,
+    staleTime: Infinity,
+  });
+}
@@ -0,0 +1,34 @@
+import { useEffect } from "react";
+import { useQueryClient } from "@tanstack/react-query";
+
+// Backend watcher emits `scanner://updated/<kind>` after rescanning; the
+// caches are already warm, so refetching is cheap.
+const QUERY_KEYS: Record<string, string[][]> = {
+  projects: [["projects"]],
+  git: [["all-git-statuses"], ["git-status"]],
+  claude: [["claude-config"]],
+  ai_tools: [["ai-tools"], ["tool-mcp-servers"]],
+  path: [["path-entries"]],
+  languages: [["languages"]],
+};
+
+export function useScannerEvents() {
+  const queryClient = useQueryClient();
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    import("@tauri-apps/api/event").then(({ listen }) => {
+      const unlisteners: (() => void)[] = [];
+      for (const [kind, keys] of Object.entries(QUERY_KEYS)) {
+        listen(`scanner://updated/${kind}`, () => {
+          keys.forEach((queryKey) =>
+            queryClient.invalidateQueries({ queryKey }),
+          );
+        }).then((u) => unlisteners.push(u));
+      }
+      unlisten = () => unlisteners.forEach((u) => u());
+    });
+
+    return () => unlisten?.();
+  }, [queryClient]);
+}
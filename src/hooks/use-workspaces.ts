@@ -16,6 +16,13 @@ export function useWorkspacePaths() {
   });
 }
 
+export function useDeepDiscoveryWorkspaces() {
+  return useQuery({
+    queryKey: ["deep-discovery-workspaces"],
+    queryFn: commands.getDeepDiscoveryWorkspaces,
+  });
+}
+
 export function useProjects() {
   return useSmartQuery({
     queryKey: ["projects"],
@@ -56,3 +63,24 @@ export function useRemoveWorkspace() {
     },
   });
 }
+
+export function useSetWorkspaceDeepDiscovery() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ path, enabled }: { path: string; enabled: boolean }) =>
+      commands.setWorkspaceDeepDiscovery(path, enabled),
+    onSuccess: (_, { enabled }) => {
+      toast.success(
+        enabled
+          ? "Nested project discovery on — rescanning..."
+          : "Nested project discovery off — rescanning...",
+      );
+      queryClient.invalidateQueries({ queryKey: ["deep-discovery-workspaces"] });
+      queryClient.invalidateQueries({ queryKey: ["projects"] });
+      queryClient.invalidateQueries({ queryKey: ["all-git-statuses"] });
+    },
+    onError: () => {
+      toast.error("Failed to update workspace discovery");
+    },
+  });
+}
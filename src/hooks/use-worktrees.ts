@@ -0,0 +1,73 @@
+import { useMutation, useQuery, useQueryClient, type QueryClient } from "@tanstack/react-query";
+import { toast } from "sonner";
+import { commands } from "@/lib/commands";
+
+/** Worktrees join and leave the project list and its "(worktrees)" groups */
+function invalidateWorktrees(queryClient: QueryClient) {
+  queryClient.invalidateQueries({ queryKey: ["worktrees"] });
+  queryClient.invalidateQueries({ queryKey: ["projects"] });
+  queryClient.invalidateQueries({ queryKey: ["all-git-statuses"] });
+  queryClient.invalidateQueries({ queryKey: ["project-branches"] });
+}
+
+export function useWorktrees(projectPath: string) {
+  return useQuery({
+    queryKey: ["worktrees", projectPath],
+    queryFn: () => commands.listWorktrees(projectPath),
+    enabled: !!projectPath,
+    staleTime: 30_000,
+  });
+}
+
+export function useCreateWorktree(projectPath: string) {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({
+      branch,
+      newBranch,
+      base,
+    }: {
+      branch: string;
+      newBranch: boolean;
+      base?: string;
+    }) => commands.createWorktree(projectPath, branch, newBranch, base),
+    onSuccess: (worktree) => {
+      toast.success(`Created worktree for ${worktree.branch ?? worktree.head}`, {
+        description: worktree.path,
+      });
+      invalidateWorktrees(queryClient);
+    },
+    onError: (e) => {
+      toast.error("Failed to create worktree", { description: String(e) });
+    },
+  });
+}
+
+export function useRemoveWorktree(projectPath: string) {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ worktreePath, force }: { worktreePath: string; force: boolean }) =>
+      commands.removeWorktree(projectPath, worktreePath, force),
+    onSuccess: () => {
+      toast.success("Worktree removed");
+      invalidateWorktrees(queryClient);
+    },
+    onError: (e) => {
+      toast.error("Failed to remove worktree", { description: String(e) });
+    },
+  });
+}
+
+export function usePruneWorktrees(projectPath: string) {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: () => commands.pruneWorktrees(projectPath, false),
+    onSuccess: (pruned) => {
+      toast.success(`Pruned ${pruned.length} worktree${pruned.length === 1 ? "" : "s"}`);
+      invalidateWorktrees(queryClient);
+    },
+    onError: (e) => {
+      toast.error("Failed to prune worktrees", { description: String(e) });
+    },
+  });
+}
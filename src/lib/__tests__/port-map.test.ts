@@ -12,6 +12,7 @@ const makeDevReport = (
     framework: s.framework ?? null,
     project_path: s.project_path,
     uptime_secs: 100,
+    managed_id: null,
   })),
   scanned_at: "",
 });
@@ -71,6 +71,32 @@ describe("computeAllMismatches", () => {
     expect(result.byProject.has("/c")).toBe(false); // skipped — no version files
   });
 
+  test("prefers backend version checks when present", () => {
+    const projects = [
+      {
+        path: "/a",
+        version_files: [makeVf(".nvmrc", "lts/*", "node")],
+        version_checks: [
+          {
+            file: ".nvmrc",
+            language: "node",
+            expected: "lts/*",
+            active: "22.0.0",
+            resolved: "20.18.0",
+            manager: "nvm",
+            status: "mismatch" as const,
+            fix: "nvm use",
+          },
+        ],
+      },
+    ];
+    const result = computeAllMismatches(projects, [makeLang("Node.js", "22.0.0")]);
+    const a = result.byProject.get("/a");
+    expect(a?.hasMismatch).toBe(true);
+    expect(a?.mismatches[0].systemVersion).toBe("20.18.0");
+    expect(a?.mismatches[0].fix).toBe("nvm use");
+  });
+
   test("returns empty when inputs are undefined", () => {
     const result = computeAllMismatches(undefined, undefined);
     expect(result.totalCount).toBe(0);
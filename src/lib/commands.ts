@@ -25,6 +25,13 @@ export interface PathEntry {
   category: string;
 }
 
+/** An external command that failed or timed out during a scan. */
+export interface ScanError {
+  source: string;
+  kind: "not_found" | "spawn" | "timed_out" | "exit";
+  message: string;
+}
+
 export interface LanguageInfo {
   name: string;
   version: string;
@@ -32,6 +39,7 @@ export interface LanguageInfo {
   manager: string;
   installed: boolean;
   icon: string;
+  error?: string | null;
 }
 
 export interface EnvVarInfo {
@@ -58,6 +66,36 @@ export interface VersionFile {
   language: string;
 }
 
+export interface VersionCheck {
+  file: string;
+  language: string;
+  expected: string;
+  active: string | null;
+  resolved: string | null;
+  manager: string | null;
+  status: "ok" | "mismatch" | "not_installed" | "unknown";
+  fix: string | null;
+}
+
+export interface DetectedStack {
+  /** Directory relative to the project root, empty for the root itself */
+  subdir: string;
+  language: string;
+  language_display: string;
+  framework: string;
+  category: string;
+  category_display: string;
+  package_manager: string;
+  package_manager_display: string;
+}
+
+export interface LineCount {
+  language: string;
+  files: number;
+  /** Non-blank lines */
+  lines: number;
+}
+
 export interface ProjectInfo {
   name: string;
   path: string;
@@ -74,6 +112,10 @@ export interface ProjectInfo {
   health_score: ProjectHealthScore | null;
   has_build_artifacts: boolean;
   version_files: VersionFile[];
+  version_checks?: VersionCheck[];
+  compose?: ComposeFile | null;
+  /** Every stack in the project, primary first */
+  stacks?: DetectedStack[];
 }
 
 export interface GitStatus {
@@ -89,7 +131,28 @@ export interface GitStatus {
   last_commit_date: string;
   last_commit_epoch: number | null;
   remote_url: string;
-}
+  error?: string | null;
+  /** Merge, rebase, etc. left in progress in the working tree */
+  operation: GitOperation;
+  /** Epoch seconds the operation started */
+  operation_since: number | null;
+  conflicted_count: number;
+  /** Abbreviated commit when HEAD isn't on a branch */
+  detached_at: string | null;
+  /** Tracking branch, e.g. `origin/main`; empty when none */
+  upstream: string;
+  /** Epoch seconds of the last `git fetch`; null if never fetched */
+  fetched_at: number | null;
+}
+
+export type GitOperation =
+  | "none"
+  | "merge"
+  | "rebase"
+  | "am"
+  | "cherry_pick"
+  | "revert"
+  | "bisect";
 
 export interface PackageInfo {
   name: string;
@@ -103,6 +166,7 @@ export interface PackageList {
   cargo: PackageInfo[];
   scoop: PackageInfo[];
   chocolatey: PackageInfo[];
+  errors?: ScanError[];
 }
 
 export interface McpServer {
@@ -176,6 +240,7 @@ export interface DiagnosticItem {
 export interface DiagnosticReport {
   items: DiagnosticItem[];
   scanned_at: string;
+  errors?: ScanError[];
 }
 
 export interface FixResult {
@@ -198,6 +263,42 @@ export interface ScanHistoryRow {
   scanned_at: string;
 }
 
+// Machine manifest types
+export interface ManifestGap {
+  kind: "missing" | "version_mismatch";
+  category: string;
+  group: string | null;
+  name: string;
+  expected: string | null;
+  actual: string | null;
+}
+
+export interface ManifestComparison {
+  reference_hostname: string;
+  reference_exported_at: string;
+  gaps: ManifestGap[];
+  missing_count: number;
+  mismatch_count: number;
+}
+
+export interface DiffEntry {
+  kind: "added" | "removed" | "changed";
+  group: string | null;
+  key: string;
+  before: string | null;
+  after: string | null;
+}
+
+export interface ScanDiff {
+  scanner: string;
+  from_id: number;
+  to_id: number;
+  from_scanned_at: string;
+  to_scanned_at: string;
+  changes: DiffEntry[];
+  summary: { added: number; removed: number; changed: number };
+}
+
 // Issue types
 export interface IssueRow {
   diagnostic_id: string;
@@ -292,6 +393,8 @@ export interface ProjectAnalysis {
   total_size: SizeInfo;
   code_size: SizeInfo;
   language_details: LanguageDetails;
+  stacks: DetectedStack[];
+  line_counts: LineCount[];
   storage_breakdown: DirectorySize[];
   git_info: ExtendedGitInfo | null;
   analyzed_at: string;
@@ -302,6 +405,29 @@ export interface PortBinding {
   host_port: number | null;
   container_port: number;
   protocol: string;
+  host_ip?: string | null;
+}
+
+export interface VolumeMount {
+  kind: string; // "volume", "bind", "tmpfs"
+  name: string | null;
+  source: string;
+  destination: string;
+  read_only: boolean;
+}
+
+export interface LogLine {
+  stream: "stdout" | "stderr" | "console" | "stdin";
+  timestamp: string | null;
+  message: string;
+}
+
+export interface LogFollowOptions {
+  stdout?: boolean;
+  stderr?: boolean;
+  timestamps?: boolean;
+  since?: string;
+  tail?: number;
 }
 
 export interface ContainerInfo {
@@ -320,6 +446,12 @@ export interface ContainerInfo {
   icon: string;
   compose_project: string | null;
   compose_service: string | null;
+  health?: string | null; // "healthy", "unhealthy", "starting"
+  restart_count?: number;
+  exit_code?: number | null;
+  labels?: Record<string, string>;
+  networks?: string[];
+  volumes?: VolumeMount[];
 }
 
 export interface ComposeProject {
@@ -327,6 +459,60 @@ export interface ComposeProject {
   container_count: number;
   running_count: number;
   containers: string[];
+  working_dir?: string | null;
+  config_files?: string[];
+  services?: string[];
+  /** Workspace project the compose file lives in */
+  project_path?: string | null;
+  project_name?: string | null;
+}
+
+export type ComposeAction = "up" | "down" | "restart" | "pull" | "recreate";
+
+export interface ComposePort {
+  host_ip: string | null;
+  published: number;
+  target: number;
+  protocol: string;
+}
+
+export interface ComposeService {
+  name: string;
+  image: string | null;
+  build: boolean;
+  container_name: string | null;
+  ports: ComposePort[];
+}
+
+export interface ComposeFile {
+  path: string;
+  project_name: string;
+  services: ComposeService[];
+}
+
+export interface StackService {
+  name: string;
+  image: string | null;
+  ports: ComposePort[];
+  running: boolean;
+  container_id: string | null;
+}
+
+export interface PortCollision {
+  port: number;
+  service: string;
+  kind: "container" | "dev_server" | "stack";
+  owner: string;
+}
+
+export interface ComposeStack {
+  project_path: string;
+  project_name: string;
+  file: string;
+  compose_project: string;
+  services: StackService[];
+  running_count: number;
+  collisions: PortCollision[];
 }
 
 export interface DockerStatus {
@@ -337,6 +523,9 @@ export interface DockerStatus {
   total_running: number;
   total_stopped: number;
   scanned_at: string;
+  errors?: ScanError[];
+  endpoint?: string | null;
+  engine?: string | null; // "docker" or "podman"
 }
 
 // Dev Server types
@@ -347,11 +536,207 @@ export interface DevServer {
   framework: string | null;
   project_path: string | null;
   uptime_secs: number | null;
+  /** Supervisor id when Houston started this server */
+  managed_id: number | null;
+}
+
+export type ManagedServerStatus = "starting" | "running" | "stopping" | "stopped" | "crashed";
+
+export interface ManagedServer {
+  id: number;
+  project_path: string;
+  command: string;
+  pid: number | null;
+  port: number | null;
+  status: ManagedServerStatus;
+  auto_restart: boolean;
+  restarts: number;
+  exit_code: number | null;
+  started_at: string | null;
+}
+
+export interface DevServerOutputLine {
+  stream: "stdout" | "stderr" | "system";
+  line: string;
+  at: string;
+}
+
+export interface DevCommand {
+  command: string;
+  /** "package.json", "Procfile", "compose", or the framework name */
+  source: string;
+  name: string | null;
+}
+
+export interface DevCommandOptions {
+  candidates: DevCommand[];
+  saved: string | null;
 }
 
 export interface DevServerReport {
   servers: DevServer[];
   scanned_at: string;
+  errors?: ScanError[];
+}
+
+export type PortOwnerKind = "dev_server" | "container" | "process" | "declared";
+
+export interface PortOwner {
+  port: number;
+  kind: PortOwnerKind;
+  /** Project, container or process name */
+  name: string;
+  project_path: string | null;
+  pid: number | null;
+  container_id: string | null;
+  /** Config file for declared ports, working directory for live listeners */
+  source: string | null;
+}
+
+export interface PortConflict {
+  port: number;
+  owners: PortOwner[];
+  /** Something is actually bound; otherwise the projects would collide once started */
+  live: boolean;
+  reason: string;
+}
+
+export interface PortMap {
+  owners: PortOwner[];
+  conflicts: PortConflict[];
+  /** Dev servers still listening from a deleted directory */
+  zombies: PortOwner[];
+  scanned_at: string;
+}
+
+// Detection registry types
+export interface RegistryError {
+  source: string;
+  message: string;
+}
+
+export interface RegistryStatus {
+  /** Files searched, whether or not they exist */
+  paths: string[];
+  /** Files that were found and parsed */
+  loaded: string[];
+  languages: number;
+  frameworks: number;
+  storage_dirs: number;
+  errors: RegistryError[];
+}
+
+// Branch inventory types
+export interface BranchInfo {
+  name: string;
+  /** Checked out in the scanned working tree */
+  is_head: boolean;
+  /** Checked out in this or another worktree */
+  checked_out: boolean;
+  is_default: boolean;
+  /** Tracking branch, e.g. `origin/feature`; empty when none */
+  upstream: string;
+  /** Upstream configured but deleted on the remote */
+  upstream_gone: boolean;
+  ahead: number;
+  behind: number;
+  /** Commits on no remote branch (only counted without an upstream) */
+  unique_commits: number;
+  /** Fully contained in the default branch */
+  merged: boolean;
+  stale: boolean;
+  last_commit_message: string;
+  last_commit_date: string;
+  last_commit_epoch: number | null;
+}
+
+export interface RepoBranches {
+  project_path: string;
+  default_branch: string;
+  has_remote: boolean;
+  branches: BranchInfo[];
+  error?: string | null;
+}
+
+export interface BranchReport {
+  repos: RepoBranches[];
+  unpushed_count: number;
+  deletable_count: number;
+  stale_count: number;
+}
+
+export interface DeletedBranch {
+  project_path: string;
+  name: string;
+  commit: string;
+}
+
+export interface BranchRef {
+  project_path: string;
+  name: string;
+}
+
+export interface DeleteMergedResult {
+  dry_run: boolean;
+  deleted: DeletedBranch[];
+  errors: string[];
+}
+
+// Bulk fetch types
+export type FetchOutcome =
+  | "fetched"
+  | "fast_forwarded"
+  | "skipped_dirty"
+  | "diverged"
+  | "no_remote"
+  | "auth_failed"
+  | "failed";
+
+export interface RepoFetchResult {
+  project_path: string;
+  outcome: FetchOutcome;
+  branch: string;
+  /** Relative to the upstream after fetching (and fast-forwarding) */
+  ahead: number;
+  behind: number;
+  message: string | null;
+  duration_ms: number;
+}
+
+/** Payload of `git://fetch-progress`, sent as each repo finishes */
+export interface FetchProgress {
+  completed: number;
+  total: number;
+  result: RepoFetchResult;
+}
+
+export interface FetchSummary {
+  total: number;
+  fetched: number;
+  fast_forwarded: string[];
+  skipped_dirty: string[];
+  diverged: string[];
+  auth_errors: RepoFetchResult[];
+  failures: RepoFetchResult[];
+  results: RepoFetchResult[];
+  duration_ms: number;
+}
+
+// Worktree types
+export interface WorktreeInfo {
+  path: string;
+  /** null when HEAD is detached */
+  branch: string | null;
+  /** Abbreviated commit checked out */
+  head: string;
+  is_main: boolean;
+  locked: boolean;
+  lock_reason: string | null;
+  /** Directory is gone; pruning cleans it up */
+  prunable: boolean;
+  prune_reason: string | null;
+  /** null for prunable worktrees */
+  status: GitStatus | null;
 }
 
 // Stats types
@@ -365,6 +750,14 @@ export interface ScannerStatsSnapshot {
   is_warm: boolean;
 }
 
+export interface ApiServerStatus {
+  enabled: boolean;
+  running: boolean;
+  port: number;
+  token: string;
+  error: string | null;
+}
+
 export interface AppStatsSnapshot {
   scanners: ScannerStatsSnapshot[];
   pid: number;
@@ -393,6 +786,10 @@ export const commands = {
   addWorkspace: (path: string) => invoke<string[]>("add_workspace", { path }),
   removeWorkspace: (path: string) =>
     invoke<string[]>("remove_workspace", { path }),
+  getDeepDiscoveryWorkspaces: () =>
+    invoke<string[]>("get_deep_discovery_workspaces"),
+  setWorkspaceDeepDiscovery: (path: string, enabled: boolean) =>
+    invoke<string[]>("set_workspace_deep_discovery", { path, enabled }),
   scanProjects: () => invoke<ProjectInfo[]>("scan_projects"),
   getGitStatus: (projectPath: string) =>
     invoke<GitStatus | null>("get_git_status", { projectPath }),
@@ -444,6 +841,11 @@ export const commands = {
     invoke<ScanHistoryRow[]>("get_scan_history", { scanner, limit }),
   getLatestScan: (scanner: string) =>
     invoke<ScanHistoryRow | null>("get_latest_scan", { scanner }),
+  diffScans: (scanner: string, fromId: number, toId: number) =>
+    invoke<ScanDiff>("diff_scans", { scanner, fromId, toId }),
+  exportManifest: (path: string) => invoke<unknown>("export_manifest", { path }),
+  compareManifest: (path: string) =>
+    invoke<ManifestComparison>("compare_manifest", { path }),
 
   // Issues
   getIssueCount: () => invoke<number>("get_issue_count"),
@@ -469,8 +871,45 @@ export const commands = {
   getDevServers: () => invoke<DevServerReport>("get_dev_servers"),
   refreshDevServers: () => invoke<DevServerReport>("refresh_dev_servers"),
   stopDevServer: (pid: number) => invoke<void>("stop_dev_server", { pid }),
-  startDevServer: (projectPath: string, command?: string) =>
-    invoke<DevServer>("start_dev_server", { projectPath, command }),
+  startDevServer: (projectPath: string, command?: string, autoRestart?: boolean) =>
+    invoke<ManagedServer>("start_dev_server", { projectPath, command, autoRestart }),
+  restartDevServer: (id: number) => invoke<ManagedServer>("restart_dev_server", { id }),
+  getManagedServers: () => invoke<ManagedServer[]>("get_managed_servers"),
+  getDevServerOutput: (id: number) =>
+    invoke<DevServerOutputLine[]>("get_dev_server_output", { id }),
+  setDevServerAutoRestart: (id: number, enabled: boolean) =>
+    invoke<ManagedServer>("set_dev_server_auto_restart", { id, enabled }),
+  getDevCommands: (projectPath: string) =>
+    invoke<DevCommandOptions>("get_dev_commands", { projectPath }),
+  setDevCommand: (projectPath: string, command: string | null) =>
+    invoke<void>("set_dev_command", { projectPath, command }),
+
+  // Ports
+  getPortMap: () => invoke<PortMap>("get_port_map"),
+  findPortOwners: (port: number) => invoke<PortOwner[]>("find_port_owners", { port }),
+
+  // Detection Registry
+  getDetectionRegistry: () => invoke<RegistryStatus>("get_detection_registry"),
+  reloadDetectionRegistry: () => invoke<RegistryStatus>("reload_detection_registry"),
+
+  // Branches
+  getBranchReport: () => invoke<BranchReport>("get_branch_report"),
+  getProjectBranches: (projectPath: string) =>
+    invoke<RepoBranches | null>("get_project_branches", { projectPath }),
+  deleteMergedBranches: (projectPath: string | null, dryRun: boolean, branches?: BranchRef[]) =>
+    invoke<DeleteMergedResult>("delete_merged_branches", { projectPath, dryRun, branches }),
+  fetchAllRepos: (fastForward: boolean, concurrency?: number) =>
+    invoke<FetchSummary>("fetch_all_repos", { fastForward, concurrency }),
+
+  // Worktrees
+  listWorktrees: (projectPath: string) =>
+    invoke<WorktreeInfo[]>("list_worktrees", { projectPath }),
+  createWorktree: (projectPath: string, branch: string, newBranch: boolean, base?: string) =>
+    invoke<WorktreeInfo>("create_worktree", { projectPath, branch, newBranch, base }),
+  removeWorktree: (projectPath: string, worktreePath: string, force: boolean) =>
+    invoke<void>("remove_worktree", { projectPath, worktreePath, force }),
+  pruneWorktrees: (projectPath: string, dryRun: boolean) =>
+    invoke<string[]>("prune_worktrees", { projectPath, dryRun }),
 
   // Docker
   getDockerStatus: () => invoke<DockerStatus>("get_docker_status"),
@@ -481,6 +920,19 @@ export const commands = {
     invoke<void>("stop_docker_container", { containerId }),
   restartDockerContainer: (containerId: string) =>
     invoke<void>("restart_docker_container", { containerId }),
+  runComposeAction: (project: string, action: ComposeAction, service?: string) =>
+    invoke<string>("run_compose_action", { project, action, service }),
+  getComposeStacks: () => invoke<ComposeStack[]>("get_compose_stacks"),
   getDockerContainerLogs: (containerId: string, tail?: number) =>
     invoke<string[]>("get_docker_container_logs", { containerId, tail }),
+  subscribeContainerLogs: (containerId: string, opts: LogFollowOptions = {}) =>
+    invoke<void>("subscribe_container_logs", { containerId, ...opts }),
+  unsubscribeContainerLogs: (containerId: string) =>
+    invoke<void>("unsubscribe_container_logs", { containerId }),
+
+  // API Server
+  getApiServerStatus: () => invoke<ApiServerStatus>("get_api_server_status"),
+  setApiServerEnabled: (enabled: boolean) =>
+    invoke<ApiServerStatus>("set_api_server_enabled", { enabled }),
+  regenerateApiToken: () => invoke<ApiServerStatus>("regenerate_api_token"),
 };
@@ -9,6 +9,11 @@ export const categoryToSection: Record<string, Section> = {
   environment: "environment",
   windows: "system",
   linux: "system",
+  policy: "system",
+  versions: "workspaces",
+  ports: "dashboard",
+  registry: "settings",
+  git: "workspaces",
 };
 
 export const sectionLabels: Record<Section, string> = {
@@ -62,6 +67,25 @@ export const snarkyComments: Record<string, string[]> = {
     "Linux: where the fix is always 'just recompile the kernel'.",
     "Have you tried checking the man pages?",
   ],
+  versions: [
+    "Your .nvmrc and your PATH are no longer on speaking terms.",
+    "Version files are more like guidelines, apparently.",
+  ],
+  policy: [
+    "The team handbook has some notes on your setup.",
+    "Works on everyone else's machine. Let's fix yours.",
+  ],
+  ports: [
+    "Two servers walk into a port. Only one walks out.",
+    "EADDRINUSE: the three saddest letters, plus nine more.",
+  ],
+  registry: [
+    "Your custom frameworks didn't make it past the bouncer.",
+  ],
+  git: [
+    "That rebase isn't going to --continue itself.",
+    "Somewhere, a MERGE_HEAD waits patiently for your return.",
+  ],
 };
 
 export function getSnarkyComment(category: string): string | null {
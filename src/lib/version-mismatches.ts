@@ -1,9 +1,10 @@
-import type { VersionFile, LanguageInfo } from "./commands";
+import type { VersionFile, VersionCheck, LanguageInfo } from "./commands";
 
 export interface VersionMismatchInfo {
   file: VersionFile;
   systemVersion: string;
   matches: boolean;
+  fix?: string | null;
 }
 
 export interface ProjectMismatches {
@@ -26,13 +27,25 @@ export function computeProjectMismatches(
   });
 }
 
+/** Backend checks understand ranges and version managers; prefer them. */
+export function fromVersionChecks(checks: VersionCheck[]): VersionMismatchInfo[] {
+  return checks.map((c) => ({
+    file: { name: c.file, expected_version: c.expected, language: c.language },
+    systemVersion: c.resolved ?? c.active ?? "not installed",
+    matches: c.status === "ok" || c.status === "unknown",
+    fix: c.fix,
+  }));
+}
+
 export interface AllMismatchesResult {
   byProject: Map<string, ProjectMismatches>;
   totalCount: number;
 }
 
 export function computeAllMismatches(
-  projects: { path: string; version_files: VersionFile[] }[] | undefined,
+  projects:
+    | { path: string; version_files: VersionFile[]; version_checks?: VersionCheck[] }[]
+    | undefined,
   languages: LanguageInfo[] | undefined,
 ): AllMismatchesResult {
   if (!projects || !languages) return { byProject: new Map(), totalCount: 0 };
@@ -42,7 +55,9 @@ export function computeAllMismatches(
 
   for (const project of projects) {
     if (!project.version_files?.length) continue;
-    const mismatches = computeProjectMismatches(project.version_files, languages);
+    const mismatches = project.version_checks?.length
+      ? fromVersionChecks(project.version_checks)
+      : computeProjectMismatches(project.version_files, languages);
     const hasMismatch = mismatches.some((m) => !m.matches);
     if (hasMismatch) totalCount++;
     byProject.set(project.path, {